@@ -23,7 +23,7 @@ use crate::functions::{display_all_functions, Function};
 use crate::print_format::PrintFormat;
 use crate::print_options::PrintOptions;
 use clap::ValueEnum;
-use datafusion::arrow::array::{ArrayRef, StringArray};
+use datafusion::arrow::array::{ArrayRef, StringArray, UInt64Array};
 use datafusion::arrow::datatypes::{DataType, Field, Schema};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::common::exec_err;
@@ -46,6 +46,9 @@ pub enum Command {
     SearchFunctions(String),
     QuietMode(Option<bool>),
     OutputFormat(Option<String>),
+    ListCaches,
+    ListCacheEntries,
+    ClearCaches,
 }
 
 pub enum OutputFormat {
@@ -115,6 +118,21 @@ impl Command {
             Self::OutputFormat(_) => exec_err!(
                 "Unexpected change output format, this should be handled outside"
             ),
+            Self::ListCaches => {
+                let now = Instant::now();
+                let batch = caches_info(ctx);
+                print_options.print_batches(batch.schema(), &[batch], now)
+            }
+            Self::ListCacheEntries => {
+                let now = Instant::now();
+                let batch = cache_entries_info(ctx);
+                print_options.print_batches(batch.schema(), &[batch], now)
+            }
+            Self::ClearCaches => {
+                ctx.session_state().runtime_env().cache_manager.clear();
+                println!("Caches cleared");
+                Ok(())
+            }
         }
     }
 
@@ -133,11 +151,14 @@ impl Command {
             Self::OutputFormat(_) => {
                 ("\\pset [NAME [VALUE]]", "set table output option\n(format)")
             }
+            Self::ListCaches => ("\\cache", "list caches"),
+            Self::ListCacheEntries => ("\\cache entries", "list cache entries"),
+            Self::ClearCaches => ("\\cache clear", "clear caches"),
         }
     }
 }
 
-const ALL_COMMANDS: [Command; 9] = [
+const ALL_COMMANDS: [Command; 12] = [
     Command::ListTables,
     Command::DescribeTableStmt(String::new()),
     Command::Quit,
@@ -147,6 +168,9 @@ const ALL_COMMANDS: [Command; 9] = [
     Command::SearchFunctions(String::new()),
     Command::QuietMode(None),
     Command::OutputFormat(None),
+    Command::ListCaches,
+    Command::ListCacheEntries,
+    Command::ClearCaches,
 ];
 
 fn all_commands_info() -> RecordBatch {
@@ -168,6 +192,78 @@ fn all_commands_info() -> RecordBatch {
     .expect("This should not fail")
 }
 
+fn caches_info(ctx: &dyn CliSessionContext) -> RecordBatch {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("Cache", DataType::Utf8, false),
+        Field::new("Entries", DataType::UInt64, false),
+        Field::new("Memory", DataType::UInt64, false),
+        Field::new("Hits", DataType::UInt64, false),
+        Field::new("Misses", DataType::UInt64, false),
+        Field::new("Evictions", DataType::UInt64, false),
+    ]));
+    let caches = ctx
+        .session_state()
+        .runtime_env()
+        .cache_manager
+        .list_caches();
+    RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(StringArray::from_iter_values(
+                caches.iter().map(|cache| cache.kind),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                caches.iter().map(|cache| cache.len as u64),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                caches.iter().map(|cache| cache.stats.memory_used as u64),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                caches.iter().map(|cache| cache.stats.hits as u64),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                caches.iter().map(|cache| cache.stats.misses as u64),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                caches.iter().map(|cache| cache.stats.evictions as u64),
+            )),
+        ],
+    )
+    .expect("This should not fail")
+}
+
+fn cache_entries_info(ctx: &dyn CliSessionContext) -> RecordBatch {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("Cache", DataType::Utf8, false),
+        Field::new("Location", DataType::Utf8, false),
+        Field::new("Memory", DataType::UInt64, false),
+        Field::new("Hits", DataType::UInt64, false),
+    ]));
+    let entries = ctx
+        .session_state()
+        .runtime_env()
+        .cache_manager
+        .list_entries();
+    RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(StringArray::from_iter_values(
+                entries.iter().map(|(kind, _)| *kind),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                entries.iter().map(|(_, entry)| entry.key.as_ref()),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                entries.iter().map(|(_, entry)| entry.memory_size as u64),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                entries.iter().map(|(_, entry)| entry.hits as u64),
+            )),
+        ],
+    )
+    .expect("This should not fail")
+}
+
 impl FromStr for Command {
     type Err = ();
 
@@ -197,6 +293,9 @@ impl FromStr for Command {
                 Self::OutputFormat(Some(subcommand.to_string()))
             }
             ("pset", None) => Self::OutputFormat(None),
+            ("cache", None) => Self::ListCaches,
+            ("cache", Some("entries")) => Self::ListCacheEntries,
+            ("cache", Some("clear")) => Self::ClearCaches,
             _ => return Err(()),
         })
     }
@@ -22,6 +22,10 @@ use std::process::ExitCode;
 use std::sync::{Arc, LazyLock};
 
 use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::cache::cache_manager::CacheManagerConfig;
+use datafusion::execution::cache::cache_unit::{
+    CacheLimits, DefaultFileStatisticsCache, DefaultFilesMetadataCache,
+};
 use datafusion::execution::context::SessionConfig;
 use datafusion::execution::memory_pool::{FairSpillPool, GreedyMemoryPool, MemoryPool};
 use datafusion::execution::runtime_env::RuntimeEnvBuilder;
@@ -78,6 +82,13 @@ struct Args {
     )]
     memory_limit: Option<usize>,
 
+    #[clap(
+        long,
+        help = "Enable the file statistics and metadata caches, each bounded to the given memory (e.g. '100m'), default to None (no caches)",
+        value_parser(extract_memory_pool_size)
+    )]
+    cache_memory_limit: Option<usize>,
+
     #[clap(
         short,
         long,
@@ -167,6 +178,18 @@ async fn main_inner() -> Result<()> {
         rt_builder = rt_builder.with_memory_pool(pool)
     }
 
+    if let Some(cache_memory_limit) = args.cache_memory_limit {
+        let limits = CacheLimits::default().with_memory_limit(cache_memory_limit);
+        let cache_config = CacheManagerConfig::default()
+            .with_files_statistics_cache(Some(Arc::new(
+                DefaultFileStatisticsCache::new_with_limits(limits),
+            )))
+            .with_file_metadata_cache(Some(Arc::new(
+                DefaultFilesMetadataCache::new_with_limits(limits),
+            )));
+        rt_builder = rt_builder.with_cache_manager(cache_config);
+    }
+
     let runtime_env = rt_builder.build_arc()?;
 
     // enable dynamic file query
@@ -108,6 +108,7 @@ impl InformationSchemaConfig {
                                     &schema_name,
                                     &table_name,
                                     table.table_type(),
+                                    table.get_comment(),
                                 );
                             }
                         }
@@ -122,6 +123,7 @@ impl InformationSchemaConfig {
                     INFORMATION_SCHEMA,
                     table_name,
                     TableType::View,
+                    None,
                 );
             }
         }
@@ -196,6 +198,7 @@ impl InformationSchemaConfig {
                                         &table_name,
                                         field_position,
                                         field,
+                                        table.get_column_comment(field.name()),
                                     )
                                 }
                             }
@@ -522,6 +525,7 @@ impl InformationSchemaTables {
             Field::new("table_schema", DataType::Utf8, false),
             Field::new("table_name", DataType::Utf8, false),
             Field::new("table_type", DataType::Utf8, false),
+            Field::new("table_comment", DataType::Utf8, true),
         ]));
 
         Self { schema, config }
@@ -533,6 +537,7 @@ impl InformationSchemaTables {
             schema_names: StringBuilder::new(),
             table_names: StringBuilder::new(),
             table_types: StringBuilder::new(),
+            table_comments: StringBuilder::new(),
             schema: Arc::clone(&self.schema),
         }
     }
@@ -566,6 +571,7 @@ struct InformationSchemaTablesBuilder {
     schema_names: StringBuilder,
     table_names: StringBuilder,
     table_types: StringBuilder,
+    table_comments: StringBuilder,
 }
 
 impl InformationSchemaTablesBuilder {
@@ -575,6 +581,7 @@ impl InformationSchemaTablesBuilder {
         schema_name: impl AsRef<str>,
         table_name: impl AsRef<str>,
         table_type: TableType,
+        table_comment: Option<&str>,
     ) {
         // Note: append_value is actually infallible.
         self.catalog_names.append_value(catalog_name.as_ref());
@@ -585,6 +592,7 @@ impl InformationSchemaTablesBuilder {
             TableType::View => "VIEW",
             TableType::Temporary => "LOCAL TEMPORARY",
         });
+        self.table_comments.append_option(table_comment);
     }
 
     fn finish(&mut self) -> RecordBatch {
@@ -595,6 +603,7 @@ impl InformationSchemaTablesBuilder {
                 Arc::new(self.schema_names.finish()),
                 Arc::new(self.table_names.finish()),
                 Arc::new(self.table_types.finish()),
+                Arc::new(self.table_comments.finish()),
             ],
         )
         .unwrap()
@@ -713,6 +722,7 @@ impl InformationSchemaColumns {
             Field::new("numeric_scale", DataType::UInt64, true),
             Field::new("datetime_precision", DataType::UInt64, true),
             Field::new("interval_type", DataType::Utf8, true),
+            Field::new("column_comment", DataType::Utf8, true),
         ]));
 
         Self { schema, config }
@@ -740,6 +750,7 @@ impl InformationSchemaColumns {
             numeric_scales: UInt64Builder::with_capacity(default_capacity),
             datetime_precisions: UInt64Builder::with_capacity(default_capacity),
             interval_types: StringBuilder::new(),
+            column_comments: StringBuilder::new(),
             schema: Arc::clone(&self.schema),
         }
     }
@@ -784,6 +795,7 @@ struct InformationSchemaColumnsBuilder {
     numeric_scales: UInt64Builder,
     datetime_precisions: UInt64Builder,
     interval_types: StringBuilder,
+    column_comments: StringBuilder,
 }
 
 impl InformationSchemaColumnsBuilder {
@@ -794,6 +806,7 @@ impl InformationSchemaColumnsBuilder {
         table_name: &str,
         field_position: usize,
         field: &Field,
+        column_comment: Option<&str>,
     ) {
         use DataType::*;
 
@@ -879,6 +892,7 @@ impl InformationSchemaColumnsBuilder {
 
         self.datetime_precisions.append_option(None);
         self.interval_types.append_null();
+        self.column_comments.append_option(column_comment);
     }
 
     fn finish(&mut self) -> RecordBatch {
@@ -900,6 +914,7 @@ impl InformationSchemaColumnsBuilder {
                 Arc::new(self.numeric_scales.finish()),
                 Arc::new(self.datetime_precisions.finish()),
                 Arc::new(self.interval_types.finish()),
+                Arc::new(self.column_comments.finish()),
             ],
         )
         .unwrap()
@@ -83,6 +83,16 @@ pub trait TableProvider: Debug + Sync + Send {
         None
     }
 
+    /// Get the comment on this table, if available.
+    fn get_comment(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the comment on a column, if available.
+    fn get_column_comment(&self, _column: &str) -> Option<&str> {
+        None
+    }
+
     /// Create an [`ExecutionPlan`] for scanning the table with optionally
     /// specified `projection`, `filter` and `limit`, described below.
     ///
@@ -296,6 +306,69 @@ pub trait TableProvider: Debug + Sync + Send {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         not_impl_err!("Insert into not implemented for this table")
     }
+
+    /// Return an [`ExecutionPlan`] to delete rows from this table, if
+    /// supported.
+    ///
+    /// `filters` are the conjuncts of the `WHERE` clause of the `DELETE`
+    /// statement, with column references unqualified and resolved against
+    /// [`Self::schema`]. A row is deleted if it satisfies all `filters`. If
+    /// `filters` is empty, all rows are deleted.
+    ///
+    /// The returned plan should return a single row in a UInt64 column
+    /// called "count" containing the number of deleted rows, in the same
+    /// format as [`Self::insert_into`].
+    async fn delete_from(
+        &self,
+        _state: &dyn Session,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Delete from not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to update rows of this table, if
+    /// supported.
+    ///
+    /// `assignments` contains a `(column name, new value)` pair for each
+    /// column of [`Self::schema`] modified by the `UPDATE` statement, in
+    /// schema order. The new values are evaluated against the existing row.
+    ///
+    /// `filters` select the rows to update, with the same semantics as
+    /// for [`Self::delete_from`].
+    ///
+    /// The returned plan should return a single row in a UInt64 column
+    /// called "count" containing the number of updated rows, in the same
+    /// format as [`Self::insert_into`].
+    async fn update(
+        &self,
+        _state: &dyn Session,
+        _assignments: Vec<(String, Expr)>,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Update not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to merge data into this table (`MERGE
+    /// INTO`), if supported.
+    ///
+    /// `input` produces the new contents of the table as well as the deleted
+    /// rows. Its columns are the columns of [`Self::schema`] followed by a
+    /// [`MERGE_ACTION_COLUMN`] describing the action applied to each row. See
+    /// [`WriteOp::Merge`] for details.
+    ///
+    /// The returned plan should return a single row in a UInt64 column
+    /// called "count" containing the number of inserted, updated and deleted
+    /// rows, in the same format as [`Self::insert_into`].
+    ///
+    /// [`MERGE_ACTION_COLUMN`]: datafusion_expr::dml::MERGE_ACTION_COLUMN
+    /// [`WriteOp::Merge`]: datafusion_expr::WriteOp::Merge
+    async fn merge_into(
+        &self,
+        _state: &dyn Session,
+        _input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Merge into not implemented for this table")
+    }
 }
 
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
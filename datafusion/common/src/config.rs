@@ -329,6 +329,15 @@ config_namespace! {
         /// batches and merged.
         pub sort_in_place_threshold_bytes: usize, default = 1024 * 1024
 
+        /// Maximum number of spilled runs a hash aggregation merges at once.
+        ///
+        /// When a hash aggregation spills more runs than this, the runs are
+        /// first merged into fewer, larger runs on disk, so that the memory
+        /// used to merge them does not grow with the number of runs. The groups
+        /// of a hash aggregation are spilled in as many slices, each a run of
+        /// its own, so that they are not all emitted at once.
+        pub aggregate_spill_merge_degree: usize, default = 32
+
         /// Number of files to read in parallel when inferring schema and statistics
         pub meta_fetch_concurrency: usize, default = 32
 
@@ -389,6 +398,32 @@ config_namespace! {
         /// in joins can reduce memory usage when joining large
         /// tables with a highly-selective join filter, but is also slightly slower.
         pub enforce_batch_size_in_joins: bool, default = false
+
+        /// The maximum number of distinct build side values of a join key for
+        /// which the dynamic filter of a hash join lists the values with an
+        /// `IN` list. Join keys with more distinct values are filtered by a
+        /// bloom filter of the build side join keys instead. See
+        /// `datafusion.optimizer.enable_join_dynamic_filter_pushdown`
+        pub join_dynamic_filter_in_list_threshold: usize, default = 128
+
+        /// Should partitioned hash joins handle skewed join keys. When set to
+        /// true, the probe side of a partitioned inner, right, right semi or
+        /// right anti hash join is sampled at runtime to find its heavy
+        /// hitters, the join key values of a large fraction of its rows. The
+        /// probe side rows of heavy hitters are spread round robin over all
+        /// the partitions instead of being hash partitioned to a single one,
+        /// and their build side rows are replicated to all the partitions.
+        pub enable_hash_join_skew_handling: bool, default = false
+
+        /// Number of probe side rows sampled by each input partition of a
+        /// skew-aware hash join to find heavy hitters. See
+        /// `datafusion.execution.enable_hash_join_skew_handling`
+        pub hash_join_skew_sample_rows: usize, default = 8192
+
+        /// Minimum fraction of the sampled probe side rows of a skew-aware
+        /// hash join with the same join key for the join key to be a heavy
+        /// hitter. See `datafusion.execution.enable_hash_join_skew_handling`
+        pub hash_join_skew_heavy_hitter_ratio: f64, default = 0.1
     }
 }
 
@@ -411,6 +446,14 @@ config_namespace! {
         /// the parquet file
         pub pruning: bool, default = true
 
+        /// (reading) If true, the parquet reader attempts to skip entire row groups
+        /// based on the dictionaries of their column chunks whose data pages are all
+        /// dictionary encoded. This can prune row groups for predicates that the
+        /// min/max values can not, such as `col LIKE '%foo%'` or `col <> 'foo'`,
+        /// at the cost of reading the dictionaries (and the data page headers of
+        /// files without page encoding statistics, using the Page Index)
+        pub dictionary_pruning: bool, default = false
+
         /// (reading) If true, the parquet reader skip the optional embedded metadata that may be in
         /// the file Schema. This setting can help avoid schema conflicts when querying
         /// multiple parquet files with schemas containing compatible types but different metadata
@@ -667,6 +710,27 @@ config_namespace! {
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold_rows: usize, default = 1024 * 128
 
+        /// When set to true, the physical plan optimizer will replace nested loop
+        /// joins whose filter contains two range conditions between the join inputs
+        /// (e.g. `l.ts >= r.start AND l.ts < r.end`) with an `IEJoinExec`, which
+        /// avoids evaluating the filter on the Cartesian product of the inputs
+        pub enable_ie_join: bool, default = true
+
+        /// When set to true, the physical plan optimizer will push a dynamic filter
+        /// on the join keys of hash joins down to the scans of their probe side. Once the
+        /// build side has been collected, the filter is set to the range of the build side
+        /// join keys, together with either the list of their values or a bloom filter of
+        /// them, and is used to skip probe side row groups, pages and rows that can not
+        /// match any build side row
+        pub enable_join_dynamic_filter_pushdown: bool, default = true
+
+        /// When set to true, the physical plan optimizer will push a dynamic filter
+        /// on the sort keys of sorts with a fetch (`ORDER BY ... LIMIT`) down to the
+        /// scans of their input. The filter is tightened to the last of the current
+        /// top rows as the sort sees better rows, and is used to skip row groups,
+        /// pages and rows that can not be part of the result
+        pub enable_topk_dynamic_filter_pushdown: bool, default = true
+
         /// The default filter selectivity used by Filter Statistics
         /// when an exact selectivity cannot be determined. Valid values are
         /// between 0 (no selectivity) and 100 (all rows are selected).
@@ -368,7 +368,10 @@ impl FunctionalDependencies {
                 left_func_dependencies.extend(right_func_dependencies);
                 left_func_dependencies
             }
-            JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
+            JoinType::LeftSemi
+            | JoinType::LeftAnti
+            | JoinType::LeftMark
+            | JoinType::LeftAsof => {
                 // These joins preserve functional dependencies of the left side:
                 left_func_dependencies
             }
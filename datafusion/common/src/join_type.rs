@@ -67,6 +67,15 @@ pub enum JoinType {
     ///
     /// [1]: http://btw2017.informatik.uni-stuttgart.de/slidesandpapers/F1-10-37/paper_web.pdf
     LeftMark,
+    /// Left ASOF join
+    ///
+    /// Returns one record for each record from the left input, joined with the *closest*
+    /// record from the right input that has the same equijoin keys and satisfies the match
+    /// condition, a single comparison (`<`, `<=`, `>` or `>=`) between a left and a right
+    /// expression stored as the join filter. For example, with the match condition
+    /// `trades.ts >= quotes.ts` each trade is joined with the latest quote at or before it.
+    /// If there is no such record, NULL values are returned for columns from the right input.
+    LeftAsof,
 }
 
 impl JoinType {
@@ -90,6 +99,9 @@ impl JoinType {
             JoinType::LeftMark => {
                 unreachable!("LeftMark join type does not support swapping")
             }
+            JoinType::LeftAsof => {
+                unreachable!("LeftAsof join type does not support swapping")
+            }
         }
     }
 
@@ -121,6 +133,7 @@ impl Display for JoinType {
             JoinType::LeftAnti => "LeftAnti",
             JoinType::RightAnti => "RightAnti",
             JoinType::LeftMark => "LeftMark",
+            JoinType::LeftAsof => "LeftAsof",
         };
         write!(f, "{join_type}")
     }
@@ -141,6 +154,7 @@ impl FromStr for JoinType {
             "LEFTANTI" => Ok(JoinType::LeftAnti),
             "RIGHTANTI" => Ok(JoinType::RightAnti),
             "LEFTMARK" => Ok(JoinType::LeftMark),
+            "LEFTASOF" => Ok(JoinType::LeftAsof),
             _ => _not_impl_err!("The join type {s} does not exist or is not implemented"),
         }
     }
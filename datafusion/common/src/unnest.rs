@@ -66,6 +66,26 @@ use crate::Column;
 /// `recursions` instruct how a column should be unnested (e.g unnesting a column multiple
 /// time, with depth = 1 and depth = 2). Any unnested column not being mentioned inside this
 /// options is inferred to be unnested with depth = 1
+///
+/// If `with_ordinality` is true, every unnested list column is followed by an
+/// `Int64` column named `<column>_ordinality`, holding the 1-based position of
+/// each value within its list. The ordinality is null for values that are
+/// padded because another list in the same row is longer, or that come from
+/// a null list.
+///
+/// ## `Unnest(c1)`, preserve_nulls: true, with_ordinality: true
+/// ```text
+///      ┌─────────┐ ┌─────┐                ┌─────────┐ ┌─────────────┐ ┌─────┐
+///      │ {1, 2}  │ │  A  │   Unnest       │    1    │ │      1      │ │  A  │
+///      ├─────────┤ ├─────┤                ├─────────┤ ├─────────────┤ ├─────┤
+///      │  null   │ │  B  │                │    2    │ │      2      │ │  A  │
+///      ├─────────┤ ├─────┤ ────────────▶  ├─────────┤ ├─────────────┤ ├─────┤
+///      │   {3}   │ │  E  │                │  null   │ │    null     │ │  B  │
+///      └─────────┘ └─────┘                ├─────────┤ ├─────────────┤ ├─────┤
+///        c1         c2                    │    3    │ │      1      │ │  E  │
+///                                         └─────────┘ └─────────────┘ └─────┘
+///                                             c1      c1_ordinality     c2
+/// ```
 #[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Eq)]
 pub struct UnnestOptions {
     /// Should nulls in the input be preserved? Defaults to true
@@ -74,6 +94,9 @@ pub struct UnnestOptions {
     /// declare them here. Any unnested columns not being mentioned inside this option
     /// will be unnested with depth = 1
     pub recursions: Vec<RecursionUnnestOption>,
+    /// Should a 1-based ordinality column be emitted after every unnested
+    /// list column? Defaults to false
+    pub with_ordinality: bool,
 }
 
 /// Instruction on how to unnest a column (mostly with a list type)
@@ -91,6 +114,7 @@ impl Default for UnnestOptions {
             // default to true to maintain backwards compatible behavior
             preserve_nulls: true,
             recursions: vec![],
+            with_ordinality: false,
         }
     }
 }
@@ -113,4 +137,11 @@ impl UnnestOptions {
         self.recursions.push(recursion);
         self
     }
+
+    /// Set whether an ordinality column is emitted for every unnested list
+    /// column, as described on [`Self`]
+    pub fn with_ordinality(mut self, with_ordinality: bool) -> Self {
+        self.with_ordinality = with_ordinality;
+        self
+    }
 }
@@ -23,9 +23,11 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 
 use crate::datasource::physical_plan::{FileOpener, FileScanConfig};
+use crate::datasource::schema_adapter::SchemaAdapterFactory;
 
 use arrow::datatypes::SchemaRef;
 use datafusion_common::Statistics;
+use datafusion_physical_expr::PhysicalExpr;
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use datafusion_physical_plan::DisplayFormatType;
 
@@ -67,4 +69,26 @@ pub trait FileSource: Send + Sync {
     /// If this returns true, the DataSourceExec may repartition the data
     /// by breaking up the input files into multiple smaller groups.
     fn supports_repartition(&self, config: &FileScanConfig) -> bool;
+    /// Return a copy of this source that also uses the dynamic `filter`,
+    /// written in terms of the file schema, to skip rows that can not pass it,
+    /// or `None` if the file format can not make use of it.
+    ///
+    /// See [`DataSource::try_pushdown_dynamic_filter`]
+    ///
+    /// [`DataSource::try_pushdown_dynamic_filter`]: datafusion_physical_plan::source::DataSource::try_pushdown_dynamic_filter
+    fn try_pushdown_dynamic_filter(
+        &self,
+        _filter: &Arc<dyn PhysicalExpr>,
+    ) -> Option<Arc<dyn FileSource>> {
+        None
+    }
+    /// Return a copy of this source that maps the columns of the files to the
+    /// table schema with `schema_adapter_factory`, or `None` if the file
+    /// format does not support custom schema adapters.
+    fn try_with_schema_adapter_factory(
+        &self,
+        _schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
+    ) -> Option<Arc<dyn FileSource>> {
+        None
+    }
 }
@@ -31,13 +31,16 @@ use super::{
     FilePushdownSupport, FileScanConfig,
 };
 use crate::arrow::array::RecordBatch;
-use crate::arrow::datatypes::{Fields, Schema, SchemaRef};
+use crate::arrow::datatypes::{DataType, Fields, Schema, SchemaRef};
 use crate::datasource::file_format::file_compression_type::FileCompressionType;
 use crate::datasource::file_format::write::get_writer_schema;
 use crate::datasource::physical_plan::parquet::can_expr_be_pushed_down_with_schemas;
 use crate::datasource::physical_plan::parquet::source::ParquetSource;
 use crate::datasource::physical_plan::{FileGroupDisplay, FileSink, FileSinkConfig};
-use crate::datasource::statistics::{create_max_min_accs, get_col_stats};
+use crate::datasource::schema_adapter::DefaultSchemaAdapterFactory;
+use crate::datasource::statistics::{
+    create_max_min_accs, get_col_stats, min_max_aggregate_data_type,
+};
 use crate::error::Result;
 use crate::execution::SessionState;
 use crate::physical_plan::insert::{DataSink, DataSinkExec};
@@ -46,7 +49,7 @@ use crate::physical_plan::{
     Statistics,
 };
 
-use arrow::compute::sum;
+use arrow::compute::{cast, sum};
 use datafusion_catalog::Session;
 use datafusion_common::config::{ConfigField, ConfigFileType, TableParquetOptions};
 use datafusion_common::parsers::CompressionTypeVariant;
@@ -397,7 +400,7 @@ impl FileFormat for ParquetFormat {
 
     async fn create_physical_plan(
         &self,
-        _state: &dyn Session,
+        state: &dyn Session,
         mut conf: FileScanConfig,
         filters: Option<&Arc<dyn PhysicalExpr>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
@@ -416,7 +419,10 @@ impl FileFormat for ParquetFormat {
             metadata_size_hint = Some(metadata);
         }
 
-        let mut source = ParquetSource::new(self.options.clone());
+        let file_metadata_cache =
+            state.runtime_env().cache_manager.get_file_metadata_cache();
+        let mut source = ParquetSource::new(self.options.clone())
+            .with_file_metadata_cache(file_metadata_cache);
 
         if let Some(predicate) = predicate {
             source = source.with_predicate(Arc::clone(&conf.file_schema), predicate);
@@ -424,6 +430,16 @@ impl FileFormat for ParquetFormat {
         if let Some(metadata_size_hint) = metadata_size_hint {
             source = source.with_metadata_size_hint(metadata_size_hint)
         }
+        // Keep the schema adapter of the table, if any
+        if let Some(schema_adapter_factory) = conf
+            .file_source()
+            .as_any()
+            .downcast_ref::<ParquetSource>()
+            .and_then(ParquetSource::schema_adapter_factory)
+        {
+            source =
+                source.with_schema_adapter_factory(Arc::clone(schema_adapter_factory));
+        }
         conf = conf.with_source(Arc::new(source));
         Ok(conf.new_exec())
     }
@@ -586,6 +602,11 @@ pub fn statistics_from_parquet_meta_calc(
         file_schema = merged;
     }
 
+    // Collect the statistics of the file columns the scan maps to the table
+    // columns (e.g. by field id), rather than of the columns named like them
+    let file_schema = DefaultSchemaAdapterFactory::from_schema(Arc::clone(&table_schema))
+        .map_file_schema_names(&file_schema);
+
     statistics.column_statistics = if has_statistics {
         let (mut max_accs, mut min_accs) = create_max_min_accs(&table_schema);
         let mut null_counts_array =
@@ -607,6 +628,7 @@ pub fn statistics_from_parquet_meta_calc(
                             &mut max_accs,
                             &mut null_counts_array,
                             idx,
+                            min_max_aggregate_data_type(field.data_type()),
                             num_rows,
                             &stats_converter,
                             row_groups_metadata,
@@ -648,17 +670,26 @@ pub async fn statistics_from_parquet_meta(
     statistics_from_parquet_meta_calc(metadata, table_schema)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn summarize_min_max_null_counts(
     min_accs: &mut [Option<MinAccumulator>],
     max_accs: &mut [Option<MaxAccumulator>],
     null_counts_array: &mut [Precision<usize>],
     arrow_schema_index: usize,
+    data_type: &DataType,
     num_rows: usize,
     stats_converter: &StatisticsConverter,
     row_groups_metadata: &[RowGroupMetaData],
 ) -> Result<()> {
-    let max_values = stats_converter.row_group_maxes(row_groups_metadata)?;
-    let min_values = stats_converter.row_group_mins(row_groups_metadata)?;
+    // The file column may have a narrower type than the table column
+    let max_values = cast(
+        &stats_converter.row_group_maxes(row_groups_metadata)?,
+        data_type,
+    )?;
+    let min_values = cast(
+        &stats_converter.row_group_mins(row_groups_metadata)?,
+        data_type,
+    )?;
     let null_counts = stats_converter.row_group_null_counts(row_groups_metadata)?;
 
     if let Some(max_acc) = &mut max_accs[arrow_schema_index] {
@@ -30,6 +30,7 @@ use crate::datasource::{
     },
     get_statistics_with_limit,
     physical_plan::{FileScanConfig, FileSinkConfig},
+    schema_adapter::RenamingSchemaAdapterFactory,
 };
 use crate::execution::context::SessionState;
 use datafusion_catalog::TableProvider;
@@ -41,8 +42,8 @@ use datafusion_physical_plan::{empty::EmptyExec, ExecutionPlan, Statistics};
 
 use arrow::datatypes::{DataType, Field, Schema, SchemaBuilder, SchemaRef};
 use datafusion_common::{
-    config_datafusion_err, internal_err, plan_err, project_schema, Constraints,
-    SchemaExt, ToDFSchema,
+    config_datafusion_err, internal_err, not_impl_datafusion_err, plan_err,
+    project_schema, ColumnStatistics, Constraints, SchemaExt, ToDFSchema,
 };
 use datafusion_execution::cache::{
     cache_manager::FileStatisticsCache, cache_unit::DefaultFileStatisticsCache,
@@ -68,6 +69,10 @@ pub struct ListingTableConfig {
     pub file_schema: Option<SchemaRef>,
     /// Optional `ListingOptions` for the to be created `ListingTable`.
     pub options: Option<ListingOptions>,
+    /// Map from the names of columns in the files to their names in the
+    /// schema of the to be created `ListingTable`.
+    /// See [`Self::with_column_renames`] for details
+    column_renames: HashMap<String, String>,
 }
 
 impl ListingTableConfig {
@@ -81,6 +86,7 @@ impl ListingTableConfig {
             table_paths,
             file_schema: None,
             options: None,
+            column_renames: HashMap::new(),
         }
     }
 
@@ -93,6 +99,7 @@ impl ListingTableConfig {
             table_paths,
             file_schema: None,
             options: None,
+            column_renames: HashMap::new(),
         }
     }
     /// Add `schema` to [`ListingTableConfig`]
@@ -101,6 +108,7 @@ impl ListingTableConfig {
             table_paths: self.table_paths,
             file_schema: Some(schema),
             options: self.options,
+            column_renames: self.column_renames,
         }
     }
 
@@ -110,6 +118,23 @@ impl ListingTableConfig {
             table_paths: self.table_paths,
             file_schema: self.file_schema,
             options: Some(listing_options),
+            column_renames: self.column_renames,
+        }
+    }
+
+    /// Map the columns of the files named like the keys of `column_renames`
+    /// to the columns of the table named like their values.
+    ///
+    /// This allows reading files written before columns of the table were
+    /// renamed. For example, `("user_id", "id")` reads the column `user_id` of
+    /// older files as the column `id` of the table. Columns with a field id
+    /// in both the file and the table schema are mapped by field id instead.
+    ///
+    /// Renames are currently only supported by the parquet format.
+    pub fn with_column_renames(self, column_renames: HashMap<String, String>) -> Self {
+        Self {
+            column_renames,
+            ..self
         }
     }
 
@@ -186,6 +211,7 @@ impl ListingTableConfig {
             table_paths: self.table_paths,
             file_schema: self.file_schema,
             options: Some(listing_options),
+            column_renames: self.column_renames,
         })
     }
 
@@ -203,6 +229,7 @@ impl ListingTableConfig {
                     table_paths: self.table_paths,
                     file_schema: Some(schema),
                     options: Some(options),
+                    column_renames: self.column_renames,
                 })
             }
             None => internal_err!("No `ListingOptions` set for inferring schema"),
@@ -240,6 +267,7 @@ impl ListingTableConfig {
                     table_paths: self.table_paths,
                     file_schema: self.file_schema,
                     options: Some(options),
+                    column_renames: self.column_renames,
                 })
             }
             None => config_err!("No `ListingOptions` set for inferring schema"),
@@ -714,6 +742,8 @@ pub struct ListingTable {
     collected_statistics: FileStatisticsCache,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
+    /// Map from the names of columns in the files to their names in the table
+    column_renames: HashMap<String, String>,
 }
 
 impl ListingTable {
@@ -757,6 +787,7 @@ impl ListingTable {
             collected_statistics: Arc::new(DefaultFileStatisticsCache::default()),
             constraints: Constraints::empty(),
             column_defaults: HashMap::new(),
+            column_renames: config.column_renames,
         };
 
         Ok(table)
@@ -927,6 +958,20 @@ impl TableProvider for ListingTable {
             return Ok(Arc::new(EmptyExec::new(Arc::new(Schema::empty()))));
         };
 
+        let mut file_source = self.options.format.file_source();
+        if !self.column_renames.is_empty() {
+            let schema_adapter_factory =
+                RenamingSchemaAdapterFactory::new(self.column_renames.clone());
+            file_source = file_source
+                .try_with_schema_adapter_factory(Arc::new(schema_adapter_factory))
+                .ok_or_else(|| {
+                    not_impl_datafusion_err!(
+                        "Column renames are not supported by the {} format",
+                        file_source.file_type()
+                    )
+                })?;
+        }
+
         // create the execution plan
         self.options
             .format
@@ -935,7 +980,7 @@ impl TableProvider for ListingTable {
                 FileScanConfig::new(
                     object_store_url,
                     Arc::clone(&self.file_schema),
-                    self.options.format.file_source(),
+                    file_source,
                 )
                 .with_file_groups(partitioned_file_lists)
                 .with_constraints(self.constraints.clone())
@@ -1175,7 +1220,8 @@ impl ListingTable {
                         &part_file.object_meta,
                     )
                     .await?;
-                let statistics = Arc::new(statistics);
+                let statistics =
+                    Arc::new(self.forget_renamed_column_statistics(statistics));
                 self.collected_statistics.put_with_extra(
                     &part_file.object_meta.location,
                     Arc::clone(&statistics),
@@ -1185,6 +1231,32 @@ impl ListingTable {
             }
         }
     }
+
+    /// Returns `statistics` without the statistics of the renamed columns.
+    ///
+    /// The statistics of a file are collected without the renames, so they may
+    /// be those of another column of the file for the columns involved in a
+    /// rename.
+    fn forget_renamed_column_statistics(&self, mut statistics: Statistics) -> Statistics {
+        if self.column_renames.is_empty() {
+            return statistics;
+        }
+        for (field, column_statistics) in self
+            .file_schema
+            .fields()
+            .iter()
+            .zip(statistics.column_statistics.iter_mut())
+        {
+            let renamed = self
+                .column_renames
+                .iter()
+                .any(|(from, to)| from == field.name() || to == field.name());
+            if renamed {
+                *column_statistics = ColumnStatistics::new_unknown();
+            }
+        }
+        statistics
+    }
 }
 
 #[cfg(test)]
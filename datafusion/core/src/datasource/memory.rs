@@ -34,15 +34,27 @@ use crate::physical_plan::{
 };
 use crate::physical_planner::create_physical_sort_exprs;
 
-use arrow::datatypes::SchemaRef;
+use arrow::array::{Array, ArrayRef, BooleanArray, StringArray, UInt64Array};
+use arrow::compute::kernels::cmp::distinct;
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{filter_record_batch, not, prep_null_mask_filter};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use datafusion_catalog::Session;
-use datafusion_common::{not_impl_err, plan_err, Constraints, DFSchema, SchemaExt};
+use datafusion_common::cast::{as_boolean_array, as_string_array};
+use datafusion_common::{
+    exec_err, internal_err, not_impl_err, plan_err, Constraints, DFSchema, SchemaExt,
+};
 use datafusion_execution::TaskContext;
-use datafusion_expr::dml::InsertOp;
+use datafusion_expr::dml::{InsertOp, MERGE_ACTION_COLUMN};
+use datafusion_expr::utils::conjunction;
 use datafusion_expr::SortExpr;
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
+use datafusion_physical_plan::execution_plan::{Boundedness, EmissionType};
 use datafusion_physical_plan::memory::MemorySourceConfig;
 use datafusion_physical_plan::source::DataSourceExec;
+use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion_physical_plan::PlanProperties;
 
 use async_trait::async_trait;
 use futures::StreamExt;
@@ -64,6 +76,8 @@ pub struct MemTable {
     pub(crate) batches: Vec<PartitionData>,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
+    comment: Option<String>,
+    column_comments: HashMap<String, String>,
     /// Optional pre-known sort order(s). Must be `SortExpr`s.
     /// inserting data into this table removes the order
     pub sort_order: Arc<Mutex<Vec<Vec<SortExpr>>>>,
@@ -91,6 +105,8 @@ impl MemTable {
                 .collect::<Vec<_>>(),
             constraints: Constraints::empty(),
             column_defaults: HashMap::new(),
+            comment: None,
+            column_comments: HashMap::new(),
             sort_order: Arc::new(Mutex::new(vec![])),
         })
     }
@@ -110,6 +126,21 @@ impl MemTable {
         self
     }
 
+    /// Assign a comment to the table
+    pub fn with_comment(mut self, comment: Option<String>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Assign column comments
+    pub fn with_column_comments(
+        mut self,
+        column_comments: HashMap<String, String>,
+    ) -> Self {
+        self.column_comments = column_comments;
+        self
+    }
+
     /// Specify an optional pre-known sort order(s). Must be `SortExpr`s.
     ///
     /// If the data is not sorted by this order, DataFusion may produce
@@ -305,9 +336,325 @@ impl TableProvider for MemTable {
         Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
     }
 
+    /// Returns an ExecutionPlan that deletes the rows of this [`MemTable`]
+    /// matching all of the given `filters`.
+    async fn delete_from(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let predicate = self.create_dml_predicate(state, filters)?;
+        Ok(Arc::new(MemDmlExec::new(
+            self.batches.clone(),
+            Arc::clone(&self.schema),
+            predicate,
+            None,
+            None,
+        )))
+    }
+
+    /// Returns an ExecutionPlan that applies the given `assignments` to the
+    /// rows of this [`MemTable`] matching all of the given `filters`.
+    async fn update(
+        &self,
+        state: &dyn Session,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let df_schema = DFSchema::try_from(self.schema.as_ref().clone())?;
+        let assignments = assignments
+            .into_iter()
+            .map(|(name, value)| {
+                let idx = self.schema.index_of(&name)?;
+                let value = state.create_physical_expr(value, &df_schema)?;
+                Ok((idx, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let predicate = self.create_dml_predicate(state, filters)?;
+        Ok(Arc::new(MemDmlExec::new(
+            self.batches.clone(),
+            Arc::clone(&self.schema),
+            predicate,
+            Some(assignments),
+            // Updated values may not respect the sort order, so it is reset
+            // when the rows are updated
+            Some(Arc::clone(&self.sort_order)),
+        )))
+    }
+
+    /// Returns an ExecutionPlan that replaces the contents of this
+    /// [`MemTable`] with the output of a `MERGE INTO` input plan.
+    ///
+    /// See [`WriteOp::Merge`] for the format of `input`.
+    ///
+    /// [`WriteOp::Merge`]: datafusion_expr::WriteOp::Merge
+    async fn merge_into(
+        &self,
+        _state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let input_schema = input.schema();
+        let num_columns = self.schema.fields().len();
+        let action_field = input_schema.fields().get(num_columns);
+        let valid_schema = input_schema.fields().len() == num_columns + 1
+            && action_field.is_some_and(|field| {
+                field.name() == MERGE_ACTION_COLUMN
+                    && matches!(field.data_type(), DataType::Utf8)
+            })
+            && self.schema.logically_equivalent_names_and_types(&Arc::new(
+                input_schema.project(&(0..num_columns).collect::<Vec<_>>())?,
+            ));
+        if !valid_schema {
+            return plan_err!(
+                "Merge input must have the columns of the table followed by a Utf8 \
+                {MERGE_ACTION_COLUMN} column. Expected: {:?}, got: {:?}",
+                self.schema,
+                input_schema
+            );
+        }
+        let sink = MemMergeSink::try_new(
+            self.batches.clone(),
+            Arc::clone(&self.schema),
+            Arc::clone(&self.sort_order),
+        )?;
+        Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
+    }
+
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
+
+    fn get_comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    fn get_column_comment(&self, column: &str) -> Option<&str> {
+        self.column_comments.get(column).map(String::as_str)
+    }
+}
+
+impl MemTable {
+    /// Creates the physical predicate selecting the rows affected by a
+    /// `DELETE` or `UPDATE`, or `None` if all rows are affected
+    fn create_dml_predicate(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+        let Some(predicate) = conjunction(filters) else {
+            return Ok(None);
+        };
+        let df_schema = DFSchema::try_from(self.schema.as_ref().clone())?;
+        state.create_physical_expr(predicate, &df_schema).map(Some)
+    }
+}
+
+/// Execution plan for deleting or updating the rows of a [`MemTable`] in place.
+///
+/// Returns a single row with the number of affected rows, in the same format
+/// as [`DataSinkExec`].
+struct MemDmlExec {
+    /// Partitions of the target table
+    batches: Vec<PartitionData>,
+    /// Schema of the target table
+    schema: SchemaRef,
+    /// Selects the affected rows. All rows are affected if `None`
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    /// New values for `(column index, value)`, or `None` to delete the rows
+    assignments: Option<Vec<(usize, Arc<dyn PhysicalExpr>)>>,
+    /// Sort order of the target table, reset if any row is affected
+    sort_order: Option<Arc<Mutex<Vec<Vec<SortExpr>>>>>,
+    cache: PlanProperties,
+}
+
+impl MemDmlExec {
+    fn new(
+        batches: Vec<PartitionData>,
+        schema: SchemaRef,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        assignments: Option<Vec<(usize, Arc<dyn PhysicalExpr>)>>,
+        sort_order: Option<Arc<Mutex<Vec<Vec<SortExpr>>>>>,
+    ) -> Self {
+        let count_schema = Arc::new(Schema::new(vec![Field::new(
+            "count",
+            DataType::UInt64,
+            false,
+        )]));
+        let cache = PlanProperties::new(
+            EquivalenceProperties::new(count_schema),
+            Partitioning::UnknownPartitioning(1),
+            EmissionType::Final,
+            Boundedness::Bounded,
+        );
+        Self {
+            batches,
+            schema,
+            predicate,
+            assignments,
+            sort_order,
+            cache,
+        }
+    }
+}
+
+/// Deletes or updates the rows of `batch` selected by `predicate`, returning
+/// the new batch and the number of affected rows
+fn apply_dml(
+    batch: &RecordBatch,
+    predicate: Option<&Arc<dyn PhysicalExpr>>,
+    assignments: Option<&[(usize, Arc<dyn PhysicalExpr>)]>,
+) -> Result<(RecordBatch, usize)> {
+    let selected = match predicate {
+        Some(predicate) => {
+            let mask = predicate.evaluate(batch)?.into_array(batch.num_rows())?;
+            let mask = as_boolean_array(&mask)?;
+            // rows for which the predicate is NULL are not affected
+            if mask.null_count() > 0 {
+                prep_null_mask_filter(mask)
+            } else {
+                mask.clone()
+            }
+        }
+        None => BooleanArray::from(vec![true; batch.num_rows()]),
+    };
+    let affected = selected.true_count();
+    if affected == 0 {
+        return Ok((batch.clone(), 0));
+    }
+
+    let batch = match assignments {
+        None => filter_record_batch(batch, &not(&selected)?)?,
+        Some(assignments) => {
+            let mut columns = batch.columns().to_vec();
+            for (idx, value) in assignments {
+                let value = value.evaluate(batch)?.into_array(batch.num_rows())?;
+                columns[*idx] = zip(&selected, &value, &columns[*idx])?;
+            }
+            RecordBatch::try_new(batch.schema(), columns)?
+        }
+    };
+    Ok((batch, affected))
+}
+
+impl Debug for MemDmlExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemDmlExec")
+            .field("num_partitions", &self.batches.len())
+            .field("predicate", &self.predicate)
+            .field("assignments", &self.assignments)
+            .finish()
+    }
+}
+
+impl DisplayAs for MemDmlExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let op = if self.assignments.is_some() {
+                    "Update"
+                } else {
+                    "Delete"
+                };
+                let partition_count = self.batches.len();
+                write!(f, "MemDmlExec: op={op}, partitions={partition_count}")?;
+                if let Some(assignments) = &self.assignments {
+                    let assignments = assignments
+                        .iter()
+                        .map(|(idx, value)| {
+                            format!("{}={value}", self.schema.field(*idx).name())
+                        })
+                        .collect::<Vec<_>>();
+                    write!(f, ", assignments=[{}]", assignments.join(", "))?;
+                }
+                if let Some(predicate) = &self.predicate {
+                    write!(f, ", predicate={predicate}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for MemDmlExec {
+    fn name(&self) -> &'static str {
+        "MemDmlExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            internal_err!("Children cannot be replaced in {self:?}")
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return internal_err!("MemDmlExec can only be called on partition 0!");
+        }
+        let batches = self.batches.clone();
+        let predicate = self.predicate.clone();
+        let assignments = self.assignments.clone();
+        let sort_order = self.sort_order.clone();
+        let count_schema = self.schema();
+        let stream = futures::stream::once(async move {
+            // Lock all partitions, so readers never see a partially applied
+            // statement
+            let mut partitions = Vec::with_capacity(batches.len());
+            for partition in batches.iter() {
+                partitions.push(partition.write().await);
+            }
+            // Compute the new batches of all partitions before modifying any
+            // of them, so an error leaves the table unchanged
+            let mut count = 0;
+            let mut new_partitions = Vec::with_capacity(partitions.len());
+            for batches in partitions.iter() {
+                let mut new_batches = Vec::with_capacity(batches.len());
+                for batch in batches.iter() {
+                    let (batch, affected) =
+                        apply_dml(batch, predicate.as_ref(), assignments.as_deref())?;
+                    count += affected;
+                    if batch.num_rows() > 0 {
+                        new_batches.push(batch);
+                    }
+                }
+                new_partitions.push(new_batches);
+            }
+            for (batches, new_batches) in partitions.iter_mut().zip(new_partitions) {
+                **batches = new_batches;
+            }
+            if let Some(sort_order) = sort_order.filter(|_| count > 0) {
+                *sort_order.lock() = vec![];
+            }
+            let count = Arc::new(UInt64Array::from(vec![count as u64])) as ArrayRef;
+            Ok(RecordBatch::try_new(count_schema, vec![count])?)
+        })
+        .boxed();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
 }
 
 /// Implements for writing to a [`MemTable`]
@@ -386,6 +733,121 @@ impl DataSink for MemSink {
     }
 }
 
+/// Replaces the contents of a [`MemTable`] with the result of a `MERGE INTO`
+struct MemMergeSink {
+    /// Target locations for writing data
+    batches: Vec<PartitionData>,
+    /// Schema of the table
+    table_schema: SchemaRef,
+    /// Schema of the table followed by the merge action column
+    schema: SchemaRef,
+    /// Sort order of the table, reset when the merge completes
+    sort_order: Arc<Mutex<Vec<Vec<SortExpr>>>>,
+}
+
+impl Debug for MemMergeSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemMergeSink")
+            .field("num_partitions", &self.batches.len())
+            .finish()
+    }
+}
+
+impl DisplayAs for MemMergeSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let partition_count = self.batches.len();
+                write!(f, "MemoryTableMerge (partitions={partition_count})")
+            }
+        }
+    }
+}
+
+impl MemMergeSink {
+    /// Creates a new [`MemMergeSink`].
+    fn try_new(
+        batches: Vec<PartitionData>,
+        table_schema: SchemaRef,
+        sort_order: Arc<Mutex<Vec<Vec<SortExpr>>>>,
+    ) -> Result<Self> {
+        if batches.is_empty() {
+            return plan_err!("Cannot merge into MemTable with zero partitions");
+        }
+        let mut fields = table_schema.fields().to_vec();
+        fields.push(Arc::new(Field::new(
+            MERGE_ACTION_COLUMN,
+            DataType::Utf8,
+            true,
+        )));
+        let schema = Arc::new(Schema::new(fields));
+        Ok(Self {
+            batches,
+            table_schema,
+            schema,
+            sort_order,
+        })
+    }
+}
+
+#[async_trait]
+impl DataSink for MemMergeSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        mut data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let num_partitions = self.batches.len();
+        let num_columns = self.table_schema.fields().len();
+
+        // buffer up the kept rows round robin style into num_partitions
+        let mut new_batches = vec![vec![]; num_partitions];
+        let mut i = 0;
+        let mut row_count = 0;
+        while let Some(batch) = data.next().await.transpose()? {
+            let actions = as_string_array(batch.column(num_columns))?;
+            if actions.iter().any(|action| action == Some("conflict")) {
+                return exec_err!(
+                    "MERGE cardinality violation: a target row matched more than one source row"
+                );
+            }
+            row_count += actions.len() - actions.null_count();
+
+            // deleted rows are dropped, all other rows are kept
+            let keep = distinct(actions, &StringArray::new_scalar("delete"))?;
+            let columns = batch.columns()[..num_columns].to_vec();
+            let batch = RecordBatch::try_new(Arc::clone(&self.table_schema), columns)?;
+            let batch = filter_record_batch(&batch, &keep)?;
+            if batch.num_rows() > 0 {
+                new_batches[i].push(batch);
+                i = (i + 1) % num_partitions;
+            }
+        }
+
+        // replace the contents of all partitions at once, so readers never see
+        // a partially applied merge
+        let mut partitions = Vec::with_capacity(num_partitions);
+        for partition in &self.batches {
+            partitions.push(partition.write().await);
+        }
+        for (target, batches) in partitions.iter_mut().zip(new_batches) {
+            **target = batches;
+        }
+        // Merged rows are redistributed and may not respect the sort order
+        *self.sort_order.lock() = vec![];
+
+        Ok(row_count as u64)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -395,10 +857,10 @@ mod tests {
     use crate::prelude::SessionContext;
 
     use arrow::array::{AsArray, Int32Array};
-    use arrow::datatypes::{DataType, Field, Schema, UInt64Type};
+    use arrow::datatypes::{DataType, Field, Int32Type, Schema, UInt64Type};
     use arrow::error::ArrowError;
-    use datafusion_common::DataFusionError;
-    use datafusion_expr::LogicalPlanBuilder;
+    use datafusion_common::{assert_contains, DataFusionError, ScalarValue};
+    use datafusion_expr::{col, lit, when, LogicalPlanBuilder};
 
     #[tokio::test]
     async fn test_with_projection() -> Result<()> {
@@ -812,4 +1274,130 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_delete_from_multi_partition() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![Some(1), Some(2), None]))],
+        )?;
+        let table = Arc::new(MemTable::try_new(
+            schema,
+            vec![vec![batch.clone()], vec![batch.clone(), batch]],
+        )?);
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+
+        let plan = table.delete_from(&state, vec![col("a").gt(lit(1))]).await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 3);
+
+        // Rows for which the predicate evaluates to NULL are kept
+        for partition in table.batches.iter() {
+            for batch in partition.read().await.iter() {
+                let a = batch.column(0).as_primitive::<Int32Type>();
+                assert_eq!(a.iter().collect::<Vec<_>>(), vec![Some(1), None]);
+            }
+        }
+
+        // Deleting without filters removes all remaining rows
+        let plan = table.delete_from(&state, vec![]).await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 6);
+        for partition in table.batches.iter() {
+            assert!(partition.read().await.is_empty());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(Int32Array::from(vec![4, 5, 6])),
+            ],
+        )?;
+        let table = Arc::new(MemTable::try_new(schema, vec![vec![batch]])?);
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+
+        let plan = table
+            .update(
+                &state,
+                vec![("b".to_string(), col("a") + col("b"))],
+                vec![col("a").gt_eq(lit(2))],
+            )
+            .await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 2);
+
+        let batches = table.batches[0].read().await;
+        assert_eq!(batches.len(), 1);
+        let a = batches[0].column(0).as_primitive::<Int32Type>();
+        let b = batches[0].column(1).as_primitive::<Int32Type>();
+        assert_eq!(a.values(), &[1, 2, 3]);
+        assert_eq!(b.values(), &[4, 7, 9]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_error_leaves_table_unchanged() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let batch = |a: Vec<i32>| {
+            let b = Int32Array::from(a.iter().map(|a| a * 10).collect::<Vec<_>>());
+            RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![Arc::new(Int32Array::from(a)), Arc::new(b)],
+            )
+        };
+        let table = Arc::new(
+            MemTable::try_new(
+                Arc::clone(&schema),
+                vec![vec![batch(vec![1, 2])?], vec![batch(vec![3, 4])?]],
+            )?
+            .with_sort_order(vec![vec![col("a").sort(true, false)]]),
+        );
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+
+        // The rows of the first partition are updated, but the rows of the
+        // last partition get a NULL for the non nullable column "b"
+        let value = when(col("a").gt(lit(2)), lit(ScalarValue::Int32(None)))
+            .otherwise(col("b") + lit(1))?;
+        let plan = table
+            .update(&state, vec![("b".to_string(), value)], vec![])
+            .await?;
+        // Planning does not change the table
+        assert_eq!(table.sort_order.lock().len(), 1);
+
+        let err = collect(plan, session_ctx.task_ctx()).await.unwrap_err();
+        assert_contains!(err.to_string(), "non-nullable");
+
+        for (partition, expected) in table.batches.iter().zip([[10, 20], [30, 40]]) {
+            let batches = partition.read().await;
+            assert_eq!(batches.len(), 1);
+            let b = batches[0].column(1).as_primitive::<Int32Type>();
+            assert_eq!(b.values(), &expected);
+        }
+        assert_eq!(table.sort_order.lock().len(), 1);
+
+        // A successful update resets the sort order
+        let plan = table
+            .update(&state, vec![("b".to_string(), lit(0))], vec![])
+            .await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 4);
+        assert!(table.sort_order.lock().is_empty());
+        Ok(())
+    }
 }
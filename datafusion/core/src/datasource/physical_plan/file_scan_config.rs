@@ -38,10 +38,15 @@ use arrow::array::{
 use arrow::buffer::Buffer;
 use arrow::datatypes::{ArrowNativeType, DataType, Field, Schema, SchemaRef, UInt16Type};
 use datafusion_common::stats::Precision;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
 use datafusion_common::{
     exec_err, ColumnStatistics, Constraints, DataFusionError, Statistics,
 };
-use datafusion_physical_expr::{EquivalenceProperties, LexOrdering, Partitioning};
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::utils::collect_columns;
+use datafusion_physical_expr::{
+    EquivalenceProperties, LexOrdering, Partitioning, PhysicalExpr,
+};
 
 use crate::datasource::data_source::FileSource;
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
@@ -276,6 +281,43 @@ impl DataSource for FileScanConfig {
             file_scan.new_exec() as _
         }))
     }
+
+    fn try_pushdown_dynamic_filter(
+        &self,
+        filter: &Arc<dyn PhysicalExpr>,
+    ) -> Option<Arc<dyn DataSource>> {
+        // The filter refers to the projected columns of the scan, while file
+        // sources evaluate predicates against the file schema. Partition
+        // columns are not part of the files, so such filters are not pushed
+        let projection = self
+            .projection
+            .clone()
+            .unwrap_or_else(|| (0..self.file_schema.fields().len()).collect());
+        let file_columns = self.file_schema.fields().len();
+        if collect_columns(filter).iter().any(|column| {
+            projection
+                .get(column.index())
+                .map_or(true, |idx| *idx >= file_columns)
+        }) {
+            return None;
+        }
+        let filter = Arc::clone(filter)
+            .transform_up(|expr| {
+                Ok(match expr.as_any().downcast_ref::<Column>() {
+                    Some(column) => Transformed::yes(Arc::new(Column::new(
+                        column.name(),
+                        projection[column.index()],
+                    )) as _),
+                    None => Transformed::no(expr),
+                })
+            })
+            .data()
+            .ok()?;
+
+        let mut config = self.clone();
+        config.source = self.source.try_pushdown_dynamic_filter(&filter)?;
+        Some(Arc::new(config))
+    }
 }
 
 impl FileScanConfig {
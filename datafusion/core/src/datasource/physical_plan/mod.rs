@@ -220,6 +220,7 @@ where
 }
 
 /// A single file or part of a file that should be read, along with its schema, statistics
+#[derive(Clone)]
 pub struct FileMeta {
     /// Path for the file (e.g. URL, filesystem path, etc)
     pub object_meta: ObjectMeta,
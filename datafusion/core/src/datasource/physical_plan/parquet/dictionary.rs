@@ -0,0 +1,444 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reads the dictionaries of Parquet column chunks to prune Row Groups with
+//! their distinct values
+
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array, Int32Array,
+    Int64Array, StringArray,
+};
+use arrow::datatypes::DataType;
+use bytes::Bytes;
+use datafusion_common::{Column, ScalarValue};
+use datafusion_physical_optimizer::pruning::PruningStatistics;
+
+use parquet::arrow::async_reader::AsyncFileReader;
+use parquet::basic::{Encoding, PageType, Repetition, Type};
+use parquet::column::page::{Page, PageMetadata, PageReader};
+use parquet::column::reader::{get_column_reader, ColumnReader, ColumnReaderImpl};
+use parquet::data_type::DataType as ParquetDataType;
+use parquet::errors::{ParquetError, Result};
+use parquet::file::metadata::ColumnChunkMetaData;
+use parquet::file::page_index::offset_index::OffsetIndexMetaData;
+use parquet::file::reader::{ChunkReader, Length};
+use parquet::file::serialized_reader::SerializedPageReader;
+use parquet::format::{PageHeader, PageLocation};
+use parquet::schema::types::{ColumnDescriptor, ColumnPath, Type as SchemaType};
+use parquet::thrift::TSerializable;
+use thrift::protocol::TCompactInputProtocol;
+
+/// Number of bytes read at the start of each data page to decode its header.
+///
+/// This holds the headers of most data pages, the whole data page is read
+/// for the longer ones, e.g. with large statistics.
+const PAGE_HEADER_PREFIX_SIZE: usize = 64;
+
+/// Reads the distinct values of the column chunk `column`, whose arrow type
+/// is `data_type`, from its dictionary page.
+///
+/// Returns `None` if the values can not be known, e.g. if some data pages of
+/// the column chunk are not dictionary encoded (writers fall back to plain
+/// encoding when dictionaries grow too large) or if `data_type` is not
+/// supported.
+///
+/// Whether all the data pages are dictionary encoded is known from the page
+/// encoding statistics of the column chunk, if any. Otherwise, the headers of
+/// the data pages are read using their locations in `offset_index`.
+pub(super) async fn read_dictionary<T: AsyncFileReader>(
+    reader: &mut T,
+    column: &ColumnChunkMetaData,
+    offset_index: Option<&OffsetIndexMetaData>,
+    data_type: &DataType,
+) -> Result<Option<ArrayRef>> {
+    if value_type(data_type, column.column_type()).is_none() {
+        return Ok(None);
+    }
+    let data_page_offset = column.data_page_offset() as usize;
+    let Some(dictionary_page_offset) = column
+        .dictionary_page_offset()
+        .map(|offset| offset as usize)
+        .filter(|offset| *offset > 0 && *offset < data_page_offset)
+    else {
+        return Ok(None);
+    };
+
+    let locations = match is_dictionary_encoded(column) {
+        Some(true) => &[][..],
+        Some(false) => return Ok(None),
+        None => match offset_index
+            .map(|index| index.page_locations().as_slice())
+            .filter(|l| l.first().map(|l| l.offset as usize) == Some(data_page_offset))
+        {
+            Some(locations) => locations,
+            None => return Ok(None),
+        },
+    };
+
+    let ranges = std::iter::once(dictionary_page_offset..data_page_offset)
+        .chain(
+            locations
+                .iter()
+                .map(|location| page_range(location, PAGE_HEADER_PREFIX_SIZE)),
+        )
+        .collect();
+    let mut data = reader.get_byte_ranges(ranges).await?.into_iter();
+    let Some(dictionary_page) = data.next() else {
+        return Err(ParquetError::EOF("Missing dictionary page".to_string()));
+    };
+    if !all_dictionary_encoded_pages(reader, locations, data.collect()).await? {
+        return Ok(None);
+    }
+
+    let chunk = Arc::new(DictionaryPageChunk {
+        offset: dictionary_page_offset as u64,
+        data: dictionary_page,
+    });
+    let mut pages =
+        SerializedPageReader::new(chunk, column, column.num_values() as usize, None)?;
+    match pages.get_next_page()? {
+        Some(Page::DictionaryPage {
+            buf,
+            num_values,
+            encoding: Encoding::PLAIN | Encoding::PLAIN_DICTIONARY,
+            ..
+        }) => Ok(
+            decode_plain(buf, num_values as usize, column.column_type())?
+                .and_then(|values| to_value_type(values, data_type)),
+        ),
+        _ => Ok(None),
+    }
+}
+
+/// Returns whether all the data pages of `column` are dictionary encoded,
+/// if this can be known from its metadata
+#[allow(deprecated)]
+fn is_dictionary_encoded(column: &ColumnChunkMetaData) -> Option<bool> {
+    if let Some(stats) = column.page_encoding_stats() {
+        return Some(
+            stats
+                .iter()
+                .filter(|s| {
+                    matches!(s.page_type, PageType::DATA_PAGE | PageType::DATA_PAGE_V2)
+                })
+                .all(|s| is_dictionary_encoding(s.encoding)),
+        );
+    }
+    let encodings = column.encodings();
+    if !encodings.iter().any(|e| is_dictionary_encoding(*e)) {
+        return Some(false);
+    }
+    // Writers of the version 1 of the format use `PLAIN_DICTIONARY` for the
+    // dictionary and data pages, and `RLE` or `BIT_PACKED` for the levels
+    if encodings.iter().all(|e| {
+        matches!(
+            e,
+            Encoding::PLAIN_DICTIONARY | Encoding::RLE | Encoding::BIT_PACKED
+        )
+    }) {
+        return Some(true);
+    }
+    None
+}
+
+fn is_dictionary_encoding(encoding: Encoding) -> bool {
+    matches!(
+        encoding,
+        Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY
+    )
+}
+
+/// Returns the range of the first `len` bytes of the page at `location`
+fn page_range(location: &PageLocation, len: usize) -> Range<usize> {
+    let start = location.offset as usize;
+    start..start + len.min(location.compressed_page_size as usize)
+}
+
+/// Returns whether all the data pages at `locations`, whose headers start
+/// `prefixes`, are dictionary encoded.
+///
+/// The pages whose header is longer than their prefix are read again as a
+/// whole to decode it.
+async fn all_dictionary_encoded_pages<T: AsyncFileReader>(
+    reader: &mut T,
+    locations: &[PageLocation],
+    prefixes: Vec<Bytes>,
+) -> Result<bool> {
+    if prefixes.len() != locations.len() {
+        return Err(ParquetError::EOF(format!(
+            "Expected {} data page headers, got {}",
+            locations.len(),
+            prefixes.len()
+        )));
+    }
+    let mut truncated = vec![];
+    for (location, prefix) in locations.iter().zip(&prefixes) {
+        match read_page_header(prefix) {
+            Ok(header) if !is_dictionary_encoded_page(&header) => return Ok(false),
+            Ok(_) => {}
+            Err(_) if prefix.len() < location.compressed_page_size as usize => {
+                truncated.push(page_range(location, usize::MAX));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if truncated.is_empty() {
+        return Ok(true);
+    }
+    for page in reader.get_byte_ranges(truncated).await? {
+        if !is_dictionary_encoded_page(&read_page_header(&page)?) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Decodes the `PageHeader` that starts `data`
+fn read_page_header(data: &[u8]) -> Result<PageHeader> {
+    let mut input = TCompactInputProtocol::new(data);
+    Ok(PageHeader::read_from_in_protocol(&mut input)?)
+}
+
+/// Returns true if `header` is the header of a dictionary encoded data page
+fn is_dictionary_encoded_page(header: &PageHeader) -> bool {
+    let encoding = match (&header.data_page_header, &header.data_page_header_v2) {
+        (Some(header), _) => header.encoding,
+        (_, Some(header)) => header.encoding,
+        _ => return false,
+    };
+    Encoding::try_from(encoding).is_ok_and(is_dictionary_encoding)
+}
+
+/// Returns the type of the values of arrow type `data_type` stored with the
+/// parquet `physical_type`, if their dictionaries can be decoded
+fn value_type(data_type: &DataType, physical_type: Type) -> Option<&DataType> {
+    match (data_type, physical_type) {
+        (DataType::Dictionary(_, values), _) => value_type(values, physical_type),
+        (
+            DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Utf8View
+            | DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView,
+            Type::BYTE_ARRAY,
+        )
+        | (
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Date32,
+            Type::INT32,
+        )
+        | (DataType::Int64, Type::INT64)
+        | (DataType::Float32, Type::FLOAT)
+        | (DataType::Float64, Type::DOUBLE) => Some(data_type),
+        _ => None,
+    }
+}
+
+/// Decodes the `num_values` plain encoded values of `physical_type` of a
+/// dictionary page, read as the values of a data page of a required column
+fn decode_plain(
+    buf: Bytes,
+    num_values: usize,
+    physical_type: Type,
+) -> Result<Option<ArrayRef>> {
+    let value_type = SchemaType::primitive_type_builder("value", physical_type)
+        .with_repetition(Repetition::REQUIRED)
+        .build()?;
+    let column =
+        ColumnDescriptor::new(Arc::new(value_type), 0, 0, ColumnPath::from("value"));
+    let page = Page::DataPage {
+        buf,
+        num_values: num_values as u32,
+        encoding: Encoding::PLAIN,
+        def_level_encoding: Encoding::RLE,
+        rep_level_encoding: Encoding::RLE,
+        statistics: None,
+    };
+    let pages = Box::new(InMemoryPageReader(vec![page].into_iter()));
+
+    let values: ArrayRef = match get_column_reader(Arc::new(column), pages) {
+        ColumnReader::ByteArrayColumnReader(mut reader) => {
+            let values = read_values(&mut reader, num_values)?;
+            Arc::new(BinaryArray::from_iter_values(
+                values.iter().map(|value| value.data()),
+            ))
+        }
+        ColumnReader::Int32ColumnReader(mut reader) => {
+            Arc::new(Int32Array::from(read_values(&mut reader, num_values)?))
+        }
+        ColumnReader::Int64ColumnReader(mut reader) => {
+            Arc::new(Int64Array::from(read_values(&mut reader, num_values)?))
+        }
+        ColumnReader::FloatColumnReader(mut reader) => {
+            Arc::new(Float32Array::from(read_values(&mut reader, num_values)?))
+        }
+        ColumnReader::DoubleColumnReader(mut reader) => {
+            Arc::new(Float64Array::from(read_values(&mut reader, num_values)?))
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(values))
+}
+
+/// Reads the `num_values` values of a required column with `reader`
+fn read_values<T: ParquetDataType>(
+    reader: &mut ColumnReaderImpl<T>,
+    num_values: usize,
+) -> Result<Vec<T::T>> {
+    let mut values = Vec::with_capacity(num_values);
+    let (records, _, _) = reader.read_records(num_values, None, None, &mut values)?;
+    if records != num_values {
+        return Err(ParquetError::EOF(format!(
+            "Expected {num_values} values in dictionary page, got {records}"
+        )));
+    }
+    Ok(values)
+}
+
+/// A [`PageReader`] over pages in memory
+struct InMemoryPageReader(std::vec::IntoIter<Page>);
+
+impl Iterator for InMemoryPageReader {
+    type Item = Result<Page>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Ok)
+    }
+}
+
+impl PageReader for InMemoryPageReader {
+    fn get_next_page(&mut self) -> Result<Option<Page>> {
+        Ok(self.0.next())
+    }
+
+    fn peek_next_page(&mut self) -> Result<Option<PageMetadata>> {
+        Ok(self.0.as_slice().first().map(|page| PageMetadata {
+            num_rows: None,
+            num_levels: Some(page.num_values() as usize),
+            is_dict: matches!(page, Page::DictionaryPage { .. }),
+        }))
+    }
+
+    fn skip_next_page(&mut self) -> Result<()> {
+        self.0.next();
+        Ok(())
+    }
+}
+
+/// Converts the decoded `values` to the value type of `data_type`
+fn to_value_type(values: ArrayRef, data_type: &DataType) -> Option<ArrayRef> {
+    match data_type {
+        DataType::Dictionary(_, value_type) => to_value_type(values, value_type),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            // invalid strings can not be read anyway
+            let values = values.as_any().downcast_ref::<BinaryArray>()?;
+            let values = StringArray::try_from_binary(values.clone()).ok()?;
+            Some(Arc::new(values))
+        }
+        DataType::Int8 | DataType::Int16 | DataType::Date32 => {
+            arrow::compute::cast(&values, data_type).ok()
+        }
+        _ => Some(values),
+    }
+}
+
+/// A [`ChunkReader`] over the bytes of a dictionary page, located at `offset`
+/// in the file
+struct DictionaryPageChunk {
+    offset: u64,
+    data: Bytes,
+}
+
+impl DictionaryPageChunk {
+    fn slice(&self, start: u64, length: Option<usize>) -> Result<Bytes> {
+        let start = start
+            .checked_sub(self.offset)
+            .map(|start| start as usize)
+            .filter(|start| *start <= self.data.len())
+            .ok_or_else(|| ParquetError::EOF("Out of dictionary page".to_string()))?;
+        let end = length.map_or(self.data.len(), |length| start + length);
+        if end > self.data.len() {
+            return Err(ParquetError::EOF("Out of dictionary page".to_string()));
+        }
+        Ok(self.data.slice(start..end))
+    }
+}
+
+impl Length for DictionaryPageChunk {
+    fn len(&self) -> u64 {
+        self.offset + self.data.len() as u64
+    }
+}
+
+impl ChunkReader for DictionaryPageChunk {
+    type T = Cursor<Bytes>;
+
+    fn get_read(&self, start: u64) -> Result<Self::T> {
+        Ok(Cursor::new(self.slice(start, None)?))
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> Result<Bytes> {
+        self.slice(start, Some(length))
+    }
+}
+
+/// Implements [`PruningStatistics`] for the dictionaries of the column chunks
+/// of a Row Group
+pub(super) struct DictionaryStatistics {
+    /// Maps column name to the distinct values of its column chunk
+    pub(super) column_values: HashMap<String, ArrayRef>,
+}
+
+impl PruningStatistics for DictionaryStatistics {
+    fn min_values(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+
+    fn max_values(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+
+    fn num_containers(&self) -> usize {
+        1
+    }
+
+    fn null_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+
+    fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+
+    fn contained(
+        &self,
+        _column: &Column,
+        _values: &HashSet<ScalarValue>,
+    ) -> Option<BooleanArray> {
+        None
+    }
+
+    fn distinct_values(&self, column: &Column) -> Option<Vec<Option<ArrayRef>>> {
+        let values = self.column_values.get(column.name.as_str())?;
+        Some(vec![Some(Arc::clone(values))])
+    }
+}
@@ -33,10 +33,18 @@ pub struct ParquetFileMetrics {
     pub row_groups_matched_bloom_filter: Count,
     /// Number of row groups pruned by bloom filters
     pub row_groups_pruned_bloom_filter: Count,
+    /// Number of row groups whose dictionaries were checked and matched (not pruned)
+    pub row_groups_matched_dictionary: Count,
+    /// Number of row groups pruned by dictionaries
+    pub row_groups_pruned_dictionary: Count,
     /// Number of row groups whose statistics were checked and matched (not pruned)
     pub row_groups_matched_statistics: Count,
     /// Number of row groups pruned by statistics
     pub row_groups_pruned_statistics: Count,
+    /// Number of row groups pruned by statistics right before being read,
+    /// because a dynamic filter, such as the filter of a TopK or of a hash
+    /// join, changed after the file was opened
+    pub row_groups_pruned_dynamic_filter: Count,
     /// Total number of bytes scanned
     pub bytes_scanned: Count,
     /// Total rows filtered out by predicates pushed into parquet scan
@@ -49,6 +57,8 @@ pub struct ParquetFileMetrics {
     pub statistics_eval_time: Time,
     /// Total time spent evaluating row group Bloom Filters
     pub bloom_filter_eval_time: Time,
+    /// Total time spent reading and evaluating row group dictionaries
+    pub dictionary_eval_time: Time,
     /// Total rows filtered out by parquet page index
     pub page_index_rows_pruned: Count,
     /// Total rows passed through the parquet page index
@@ -78,6 +88,14 @@ impl ParquetFileMetrics {
             .with_new_label("filename", filename.to_string())
             .counter("row_groups_pruned_bloom_filter", partition);
 
+        let row_groups_matched_dictionary = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("row_groups_matched_dictionary", partition);
+
+        let row_groups_pruned_dictionary = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("row_groups_pruned_dictionary", partition);
+
         let row_groups_matched_statistics = MetricBuilder::new(metrics)
             .with_new_label("filename", filename.to_string())
             .counter("row_groups_matched_statistics", partition);
@@ -86,6 +104,10 @@ impl ParquetFileMetrics {
             .with_new_label("filename", filename.to_string())
             .counter("row_groups_pruned_statistics", partition);
 
+        let row_groups_pruned_dynamic_filter = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("row_groups_pruned_dynamic_filter", partition);
+
         let bytes_scanned = MetricBuilder::new(metrics)
             .with_new_label("filename", filename.to_string())
             .counter("bytes_scanned", partition);
@@ -106,6 +128,9 @@ impl ParquetFileMetrics {
         let bloom_filter_eval_time = MetricBuilder::new(metrics)
             .with_new_label("filename", filename.to_string())
             .subset_time("bloom_filter_eval_time", partition);
+        let dictionary_eval_time = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .subset_time("dictionary_eval_time", partition);
 
         let page_index_rows_pruned = MetricBuilder::new(metrics)
             .with_new_label("filename", filename.to_string())
@@ -126,8 +151,11 @@ impl ParquetFileMetrics {
             predicate_evaluation_errors,
             row_groups_matched_bloom_filter,
             row_groups_pruned_bloom_filter,
+            row_groups_matched_dictionary,
+            row_groups_pruned_dictionary,
             row_groups_matched_statistics,
             row_groups_pruned_statistics,
+            row_groups_pruned_dynamic_filter,
             bytes_scanned,
             pushdown_rows_pruned,
             pushdown_rows_matched,
@@ -136,6 +164,7 @@ impl ParquetFileMetrics {
             page_index_rows_matched,
             statistics_eval_time,
             bloom_filter_eval_time,
+            dictionary_eval_time,
             page_index_eval_time,
             metadata_load_time,
         }
@@ -18,6 +18,7 @@
 //! [`ParquetExec`] FileSource for reading Parquet files
 
 mod access_plan;
+mod dictionary;
 mod metrics;
 mod opener;
 mod page_filter;
@@ -56,7 +57,9 @@ use datafusion_physical_plan::execution_plan::{Boundedness, EmissionType};
 use datafusion_physical_plan::source::DataSourceExec;
 pub use metrics::ParquetFileMetrics;
 pub use page_filter::PagePruningAccessPlanFilter;
-pub use reader::{DefaultParquetFileReaderFactory, ParquetFileReaderFactory};
+pub use reader::{
+    CachedParquetMetaData, DefaultParquetFileReaderFactory, ParquetFileReaderFactory,
+};
 pub use row_filter::can_expr_be_pushed_down_with_schemas;
 pub use row_group_filter::RowGroupAccessPlanFilter;
 pub use writer::plan_to_parquet;
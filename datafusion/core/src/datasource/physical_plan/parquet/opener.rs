@@ -30,20 +30,28 @@ use crate::datasource::physical_plan::parquet::{
 use crate::datasource::physical_plan::{
     FileMeta, FileOpenFuture, FileOpener, ParquetFileMetrics, ParquetFileReaderFactory,
 };
-use crate::datasource::schema_adapter::SchemaAdapterFactory;
+use crate::datasource::schema_adapter::{SchemaAdapterFactory, SchemaMapper};
 
+use arrow::array::RecordBatch;
 use arrow::datatypes::SchemaRef;
 use arrow::error::ArrowError;
-use datafusion_common::{exec_err, Result};
+use datafusion_common::{exec_err, Result, ScalarValue};
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{
+    dynamic_filter_generation, has_incremental_dynamic_filter, snapshot_physical_expr,
+    BinaryExpr, Literal,
+};
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
 use datafusion_physical_optimizer::pruning::PruningPredicate;
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 
+use futures::stream::BoxStream;
 use futures::{StreamExt, TryStreamExt};
 use log::debug;
 use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ArrowReaderOptions};
 use parquet::arrow::async_reader::AsyncFileReader;
 use parquet::arrow::{ParquetRecordBatchStreamBuilder, ProjectionMask};
+use parquet::file::metadata::ParquetMetaData;
 
 /// Implements [`FileOpener`] for a parquet file
 pub(super) struct ParquetOpener {
@@ -61,6 +69,10 @@ pub(super) struct ParquetOpener {
     pub pruning_predicate: Option<Arc<PruningPredicate>>,
     /// Optional pruning predicate applied to data page statistics
     pub page_pruning_predicate: Option<Arc<PagePruningAccessPlanFilter>>,
+    /// Optional dynamic filter, combined with `predicate` when opening each file
+    /// and, as it may change while the file is read, before reading each of its
+    /// row groups
+    pub dynamic_filter: Option<Arc<dyn PhysicalExpr>>,
     /// Schema of the output table
     pub table_schema: SchemaRef,
     /// Optional hint for how large the initial request to read parquet metadata
@@ -81,6 +93,9 @@ pub(super) struct ParquetOpener {
     /// Should the bloom filter be read from parquet, if present, to skip row
     /// groups
     pub enable_bloom_filter: bool,
+    /// Should the dictionaries of fully dictionary encoded column chunks be
+    /// read from parquet to skip row groups
+    pub enable_dictionary_pruning: bool,
     /// Schema adapter factory
     pub schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
 }
@@ -95,6 +110,29 @@ impl FileOpener for ParquetOpener {
 
         let metadata_size_hint = file_meta.metadata_size_hint.or(self.metadata_size_hint);
 
+        // The generation of the dynamic filter when the file is opened, to
+        // tell whether it changes while the file is opened
+        let opened_generation = self
+            .dynamic_filter
+            .as_ref()
+            .map(dynamic_filter_generation)
+            .transpose()?;
+        let incremental_dynamic_filter = self
+            .dynamic_filter
+            .as_ref()
+            .map(has_incremental_dynamic_filter)
+            .transpose()?
+            .unwrap_or(false);
+        // Reading the row groups one at a time needs a reader for each of them
+        let row_group_file_meta = self
+            .dynamic_filter
+            .as_ref()
+            .filter(|_| self.limit.is_none())
+            .map(|_| file_meta.clone());
+        // Reading the dictionaries needs a reader too, as the one of the file
+        // is moved into the stream builder
+        let dictionary_file_meta =
+            self.enable_dictionary_pruning.then(|| file_meta.clone());
         let mut reader: Box<dyn AsyncFileReader> =
             self.parquet_file_reader_factory.create_reader(
                 self.partition_index,
@@ -113,17 +151,63 @@ impl FileOpener for ParquetOpener {
         let predicate = self.predicate.clone();
         let pruning_predicate = self.pruning_predicate.clone();
         let page_pruning_predicate = self.page_pruning_predicate.clone();
+        let dynamic_filter = self.dynamic_filter.clone();
         let table_schema = Arc::clone(&self.table_schema);
         let reorder_predicates = self.reorder_filters;
         let pushdown_filters = self.pushdown_filters;
-        let enable_page_index = should_enable_page_index(
-            self.enable_page_index,
-            &self.page_pruning_predicate,
-        );
+        let enable_page_index = self.enable_page_index;
         let enable_bloom_filter = self.enable_bloom_filter;
         let limit = self.limit;
+        let partition_index = self.partition_index;
+        let metrics = self.metrics.clone();
+        let parquet_file_reader_factory = Arc::clone(&self.parquet_file_reader_factory);
 
         Ok(Box::pin(async move {
+            let static_predicates = (
+                predicate.clone(),
+                pruning_predicate.clone(),
+                page_pruning_predicate.clone(),
+            );
+            // The value of the dynamic filter may change until the file is
+            // opened, so the predicates derived from it are built here
+            let snapshot_generation = dynamic_filter
+                .as_ref()
+                .map(dynamic_filter_generation)
+                .transpose()?;
+            let dynamic_filter_snapshot = dynamic_filter
+                .as_ref()
+                .map(|filter| snapshot_physical_expr(Arc::clone(filter)))
+                .transpose()?;
+            // Only read the row groups one at a time if the dynamic filter is
+            // expected to change while the file is read: either it is tightened
+            // many times, such as the filter of a TopK, or it already changed
+            // since the file was opened
+            let row_group_file_meta = row_group_file_meta.filter(|_| {
+                incremental_dynamic_filter || snapshot_generation != opened_generation
+            });
+            let (predicate, pruning_predicate, page_pruning_predicate) =
+                apply_dynamic_filter(
+                    predicate,
+                    pruning_predicate,
+                    page_pruning_predicate,
+                    dynamic_filter_snapshot.clone(),
+                    &table_schema,
+                )?;
+            // The page index is needed to prune the pages with the future
+            // values of the dynamic filter, if any, and its offset index to
+            // locate the data page headers checked to prune with dictionaries
+            let dictionary_pruning_predicate = pruning_predicate
+                .as_ref()
+                .filter(|p| !p.distinct_value_columns().is_empty())
+                .filter(|_| dictionary_file_meta.is_some());
+            let enable_page_index = if row_group_file_meta.is_some()
+                || dictionary_pruning_predicate.is_some()
+            {
+                enable_page_index
+            } else {
+                should_enable_page_index(enable_page_index, &page_pruning_predicate)
+            };
+
             let options = ArrowReaderOptions::new().with_page_index(enable_page_index);
 
             let mut metadata_timer = file_metrics.metadata_load_time.timer();
@@ -151,13 +235,18 @@ impl FileOpener for ParquetOpener {
 
             metadata_timer.stop();
 
-            let mut builder =
-                ParquetRecordBatchStreamBuilder::new_with_metadata(reader, metadata);
+            let mut builder = ParquetRecordBatchStreamBuilder::new_with_metadata(
+                reader,
+                metadata.clone(),
+            );
 
             let file_schema = Arc::clone(builder.schema());
 
             let (schema_mapping, adapted_projections) =
                 schema_adapter.map_schema(&file_schema)?;
+            // The predicates refer to the columns of the table, so they are
+            // evaluated against the file schema with their names
+            let file_schema = schema_adapter.map_file_schema_names(&file_schema);
 
             let mask = ProjectionMask::roots(
                 builder.parquet_schema(),
@@ -165,35 +254,23 @@ impl FileOpener for ParquetOpener {
             );
 
             // Filter pushdown: evaluate predicates during scan
-            if let Some(predicate) = pushdown_filters.then_some(predicate).flatten() {
-                let row_filter = row_filter::build_row_filter(
-                    &predicate,
+            if let Some(predicate) = predicate.as_ref().filter(|_| pushdown_filters) {
+                if let Some(filter) = build_row_filter(
+                    predicate,
                     &file_schema,
                     &table_schema,
                     builder.metadata(),
                     reorder_predicates,
                     &file_metrics,
-                    Arc::clone(&schema_mapping),
-                );
-
-                match row_filter {
-                    Ok(Some(filter)) => {
-                        builder = builder.with_row_filter(filter);
-                    }
-                    Ok(None) => {}
-                    Err(e) => {
-                        debug!(
-                            "Ignoring error building row filter for '{:?}': {}",
-                            predicate, e
-                        );
-                    }
-                };
+                    &schema_mapping,
+                ) {
+                    builder = builder.with_row_filter(filter);
+                }
             };
 
             // Determine which row groups to actually read. The idea is to skip
             // as many row groups as possible based on the metadata and query
             let file_metadata = Arc::clone(builder.metadata());
-            let predicate = pruning_predicate.as_ref().map(|p| p.as_ref());
             let rg_metadata = file_metadata.row_groups();
             // track which row groups to actually read
             let access_plan =
@@ -204,7 +281,7 @@ impl FileOpener for ParquetOpener {
                 row_groups.prune_by_range(rg_metadata, range);
             }
             // If there is a predicate that can be evaluated against the metadata
-            if let Some(predicate) = predicate.as_ref() {
+            if let Some(predicate) = pruning_predicate.as_deref() {
                 row_groups.prune_by_statistics(
                     &file_schema,
                     builder.parquet_schema(),
@@ -223,6 +300,30 @@ impl FileOpener for ParquetOpener {
                         )
                         .await;
                 }
+
+                if let (Some(file_meta), Some(predicate)) =
+                    (dictionary_file_meta, dictionary_pruning_predicate)
+                {
+                    if !row_groups.is_empty() {
+                        let mut reader: Box<dyn AsyncFileReader> =
+                            parquet_file_reader_factory.create_reader(
+                                partition_index,
+                                file_meta,
+                                metadata_size_hint,
+                                &metrics,
+                            )?;
+                        row_groups
+                            .prune_by_dictionaries(
+                                &file_schema,
+                                builder.parquet_schema(),
+                                file_metadata.as_ref(),
+                                &mut reader,
+                                predicate,
+                                &file_metrics,
+                            )
+                            .await;
+                    }
+                }
             }
 
             let mut access_plan = row_groups.build();
@@ -231,7 +332,7 @@ impl FileOpener for ParquetOpener {
             // be ruled using page metadata, rows from other columns
             // with that range can be skipped as well
             if enable_page_index && !access_plan.is_empty() {
-                if let Some(p) = page_pruning_predicate {
+                if let Some(p) = &page_pruning_predicate {
                     access_plan = p.prune_plan_with_page_index(
                         access_plan,
                         &file_schema,
@@ -242,6 +343,39 @@ impl FileOpener for ParquetOpener {
                 }
             }
 
+            // Dynamic filters, such as the filter of a TopK, may become more
+            // selective while the file is read: read the row groups one at a
+            // time, pruning each of them with the latest value of the filter
+            if let (Some(file_meta), Some(dynamic_filter), Some(generation)) =
+                (row_group_file_meta, dynamic_filter, snapshot_generation)
+            {
+                let row_groups = DynamicFilterRowGroups {
+                    partition_index,
+                    file_meta,
+                    metadata_size_hint,
+                    metrics,
+                    parquet_file_reader_factory,
+                    reader_metadata: metadata,
+                    file_schema,
+                    table_schema,
+                    schema_mapping,
+                    mask,
+                    batch_size,
+                    pushdown_filters,
+                    reorder_predicates,
+                    enable_page_index,
+                    file_metrics: file_metrics.clone(),
+                    static_predicates,
+                    dynamic_filter,
+                    opened_generation: generation,
+                    generation,
+                    predicates: (predicate, pruning_predicate, page_pruning_predicate),
+                    row_groups: access_plan.row_group_indexes().into_iter(),
+                    access_plan,
+                };
+                return Ok(row_groups.into_stream());
+            }
+
             let row_group_indexes = access_plan.row_group_indexes();
             if let Some(row_selection) =
                 access_plan.into_overall_row_selection(rg_metadata)?
@@ -271,6 +405,253 @@ impl FileOpener for ParquetOpener {
     }
 }
 
+/// Builds the [`RowFilter`] evaluating `predicate` while decoding the rows of a
+/// file, ignoring errors
+///
+/// [`RowFilter`]: parquet::arrow::arrow_reader::RowFilter
+fn build_row_filter(
+    predicate: &Arc<dyn PhysicalExpr>,
+    file_schema: &SchemaRef,
+    table_schema: &SchemaRef,
+    metadata: &ParquetMetaData,
+    reorder_predicates: bool,
+    file_metrics: &ParquetFileMetrics,
+    schema_mapping: &Arc<dyn SchemaMapper>,
+) -> Option<parquet::arrow::arrow_reader::RowFilter> {
+    let row_filter = row_filter::build_row_filter(
+        predicate,
+        file_schema,
+        table_schema,
+        metadata,
+        reorder_predicates,
+        file_metrics,
+        Arc::clone(schema_mapping),
+    );
+    match row_filter {
+        Ok(filter) => filter,
+        Err(e) => {
+            debug!(
+                "Ignoring error building row filter for '{:?}': {}",
+                predicate, e
+            );
+            None
+        }
+    }
+}
+
+/// Reads the row groups of a parquet file one at a time, pruning each of them
+/// with the latest value of a dynamic filter right before reading it.
+///
+/// The row groups were already pruned with the value of the filter when the
+/// file was opened, so they are only pruned again once the filter changed.
+struct DynamicFilterRowGroups {
+    partition_index: usize,
+    file_meta: FileMeta,
+    metadata_size_hint: Option<usize>,
+    metrics: ExecutionPlanMetricsSet,
+    parquet_file_reader_factory: Arc<dyn ParquetFileReaderFactory>,
+    reader_metadata: ArrowReaderMetadata,
+    file_schema: SchemaRef,
+    table_schema: SchemaRef,
+    schema_mapping: Arc<dyn SchemaMapper>,
+    mask: ProjectionMask,
+    batch_size: usize,
+    pushdown_filters: bool,
+    reorder_predicates: bool,
+    enable_page_index: bool,
+    file_metrics: ParquetFileMetrics,
+    /// The predicates of the scan, without the dynamic filter
+    static_predicates: ScanPredicates,
+    dynamic_filter: Arc<dyn PhysicalExpr>,
+    /// The generation of the dynamic filter when the file was opened
+    opened_generation: u64,
+    /// The generation of the dynamic filter combined with `predicates`
+    generation: u64,
+    /// The predicates of the scan, combined with the dynamic filter
+    predicates: ScanPredicates,
+    /// The row groups still to read
+    row_groups: std::vec::IntoIter<usize>,
+    /// The access plan the file was opened with
+    access_plan: ParquetAccessPlan,
+}
+
+impl DynamicFilterRowGroups {
+    fn into_stream(self) -> BoxStream<'static, Result<RecordBatch, ArrowError>> {
+        futures::stream::try_unfold(self, |mut row_groups| async move {
+            match row_groups.next_row_group() {
+                Ok(Some(stream)) => Ok(Some((stream, row_groups))),
+                Ok(None) => Ok(None),
+                Err(e) => Err(ArrowError::ExternalError(Box::new(e))),
+            }
+        })
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Returns the stream of the next row group that can not be pruned with
+    /// the latest value of the dynamic filter, if any
+    fn next_row_group(
+        &mut self,
+    ) -> Result<Option<BoxStream<'static, Result<RecordBatch, ArrowError>>>> {
+        let file_metadata = Arc::clone(self.reader_metadata.metadata());
+        let rg_metadata = file_metadata.row_groups();
+        for idx in self.row_groups.by_ref() {
+            let generation = dynamic_filter_generation(&self.dynamic_filter)?;
+            if generation != self.generation {
+                let snapshot = snapshot_physical_expr(Arc::clone(&self.dynamic_filter))?;
+                let (predicate, pruning_predicate, page_pruning_predicate) =
+                    self.static_predicates.clone();
+                self.predicates = apply_dynamic_filter(
+                    predicate,
+                    pruning_predicate,
+                    page_pruning_predicate,
+                    Some(snapshot),
+                    &self.table_schema,
+                )?;
+                self.generation = generation;
+            }
+
+            let mut access_plan = ParquetAccessPlan::new_none(rg_metadata.len());
+            access_plan.set(idx, self.access_plan.inner()[idx].clone());
+            if self.generation != self.opened_generation {
+                let parquet_schema = self.reader_metadata.parquet_schema();
+                if let Some(pruning_predicate) = &self.predicates.1 {
+                    let mut row_groups = RowGroupAccessPlanFilter::new(access_plan);
+                    row_groups.prune_by_dynamic_filter(
+                        &self.file_schema,
+                        parquet_schema,
+                        rg_metadata,
+                        pruning_predicate,
+                        &self.file_metrics,
+                    );
+                    access_plan = row_groups.build();
+                }
+                if let Some(page_pruning_predicate) = self
+                    .predicates
+                    .2
+                    .as_ref()
+                    .filter(|_| self.enable_page_index && !access_plan.is_empty())
+                {
+                    access_plan = page_pruning_predicate.prune_plan_with_page_index(
+                        access_plan,
+                        &self.file_schema,
+                        parquet_schema,
+                        file_metadata.as_ref(),
+                        &self.file_metrics,
+                    );
+                }
+                if !access_plan.should_scan(idx) {
+                    continue;
+                }
+            }
+
+            let reader: Box<dyn AsyncFileReader> =
+                self.parquet_file_reader_factory.create_reader(
+                    self.partition_index,
+                    self.file_meta.clone(),
+                    self.metadata_size_hint,
+                    &self.metrics,
+                )?;
+            let mut builder = ParquetRecordBatchStreamBuilder::new_with_metadata(
+                reader,
+                self.reader_metadata.clone(),
+            );
+            if let Some(predicate) =
+                self.predicates.0.as_ref().filter(|_| self.pushdown_filters)
+            {
+                if let Some(filter) = build_row_filter(
+                    predicate,
+                    &self.file_schema,
+                    &self.table_schema,
+                    builder.metadata(),
+                    self.reorder_predicates,
+                    &self.file_metrics,
+                    &self.schema_mapping,
+                ) {
+                    builder = builder.with_row_filter(filter);
+                }
+            }
+            if let Some(row_selection) =
+                access_plan.into_overall_row_selection(rg_metadata)?
+            {
+                builder = builder.with_row_selection(row_selection);
+            }
+
+            let stream = builder
+                .with_projection(self.mask.clone())
+                .with_batch_size(self.batch_size)
+                .with_row_groups(vec![idx])
+                .build()?;
+
+            let schema_mapping = Arc::clone(&self.schema_mapping);
+            let adapted = stream
+                .map_err(|e| ArrowError::ExternalError(Box::new(e)))
+                .map(move |maybe_batch| {
+                    maybe_batch
+                        .and_then(|b| schema_mapping.map_batch(b).map_err(Into::into))
+                });
+            return Ok(Some(adapted.boxed()));
+        }
+        Ok(None)
+    }
+}
+
+/// The predicate of a scan, and the row group and page pruning predicates
+/// derived from it
+type ScanPredicates = (
+    Option<Arc<dyn PhysicalExpr>>,
+    Option<Arc<PruningPredicate>>,
+    Option<Arc<PagePruningAccessPlanFilter>>,
+);
+
+/// Combines `dynamic_filter`, a snapshot of the dynamic filter of the scan,
+/// unless it is `true`, with the static `predicate` of the scan, and rebuilds
+/// the pruning predicates derived from the combined predicate
+fn apply_dynamic_filter(
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    pruning_predicate: Option<Arc<PruningPredicate>>,
+    page_pruning_predicate: Option<Arc<PagePruningAccessPlanFilter>>,
+    dynamic_filter: Option<Arc<dyn PhysicalExpr>>,
+    table_schema: &SchemaRef,
+) -> Result<ScanPredicates> {
+    let Some(dynamic_filter) = dynamic_filter else {
+        return Ok((predicate, pruning_predicate, page_pruning_predicate));
+    };
+    if let Some(literal) = dynamic_filter.as_any().downcast_ref::<Literal>() {
+        if literal.value() == &ScalarValue::Boolean(Some(true)) {
+            return Ok((predicate, pruning_predicate, page_pruning_predicate));
+        }
+    }
+
+    let predicate = match predicate {
+        Some(predicate) => {
+            Arc::new(BinaryExpr::new(predicate, Operator::And, dynamic_filter)) as _
+        }
+        None => dynamic_filter,
+    };
+    let pruning_predicate =
+        match PruningPredicate::try_new(Arc::clone(&predicate), Arc::clone(table_schema))
+        {
+            Ok(pruning_predicate) => {
+                (!pruning_predicate.always_true()).then(|| Arc::new(pruning_predicate))
+            }
+            Err(e) => {
+                debug!("Could not create pruning predicate for: {e}");
+                pruning_predicate
+            }
+        };
+    let page_pruning_predicate = Arc::new(PagePruningAccessPlanFilter::new(
+        &predicate,
+        Arc::clone(table_schema),
+    ));
+
+    Ok((
+        Some(predicate),
+        pruning_predicate,
+        Some(page_pruning_predicate),
+    ))
+}
+
 /// Return the initial [`ParquetAccessPlan`]
 ///
 /// If the user has supplied one as an extension, use that
@@ -20,11 +20,14 @@
 
 use crate::datasource::physical_plan::{FileMeta, ParquetFileMetrics};
 use bytes::Bytes;
+use datafusion_execution::cache::cache_manager::{FileMetadata, FileMetadataCache};
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use futures::future::BoxFuture;
-use object_store::ObjectStore;
+use futures::FutureExt;
+use object_store::{ObjectMeta, ObjectStore};
 use parquet::arrow::async_reader::{AsyncFileReader, ParquetObjectReader};
 use parquet::file::metadata::ParquetMetaData;
+use std::any::Any;
 use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
@@ -64,17 +67,58 @@ pub trait ParquetFileReaderFactory: Debug + Send + Sync + 'static {
 ///
 /// This implementation:
 /// 1. Reads parquet directly from an underlying [`ObjectStore`] instance.
-/// 2. Reads the footer and page metadata on demand.
-/// 3. Does not cache metadata or coalesce I/O operations.
+/// 2. Reads the footer and page metadata on demand, or from a
+///    [`FileMetadataCache`] if provided with [`Self::with_file_metadata_cache`].
+/// 3. Does not coalesce I/O operations.
 #[derive(Debug)]
 pub struct DefaultParquetFileReaderFactory {
     store: Arc<dyn ObjectStore>,
+    metadata_cache: Option<FileMetadataCache>,
 }
 
 impl DefaultParquetFileReaderFactory {
     /// Create a new `DefaultParquetFileReaderFactory`.
     pub fn new(store: Arc<dyn ObjectStore>) -> Self {
-        Self { store }
+        Self {
+            store,
+            metadata_cache: None,
+        }
+    }
+
+    /// Cache the metadata of the files, including their page indexes, in
+    /// `metadata_cache`, and read it from there while the files do not change.
+    pub fn with_file_metadata_cache(
+        mut self,
+        metadata_cache: Option<FileMetadataCache>,
+    ) -> Self {
+        self.metadata_cache = metadata_cache;
+        self
+    }
+}
+
+/// The [`ParquetMetaData`] of a file cached in a [`FileMetadataCache`]
+#[derive(Debug)]
+pub struct CachedParquetMetaData(Arc<ParquetMetaData>);
+
+impl CachedParquetMetaData {
+    /// Create a new `CachedParquetMetaData`
+    pub fn new(metadata: Arc<ParquetMetaData>) -> Self {
+        Self(metadata)
+    }
+
+    /// Return the cached [`ParquetMetaData`]
+    pub fn parquet_metadata(&self) -> &Arc<ParquetMetaData> {
+        &self.0
+    }
+}
+
+impl FileMetadata for CachedParquetMetaData {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn memory_size(&self) -> usize {
+        self.0.memory_size()
     }
 }
 
@@ -89,6 +133,8 @@ impl DefaultParquetFileReaderFactory {
 pub(crate) struct ParquetFileReader {
     pub file_metrics: ParquetFileMetrics,
     pub inner: ParquetObjectReader,
+    /// Cache of the metadata of the file, and the file
+    pub metadata_cache: Option<(FileMetadataCache, ObjectMeta)>,
 }
 
 impl AsyncFileReader for ParquetFileReader {
@@ -115,7 +161,32 @@ impl AsyncFileReader for ParquetFileReader {
     fn get_metadata(
         &mut self,
     ) -> BoxFuture<'_, parquet::errors::Result<Arc<ParquetMetaData>>> {
-        self.inner.get_metadata()
+        let Some((metadata_cache, object_meta)) = &self.metadata_cache else {
+            return self.inner.get_metadata();
+        };
+        if let Some(metadata) = metadata_cache
+            .get_with_extra(&object_meta.location, object_meta)
+            .as_ref()
+            .and_then(|metadata| {
+                metadata.as_any().downcast_ref::<CachedParquetMetaData>()
+            })
+        {
+            let metadata = Arc::clone(metadata.parquet_metadata());
+            return futures::future::ready(Ok(metadata)).boxed();
+        }
+
+        let metadata_cache = Arc::clone(metadata_cache);
+        let object_meta = object_meta.clone();
+        async move {
+            let metadata = self.inner.get_metadata().await?;
+            metadata_cache.put_with_extra(
+                &object_meta.location,
+                Arc::new(CachedParquetMetaData::new(Arc::clone(&metadata))),
+                &object_meta,
+            );
+            Ok(metadata)
+        }
+        .boxed()
     }
 }
 
@@ -133,15 +204,27 @@ impl ParquetFileReaderFactory for DefaultParquetFileReaderFactory {
             metrics,
         );
         let store = Arc::clone(&self.store);
+        let metadata_cache = self
+            .metadata_cache
+            .as_ref()
+            .map(|cache| (Arc::clone(cache), file_meta.object_meta.clone()));
         let mut inner = ParquetObjectReader::new(store, file_meta.object_meta);
 
         if let Some(hint) = metadata_size_hint {
             inner = inner.with_footer_size_hint(hint)
         };
+        // The page indexes are cached with the footer, so they are loaded
+        // even if this reader does not use them
+        if metadata_cache.is_some() {
+            inner = inner
+                .with_preload_column_index(true)
+                .with_preload_offset_index(true);
+        }
 
         Ok(Box::new(ParquetFileReader {
             inner,
             file_metrics,
+            metadata_cache,
         }))
     }
 }
@@ -18,8 +18,10 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use super::dictionary::{read_dictionary, DictionaryStatistics};
 use super::{ParquetAccessPlan, ParquetFileMetrics};
 use crate::datasource::listing::FileRange;
+use crate::physical_plan::metrics::Count;
 
 use arrow::array::{ArrayRef, BooleanArray};
 use arrow::datatypes::Schema;
@@ -34,7 +36,7 @@ use parquet::schema::types::SchemaDescriptor;
 use parquet::{
     arrow::{async_reader::AsyncFileReader, ParquetRecordBatchStreamBuilder},
     bloom_filter::Sbbf,
-    file::metadata::RowGroupMetaData,
+    file::metadata::{ParquetMetaData, RowGroupMetaData},
 };
 
 /// Reduces the [`ParquetAccessPlan`] based on row group level metadata.
@@ -110,6 +112,59 @@ impl RowGroupAccessPlanFilter {
         groups: &[RowGroupMetaData],
         predicate: &PruningPredicate,
         metrics: &ParquetFileMetrics,
+    ) {
+        self.prune_by_statistics_inner(
+            arrow_schema,
+            parquet_schema,
+            groups,
+            predicate,
+            metrics,
+            &metrics.row_groups_pruned_statistics,
+            Some(&metrics.row_groups_matched_statistics),
+        )
+    }
+
+    /// Prune remaining row groups using min/max/null_count statistics and
+    /// the [`PruningPredicate`] of a dynamic filter whose value changed since
+    /// the row groups were pruned by [`Self::prune_by_statistics`].
+    ///
+    /// This is used to re-check each row group right before it is read, as
+    /// the filter of a TopK becomes more selective while the previous row
+    /// groups are read, and the filter of a hash join is only known once its
+    /// build side is collected. Pruned row groups are counted in
+    /// `row_groups_pruned_dynamic_filter`.
+    ///
+    /// # Panics
+    /// if `groups.len() != self.len()`
+    pub fn prune_by_dynamic_filter(
+        &mut self,
+        arrow_schema: &Schema,
+        parquet_schema: &SchemaDescriptor,
+        groups: &[RowGroupMetaData],
+        predicate: &PruningPredicate,
+        metrics: &ParquetFileMetrics,
+    ) {
+        self.prune_by_statistics_inner(
+            arrow_schema,
+            parquet_schema,
+            groups,
+            predicate,
+            metrics,
+            &metrics.row_groups_pruned_dynamic_filter,
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn prune_by_statistics_inner(
+        &mut self,
+        arrow_schema: &Schema,
+        parquet_schema: &SchemaDescriptor,
+        groups: &[RowGroupMetaData],
+        predicate: &PruningPredicate,
+        metrics: &ParquetFileMetrics,
+        pruned: &Count,
+        matched: Option<&Count>,
     ) {
         // scoped timer updates on drop
         let _timer_guard = metrics.statistics_eval_time.timer();
@@ -135,9 +190,9 @@ impl RowGroupAccessPlanFilter {
                 for (idx, &value) in row_group_indexes.iter().zip(values.iter()) {
                     if !value {
                         self.access_plan.skip(*idx);
-                        metrics.row_groups_pruned_statistics.add(1);
-                    } else {
-                        metrics.row_groups_matched_statistics.add(1);
+                        pruned.add(1);
+                    } else if let Some(matched) = matched {
+                        matched.add(1);
                     }
                 }
             }
@@ -223,6 +278,94 @@ impl RowGroupAccessPlanFilter {
             }
         }
     }
+
+    /// Prune remaining row groups using the dictionaries of their column
+    /// chunks whose data pages are all dictionary encoded, and the
+    /// [`PruningPredicate`].
+    ///
+    /// `reader` reads the dictionaries and, for the column chunks of files
+    /// without page encoding statistics, the headers of their data pages, whose
+    /// locations are found in the offset index of `metadata`, if loaded.
+    ///
+    /// Updates this set with row groups that should not be scanned
+    ///
+    /// # Panics
+    /// if `metadata` does not have the same number of row groups as this set
+    pub async fn prune_by_dictionaries<T: AsyncFileReader + Send + 'static>(
+        &mut self,
+        arrow_schema: &Schema,
+        parquet_schema: &SchemaDescriptor,
+        metadata: &ParquetMetaData,
+        reader: &mut T,
+        predicate: &PruningPredicate,
+        metrics: &ParquetFileMetrics,
+    ) {
+        // scoped timer updates on drop
+        let _timer_guard = metrics.dictionary_eval_time.timer();
+
+        assert_eq!(metadata.num_row_groups(), self.access_plan.len());
+        let distinct_value_columns = predicate.distinct_value_columns();
+        for idx in 0..self.access_plan.len() {
+            if !self.access_plan.should_scan(idx) {
+                continue;
+            }
+
+            // Attempt to read the dictionaries of this row group
+            let mut column_values = HashMap::with_capacity(distinct_value_columns.len());
+            for column_name in &distinct_value_columns {
+                let Some((column_idx, field)) =
+                    parquet_column(parquet_schema, arrow_schema, column_name)
+                else {
+                    continue;
+                };
+                let offset_index = metadata
+                    .offset_index()
+                    .and_then(|index| index.get(idx)?.get(column_idx));
+
+                match read_dictionary(
+                    reader,
+                    metadata.row_group(idx).column(column_idx),
+                    offset_index,
+                    field.data_type(),
+                )
+                .await
+                {
+                    Ok(Some(values)) => {
+                        column_values.insert(column_name.to_string(), values);
+                    }
+                    Ok(None) => {} // the distinct values are not known
+                    Err(e) => {
+                        log::debug!("Ignoring error reading dictionary: {e}");
+                        metrics.predicate_evaluation_errors.add(1);
+                    }
+                }
+            }
+            if column_values.is_empty() {
+                continue;
+            }
+
+            let stats = DictionaryStatistics { column_values };
+
+            // Can this group be pruned?
+            let prune_group = match predicate.prune(&stats) {
+                Ok(values) => !values[0],
+                Err(e) => {
+                    log::debug!(
+                        "Error evaluating row group predicate on dictionaries: {e}"
+                    );
+                    metrics.predicate_evaluation_errors.add(1);
+                    false
+                }
+            };
+
+            if prune_group {
+                metrics.row_groups_pruned_dictionary.add(1);
+                self.access_plan.skip(idx)
+            } else {
+                metrics.row_groups_matched_dictionary.add(1);
+            }
+        }
+    }
 }
 /// Implements [`PruningStatistics`] for Parquet Split Block Bloom Filters (SBBF)
 struct BloomFilterStatistics {
@@ -431,6 +574,7 @@ impl PruningStatistics for RowGroupPruningStatistics<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Cursor;
     use std::ops::Rem;
     use std::sync::Arc;
 
@@ -444,11 +588,14 @@ mod tests {
     use datafusion_expr::{cast, col, lit, Expr};
     use datafusion_physical_expr::planner::logical2physical;
 
+    use arrow::array::{AsArray, Int32Array, RecordBatch, StringArray};
+    use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ArrowReaderOptions};
     use parquet::arrow::async_reader::ParquetObjectReader;
-    use parquet::arrow::ArrowSchemaConverter;
+    use parquet::arrow::{ArrowSchemaConverter, ArrowWriter};
     use parquet::basic::LogicalType;
     use parquet::data_type::{ByteArray, FixedLenByteArray};
     use parquet::file::metadata::ColumnChunkMetaData;
+    use parquet::file::properties::WriterProperties;
     use parquet::{
         basic::Type as PhysicalType, file::statistics::Statistics as ParquetStatistics,
         schema::types::SchemaDescPtr,
@@ -1409,6 +1556,188 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_row_group_dictionary_pruning() {
+        let test = DictionaryTest::new();
+        // `s` is only "apple" or "apricot" in the first row group, and min/max
+        // values can not prune any row group
+        test.run(col("s").like(lit("%rr%")), &[1, 2]).await;
+        test.run(col("s").like(lit("%ch%")), &[2]).await;
+        test.run(col("s").not_like(lit("ap%")), &[1, 2]).await;
+        test.run(col("s").not_eq(lit("banana")), &[0, 1, 2]).await;
+        test.run(col("s").in_list(vec![lit("apricot")], false), &[0])
+            .await;
+        test.run(
+            col("s")
+                .in_list(vec![lit("banana")], true)
+                .and(col("i").gt(lit(0))),
+            &[0, 1, 2],
+        )
+        .await;
+        // The nulls of the first row group may match
+        test.run(
+            col("s").like(lit("%rr%")).or(col("s").is_null()),
+            &[0, 1, 2],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_row_group_dictionary_pruning_int() {
+        DictionaryTest::new()
+            .run(col("i").in_list(vec![lit(1), lit(2)], true), &[2])
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_row_group_dictionary_pruning_without_page_index() {
+        // Without the offset index, it is not known whether the data pages of
+        // files without page encoding statistics are dictionary encoded
+        DictionaryTest::new()
+            .with_page_index(false)
+            .run(col("s").like(lit("%rr%")), &[0, 1, 2])
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_row_group_dictionary_pruning_long_page_headers() {
+        // The statistics of the long values make the data page headers longer
+        // than the prefix of the pages read to decode them
+        let mut test = DictionaryTest::new();
+        let s = test.batch.column(0).as_string::<i32>();
+        let s: StringArray = s.iter().map(|s| s.map(|s| s.repeat(20))).collect();
+        test.batch = RecordBatch::try_new(
+            test.batch.schema(),
+            vec![Arc::new(s), Arc::clone(test.batch.column(1))],
+        )
+        .unwrap();
+        test.run(col("s").like(lit("%rr%")), &[1, 2]).await;
+    }
+
+    #[tokio::test]
+    async fn test_row_group_dictionary_pruning_plain_encoding() {
+        // Some data pages of the second row group are plain encoded, as its
+        // dictionary is too large
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(4)
+            .set_dictionary_page_size_limit(21)
+            .set_write_batch_size(1)
+            .set_data_page_row_count_limit(1)
+            .build();
+        DictionaryTest::new()
+            .with_properties(props)
+            .run(col("s").like(lit("%ch%")), &[1, 2])
+            .await;
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(4)
+            .set_dictionary_enabled(false)
+            .build();
+        DictionaryTest::new()
+            .with_properties(props)
+            .run(col("s").like(lit("%rr%")), &[0, 1, 2])
+            .await;
+    }
+
+    struct DictionaryTest {
+        batch: RecordBatch,
+        props: WriterProperties,
+        page_index: bool,
+    }
+
+    impl DictionaryTest {
+        /// Return a test for a file with 3 row groups:
+        /// ```text
+        /// s: ["apple", "apricot", "apple", NULL], i: [1, 2, 1, 2]
+        /// s: ["banana", "blueberry", "banana", "banana"], i: [1, 2, 1, 2]
+        /// s: ["cherry", "apple", "cherry", "cherry"], i: [3, 3, 3, 3]
+        /// ```
+        fn new() -> Self {
+            let s = StringArray::from(vec![
+                Some("apple"),
+                Some("apricot"),
+                Some("apple"),
+                None,
+                Some("banana"),
+                Some("blueberry"),
+                Some("banana"),
+                Some("banana"),
+                Some("cherry"),
+                Some("apple"),
+                Some("cherry"),
+                Some("cherry"),
+            ]);
+            let i = Int32Array::from(vec![1, 2, 1, 2, 1, 2, 1, 2, 3, 3, 3, 3]);
+            let batch = RecordBatch::try_from_iter(vec![
+                ("s", Arc::new(s) as ArrayRef),
+                ("i", Arc::new(i) as ArrayRef),
+            ])
+            .unwrap();
+            let props = WriterProperties::builder()
+                .set_max_row_group_size(4)
+                .build();
+            Self {
+                batch,
+                props,
+                page_index: true,
+            }
+        }
+
+        fn with_properties(mut self, props: WriterProperties) -> Self {
+            self.props = props;
+            self
+        }
+
+        fn with_page_index(mut self, page_index: bool) -> Self {
+            self.page_index = page_index;
+            self
+        }
+
+        /// Writes the file, prunes it using the dictionaries and `expr`, and
+        /// checks that the `expected` row groups are left
+        async fn run(&self, expr: Expr, expected: &[usize]) {
+            let mut data = vec![];
+            let mut writer = ArrowWriter::try_new(
+                &mut data,
+                self.batch.schema(),
+                Some(self.props.clone()),
+            )
+            .unwrap();
+            writer.write(&self.batch).unwrap();
+            writer.close().unwrap();
+
+            let mut reader = Cursor::new(bytes::Bytes::from(data));
+            let options = ArrowReaderOptions::new().with_page_index(self.page_index);
+            let metadata = ArrowReaderMetadata::load_async(&mut reader, options)
+                .await
+                .unwrap();
+
+            let schema = self.batch.schema();
+            let expr = logical2physical(&expr, &schema);
+            let pruning_predicate = PruningPredicate::try_new(expr, schema).unwrap();
+
+            let metrics = parquet_file_metrics();
+            let access_plan =
+                ParquetAccessPlan::new_all(metadata.metadata().num_row_groups());
+            let mut row_groups = RowGroupAccessPlanFilter::new(access_plan);
+            row_groups
+                .prune_by_dictionaries(
+                    metadata.schema(),
+                    metadata.parquet_schema(),
+                    metadata.metadata(),
+                    &mut reader,
+                    &pruning_predicate,
+                    &metrics,
+                )
+                .await;
+
+            assert_eq!(row_groups.access_plan.row_group_indexes(), expected);
+            assert_eq!(
+                metrics.row_groups_pruned_dictionary.value(),
+                3 - expected.len()
+            );
+        }
+    }
+
     fn assert_pruned(row_groups: RowGroupAccessPlanFilter, expected: ExpectedPruning) {
         expected.assert(&row_groups);
     }
@@ -1532,6 +1861,7 @@ mod tests {
         let reader = ParquetFileReader {
             inner: ParquetObjectReader::new(Arc::new(in_memory), object_meta),
             file_metrics: file_metrics.clone(),
+            metadata_cache: None,
         };
         let mut builder = ParquetRecordBatchStreamBuilder::new(reader).await.unwrap();
 
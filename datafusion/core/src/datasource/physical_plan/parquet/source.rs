@@ -34,6 +34,9 @@ use crate::datasource::schema_adapter::{
 use arrow::datatypes::{Schema, SchemaRef};
 use datafusion_common::config::TableParquetOptions;
 use datafusion_common::Statistics;
+use datafusion_execution::cache::cache_manager::FileMetadataCache;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::BinaryExpr;
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
 use datafusion_physical_optimizer::pruning::PruningPredicate;
 use datafusion_physical_plan::metrics::{ExecutionPlanMetricsSet, MetricBuilder};
@@ -262,8 +265,14 @@ pub struct ParquetSource {
     pub(crate) pruning_predicate: Option<Arc<PruningPredicate>>,
     /// Optional predicate for pruning pages (derived from `predicate`)
     pub(crate) page_pruning_predicate: Option<Arc<PagePruningAccessPlanFilter>>,
+    /// Optional dynamic filters pushed down from other operators, such as
+    /// hash joins, which are only known while executing the plan
+    pub(crate) dynamic_filter: Option<Arc<dyn PhysicalExpr>>,
     /// Optional user defined parquet file reader factory
     pub(crate) parquet_file_reader_factory: Option<Arc<dyn ParquetFileReaderFactory>>,
+    /// Optional cache of the metadata of the files, used by the default
+    /// parquet file reader factory
+    pub(crate) file_metadata_cache: Option<FileMetadataCache>,
     /// Optional user defined schema adapter
     pub(crate) schema_adapter_factory: Option<Arc<dyn SchemaAdapterFactory>>,
     /// Batch size configuration
@@ -319,7 +328,11 @@ impl ParquetSource {
         match PruningPredicate::try_new(Arc::clone(&predicate), Arc::clone(&file_schema))
         {
             Ok(pruning_predicate) => {
-                if !pruning_predicate.always_true() {
+                // Predicates min/max values can not prune may still prune
+                // row groups with the dictionaries
+                let dictionary_pruning = conf.dictionary_pruning()
+                    && !pruning_predicate.distinct_value_columns().is_empty();
+                if !pruning_predicate.always_true() || dictionary_pruning {
                     conf.pruning_predicate = Some(Arc::new(pruning_predicate));
                 }
             }
@@ -348,6 +361,11 @@ impl ParquetSource {
         self.predicate.as_ref()
     }
 
+    /// Optional dynamic filter, see [`FileSource::try_pushdown_dynamic_filter`]
+    pub fn dynamic_filter(&self) -> Option<&Arc<dyn PhysicalExpr>> {
+        self.dynamic_filter.as_ref()
+    }
+
     /// Optional reference to this parquet scan's pruning predicate
     pub fn pruning_predicate(&self) -> Option<&Arc<PruningPredicate>> {
         self.pruning_predicate.as_ref()
@@ -375,6 +393,18 @@ impl ParquetSource {
         self
     }
 
+    /// Cache the metadata of the files in `file_metadata_cache`, unless a
+    /// user defined [`ParquetFileReaderFactory`] is set.
+    ///
+    /// See [`DefaultParquetFileReaderFactory::with_file_metadata_cache`]
+    pub fn with_file_metadata_cache(
+        mut self,
+        file_metadata_cache: Option<FileMetadataCache>,
+    ) -> Self {
+        self.file_metadata_cache = file_metadata_cache;
+        self
+    }
+
     /// return the optional schema adapter factory
     pub fn schema_adapter_factory(&self) -> Option<&Arc<dyn SchemaAdapterFactory>> {
         self.schema_adapter_factory.as_ref()
@@ -458,6 +488,21 @@ impl ParquetSource {
     fn bloom_filter_on_read(&self) -> bool {
         self.table_parquet_options.global.bloom_filter_on_read
     }
+
+    /// If enabled, the reader will prune row groups using the dictionaries of
+    /// their fully dictionary encoded column chunks
+    ///
+    /// This must be set before [`Self::with_predicate`] to prune with
+    /// predicates that min/max statistics can not evaluate.
+    pub fn with_dictionary_pruning(mut self, dictionary_pruning: bool) -> Self {
+        self.table_parquet_options.global.dictionary_pruning = dictionary_pruning;
+        self
+    }
+
+    /// Return the value described in [`Self::with_dictionary_pruning`]
+    fn dictionary_pruning(&self) -> bool {
+        self.table_parquet_options.global.dictionary_pruning
+    }
 }
 
 impl FileSource for ParquetSource {
@@ -481,7 +526,10 @@ impl FileSource for ParquetSource {
             .map(|f| Ok(Arc::clone(f)))
             .unwrap_or_else(|| {
                 object_store.map(|store| {
-                    Arc::new(DefaultParquetFileReaderFactory::new(store)) as _
+                    Arc::new(
+                        DefaultParquetFileReaderFactory::new(store)
+                            .with_file_metadata_cache(self.file_metadata_cache.clone()),
+                    ) as _
                 })
             })?;
 
@@ -495,6 +543,7 @@ impl FileSource for ParquetSource {
             predicate: self.predicate.clone(),
             pruning_predicate: self.pruning_predicate.clone(),
             page_pruning_predicate: self.page_pruning_predicate.clone(),
+            dynamic_filter: self.dynamic_filter.clone(),
             table_schema: Arc::clone(&base_config.file_schema),
             metadata_size_hint: self.metadata_size_hint,
             metrics: self.metrics().clone(),
@@ -503,6 +552,7 @@ impl FileSource for ParquetSource {
             reorder_filters: self.reorder_filters(),
             enable_page_index: self.enable_page_index(),
             enable_bloom_filter: self.bloom_filter_on_read(),
+            enable_dictionary_pruning: self.dictionary_pruning(),
             schema_adapter_factory,
         }))
     }
@@ -545,6 +595,7 @@ impl FileSource for ParquetSource {
         // (bloom filters use `pruning_predicate` too)
         if self.pruning_predicate().is_some()
             || self.page_pruning_predicate().is_some()
+            || self.dynamic_filter().is_some()
             || (self.predicate().is_some() && self.pushdown_filters())
         {
             Ok(statistics.to_inexact())
@@ -582,11 +633,46 @@ impl FileSource for ParquetSource {
                     })
                     .unwrap_or_default();
 
-                write!(f, "{}{}", predicate_string, pruning_predicate_string)
+                let dynamic_filter_string = self
+                    .dynamic_filter()
+                    .map(|p| format!(", dynamic_filter={p}"))
+                    .unwrap_or_default();
+
+                write!(
+                    f,
+                    "{}{}{}",
+                    predicate_string, pruning_predicate_string, dynamic_filter_string
+                )
             }
         }
     }
     fn supports_repartition(&self, _config: &FileScanConfig) -> bool {
         true
     }
+
+    fn try_pushdown_dynamic_filter(
+        &self,
+        filter: &Arc<dyn PhysicalExpr>,
+    ) -> Option<Arc<dyn FileSource>> {
+        let mut conf = self.clone();
+        conf.dynamic_filter = Some(match &self.dynamic_filter {
+            Some(existing) => Arc::new(BinaryExpr::new(
+                Arc::clone(existing),
+                Operator::And,
+                Arc::clone(filter),
+            )),
+            None => Arc::clone(filter),
+        });
+        Some(Arc::new(conf))
+    }
+
+    fn try_with_schema_adapter_factory(
+        &self,
+        schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
+    ) -> Option<Arc<dyn FileSource>> {
+        Some(Arc::new(
+            self.clone()
+                .with_schema_adapter_factory(schema_adapter_factory),
+        ))
+    }
 }
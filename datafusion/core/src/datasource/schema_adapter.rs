@@ -21,13 +21,21 @@
 //! physical format into how they should be used by DataFusion.  For instance, a schema
 //! can be stored external to a parquet file that maps parquet logical types to arrow types.
 
-use arrow::array::{new_null_array, RecordBatch, RecordBatchOptions};
+use arrow::array::{
+    new_null_array, Array, ArrayRef, AsArray, FixedSizeListArray, LargeListArray,
+    ListArray, MapArray, RecordBatch, RecordBatchOptions, StructArray,
+};
 use arrow::compute::{can_cast_types, cast};
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::datatypes::{DataType, Field, FieldRef, Fields, Schema, SchemaRef};
 use datafusion_common::plan_err;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+/// The key of the field metadata holding the field id of a column, as written
+/// by parquet (see `parquet::arrow::PARQUET_FIELD_ID_META_KEY`)
+pub const FIELD_ID_META_KEY: &str = "PARQUET:field_id";
+
 /// Factory for creating [`SchemaAdapter`]
 ///
 /// This interface provides a way to implement custom schema adaptation logic
@@ -88,6 +96,19 @@ pub trait SchemaAdapter: Send + Sync {
         &self,
         file_schema: &Schema,
     ) -> datafusion_common::Result<(Arc<dyn SchemaMapper>, Vec<usize>)>;
+
+    /// Returns `file_schema` with its fields renamed to the names of the
+    /// table schema fields they are mapped to.
+    ///
+    /// This is used to evaluate expressions written in terms of the table
+    /// schema, such as pruning predicates, against the metadata of a file
+    /// whose columns are not mapped by name.
+    ///
+    /// The default implementation returns `file_schema` unchanged, which is
+    /// correct for adapters that map columns by name.
+    fn map_file_schema_names(&self, file_schema: &Schema) -> SchemaRef {
+        Arc::new(file_schema.clone())
+    }
 }
 
 /// Maps, columns from a specific file schema to the table schema.
@@ -123,6 +144,13 @@ pub trait SchemaMapper: Debug + Send + Sync {
 /// 2. Cast columns to the correct type
 /// 3. Fill missing columns with nulls
 ///
+/// Columns are matched by their field ids (stored in the field metadata under
+/// [`FIELD_ID_META_KEY`]) when both the file and the table field have one,
+/// and by name otherwise. Fields of structs are matched the same way, so
+/// fields missing from the file are filled with nulls and fields missing from
+/// the table are dropped. Use [`RenamingSchemaAdapterFactory`] to also match
+/// columns that were renamed.
+///
 /// # Errors:
 ///
 /// * If a column in the table schema is non-nullable but is not present in the
@@ -220,6 +248,73 @@ impl SchemaAdapterFactory for DefaultSchemaAdapterFactory {
         Box::new(DefaultSchemaAdapter {
             projected_table_schema,
             table_schema,
+            column_renames: Arc::default(),
+        })
+    }
+}
+
+/// [`SchemaAdapterFactory`] for mapping files whose columns were renamed.
+///
+/// This works like [`DefaultSchemaAdapterFactory`], but the file columns that
+/// are not matched by field id are matched to the table columns using their
+/// new names.
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use std::sync::Arc;
+/// # use arrow::datatypes::{DataType, Field, Schema};
+/// # use datafusion::datasource::schema_adapter::{RenamingSchemaAdapterFactory, SchemaAdapterFactory};
+/// let table_schema = Arc::new(Schema::new(vec![
+///     Field::new("id", DataType::Int64, true),
+/// ]));
+///
+/// // column "id" was named "user_id" in older files
+/// let renames = HashMap::from([("user_id".to_string(), "id".to_string())]);
+/// let factory = RenamingSchemaAdapterFactory::new(renames);
+/// let adapter = factory.create(Arc::clone(&table_schema), table_schema);
+///
+/// let file_schema = Schema::new(vec![
+///     Field::new("user_id", DataType::Int32, true),
+/// ]);
+/// assert_eq!(adapter.map_column_index(0, &file_schema), Some(0));
+/// ```
+///
+/// # Errors:
+///
+/// * If a file has both a column with the old name and one with the new name
+///   of a column, the mapping of its schema fails.
+#[derive(Clone, Debug, Default)]
+pub struct RenamingSchemaAdapterFactory {
+    /// Map from the names of columns in the files to their names in the table
+    column_renames: Arc<HashMap<String, String>>,
+}
+
+impl RenamingSchemaAdapterFactory {
+    /// Create a new factory mapping the file columns named like the keys of
+    /// `column_renames` to the table columns named like their values
+    pub fn new(column_renames: HashMap<String, String>) -> Self {
+        Self {
+            column_renames: Arc::new(column_renames),
+        }
+    }
+
+    /// Return the map from the names of columns in the files to their names
+    /// in the table
+    pub fn column_renames(&self) -> &HashMap<String, String> {
+        &self.column_renames
+    }
+}
+
+impl SchemaAdapterFactory for RenamingSchemaAdapterFactory {
+    fn create(
+        &self,
+        projected_table_schema: SchemaRef,
+        table_schema: SchemaRef,
+    ) -> Box<dyn SchemaAdapter> {
+        Box::new(DefaultSchemaAdapter {
+            projected_table_schema,
+            table_schema,
+            column_renames: Arc::clone(&self.column_renames),
         })
     }
 }
@@ -237,6 +332,24 @@ pub(crate) struct DefaultSchemaAdapter {
     /// which may refer to columns that are not referred to anywhere
     /// else in the plan.
     table_schema: SchemaRef,
+    /// Map from the names of columns in the files to their names in the table
+    column_renames: Arc<HashMap<String, String>>,
+}
+
+impl DefaultSchemaAdapter {
+    /// Returns the index and field of `table_schema` the file field
+    /// `file_field` is mapped to, if any
+    fn find_table_field<'a>(
+        &self,
+        table_schema: &'a Schema,
+        file_field: &Field,
+    ) -> Option<(usize, &'a FieldRef)> {
+        let name = self
+            .column_renames
+            .get(file_field.name())
+            .unwrap_or(file_field.name());
+        find_field(table_schema.fields(), file_field, name)
+    }
 }
 
 impl SchemaAdapter for DefaultSchemaAdapter {
@@ -246,7 +359,10 @@ impl SchemaAdapter for DefaultSchemaAdapter {
     /// Panics if index is not in range for the table schema
     fn map_column_index(&self, index: usize, file_schema: &Schema) -> Option<usize> {
         let field = self.projected_table_schema.field(index);
-        Some(file_schema.fields.find(field.name())?.0)
+        file_schema.fields().iter().position(|file_field| {
+            self.find_table_field(&self.projected_table_schema, file_field)
+                .is_some_and(|(_, table_field)| table_field.name() == field.name())
+        })
     }
 
     /// Creates a `SchemaMapping` for casting or mapping the columns from the
@@ -267,34 +383,247 @@ impl SchemaAdapter for DefaultSchemaAdapter {
 
         for (file_idx, file_field) in file_schema.fields.iter().enumerate() {
             if let Some((table_idx, table_field)) =
-                self.projected_table_schema.fields().find(file_field.name())
+                self.find_table_field(&self.projected_table_schema, file_field)
             {
-                match can_cast_types(file_field.data_type(), table_field.data_type()) {
-                    true => {
-                        field_mappings[table_idx] = Some(projection.len());
-                        projection.push(file_idx);
-                    }
-                    false => {
-                        return plan_err!(
-                            "Cannot cast file schema field {} of type {:?} to table schema field of type {:?}",
-                            file_field.name(),
-                            file_field.data_type(),
-                            table_field.data_type()
-                        )
-                    }
+                if !can_map_types(file_field.data_type(), table_field.data_type()) {
+                    return plan_err!(
+                        "Cannot cast file schema field {} of type {:?} to table schema field of type {:?}",
+                        file_field.name(),
+                        file_field.data_type(),
+                        table_field.data_type()
+                    );
+                }
+                if let Some(mapped_idx) = field_mappings[table_idx] {
+                    return plan_err!(
+                        "File schema fields {} and {} are both mapped to table schema field {}",
+                        file_schema.field(projection[mapped_idx]).name(),
+                        file_field.name(),
+                        table_field.name()
+                    );
                 }
+                field_mappings[table_idx] = Some(projection.len());
+                projection.push(file_idx);
             }
         }
 
+        let table_field_mappings = file_schema
+            .fields()
+            .iter()
+            .filter_map(|file_field| {
+                let (table_idx, _) =
+                    self.find_table_field(&self.table_schema, file_field)?;
+                Some((file_field.name().clone(), table_idx))
+            })
+            .collect();
+
         Ok((
             Arc::new(SchemaMapping {
                 projected_table_schema: Arc::clone(&self.projected_table_schema),
                 field_mappings,
                 table_schema: Arc::clone(&self.table_schema),
+                table_field_mappings,
             }),
             projection,
         ))
     }
+
+    /// Renames the file fields mapped to a table field after it. The file
+    /// fields that are not mapped, but named like a table field, are given an
+    /// empty name so they are not mistaken for it.
+    fn map_file_schema_names(&self, file_schema: &Schema) -> SchemaRef {
+        let fields = file_schema
+            .fields()
+            .iter()
+            .map(|file_field| {
+                match self.find_table_field(&self.table_schema, file_field) {
+                    Some((_, table_field)) if table_field.name() == file_field.name() => {
+                        Arc::clone(file_field)
+                    }
+                    Some((_, table_field)) => Arc::new(
+                        file_field.as_ref().clone().with_name(table_field.name()),
+                    ),
+                    None if self
+                        .table_schema
+                        .fields()
+                        .find(file_field.name())
+                        .is_some() =>
+                    {
+                        Arc::new(file_field.as_ref().clone().with_name(""))
+                    }
+                    None => Arc::clone(file_field),
+                }
+            })
+            .collect::<Fields>();
+        Arc::new(Schema::new_with_metadata(
+            fields,
+            file_schema.metadata().clone(),
+        ))
+    }
+}
+
+/// Returns the field id of `field`, if any
+fn field_id(field: &Field) -> Option<&str> {
+    field.metadata().get(FIELD_ID_META_KEY).map(String::as_str)
+}
+
+/// Returns the index and field of `fields` that `field`, named `name`, is
+/// mapped to.
+///
+/// Fields with the same field id are mapped to each other. Otherwise, fields
+/// with the same name are, unless both have a (different) field id.
+fn find_field<'a>(
+    fields: &'a Fields,
+    field: &Field,
+    name: &str,
+) -> Option<(usize, &'a FieldRef)> {
+    let id = field_id(field);
+    if let Some(id) = id {
+        let found = fields
+            .iter()
+            .enumerate()
+            .find(|(_, other)| field_id(other) == Some(id));
+        if found.is_some() {
+            return found;
+        }
+    }
+    fields.iter().enumerate().find(|(_, other)| {
+        other.name() == name && (id.is_none() || field_id(other).is_none())
+    })
+}
+
+/// Returns true if columns of type `from_type` can be mapped to columns of type
+/// `to_type` by [`map_column`]
+fn can_map_types(from_type: &DataType, to_type: &DataType) -> bool {
+    match (from_type, to_type) {
+        (DataType::Struct(from_fields), DataType::Struct(to_fields)) => {
+            let from_fields_mapped = from_fields.iter().all(|from_field| {
+                find_field(to_fields, from_field, from_field.name()).map_or(
+                    true,
+                    |(_, to_field)| {
+                        can_map_types(from_field.data_type(), to_field.data_type())
+                    },
+                )
+            });
+            // The missing fields are filled with nulls
+            let to_fields_mapped = to_fields.iter().all(|to_field| {
+                to_field.is_nullable()
+                    || find_field(from_fields, to_field, to_field.name()).is_some()
+            });
+            from_fields_mapped && to_fields_mapped
+        }
+        (DataType::List(from_field), DataType::List(to_field))
+        | (DataType::LargeList(from_field), DataType::LargeList(to_field)) => {
+            can_map_types(from_field.data_type(), to_field.data_type())
+        }
+        (
+            DataType::FixedSizeList(from_field, from_size),
+            DataType::FixedSizeList(to_field, to_size),
+        ) if from_size == to_size => {
+            can_map_types(from_field.data_type(), to_field.data_type())
+        }
+        (DataType::Map(from_field, _), DataType::Map(to_field, _)) => {
+            match (map_entry_fields(from_field), map_entry_fields(to_field)) {
+                (Some((from_key, from_value)), Some((to_key, to_value))) => {
+                    can_map_types(from_key.data_type(), to_key.data_type())
+                        && can_map_types(from_value.data_type(), to_value.data_type())
+                }
+                _ => false,
+            }
+        }
+        _ => can_cast_types(from_type, to_type),
+    }
+}
+
+/// Returns the key and value fields of the entries field of a map
+fn map_entry_fields(entries_field: &Field) -> Option<(&FieldRef, &FieldRef)> {
+    match entries_field.data_type() {
+        DataType::Struct(fields) if fields.len() == 2 => Some((&fields[0], &fields[1])),
+        _ => None,
+    }
+}
+
+/// Casts `array` to the type of `field`.
+///
+/// The fields of structs, also within lists and maps, are matched like the
+/// columns of the file and table schemas: the fields missing from `array` are
+/// filled with nulls, and the fields missing from `field` are dropped.
+fn map_column(array: &ArrayRef, field: &Field) -> datafusion_common::Result<ArrayRef> {
+    match (array.data_type(), field.data_type()) {
+        (DataType::Struct(from_fields), DataType::Struct(to_fields)) => {
+            let array = array.as_struct();
+            let columns = to_fields
+                .iter()
+                .map(|to_field| {
+                    match find_field(from_fields, to_field, to_field.name()) {
+                        Some((idx, _)) => map_column(array.column(idx), to_field),
+                        None => Ok(new_null_array(to_field.data_type(), array.len())),
+                    }
+                })
+                .collect::<datafusion_common::Result<Vec<_>>>()?;
+            let array =
+                StructArray::try_new(to_fields.clone(), columns, array.nulls().cloned())?;
+            Ok(Arc::new(array))
+        }
+        (DataType::List(_), DataType::List(to_field)) => {
+            let array = array.as_list::<i32>();
+            let values = map_column(array.values(), to_field)?;
+            let array = ListArray::try_new(
+                Arc::clone(to_field),
+                array.offsets().clone(),
+                values,
+                array.nulls().cloned(),
+            )?;
+            Ok(Arc::new(array))
+        }
+        (DataType::LargeList(_), DataType::LargeList(to_field)) => {
+            let array = array.as_list::<i64>();
+            let values = map_column(array.values(), to_field)?;
+            let array = LargeListArray::try_new(
+                Arc::clone(to_field),
+                array.offsets().clone(),
+                values,
+                array.nulls().cloned(),
+            )?;
+            Ok(Arc::new(array))
+        }
+        (
+            DataType::FixedSizeList(_, from_size),
+            DataType::FixedSizeList(to_field, to_size),
+        ) if from_size == to_size => {
+            let array = array.as_fixed_size_list();
+            let values = map_column(array.values(), to_field)?;
+            let array = FixedSizeListArray::try_new(
+                Arc::clone(to_field),
+                *to_size,
+                values,
+                array.nulls().cloned(),
+            )?;
+            Ok(Arc::new(array))
+        }
+        (DataType::Map(_, _), DataType::Map(to_field, ordered)) => {
+            let Some((to_key, to_value)) = map_entry_fields(to_field) else {
+                return Ok(cast(array, field.data_type())?);
+            };
+            let array = array.as_map();
+            let entries = StructArray::try_new(
+                Fields::from(vec![Arc::clone(to_key), Arc::clone(to_value)]),
+                vec![
+                    map_column(array.keys(), to_key)?,
+                    map_column(array.values(), to_value)?,
+                ],
+                None,
+            )?;
+            let array = MapArray::try_new(
+                Arc::clone(to_field),
+                array.offsets().clone(),
+                entries,
+                array.nulls().cloned(),
+                *ordered,
+            )?;
+            Ok(Arc::new(array))
+        }
+        _ => Ok(cast(array, field.data_type())?),
+    }
 }
 
 /// The SchemaMapping struct holds a mapping from the file schema to the table
@@ -337,6 +666,9 @@ pub struct SchemaMapping {
     /// This contains all fields in the table, regardless of if they will be
     /// projected out or not.
     table_schema: SchemaRef,
+    /// Mapping from the names of the fields of the file schema to the index of
+    /// the field of `table_schema` they are mapped to.
+    table_field_mappings: HashMap<String, usize>,
 }
 
 impl SchemaMapper for SchemaMapping {
@@ -364,7 +696,7 @@ impl SchemaMapper for SchemaMapping {
                     || Ok(new_null_array(field.data_type(), batch_rows)),
                     // However, if it does exist in both, then try to cast it to the correct output
                     // type
-                    |batch_idx| cast(&batch_cols[batch_idx], field.data_type()),
+                    |batch_idx| map_column(&batch_cols[batch_idx], field),
                 )
             })
             .collect::<datafusion_common::Result<Vec<_>, _>>()?;
@@ -396,20 +728,19 @@ impl SchemaMapper for SchemaMapping {
             .iter()
             .zip(batch_cols.iter())
             .flat_map(|(field, batch_col)| {
-                self.table_schema
-                    // try to get the same field from the table schema that we have stored in self
-                    .field_with_name(field.name())
+                self.table_field_mappings
+                    // try to get the field of the table schema this field is mapped to
+                    .get(field.name())
                     // and if we don't have it, that's fine, ignore it. This may occur when we've
                     // created an external table whose fields are a subset of the fields in this
                     // file, then tried to read data from the file into this table. If that is the
                     // case here, it's fine to ignore because we don't care about this field
                     // anyways
-                    .ok()
-                    // but if we do have it,
-                    .map(|table_field| {
+                    .map(|&table_idx| {
+                        let table_field = self.table_schema.field(table_idx);
                         // try to cast it into the correct output type. we don't want to ignore this
                         // error, though, so it's propagated.
-                        cast(batch_col, table_field.data_type())
+                        map_column(batch_col, table_field)
                             // and if that works, return the field and column.
                             .map(|new_col| (new_col, table_field.clone()))
                     })
@@ -433,9 +764,15 @@ mod tests {
     use std::fs;
     use std::sync::Arc;
 
+    use std::collections::HashMap;
+
     use crate::assert_batches_sorted_eq;
-    use arrow::array::{Int32Array, StringArray};
-    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use arrow::array::{
+        Array, ArrayRef, AsArray, Float64Array, Int32Array, Int64Array, ListArray,
+        MapArray, StringArray, StructArray,
+    };
+    use arrow::buffer::{NullBuffer, OffsetBuffer};
+    use arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef};
     use arrow::record_batch::RecordBatch;
     use object_store::path::Path;
     use object_store::ObjectMeta;
@@ -444,7 +781,8 @@ mod tests {
     use crate::datasource::object_store::ObjectStoreUrl;
     use crate::datasource::physical_plan::{FileScanConfig, ParquetSource};
     use crate::datasource::schema_adapter::{
-        DefaultSchemaAdapterFactory, SchemaAdapter, SchemaAdapterFactory, SchemaMapper,
+        DefaultSchemaAdapterFactory, RenamingSchemaAdapterFactory, SchemaAdapter,
+        SchemaAdapterFactory, SchemaMapper, FIELD_ID_META_KEY,
     };
     use crate::physical_plan::collect;
     use crate::prelude::SessionContext;
@@ -576,6 +914,290 @@ mod tests {
         assert!(err.contains("Invalid argument error: Column 'a' is declared as non-nullable but contains null values"), "{err}");
     }
 
+    #[test]
+    fn default_schema_adapter_field_ids() {
+        let table_schema = Schema::new(vec![
+            field_with_id(Field::new("a", DataType::Int64, true), 1),
+            field_with_id(Field::new("b", DataType::Utf8, true), 2),
+        ]);
+        // "a" was renamed from "b", and "b" is a new column
+        let file_schema = Schema::new(vec![
+            field_with_id(Field::new("b", DataType::Int32, true), 1),
+            field_with_id(Field::new("c", DataType::Utf8, true), 3),
+        ]);
+
+        let adapter = DefaultSchemaAdapterFactory::from_schema(Arc::new(table_schema));
+        assert_eq!(adapter.map_column_index(0, &file_schema), Some(0));
+        assert_eq!(adapter.map_column_index(1, &file_schema), None);
+
+        let (mapper, indices) = adapter.map_schema(&file_schema).unwrap();
+        assert_eq!(indices, vec![0]);
+
+        let file_batch = record_batch!(("b", Int32, vec![1, 2])).unwrap();
+        let mapped_batch = mapper.map_batch(file_batch.clone()).unwrap();
+        let expected_batch = record_batch!(
+            ("a", Int64, vec![1, 2]), // mapped by field id and widened
+            ("b", Utf8, vec![None::<&str>, None])
+        )
+        .unwrap();
+        assert_eq!(mapped_batch.columns(), expected_batch.columns());
+
+        let mapped_batch = mapper.map_partial_batch(file_batch).unwrap();
+        assert_eq!(mapped_batch.schema().field(0).name(), "a");
+        assert_eq!(mapped_batch.column(0), expected_batch.column(0));
+
+        let names = adapter.map_file_schema_names(&file_schema);
+        assert_eq!(names.field(0).name(), "a");
+        assert_eq!(names.field(1).name(), "c");
+
+        // a file column named like a table column with another field id is
+        // not mistaken for it
+        let file_schema = Schema::new(vec![field_with_id(
+            Field::new("b", DataType::Utf8, true),
+            4,
+        )]);
+        assert_eq!(adapter.map_column_index(1, &file_schema), None);
+        let names = adapter.map_file_schema_names(&file_schema);
+        assert_eq!(names.field(0).name(), "");
+    }
+
+    #[test]
+    fn renaming_schema_adapter() {
+        let table_schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("login", DataType::Utf8, true),
+        ]);
+        let file_schema = Schema::new(vec![
+            Field::new("login", DataType::Utf8, true),
+            Field::new("user_id", DataType::Int32, true),
+        ]);
+
+        let factory = RenamingSchemaAdapterFactory::new(HashMap::from([(
+            "user_id".to_string(),
+            "id".to_string(),
+        )]));
+        let table_schema = Arc::new(table_schema);
+        let adapter = factory.create(Arc::clone(&table_schema), table_schema);
+        assert_eq!(adapter.map_column_index(0, &file_schema), Some(1));
+        assert_eq!(adapter.map_column_index(1, &file_schema), Some(0));
+
+        let (mapper, indices) = adapter.map_schema(&file_schema).unwrap();
+        assert_eq!(indices, vec![0, 1]);
+
+        let file_batch =
+            record_batch!(("login", Utf8, vec!["a"]), ("user_id", Int32, vec![1]))
+                .unwrap();
+        let mapped_batch = mapper.map_batch(file_batch).unwrap();
+        let expected_batch =
+            record_batch!(("id", Int64, vec![1]), ("login", Utf8, vec!["a"])).unwrap();
+        assert_eq!(mapped_batch, expected_batch);
+
+        let names = adapter.map_file_schema_names(&file_schema);
+        assert_eq!(names.field(0).name(), "login");
+        assert_eq!(names.field(1).name(), "id");
+
+        // the file has both the old and the new name of the column
+        let file_schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("user_id", DataType::Int32, true),
+        ]);
+        let err = adapter.map_schema(&file_schema).unwrap_err().to_string();
+        assert!(
+            err.contains(
+                "fields id and user_id are both mapped to table schema field id"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn default_schema_adapter_structs() {
+        let table_fields = Fields::from(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let table_schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(table_fields.clone()),
+            true,
+        )]);
+        // the struct gained "b" and lost "c"
+        let file_fields = Fields::from(vec![
+            Field::new("c", DataType::Float64, true),
+            Field::new("a", DataType::Int32, true),
+        ]);
+        let file_schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(file_fields.clone()),
+            true,
+        )]);
+
+        let adapter = DefaultSchemaAdapterFactory::from_schema(Arc::new(table_schema));
+        let (mapper, indices) = adapter.map_schema(&file_schema).unwrap();
+        assert_eq!(indices, vec![0]);
+
+        let file_struct = StructArray::new(
+            file_fields,
+            vec![
+                Arc::new(Float64Array::from(vec![1.0, 2.0])),
+                Arc::new(Int32Array::from(vec![1, 2])),
+            ],
+            Some(NullBuffer::from(vec![true, false])),
+        );
+        let file_batch =
+            RecordBatch::try_new(Arc::new(file_schema), vec![Arc::new(file_struct)])
+                .unwrap();
+
+        let mapped_batch = mapper.map_batch(file_batch).unwrap();
+        let expected_struct = StructArray::new(
+            table_fields,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::new_null(2)),
+            ],
+            Some(NullBuffer::from(vec![true, false])),
+        );
+        assert_eq!(
+            mapped_batch.column(0).as_ref(),
+            &expected_struct as &dyn Array
+        );
+
+        // the struct fields can not be cast
+        let file_schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(Fields::from(vec![Field::new(
+                "a",
+                DataType::Struct(Fields::empty()),
+                true,
+            )])),
+            true,
+        )]);
+        assert!(adapter.map_schema(&file_schema).is_err());
+
+        // a missing struct field that is not nullable can not be filled
+        let table_schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(Fields::from(vec![
+                Field::new("a", DataType::Int64, true),
+                Field::new("b", DataType::Utf8, false),
+            ])),
+            true,
+        )]);
+        let file_schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(Fields::from(vec![Field::new("a", DataType::Int32, true)])),
+            true,
+        )]);
+        let adapter = DefaultSchemaAdapterFactory::from_schema(Arc::new(table_schema));
+        assert!(adapter.map_schema(&file_schema).is_err());
+    }
+
+    #[test]
+    fn default_schema_adapter_nested_structs() {
+        // the struct within the list and the map values gained "b"
+        let file_struct_fields =
+            Fields::from(vec![Field::new("a", DataType::Int32, true)]);
+        let table_struct_fields = Fields::from(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let list_field = |fields: &Fields| {
+            Field::new_list(
+                "l",
+                Field::new_list_field(DataType::Struct(fields.clone()), true),
+                true,
+            )
+        };
+        let map_field = |fields: &Fields| {
+            Field::new_map(
+                "m",
+                "entries",
+                Field::new("keys", DataType::Utf8, false),
+                Field::new("values", DataType::Struct(fields.clone()), true),
+                false,
+                true,
+            )
+        };
+        let table_schema = Schema::new(vec![
+            list_field(&table_struct_fields),
+            map_field(&table_struct_fields),
+        ]);
+        let file_schema = Schema::new(vec![
+            list_field(&file_struct_fields),
+            map_field(&file_struct_fields),
+        ]);
+
+        let adapter = DefaultSchemaAdapterFactory::from_schema(Arc::new(table_schema));
+        let (mapper, indices) = adapter.map_schema(&file_schema).unwrap();
+        assert_eq!(indices, vec![0, 1]);
+
+        let file_struct = StructArray::new(
+            file_struct_fields,
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+            None,
+        );
+        let file_list = ListArray::new(
+            Arc::new(Field::new_list_field(file_struct.data_type().clone(), true)),
+            OffsetBuffer::from_lengths([2, 1]),
+            Arc::new(file_struct.clone()),
+            None,
+        );
+        let DataType::Map(entries_field, _) = file_schema.field(1).data_type() else {
+            unreachable!()
+        };
+        let file_map = MapArray::new(
+            Arc::clone(entries_field),
+            OffsetBuffer::from_lengths([1, 2]),
+            StructArray::new(
+                Fields::from(vec![
+                    Field::new("keys", DataType::Utf8, false),
+                    Field::new("values", file_struct.data_type().clone(), true),
+                ]),
+                vec![
+                    Arc::new(StringArray::from(vec!["x", "y", "z"])),
+                    Arc::new(file_struct),
+                ],
+                None,
+            ),
+            None,
+            false,
+        );
+        let file_batch = RecordBatch::try_new(
+            Arc::new(file_schema),
+            vec![Arc::new(file_list), Arc::new(file_map)],
+        )
+        .unwrap();
+
+        let mapped_batch = mapper.map_batch(file_batch).unwrap();
+        let expected_struct: ArrayRef = Arc::new(StructArray::new(
+            table_struct_fields,
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::new_null(3)),
+            ],
+            None,
+        ));
+        let mapped_list = mapped_batch.column(0).as_list::<i32>();
+        assert_eq!(mapped_list.offsets().as_ref(), &[0, 2, 3]);
+        assert_eq!(mapped_list.values(), &expected_struct);
+        let mapped_map = mapped_batch.column(1).as_map();
+        assert_eq!(mapped_map.offsets().as_ref(), &[0, 1, 3]);
+        assert_eq!(mapped_map.values(), &expected_struct);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn field_id_meta_key() {
+        assert_eq!(FIELD_ID_META_KEY, parquet::arrow::PARQUET_FIELD_ID_META_KEY);
+    }
+
+    fn field_with_id(field: Field, id: i32) -> Field {
+        field.with_metadata(HashMap::from([(
+            FIELD_ID_META_KEY.to_string(),
+            id.to_string(),
+        )]))
+    }
+
     #[derive(Debug)]
     struct TestSchemaAdapterFactory;
 
@@ -221,7 +221,7 @@ pub(crate) fn get_col_stats(
 //
 // only adding this cfg b/c this is the only feature it's used with currently
 #[cfg(feature = "parquet")]
-fn min_max_aggregate_data_type(
+pub(crate) fn min_max_aggregate_data_type(
     input_type: &arrow_schema::DataType,
 ) -> &arrow_schema::DataType {
     if let arrow_schema::DataType::Dictionary(_, value_type) = input_type {
@@ -45,7 +45,11 @@ use crate::{
         TableType, UNNAMED_TABLE,
     },
     physical_expr::PhysicalExpr,
-    physical_plan::ExecutionPlan,
+    physical_plan::{
+        collect_partitioned, repartition::RepartitionExec, sorts::sort::SortExec,
+        ExecutionPlan, Partitioning,
+    },
+    physical_planner::create_physical_sort_exprs,
     variable::{VarProvider, VarType},
 };
 
@@ -57,12 +61,14 @@ use datafusion_common::{
     tree_node::{TreeNodeRecursion, TreeNodeVisitor},
     DFSchema, ParamValues, ScalarValue, SchemaReference, TableReference,
 };
+use datafusion_execution::cache::cache_manager::CacheInfo;
+use datafusion_execution::cache::CacheEntry;
 use datafusion_execution::registry::SerializerRegistry;
 use datafusion_expr::{
     expr_rewriter::FunctionRewrite,
     logical_plan::{DdlStatement, Statement},
     planner::ExprPlanner,
-    Expr, UserDefinedLogicalNode, WindowUDF,
+    Expr, SortExpr, UserDefinedLogicalNode, WindowUDF,
 };
 
 // backwards compatibility
@@ -79,6 +85,7 @@ pub use datafusion_execution::config::SessionConfig;
 pub use datafusion_execution::TaskContext;
 pub use datafusion_expr::execution_props::ExecutionProps;
 use datafusion_optimizer::{AnalyzerRule, OptimizerRule};
+use object_store::path::Path;
 use object_store::ObjectStore;
 use parking_lot::RwLock;
 use url::Url;
@@ -514,6 +521,30 @@ impl SessionContext {
         Arc::clone(self.state.read().runtime_env())
     }
 
+    /// List the enabled caches of the [`CacheManager`] of this
+    /// `SessionContext`, with their statistics.
+    ///
+    /// [`CacheManager`]: datafusion_execution::cache::cache_manager::CacheManager
+    pub fn list_caches(&self) -> Vec<CacheInfo> {
+        self.runtime_env().cache_manager.list_caches()
+    }
+
+    /// List the entries of the enabled caches of the [`CacheManager`] of this
+    /// `SessionContext`, with the kind of their cache.
+    ///
+    /// [`CacheManager`]: datafusion_execution::cache::cache_manager::CacheManager
+    pub fn list_cache_entries(&self) -> Vec<(&'static str, CacheEntry<Path>)> {
+        self.runtime_env().cache_manager.list_entries()
+    }
+
+    /// Remove all the entries of the enabled caches of the [`CacheManager`] of
+    /// this `SessionContext`.
+    ///
+    /// [`CacheManager`]: datafusion_execution::cache::cache_manager::CacheManager
+    pub fn clear_caches(&self) {
+        self.runtime_env().cache_manager.clear()
+    }
+
     /// Returns an id that uniquely identifies this `SessionContext`.
     pub fn session_id(&self) -> String {
         self.session_id.clone()
@@ -803,6 +834,10 @@ impl SessionContext {
             constraints,
             column_defaults,
             temporary,
+            partition_by,
+            sort_order,
+            comment,
+            column_comments,
         } = cmd;
 
         let input = Arc::unwrap_or_clone(input);
@@ -815,45 +850,71 @@ impl SessionContext {
         let table = self.table(name.clone()).await;
         match (if_not_exists, or_replace, table) {
             (true, false, Ok(_)) => self.return_empty_dataframe(),
-            (false, true, Ok(_)) => {
-                self.deregister_table(name.clone())?;
-                let schema = Arc::new(input.schema().as_ref().into());
-                let physical = DataFrame::new(self.state(), input);
-
-                let batches: Vec<_> = physical.collect_partitioned().await?;
-                let table = Arc::new(
-                    // pass constraints and column defaults to the mem table.
-                    MemTable::try_new(schema, batches)?
-                        .with_constraints(constraints)
-                        .with_column_defaults(column_defaults.into_iter().collect()),
-                );
-
-                self.register_table(name.clone(), table)?;
-                self.return_empty_dataframe()
-            }
             (true, true, Ok(_)) => {
                 exec_err!("'IF NOT EXISTS' cannot coexist with 'REPLACE'")
             }
-            (_, _, Err(_)) => {
-                let df_schema = input.schema();
-                let schema = Arc::new(df_schema.as_ref().into());
-                let physical = DataFrame::new(self.state(), input);
-
-                let batches: Vec<_> = physical.collect_partitioned().await?;
-                let table = Arc::new(
-                    // pass constraints and column defaults to the mem table.
-                    MemTable::try_new(schema, batches)?
-                        .with_constraints(constraints)
-                        .with_column_defaults(column_defaults.into_iter().collect()),
-                );
+            (false, false, Ok(_)) => exec_err!("Table '{name}' already exists"),
+            (_, _, table) => {
+                // only reachable for an existing table with `OR REPLACE`
+                if table.is_ok() {
+                    self.deregister_table(name.clone())?;
+                }
+                let schema = Arc::new(input.schema().as_ref().into());
+                let batches = self
+                    .collect_memory_table_batches(input, partition_by, &sort_order)
+                    .await?;
+                let mut table = MemTable::try_new(schema, batches)?
+                    // pass constraints, column defaults and comments to the mem table.
+                    .with_constraints(constraints)
+                    .with_column_defaults(column_defaults.into_iter().collect())
+                    .with_comment(comment)
+                    .with_column_comments(column_comments.into_iter().collect());
+                if !sort_order.is_empty() {
+                    table = table.with_sort_order(vec![sort_order]);
+                }
 
-                self.register_table(name, table)?;
+                self.register_table(name, Arc::new(table))?;
                 self.return_empty_dataframe()
             }
-            (false, false, Ok(_)) => exec_err!("Table '{name}' already exists"),
         }
     }
 
+    /// Execute the input of a `CREATE TABLE`, hash partitioning the result by
+    /// `partition_by` and sorting each partition by `sort_order`
+    async fn collect_memory_table_batches(
+        &self,
+        input: LogicalPlan,
+        partition_by: Vec<Expr>,
+        sort_order: &[SortExpr],
+    ) -> Result<Vec<Vec<RecordBatch>>> {
+        let state = self.state();
+        let df_schema = Arc::clone(input.schema());
+        let mut plan = state.create_physical_plan(&input).await?;
+
+        if !partition_by.is_empty() {
+            let exprs = partition_by
+                .into_iter()
+                .map(|expr| state.create_physical_expr(expr, &df_schema))
+                .collect::<Result<Vec<_>>>()?;
+            let partitioning =
+                Partitioning::Hash(exprs, state.config().target_partitions());
+            plan = Arc::new(RepartitionExec::try_new(plan, partitioning)?);
+        }
+
+        if !sort_order.is_empty() {
+            let sort_exprs = create_physical_sort_exprs(
+                sort_order,
+                &df_schema,
+                state.execution_props(),
+            )?;
+            plan = Arc::new(
+                SortExec::new(sort_exprs, plan).with_preserve_partitioning(true),
+            );
+        }
+
+        collect_partitioned(plan, state.task_ctx()).await
+    }
+
     async fn create_view(&self, cmd: CreateView) -> Result<DataFrame> {
         let CreateView {
             name,
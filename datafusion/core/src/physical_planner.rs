@@ -44,9 +44,10 @@ use crate::physical_plan::expressions::PhysicalSortExpr;
 use crate::physical_plan::filter::FilterExec;
 use crate::physical_plan::joins::utils as join_utils;
 use crate::physical_plan::joins::{
-    CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode, SortMergeJoinExec,
+    AsofJoinExec, CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode,
+    SortMergeJoinExec,
 };
-use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
+use crate::physical_plan::limit::{GlobalLimitExec, LimitExpr, LocalLimitExec};
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::recursive_query::RecursiveQueryExec;
 use crate::physical_plan::repartition::RepartitionExec;
@@ -61,12 +62,14 @@ use crate::physical_plan::{
 
 use arrow::array::{builder::StringBuilder, RecordBatch};
 use arrow::compute::SortOptions;
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::datatypes::{Field, Schema, SchemaRef};
 use datafusion_common::display::ToStringifiedPlan;
-use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion, TreeNodeVisitor};
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion, TreeNodeVisitor,
+};
 use datafusion_common::{
-    exec_err, internal_datafusion_err, internal_err, not_impl_err, plan_err, DFSchema,
-    ScalarValue,
+    exec_err, internal_datafusion_err, internal_err, not_impl_err, plan_err, Column,
+    DFSchema, ScalarValue,
 };
 use datafusion_expr::dml::{CopyTo, InsertOp};
 use datafusion_expr::expr::{
@@ -74,9 +77,11 @@ use datafusion_expr::expr::{
 };
 use datafusion_expr::expr_rewriter::unnormalize_cols;
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
+use datafusion_expr::utils::split_conjunction;
 use datafusion_expr::{
-    DescribeTable, DmlStatement, Extension, FetchType, Filter, JoinType, RecursiveQuery,
-    SkipType, SortExpr, StringifiedPlan, WindowFrame, WindowFrameBound, WriteOp,
+    lit, BinaryExpr, DescribeTable, DmlStatement, Extension, FetchType, Filter, JoinType,
+    Operator, RecursiveQuery, SkipType, SortExpr, SortWithinPartitions, StringifiedPlan,
+    WindowFrame, WindowFrameBound, WriteOp,
 };
 use datafusion_physical_expr::aggregate::{AggregateExprBuilder, AggregateFunctionExpr};
 use datafusion_physical_expr::expressions::Literal;
@@ -288,6 +293,14 @@ impl DefaultPhysicalPlanner {
         // up concurrently.
         let mut flat_tree_leaf_indices = vec![];
         while let Some((parent_index, node)) = dfs_visit_stack.pop() {
+            // Subqueries left in the plan are correlated LATERAL subqueries
+            // that the optimizer could not decorrelate
+            if let LogicalPlan::Subquery(subquery) = node {
+                return not_impl_err!(
+                    "Unsupported correlated LATERAL subquery: {}",
+                    subquery.subquery.display()
+                );
+            }
             let current_index = flat_tree.len();
             // Because of how we extend the visit stack here, we visit the children
             // in reverse order of how they appear, so later we need to reverse
@@ -556,6 +569,51 @@ impl DefaultPhysicalPlanner {
                     return exec_err!("Table '{table_name}' does not exist");
                 }
             }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                op: WriteOp::Delete,
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let filters = extract_dml_filters(input)?;
+                    provider.delete_from(session_state, filters).await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                op: WriteOp::Update,
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let assignments = extract_update_assignments(input)?;
+                    let filters = extract_dml_filters(input)?;
+                    provider.update(session_state, assignments, filters).await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                op: WriteOp::Merge,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let input_exec = children.one()?;
+                    provider.merge_into(session_state, input_exec).await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
             LogicalPlan::Window(Window { window_expr, .. }) => {
                 if window_expr.is_empty() {
                     return internal_err!("Impossibly got empty window expression");
@@ -773,28 +831,25 @@ impl DefaultPhysicalPlanner {
             }) => {
                 let physical_input = children.one()?;
                 let input_dfschema = input.as_ref().schema();
+                let create_runtime_exprs = |expr: &[Expr]| {
+                    expr.iter()
+                        .map(|e| {
+                            self.create_physical_expr(e, input_dfschema, session_state)
+                        })
+                        .collect::<Result<Vec<_>>>()
+                };
                 let physical_partitioning = match partitioning_scheme {
                     LogicalPartitioning::RoundRobinBatch(n) => {
                         Partitioning::RoundRobinBatch(*n)
                     }
                     LogicalPartitioning::Hash(expr, n) => {
-                        let runtime_expr = expr
-                            .iter()
-                            .map(|e| {
-                                self.create_physical_expr(
-                                    e,
-                                    input_dfschema,
-                                    session_state,
-                                )
-                            })
-                            .collect::<Result<Vec<_>>>()?;
-                        Partitioning::Hash(runtime_expr, *n)
-                    }
-                    LogicalPartitioning::DistributeBy(_) => {
-                        return not_impl_err!(
-                            "Physical plan does not support DistributeBy partitioning"
-                        );
+                        Partitioning::Hash(create_runtime_exprs(expr)?, *n)
                     }
+                    // DISTRIBUTE BY leaves the number of partitions to the engine
+                    LogicalPartitioning::DistributeBy(expr) => Partitioning::Hash(
+                        create_runtime_exprs(expr)?,
+                        session_state.config().target_partitions(),
+                    ),
                 };
                 Arc::new(RepartitionExec::try_new(
                     physical_input,
@@ -815,37 +870,67 @@ impl DefaultPhysicalPlanner {
                     SortExec::new(sort_expr, physical_input).with_fetch(*fetch);
                 Arc::new(new_sort)
             }
+            // SORT BY
+            LogicalPlan::Extension(Extension { node })
+                if node.as_any().is::<SortWithinPartitions>() =>
+            {
+                let sort = node
+                    .as_any()
+                    .downcast_ref::<SortWithinPartitions>()
+                    .unwrap();
+                let physical_input = children.one()?;
+                let sort_expr = create_physical_sort_exprs(
+                    sort.expr(),
+                    sort.input().schema(),
+                    session_state.execution_props(),
+                )?;
+                Arc::new(
+                    SortExec::new(sort_expr, physical_input)
+                        .with_preserve_partitioning(true),
+                )
+            }
             LogicalPlan::Subquery(_) => todo!(),
             LogicalPlan::SubqueryAlias(_) => children.one()?,
             LogicalPlan::Limit(limit) => {
                 let input = children.one()?;
-                let SkipType::Literal(skip) = limit.get_skip_type()? else {
-                    return not_impl_err!(
-                        "Unsupported OFFSET expression: {:?}",
-                        limit.skip
-                    );
+                // Expressions that are not literals, such as scalar subqueries,
+                // are computed when the plan is executed
+                let (skip, skip_expr) = match limit.get_skip_type()? {
+                    SkipType::Literal(skip) => (skip, None),
+                    SkipType::UnsupportedExpr => (0, limit.skip.as_deref()),
                 };
-                let FetchType::Literal(fetch) = limit.get_fetch_type()? else {
-                    return not_impl_err!(
-                        "Unsupported LIMIT expression: {:?}",
-                        limit.fetch
-                    );
+                let (fetch, fetch_expr) = match limit.get_fetch_type()? {
+                    FetchType::Literal(fetch) => (fetch, None),
+                    FetchType::UnsupportedExpr => (None, limit.fetch.as_deref()),
                 };
 
                 // GlobalLimitExec requires a single partition for input
                 let input = if input.output_partitioning().partition_count() == 1 {
                     input
                 } else {
-                    // Apply a LocalLimitExec to each partition. The optimizer will also insert
-                    // a CoalescePartitionsExec between the GlobalLimitExec and LocalLimitExec
-                    if let Some(fetch) = fetch {
-                        Arc::new(LocalLimitExec::new(input, fetch + skip))
-                    } else {
-                        input
+                    // Apply a LocalLimitExec to each partition if the number of rows
+                    // it needs is known. The optimizer will also insert a
+                    // CoalescePartitionsExec between the GlobalLimitExec and LocalLimitExec
+                    match fetch {
+                        Some(fetch) if skip_expr.is_none() => {
+                            Arc::new(LocalLimitExec::new(input, fetch + skip))
+                        }
+                        _ => input,
                     }
                 };
 
-                Arc::new(GlobalLimitExec::new(input, skip, fetch))
+                let mut limit_exec = GlobalLimitExec::new(input, skip, fetch);
+                if let Some(skip_expr) = skip_expr {
+                    let skip_expr =
+                        self.create_limit_expr(skip_expr, session_state).await?;
+                    limit_exec = limit_exec.with_skip_expr(skip_expr);
+                }
+                if let Some(fetch_expr) = fetch_expr {
+                    let fetch_expr =
+                        self.create_limit_expr(fetch_expr, session_state).await?;
+                    limit_exec = limit_exec.with_fetch_expr(fetch_expr);
+                }
+                Arc::new(limit_exec)
             }
             LogicalPlan::Unnest(Unnest {
                 list_type_columns,
@@ -1084,7 +1169,29 @@ impl DefaultPhysicalPlanner {
                 let prefer_hash_join =
                     session_state.config_options().optimizer.prefer_hash_join;
 
-                let join: Arc<dyn ExecutionPlan> = if join_on.is_empty() {
+                let join: Arc<dyn ExecutionPlan> = if *join_type == JoinType::LeftAsof {
+                    let Some(filter) = filter else {
+                        return internal_err!("Left Asof join without match condition");
+                    };
+                    let (match_left, match_op, match_right) =
+                        asof_match_condition(filter, left_df_schema, right_df_schema)?;
+                    Arc::new(AsofJoinExec::try_new(
+                        physical_left,
+                        physical_right,
+                        join_on,
+                        create_physical_expr(
+                            &match_left,
+                            left_df_schema,
+                            execution_props,
+                        )?,
+                        match_op,
+                        create_physical_expr(
+                            &match_right,
+                            right_df_schema,
+                            execution_props,
+                        )?,
+                    )?)
+                } else if join_on.is_empty() {
                     if join_filter.is_none() && matches!(join_type, JoinType::Inner) {
                         // cross join if there is no join conditions and no join filter set
                         Arc::new(CrossJoinExec::new(physical_left, physical_right))
@@ -1933,6 +2040,46 @@ impl DefaultPhysicalPlanner {
         Ok(mem_exec)
     }
 
+    /// Plans a `LIMIT` or `OFFSET` expression that is not a literal, such as
+    /// `LIMIT (SELECT cfg FROM settings)`, so it can be computed when the plan
+    /// is executed. Each scalar subquery is planned separately and replaced by
+    /// a column holding its result.
+    async fn create_limit_expr(
+        &self,
+        expr: &Expr,
+        session_state: &SessionState,
+    ) -> Result<LimitExpr> {
+        let mut subqueries = vec![];
+        let expr = expr
+            .clone()
+            .transform_up(|expr| {
+                let Expr::ScalarSubquery(subquery) = expr else {
+                    return Ok(Transformed::no(expr));
+                };
+                if !subquery.outer_ref_columns.is_empty() {
+                    return not_impl_err!(
+                        "Correlated subqueries are not supported in LIMIT or OFFSET"
+                    );
+                }
+                let column = Column::from_name(format!("subquery_{}", subqueries.len()));
+                subqueries.push(subquery.subquery);
+                Ok(Transformed::yes(Expr::Column(column)))
+            })
+            .data()?;
+
+        let mut fields = Vec::with_capacity(subqueries.len());
+        let mut subquery_plans = Vec::with_capacity(subqueries.len());
+        for (i, subquery) in subqueries.iter().enumerate() {
+            let data_type = subquery.schema().field(0).data_type().clone();
+            fields.push(Field::new(format!("subquery_{i}"), data_type, true));
+            subquery_plans
+                .push(Box::pin(self.create_initial_plan(subquery, session_state)).await?);
+        }
+        let schema = DFSchema::try_from(Schema::new(fields))?;
+        let expr = self.create_physical_expr(&expr, &schema, session_state)?;
+        Ok(LimitExpr::new(expr, subquery_plans))
+    }
+
     fn create_project_physical_exec(
         &self,
         session_state: &SessionState,
@@ -1986,6 +2133,122 @@ impl DefaultPhysicalPlanner {
     }
 }
 
+/// Splits the match condition of an ASOF join into the expression of the left
+/// input, the comparison operator and the expression of the right input.
+fn asof_match_condition(
+    filter: &Expr,
+    left_schema: &DFSchema,
+    right_schema: &DFSchema,
+) -> Result<(Expr, Operator, Expr)> {
+    let references_only = |expr: &Expr, schema: &DFSchema| {
+        expr.column_refs()
+            .iter()
+            .all(|column| schema.has_column(column))
+    };
+    if let Expr::BinaryExpr(BinaryExpr { left, op, right }) = filter {
+        if matches!(
+            op,
+            Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq
+        ) {
+            if references_only(left, left_schema) && references_only(right, right_schema)
+            {
+                return Ok((left.as_ref().clone(), *op, right.as_ref().clone()));
+            }
+            if references_only(left, right_schema) && references_only(right, left_schema)
+            {
+                let op = op.swap().unwrap();
+                return Ok((right.as_ref().clone(), op, left.as_ref().clone()));
+            }
+        }
+    }
+    plan_err!(
+        "ASOF JOIN match condition must be a single comparison between an expression \
+         of the left input and an expression of the right input, got {filter}"
+    )
+}
+
+/// Collects the filters of the input of a `DELETE` or `UPDATE` statement, with
+/// column qualifiers removed, so they can be passed to [`TableProvider::delete_from`]
+/// and [`TableProvider::update`].
+///
+/// [`TableProvider::delete_from`]: crate::datasource::TableProvider::delete_from
+/// [`TableProvider::update`]: crate::datasource::TableProvider::update
+fn extract_dml_filters(input: &Arc<LogicalPlan>) -> Result<Vec<Expr>> {
+    let mut filters = vec![];
+    input.apply(|node| {
+        match node {
+            LogicalPlan::Filter(filter) => {
+                filters.extend(split_conjunction(&filter.predicate).into_iter().cloned())
+            }
+            LogicalPlan::TableScan(scan) => filters.extend(scan.filters.iter().cloned()),
+            LogicalPlan::Projection(_) | LogicalPlan::SubqueryAlias(_) => {}
+            // The optimizer replaces the table scan by an empty relation when the
+            // predicate is always false
+            LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                ..
+            }) => filters.push(lit(false)),
+            other => {
+                return not_impl_err!(
+                    "Unsupported input for DML statement: {}",
+                    other.display()
+                )
+            }
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    filters.into_iter().map(strip_column_qualifiers).collect()
+}
+
+/// Collects the `(column name, new value)` pairs of an `UPDATE` statement from
+/// the projection the SQL planner places on top of its input. Columns that are
+/// passed through unchanged are skipped.
+fn extract_update_assignments(input: &Arc<LogicalPlan>) -> Result<Vec<(String, Expr)>> {
+    // The projection may have been removed by the optimizer if no column changes
+    let LogicalPlan::Projection(projection) = input.as_ref() else {
+        return Ok(vec![]);
+    };
+
+    let mut assignments = vec![];
+    for (expr, (_, field)) in projection.expr.iter().zip(projection.schema.iter()) {
+        let mut value = expr.clone().unalias();
+        // Inline expressions computed by projections introduced by the optimizer
+        // (e.g. common subexpression elimination)
+        let mut child = projection.input.as_ref();
+        while let LogicalPlan::Projection(inner) = child {
+            value = value
+                .transform(|e| {
+                    if let Expr::Column(col) = &e {
+                        if let Ok(idx) = inner.schema.index_of_column(col) {
+                            return Ok(Transformed::yes(
+                                inner.expr[idx].clone().unalias(),
+                            ));
+                        }
+                    }
+                    Ok(Transformed::no(e))
+                })
+                .data()?;
+            child = inner.input.as_ref();
+        }
+        let value = strip_column_qualifiers(value)?;
+        if matches!(&value, Expr::Column(col) if col.name == *field.name()) {
+            continue;
+        }
+        assignments.push((field.name().clone(), value));
+    }
+    Ok(assignments)
+}
+
+fn strip_column_qualifiers(expr: Expr) -> Result<Expr> {
+    expr.transform(|e| match e {
+        Expr::Column(col) if col.relation.is_some() => Ok(Transformed::yes(
+            Expr::Column(Column::new_unqualified(col.name)),
+        )),
+        _ => Ok(Transformed::no(e)),
+    })
+    .data()
+}
+
 fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
     match value {
         (Ok(e), Ok(e1)) => Ok((e, e1)),
@@ -2278,6 +2541,28 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_distribute_by_plan() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+
+        let logical_plan = scan_empty_with_partitions(Some("test"), &schema, None, 2)?
+            .repartition(LogicalPartitioning::DistributeBy(vec![col("id")]))?
+            .build()?;
+        let session_state = make_session_state();
+        let plan = DefaultPhysicalPlanner::default()
+            .create_initial_plan(&logical_plan, &session_state)
+            .await?;
+
+        let repartition = plan
+            .as_any()
+            .downcast_ref::<RepartitionExec>()
+            .expect("expected RepartitionExec");
+        // distributed into the configured number of target partitions
+        assert_eq!(repartition.partitioning().to_string(), "Hash([id@0], 4)");
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn errors() -> Result<()> {
         let bool_expr = col("c1").eq(col("c1"));
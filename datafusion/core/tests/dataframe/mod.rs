@@ -2056,7 +2056,8 @@ async fn verify_join_output_partitioning() -> Result<()> {
             JoinType::Left
             | JoinType::LeftSemi
             | JoinType::LeftAnti
-            | JoinType::LeftMark => {
+            | JoinType::LeftMark
+            | JoinType::LeftAsof => {
                 let left_exprs: Vec<Arc<dyn PhysicalExpr>> = vec![
                     Arc::new(Column::new_with_schema("c1", &join_schema)?),
                     Arc::new(Column::new_with_schema("c2", &join_schema)?),
@@ -3477,6 +3478,32 @@ async fn unnest_column_nulls() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn unnest_column_with_ordinality() -> Result<()> {
+    let df = table_with_lists_and_nulls().await?;
+
+    let options = UnnestOptions::new()
+        .with_preserve_nulls(true)
+        .with_ordinality(true);
+    let results = df
+        .unnest_columns_with_options(&["list"], options)?
+        .collect()
+        .await?;
+    let expected = [
+        "+------+-----------------+----+",
+        "| list | list_ordinality | id |",
+        "+------+-----------------+----+",
+        "| 1    | 1               | A  |",
+        "| 2    | 2               | A  |",
+        "|      |                 | B  |",
+        "| 3    | 1               | D  |",
+        "+------+-----------------+----+",
+    ];
+    assert_batches_eq!(expected, &results);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn unnest_fixed_list() -> Result<()> {
     let batch = get_fixed_list_batch()?;
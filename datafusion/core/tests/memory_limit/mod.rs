@@ -125,6 +125,21 @@ async fn join_by_key_multiple_partitions() {
         .await
 }
 
+#[tokio::test]
+async fn join_by_key_multiple_partitions_spill() {
+    let config = SessionConfig::new().with_target_partitions(2);
+    TestCase::new()
+        .with_query(
+            "select t1.* from t t1 JOIN t t2 ON t1.pod = t2.pod AND t1.time = t2.time",
+        )
+        .with_memory_limit(200_000)
+        .with_config(config)
+        .with_disk_manager_config(DiskManagerConfig::NewOs)
+        .with_expected_success()
+        .run()
+        .await
+}
+
 #[tokio::test]
 async fn join_by_key_single_partition() {
     let config = SessionConfig::new().with_target_partitions(1);
@@ -27,16 +27,23 @@ use datafusion::execution::context::SessionState;
 use datafusion::execution::session_state::SessionStateBuilder;
 use datafusion::prelude::SessionContext;
 use datafusion_common::stats::Precision;
-use datafusion_execution::cache::cache_manager::CacheManagerConfig;
+use datafusion_execution::cache::cache_manager::{
+    CacheManagerConfig, FILE_METADATA_CACHE,
+};
 use datafusion_execution::cache::cache_unit::{
-    DefaultFileStatisticsCache, DefaultListFilesCache,
+    CacheLimits, DefaultFileStatisticsCache, DefaultFilesMetadataCache,
+    DefaultListFilesCache,
 };
+use datafusion_execution::cache::CacheAccessor;
 use datafusion_execution::config::SessionConfig;
+use datafusion_execution::object_store::ObjectStoreUrl;
 use datafusion_execution::runtime_env::RuntimeEnvBuilder;
 use datafusion_expr::{col, lit, Expr};
 use datafusion_physical_plan::source::DataSourceExec;
 
+use datafusion::datasource::physical_plan::parquet::CachedParquetMetaData;
 use datafusion::datasource::physical_plan::FileScanConfig;
+use object_store::path::Path;
 use tempfile::tempdir;
 
 #[tokio::test]
@@ -188,6 +195,127 @@ async fn list_files_with_session_level_cache() {
     assert_eq!(get_list_file_cache_size(&state1), 1);
 }
 
+#[tokio::test]
+async fn load_metadata_with_session_level_cache() {
+    let metadata_cache = Arc::new(DefaultFilesMetadataCache::new(1024 * 1024));
+    let cache_config = CacheManagerConfig::default()
+        .with_file_metadata_cache(Some(Arc::clone(&metadata_cache) as _));
+    let rt = RuntimeEnvBuilder::new()
+        .with_cache_manager(cache_config)
+        .build_arc()
+        .unwrap();
+    let ctx = SessionContext::new_with_config_rt(SessionConfig::default(), rt);
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("data.parquet");
+    ctx.sql(&format!(
+        "COPY (SELECT * FROM generate_series(1, 100)) TO '{}'",
+        path.display()
+    ))
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx.register_parquet("t", path.to_str().unwrap(), Default::default())
+        .await
+        .unwrap();
+
+    let query = "SELECT count(*), sum(value) FROM t WHERE value > 10";
+    let expected = ctx.sql(query).await.unwrap().collect().await.unwrap();
+    assert_eq!(metadata_cache.len(), 1);
+    let object_meta = ctx
+        .runtime_env()
+        .object_store(ObjectStoreUrl::local_filesystem())
+        .unwrap()
+        .head(&Path::from_filesystem_path(&path).unwrap())
+        .await
+        .unwrap();
+    let metadata = metadata_cache
+        .get_with_extra(&object_meta.location, &object_meta)
+        .unwrap();
+    let parquet_metadata = metadata
+        .as_any()
+        .downcast_ref::<CachedParquetMetaData>()
+        .unwrap()
+        .parquet_metadata();
+    // The page indexes are cached with the footer
+    assert!(parquet_metadata.column_index().is_some());
+    assert!(parquet_metadata.offset_index().is_some());
+
+    // The second query reads the cached metadata
+    let actual = ctx.sql(query).await.unwrap().collect().await.unwrap();
+    assert_eq!(expected, actual);
+    let cached = metadata_cache
+        .get_with_extra(&object_meta.location, &object_meta)
+        .unwrap();
+    assert!(Arc::ptr_eq(&metadata, &cached));
+}
+
+#[tokio::test]
+async fn list_and_clear_session_level_caches() {
+    let metadata_cache = Arc::new(DefaultFilesMetadataCache::new_with_limits(
+        CacheLimits::default().with_max_entries(1),
+    ));
+    let cache_config =
+        CacheManagerConfig::default().with_file_metadata_cache(Some(metadata_cache));
+    let rt = RuntimeEnvBuilder::new()
+        .with_cache_manager(cache_config)
+        .build_arc()
+        .unwrap();
+    let ctx = SessionContext::new_with_config_rt(SessionConfig::default(), rt);
+
+    let dir = tempdir().unwrap();
+    for name in ["a", "b"] {
+        let path = dir.path().join(format!("{name}.parquet"));
+        ctx.sql(&format!(
+            "COPY (SELECT * FROM generate_series(1, 100)) TO '{}'",
+            path.display()
+        ))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx.register_parquet(name, path.to_str().unwrap(), Default::default())
+            .await
+            .unwrap();
+        ctx.sql(&format!("SELECT * FROM {name}"))
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+    }
+
+    // The metadata of "a" was evicted by the metadata of "b"
+    let caches = ctx.list_caches();
+    assert_eq!(caches.len(), 1);
+    assert_eq!(caches[0].kind, FILE_METADATA_CACHE);
+    assert_eq!(caches[0].len, 1);
+    assert_eq!(caches[0].stats.evictions, 1);
+    assert_eq!(caches[0].stats.misses, 2);
+    let entries = ctx.list_cache_entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].0, FILE_METADATA_CACHE);
+    assert_eq!(entries[0].1.key.filename(), Some("b.parquet"));
+    assert_eq!(caches[0].stats.memory_used, entries[0].1.memory_size);
+
+    ctx.sql("SELECT * FROM b")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(ctx.list_caches()[0].stats.hits, 1);
+
+    ctx.clear_caches();
+    let caches = ctx.list_caches();
+    assert_eq!(caches[0].len, 0);
+    assert_eq!(caches[0].stats.memory_used, 0);
+    assert!(ctx.list_cache_entries().is_empty());
+}
+
 async fn get_listing_table(
     table_path: &ListingTableUrl,
     static_cache: Option<Arc<DefaultFileStatisticsCache>>,
@@ -142,6 +142,11 @@ impl TestOutput {
         self.metric_value("row_groups_pruned_bloom_filter")
     }
 
+    /// The number of row_groups pruned by dictionaries
+    fn row_groups_pruned_dictionary(&self) -> Option<usize> {
+        self.metric_value("row_groups_pruned_dictionary")
+    }
+
     /// The number of row_groups matched by statistics
     fn row_groups_matched_statistics(&self) -> Option<usize> {
         self.metric_value("row_groups_matched_statistics")
@@ -152,6 +157,11 @@ impl TestOutput {
         self.metric_value("row_groups_pruned_statistics")
     }
 
+    /// The number of row_groups pruned by a dynamic filter right before being read
+    fn row_groups_pruned_dynamic_filter(&self) -> Option<usize> {
+        self.metric_value("row_groups_pruned_dynamic_filter")
+    }
+
     /// The number of row_groups matched by bloom filter or statistics
     fn row_groups_matched(&self) -> Option<usize> {
         self.row_groups_matched_bloom_filter()
@@ -33,6 +33,7 @@ struct RowGroupPruningTest {
     expected_row_group_pruned_by_statistics: Option<usize>,
     expected_row_group_matched_by_bloom_filter: Option<usize>,
     expected_row_group_pruned_by_bloom_filter: Option<usize>,
+    expected_row_group_pruned_by_dictionary: Option<usize>,
     expected_results: usize,
 }
 impl RowGroupPruningTest {
@@ -46,6 +47,7 @@ impl RowGroupPruningTest {
             expected_row_group_pruned_by_statistics: None,
             expected_row_group_matched_by_bloom_filter: None,
             expected_row_group_pruned_by_bloom_filter: None,
+            expected_row_group_pruned_by_dictionary: None,
             expected_results: 0,
         }
     }
@@ -92,6 +94,13 @@ impl RowGroupPruningTest {
         self
     }
 
+    // Enable dictionary pruning and set the expected pruned row groups by
+    // dictionaries
+    fn with_pruned_by_dictionary(mut self, pruned_by_dictionary: usize) -> Self {
+        self.expected_row_group_pruned_by_dictionary = Some(pruned_by_dictionary);
+        self
+    }
+
     // Set the expected rows for the test
     fn with_expected_rows(mut self, rows: usize) -> Self {
         self.expected_results = rows;
@@ -100,7 +109,10 @@ impl RowGroupPruningTest {
 
     // Execute the test with the current configuration
     async fn test_row_group_prune(self) {
-        let output = ContextWithParquet::new(self.scenario, RowGroup(5))
+        let mut config = SessionConfig::new();
+        config.options_mut().execution.parquet.dictionary_pruning =
+            self.expected_row_group_pruned_by_dictionary.is_some();
+        let output = ContextWithParquet::with_config(self.scenario, RowGroup(5), config)
             .await
             .query(&self.query)
             .await;
@@ -131,6 +143,13 @@ impl RowGroupPruningTest {
             self.expected_row_group_pruned_by_bloom_filter,
             "mismatched row_groups_pruned_bloom_filter",
         );
+        if let Some(expected) = self.expected_row_group_pruned_by_dictionary {
+            assert_eq!(
+                output.row_groups_pruned_dictionary(),
+                Some(expected),
+                "mismatched row_groups_pruned_dictionary",
+            );
+        }
         assert_eq!(
             output.result_rows,
             self.expected_results,
@@ -987,6 +1006,40 @@ async fn prune_string_neq() {
         .await;
 }
 
+#[tokio::test]
+async fn prune_string_like_dictionary() {
+    // min/max values can not prune any row group
+    RowGroupPruningTest::new()
+        .with_scenario(Scenario::ByteArray)
+        .with_query(
+            "SELECT name, service_string FROM t WHERE service_string LIKE '%e%ght'",
+        )
+        .with_expected_errors(Some(0))
+        .with_matched_by_stats(Some(3))
+        .with_pruned_by_stats(Some(0))
+        .with_matched_by_bloom_filter(Some(0))
+        .with_pruned_by_bloom_filter(Some(0))
+        .with_pruned_by_dictionary(2)
+        .with_expected_rows(1)
+        .test_row_group_prune()
+        .await;
+
+    RowGroupPruningTest::new()
+        .with_scenario(Scenario::ByteArray)
+        .with_query(
+            "SELECT name, service_string FROM t WHERE service_string NOT LIKE 'frontend%'",
+        )
+        .with_expected_errors(Some(0))
+        .with_matched_by_stats(Some(3))
+        .with_pruned_by_stats(Some(0))
+        .with_matched_by_bloom_filter(Some(0))
+        .with_pruned_by_bloom_filter(Some(0))
+        .with_pruned_by_dictionary(1)
+        .with_expected_rows(8)
+        .test_row_group_prune()
+        .await;
+}
+
 #[tokio::test]
 async fn prune_string_lt() {
     RowGroupPruningTest::new()
@@ -1535,3 +1588,51 @@ async fn test_bloom_filter_decimal_dict() {
         .test_row_group_prune()
         .await;
 }
+
+#[tokio::test]
+async fn prune_int32_topk_dynamic_filter() {
+    // The TopK tightens its dynamic filter to the 3rd smallest value seen so
+    // far while scanning `t`: once the first row group (-5..-1) and the second
+    // one (-4..0) have been read, the last two row groups (0..4 and 5..9) can
+    // not contain any of the 3 smallest values
+    let output = ContextWithParquet::new(Scenario::Int, RowGroup(5))
+        .await
+        .query("SELECT i32 FROM t ORDER BY i32 LIMIT 3")
+        .await;
+    println!("{}", output.description());
+    assert_eq!(output.predicate_evaluation_errors(), Some(0));
+    assert_eq!(output.row_groups_pruned_statistics(), Some(0));
+    assert_eq!(output.row_groups_pruned_dynamic_filter(), Some(2));
+    assert_eq!(output.result_rows, 3);
+}
+
+#[tokio::test]
+async fn prune_int32_join_dynamic_filter() {
+    // The hash join collects the values on its build side before scanning `t`,
+    // and prunes the row groups of `t` that can not match any of them
+    RowGroupPruningTest::new()
+        .with_scenario(Scenario::Int)
+        .with_query(
+            "SELECT t.i32 FROM (VALUES (6::INT), (7::INT)) AS v(x) JOIN t ON t.i32 = v.x",
+        )
+        .with_expected_errors(Some(0))
+        .with_matched_by_stats(Some(1))
+        .with_pruned_by_stats(Some(3))
+        .with_matched_by_bloom_filter(Some(1))
+        .with_pruned_by_bloom_filter(Some(0))
+        .with_expected_rows(2)
+        .test_row_group_prune()
+        .await;
+
+    RowGroupPruningTest::new()
+        .with_scenario(Scenario::Int)
+        .with_query("SELECT t.i32 FROM (VALUES (100::INT)) AS v(x) JOIN t ON t.i32 = v.x")
+        .with_expected_errors(Some(0))
+        .with_matched_by_stats(Some(0))
+        .with_pruned_by_stats(Some(4))
+        .with_matched_by_bloom_filter(Some(0))
+        .with_pruned_by_bloom_filter(Some(0))
+        .with_expected_rows(0)
+        .test_row_group_prune()
+        .await;
+}
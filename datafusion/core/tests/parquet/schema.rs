@@ -21,7 +21,14 @@ use std::{collections::HashMap, fs, path::Path};
 use tempfile::TempDir;
 
 use super::*;
+use arrow::array::StructArray;
+use arrow::datatypes::{Fields, SchemaRef};
+use datafusion::datasource::file_format::parquet::ParquetFormat;
+use datafusion::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
 use datafusion_common::assert_batches_sorted_eq;
+use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
 
 #[tokio::test]
 async fn schema_merge_ignores_metadata_by_default() {
@@ -171,6 +178,210 @@ async fn schema_merge_can_preserve_metadata() {
     assert_metadata(&actual, &expected_metadata);
 }
 
+#[tokio::test]
+async fn schema_evolution_by_field_id() {
+    let tmp_dir = TempDir::new().unwrap();
+    let table_dir = tmp_dir.path().join("parquet_test");
+    fs::create_dir(&table_dir).unwrap();
+
+    // "id" was named "user_id", was widened from Int32 to Int64 and its
+    // struct "info" gained the field "b". The field ids did not change.
+    let old_info = Fields::from(vec![Field::new("a", DataType::Int32, true)]);
+    let old_batch = RecordBatch::try_from_iter(vec![
+        (
+            "user_id",
+            Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+        ),
+        (
+            "info",
+            Arc::new(StructArray::new(
+                old_info,
+                vec![Arc::new(Int32Array::from(vec![10, 20]))],
+                None,
+            )),
+        ),
+    ])
+    .unwrap();
+    let old_batch = with_field_ids(old_batch);
+    write_batch(&table_dir.join("old.parquet"), &old_batch);
+
+    let new_info = Fields::from(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ]);
+    let new_batch = RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int64Array::from(vec![3])) as ArrayRef),
+        (
+            "info",
+            Arc::new(StructArray::new(
+                new_info.clone(),
+                vec![
+                    Arc::new(Int32Array::from(vec![30])),
+                    Arc::new(StringArray::from(vec!["x"])),
+                ],
+                None,
+            )),
+        ),
+    ])
+    .unwrap();
+    let new_batch = with_field_ids(new_batch);
+    write_batch(&table_dir.join("new.parquet"), &new_batch);
+
+    // a column named like the old name of "id", but with another field id
+    let table_schema = Arc::new(Schema::new(vec![
+        field_with_id(Field::new("id", DataType::Int64, true), 1),
+        field_with_id(Field::new("info", DataType::Struct(new_info), true), 2),
+        field_with_id(Field::new("user_id", DataType::Utf8, true), 3),
+    ]));
+
+    let ctx = SessionContext::new_with_config(
+        SessionConfig::new()
+            .set_bool("datafusion.execution.parquet.pushdown_filters", true),
+    );
+    let config = listing_table_config(&ctx, &table_dir, table_schema).await;
+    ctx.register_table("t", Arc::new(ListingTable::try_new(config).unwrap()))
+        .unwrap();
+
+    for (sql, expected) in [
+        (
+            "SELECT * FROM t WHERE id >= 2",
+            vec![
+                "+----+---------------+---------+",
+                "| id | info          | user_id |",
+                "+----+---------------+---------+",
+                "| 2  | {a: 20, b: }  |         |",
+                "| 3  | {a: 30, b: x} |         |",
+                "+----+---------------+---------+",
+            ],
+        ),
+        // answered from the statistics of the files
+        (
+            "SELECT min(id), max(id), count(id), count(user_id) FROM t",
+            vec![
+                "+-----------+-----------+-------------+------------------+",
+                "| min(t.id) | max(t.id) | count(t.id) | count(t.user_id) |",
+                "+-----------+-----------+-------------+------------------+",
+                "| 1         | 3         | 3           | 0                |",
+                "+-----------+-----------+-------------+------------------+",
+            ],
+        ),
+    ] {
+        let actual = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+        assert_batches_sorted_eq!(expected, &actual);
+    }
+}
+
+#[tokio::test]
+async fn schema_evolution_with_column_renames() {
+    let tmp_dir = TempDir::new().unwrap();
+    let table_dir = tmp_dir.path().join("parquet_test");
+    fs::create_dir(&table_dir).unwrap();
+
+    // "id" was named "user_id", and "user_id" was then reused for another column
+    let old_batch = RecordBatch::try_from_iter(vec![(
+        "user_id",
+        Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+    )])
+    .unwrap();
+    write_batch(&table_dir.join("old.parquet"), &old_batch);
+    let new_batch = RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int64Array::from(vec![3, 4])) as ArrayRef),
+        ("login", Arc::new(StringArray::from(vec!["c", "d"]))),
+    ])
+    .unwrap();
+    write_batch(&table_dir.join("new.parquet"), &new_batch);
+
+    let table_schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new("user_id", DataType::Utf8, true),
+    ]));
+
+    let ctx = SessionContext::new_with_config(
+        SessionConfig::new()
+            .set_bool("datafusion.execution.parquet.pushdown_filters", true),
+    );
+    let config = listing_table_config(&ctx, &table_dir, table_schema)
+        .await
+        .with_column_renames(HashMap::from([
+            ("user_id".to_string(), "id".to_string()),
+            ("login".to_string(), "user_id".to_string()),
+        ]));
+    ctx.register_table("t", Arc::new(ListingTable::try_new(config).unwrap()))
+        .unwrap();
+
+    for (sql, expected) in [
+        (
+            "SELECT * FROM t WHERE id IN (2, 3)",
+            vec![
+                "+----+---------+",
+                "| id | user_id |",
+                "+----+---------+",
+                "| 2  |         |",
+                "| 3  | c       |",
+                "+----+---------+",
+            ],
+        ),
+        (
+            "SELECT max(id), count(user_id) FROM t",
+            vec![
+                "+-----------+------------------+",
+                "| max(t.id) | count(t.user_id) |",
+                "+-----------+------------------+",
+                "| 4         | 2                |",
+                "+-----------+------------------+",
+            ],
+        ),
+    ] {
+        let actual = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+        assert_batches_sorted_eq!(expected, &actual);
+    }
+}
+
+/// Returns a [`ListingTableConfig`] for the parquet files in `table_dir`
+async fn listing_table_config(
+    ctx: &SessionContext,
+    table_dir: &Path,
+    table_schema: SchemaRef,
+) -> ListingTableConfig {
+    let table_path = ListingTableUrl::parse(table_dir.to_str().unwrap()).unwrap();
+    let options = ListingOptions::new(Arc::new(ParquetFormat::default()))
+        .with_file_extension(".parquet")
+        .with_collect_stat(true);
+    ListingTableConfig::new(table_path)
+        .with_listing_options(options)
+        .with_schema(table_schema)
+        .infer_partitions_from_path(&ctx.state())
+        .await
+        .unwrap()
+}
+
+/// Writes `batch` to a parquet file at `path`
+fn write_batch(path: &Path, batch: &RecordBatch) {
+    let file = fs::File::create(path).unwrap();
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None).unwrap();
+    writer.write(batch).unwrap();
+    writer.close().unwrap();
+}
+
+/// Returns `batch` with the field ids 1, 2, ... for its columns
+fn with_field_ids(batch: RecordBatch) -> RecordBatch {
+    let fields = batch
+        .schema()
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| field_with_id(field.as_ref().clone(), i as i32 + 1))
+        .collect::<Vec<_>>();
+    batch.with_schema(Arc::new(Schema::new(fields))).unwrap()
+}
+
+fn field_with_id(field: Field, id: i32) -> Field {
+    field.with_metadata(HashMap::from([(
+        PARQUET_FIELD_ID_META_KEY.to_string(),
+        id.to_string(),
+    )]))
+}
+
 fn make_meta(k: impl Into<String>, v: impl Into<String>) -> HashMap<String, String> {
     let mut meta = HashMap::new();
     meta.insert(k.into(), v.into());
@@ -596,7 +596,8 @@ fn multi_hash_joins() -> Result<()> {
                 assert_optimized!(expected, top_join.clone(), true);
                 assert_optimized!(expected, top_join, false);
             }
-            JoinType::RightSemi | JoinType::RightAnti => {}
+            // Asof joins are never planned as hash joins
+            JoinType::RightSemi | JoinType::RightAnti | JoinType::LeftAsof => {}
         }
 
         match join_type {
@@ -660,7 +661,10 @@ fn multi_hash_joins() -> Result<()> {
                 assert_optimized!(expected, top_join.clone(), true);
                 assert_optimized!(expected, top_join, false);
             }
-            JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {}
+            JoinType::LeftSemi
+            | JoinType::LeftAnti
+            | JoinType::LeftMark
+            | JoinType::LeftAsof => {}
         }
     }
 
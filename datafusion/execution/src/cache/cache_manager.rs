@@ -15,10 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::cache::CacheAccessor;
+use crate::cache::{CacheAccessor, CacheEntry, CacheStats};
 use datafusion_common::{Result, Statistics};
 use object_store::path::Path;
 use object_store::ObjectMeta;
+use std::any::Any;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
@@ -32,6 +33,26 @@ pub type FileStatisticsCache =
 pub type ListFilesCache =
     Arc<dyn CacheAccessor<Path, Arc<Vec<ObjectMeta>>, Extra = ObjectMeta>>;
 
+/// Metadata of a file cached by a [`FileMetadataCache`], such as the footer
+/// and page indexes of a Parquet file
+pub trait FileMetadata: Any + Send + Sync {
+    /// Returns the metadata as [`Any`] so that it can be downcast to its
+    /// concrete type
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the memory used by the metadata, in bytes
+    fn memory_size(&self) -> usize;
+}
+
+/// The cache of file metadata, such as the footers and page indexes of
+/// Parquet files.
+/// if set [`CacheManagerConfig::with_file_metadata_cache`]
+/// Will avoid fetching and decoding the metadata of the same files
+/// repeatedly during the session lifetime, as long as the files do not change,
+/// this cache will store in [`crate::runtime_env::RuntimeEnv`].
+pub type FileMetadataCache =
+    Arc<dyn CacheAccessor<Path, Arc<dyn FileMetadata>, Extra = ObjectMeta>>;
+
 impl Debug for dyn CacheAccessor<Path, Arc<Statistics>, Extra = ObjectMeta> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "Cache name: {} with length: {}", self.name(), self.len())
@@ -44,10 +65,17 @@ impl Debug for dyn CacheAccessor<Path, Arc<Vec<ObjectMeta>>, Extra = ObjectMeta>
     }
 }
 
+impl Debug for dyn CacheAccessor<Path, Arc<dyn FileMetadata>, Extra = ObjectMeta> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cache name: {} with length: {}", self.name(), self.len())
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct CacheManager {
     file_statistic_cache: Option<FileStatisticsCache>,
     list_files_cache: Option<ListFilesCache>,
+    file_metadata_cache: Option<FileMetadataCache>,
 }
 
 impl CacheManager {
@@ -59,6 +87,9 @@ impl CacheManager {
         if let Some(lc) = &config.list_files_cache {
             manager.list_files_cache = Some(Arc::clone(lc))
         }
+        if let Some(mc) = &config.file_metadata_cache {
+            manager.file_metadata_cache = Some(Arc::clone(mc))
+        }
         Ok(Arc::new(manager))
     }
 
@@ -71,6 +102,105 @@ impl CacheManager {
     pub fn get_list_files_cache(&self) -> Option<ListFilesCache> {
         self.list_files_cache.clone()
     }
+
+    /// Get the cache of file metadata, such as Parquet footers.
+    pub fn get_file_metadata_cache(&self) -> Option<FileMetadataCache> {
+        self.file_metadata_cache.clone()
+    }
+
+    /// List the enabled caches, with their statistics.
+    pub fn list_caches(&self) -> Vec<CacheInfo> {
+        let mut caches = vec![];
+        if let Some(cache) = &self.file_statistic_cache {
+            caches.push(CacheInfo::new(FILE_STATISTICS_CACHE, cache.as_ref()));
+        }
+        if let Some(cache) = &self.list_files_cache {
+            caches.push(CacheInfo::new(LIST_FILES_CACHE, cache.as_ref()));
+        }
+        if let Some(cache) = &self.file_metadata_cache {
+            caches.push(CacheInfo::new(FILE_METADATA_CACHE, cache.as_ref()));
+        }
+        caches
+    }
+
+    /// List the entries of the enabled caches, with the kind of their cache,
+    /// from the least to the most recently used entry of each cache.
+    pub fn list_entries(&self) -> Vec<(&'static str, CacheEntry<Path>)> {
+        let mut entries = vec![];
+        if let Some(cache) = &self.file_statistic_cache {
+            entries.extend(
+                cache
+                    .list_entries()
+                    .into_iter()
+                    .map(|entry| (FILE_STATISTICS_CACHE, entry)),
+            );
+        }
+        if let Some(cache) = &self.list_files_cache {
+            entries.extend(
+                cache
+                    .list_entries()
+                    .into_iter()
+                    .map(|entry| (LIST_FILES_CACHE, entry)),
+            );
+        }
+        if let Some(cache) = &self.file_metadata_cache {
+            entries.extend(
+                cache
+                    .list_entries()
+                    .into_iter()
+                    .map(|entry| (FILE_METADATA_CACHE, entry)),
+            );
+        }
+        entries
+    }
+
+    /// Remove all the entries of the enabled caches.
+    pub fn clear(&self) {
+        if let Some(cache) = &self.file_statistic_cache {
+            cache.clear();
+        }
+        if let Some(cache) = &self.list_files_cache {
+            cache.clear();
+        }
+        if let Some(cache) = &self.file_metadata_cache {
+            cache.clear();
+        }
+    }
+}
+
+/// Kind of the cache of listing files statistics
+pub const FILE_STATISTICS_CACHE: &str = "file_statistics";
+/// Kind of the cache of listed files
+pub const LIST_FILES_CACHE: &str = "list_files";
+/// Kind of the cache of file metadata
+pub const FILE_METADATA_CACHE: &str = "file_metadata";
+
+/// An enabled cache of a [`CacheManager`], returned by
+/// [`CacheManager::list_caches`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheInfo {
+    /// Kind of the cache, such as [`FILE_STATISTICS_CACHE`]
+    pub kind: &'static str,
+    /// Name of the cache, see [`CacheAccessor::name`]
+    pub name: String,
+    /// Number of entries of the cache
+    pub len: usize,
+    /// Statistics of the cache
+    pub stats: CacheStats,
+}
+
+impl CacheInfo {
+    fn new<V>(
+        kind: &'static str,
+        cache: &dyn CacheAccessor<Path, V, Extra = ObjectMeta>,
+    ) -> Self {
+        Self {
+            kind,
+            name: cache.name(),
+            len: cache.len(),
+            stats: cache.stats(),
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -83,12 +213,26 @@ pub struct CacheManagerConfig {
     /// This setting avoids listing file meta of the same path repeatedly
     /// in same session, which may be expensive in certain situations (e.g. remote object storage).
     /// Note that if this option is enabled, DataFusion will not see any updates to the underlying
-    /// location.  
+    /// location until the entries expire, see [`CacheLimits::ttl`].
     /// Default is disable.
+    ///
+    /// [`CacheLimits::ttl`]: crate::cache::cache_unit::CacheLimits::ttl
     pub list_files_cache: Option<ListFilesCache>,
+    /// Enable cache of file metadata, such as the footers and page indexes of
+    /// Parquet files, when reading files.
+    /// This setting avoids fetching and decoding the metadata of the same files
+    /// repeatedly in same session. Entries are invalidated when the size, last
+    /// modification time or e_tag of the files change.
+    /// See [`DefaultFilesMetadataCache`] for a cache bounded by memory.
+    /// Default is disable.
+    ///
+    /// [`DefaultFilesMetadataCache`]: crate::cache::cache_unit::DefaultFilesMetadataCache
+    pub file_metadata_cache: Option<FileMetadataCache>,
 }
 
 impl CacheManagerConfig {
+    /// Set the cache of file statistics, see
+    /// [`Self::table_files_statistics_cache`]
     pub fn with_files_statistics_cache(
         mut self,
         cache: Option<FileStatisticsCache>,
@@ -97,8 +241,16 @@ impl CacheManagerConfig {
         self
     }
 
+    /// Set the cache of listed files, see [`Self::list_files_cache`]
     pub fn with_list_files_cache(mut self, cache: Option<ListFilesCache>) -> Self {
         self.list_files_cache = cache;
         self
     }
+
+    /// Set the cache of file metadata, such as Parquet footers, see
+    /// [`Self::file_metadata_cache`]
+    pub fn with_file_metadata_cache(mut self, cache: Option<FileMetadataCache>) -> Self {
+        self.file_metadata_cache = cache;
+        self
+    }
 }
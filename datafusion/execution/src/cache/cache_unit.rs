@@ -15,21 +15,375 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::cache::CacheAccessor;
+use crate::cache::cache_manager::FileMetadata;
+use crate::cache::{CacheAccessor, CacheEntry, CacheStats};
 
-use datafusion_common::Statistics;
+use datafusion_common::instant::Instant;
+use datafusion_common::stats::Precision;
+use datafusion_common::{ColumnStatistics, ScalarValue, Statistics};
 
-use dashmap::DashMap;
 use object_store::path::Path;
 use object_store::ObjectMeta;
+use parking_lot::Mutex;
+
+/// Limits of the default caches.
+/// Once an entry is added beyond the maximum number of entries or memory, the
+/// least recently used entries are evicted. Entries older than the time to
+/// live are not returned anymore.
+/// By default, caches are unbounded and their entries never expire.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheLimits {
+    /// Maximum number of entries
+    pub max_entries: Option<usize>,
+    /// Maximum estimated memory used by the entries, in bytes
+    pub memory_limit: Option<usize>,
+    /// Time to live of the entries, from their insertion
+    pub ttl: Option<Duration>,
+}
+
+impl CacheLimits {
+    /// Set the maximum number of entries
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Set the maximum estimated memory used by the entries, in bytes
+    pub fn with_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Set the time to live of the entries, from their insertion
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+/// Number of shards of a [`LruCache`]
+const LRU_CACHE_SHARDS: usize = 16;
+
+/// Entries of a default cache, evicted in least recently used order once the
+/// [`CacheLimits`] are exceeded.
+///
+/// The entries are distributed over shards by the hash of their keys, each
+/// guarded by its own lock, so that concurrent lookups of different keys
+/// rarely wait for each other.
+pub(crate) struct LruCache<K, V> {
+    limits: CacheLimits,
+    shards: Box<[Mutex<LruCacheShard<K, V>>]>,
+    hash_builder: RandomState,
+    /// Next value of `LruCacheEntry::last_use`, shared by all the shards so
+    /// that the least recently used entry of the cache is the oldest of the
+    /// least recently used entries of the shards
+    next_use: AtomicU64,
+    len: AtomicUsize,
+    memory_used: AtomicUsize,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    evictions: AtomicUsize,
+}
+
+struct LruCacheShard<K, V> {
+    entries: HashMap<K, LruCacheEntry<V>>,
+    /// Keys of the entries, by last use
+    lru: BTreeMap<u64, K>,
+}
+
+struct LruCacheEntry<V> {
+    value: V,
+    /// Estimated memory used by the entry, in bytes
+    memory_size: usize,
+    last_use: u64,
+    hits: usize,
+    inserted_at: Instant,
+}
+
+impl<K, V> Default for LruCacheShard<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            lru: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for LruCache<K, V> {
+    fn default() -> Self {
+        Self::new(CacheLimits::default())
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub(crate) fn new(limits: CacheLimits) -> Self {
+        Self {
+            limits,
+            shards: (0..LRU_CACHE_SHARDS)
+                .map(|_| Mutex::new(LruCacheShard::default()))
+                .collect(),
+            hash_builder: RandomState::new(),
+            next_use: AtomicU64::new(0),
+            len: AtomicUsize::new(0),
+            memory_used: AtomicUsize::new(0),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            evictions: AtomicUsize::new(0),
+        }
+    }
+
+    fn shard(&self, k: &K) -> &Mutex<LruCacheShard<K, V>> {
+        let hash = self.hash_builder.hash_one(k) as usize;
+        &self.shards[hash % self.shards.len()]
+    }
+
+    fn next_use(&self) -> u64 {
+        self.next_use.fetch_add(1, AtomicOrdering::Relaxed)
+    }
+
+    fn is_expired(&self, entry: &LruCacheEntry<V>) -> bool {
+        self.limits
+            .ttl
+            .is_some_and(|ttl| entry.inserted_at.elapsed() >= ttl)
+    }
+
+    /// Remove the entry of `k` from `shard`
+    fn remove_entry(&self, shard: &mut LruCacheShard<K, V>, k: &K) -> Option<V> {
+        let entry = shard.entries.remove(k)?;
+        shard.lru.remove(&entry.last_use);
+        self.len.fetch_sub(1, AtomicOrdering::Relaxed);
+        self.memory_used
+            .fetch_sub(entry.memory_size, AtomicOrdering::Relaxed);
+        Some(entry.value)
+    }
+
+    /// Get the value of `k` mapped by `f`, which returns `None` if the value
+    /// is stale, marking the entry as the most recently used one
+    pub(crate) fn get<R>(&self, k: &K, f: impl FnOnce(&V) -> Option<R>) -> Option<R> {
+        let mut shard = self.shard(k).lock();
+        let shard = &mut *shard;
+        let result = match shard.entries.get_mut(k) {
+            Some(entry) if self.is_expired(entry) => {
+                self.remove_entry(shard, k);
+                None
+            }
+            Some(entry) => {
+                let result = f(&entry.value);
+                if result.is_some() {
+                    entry.hits += 1;
+                    shard.lru.remove(&entry.last_use);
+                    entry.last_use = self.next_use();
+                    shard.lru.insert(entry.last_use, k.clone());
+                }
+                result
+            }
+            None => None,
+        };
+        let counter = if result.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, AtomicOrdering::Relaxed);
+        result
+    }
+
+    /// Put the value of `k`, using `memory_size` bytes, then evict the least
+    /// recently used entries exceeding the limits
+    pub(crate) fn put(&self, k: &K, value: V, memory_size: usize) -> Option<V> {
+        let mut shard = self.shard(k).lock();
+        let old = self.remove_entry(&mut shard, k);
+        // An entry larger than the whole cache would evict all the entries
+        if self
+            .limits
+            .memory_limit
+            .is_some_and(|memory_limit| memory_size > memory_limit)
+        {
+            return old;
+        }
+        let last_use = self.next_use();
+        shard.entries.insert(
+            k.clone(),
+            LruCacheEntry {
+                value,
+                memory_size,
+                last_use,
+                hits: 0,
+                inserted_at: Instant::now(),
+            },
+        );
+        shard.lru.insert(last_use, k.clone());
+        self.len.fetch_add(1, AtomicOrdering::Relaxed);
+        self.memory_used
+            .fetch_add(memory_size, AtomicOrdering::Relaxed);
+        drop(shard);
+        self.evict();
+        old
+    }
+
+    fn exceeds_limits(&self) -> bool {
+        let too_many_entries = self.limits.max_entries.is_some_and(|max_entries| {
+            self.len.load(AtomicOrdering::Relaxed) > max_entries
+        });
+        let too_much_memory = self.limits.memory_limit.is_some_and(|memory_limit| {
+            self.memory_used.load(AtomicOrdering::Relaxed) > memory_limit
+        });
+        too_many_entries || too_much_memory
+    }
+
+    /// Evict the least recently used entries while the limits are exceeded.
+    /// Only one shard is locked at a time.
+    fn evict(&self) {
+        while self.exceeds_limits() {
+            let oldest = self
+                .shards
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, shard)| {
+                    let last_use = *shard.lock().lru.first_key_value()?.0;
+                    Some((last_use, idx))
+                })
+                .min();
+            let Some((_, idx)) = oldest else {
+                break;
+            };
+            let mut shard = self.shards[idx].lock();
+            // The entry may have been used or removed since, in which case
+            // the now least recently used entry of the shard is evicted
+            let Some((_, k)) = shard.lru.first_key_value() else {
+                continue;
+            };
+            let k = k.clone();
+            if self.remove_entry(&mut shard, &k).is_some() {
+                self.evictions.fetch_add(1, AtomicOrdering::Relaxed);
+            }
+        }
+    }
+
+    pub(crate) fn remove(&self, k: &K) -> Option<V> {
+        self.remove_entry(&mut self.shard(k).lock(), k)
+    }
+
+    pub(crate) fn contains_key(&self, k: &K) -> bool {
+        self.shard(k)
+            .lock()
+            .entries
+            .get(k)
+            .is_some_and(|entry| !self.is_expired(entry))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Remove all the entries, keeping the hit, miss and eviction counters
+    pub(crate) fn clear(&self) {
+        for shard in self.shards.iter() {
+            let mut shard = shard.lock();
+            let memory_size = shard
+                .entries
+                .values()
+                .map(|entry| entry.memory_size)
+                .sum::<usize>();
+            self.len
+                .fetch_sub(shard.entries.len(), AtomicOrdering::Relaxed);
+            self.memory_used
+                .fetch_sub(memory_size, AtomicOrdering::Relaxed);
+            shard.entries.clear();
+            shard.lru.clear();
+        }
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(AtomicOrdering::Relaxed),
+            misses: self.misses.load(AtomicOrdering::Relaxed),
+            evictions: self.evictions.load(AtomicOrdering::Relaxed),
+            memory_used: self.memory_used.load(AtomicOrdering::Relaxed),
+        }
+    }
+
+    /// Returns the entries that did not expire, by last use
+    pub(crate) fn list_entries(&self) -> Vec<CacheEntry<K>> {
+        let mut entries = vec![];
+        for shard in self.shards.iter() {
+            let shard = shard.lock();
+            entries.extend(
+                shard
+                    .entries
+                    .iter()
+                    .filter(|(_, entry)| !self.is_expired(entry))
+                    .map(|(k, entry)| {
+                        let cache_entry = CacheEntry {
+                            key: k.clone(),
+                            memory_size: entry.memory_size,
+                            hits: entry.hits,
+                        };
+                        (entry.last_use, cache_entry)
+                    }),
+            );
+        }
+        entries.sort_unstable_by_key(|(last_use, _)| *last_use);
+        entries.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
+
+/// Returns the estimated memory used by `meta`, in bytes
+fn object_meta_memory_size(meta: &ObjectMeta) -> usize {
+    size_of::<ObjectMeta>()
+        + meta.location.as_ref().len()
+        + meta.e_tag.as_ref().map_or(0, String::len)
+        + meta.version.as_ref().map_or(0, String::len)
+}
+
+/// Returns the estimated memory used by `statistics`, in bytes
+fn statistics_memory_size(statistics: &Statistics) -> usize {
+    fn scalar_memory_size(value: &Precision<ScalarValue>) -> usize {
+        value
+            .get_value()
+            .map_or(0, |value| value.size() - size_of::<ScalarValue>())
+    }
+    size_of::<Statistics>()
+        + statistics
+            .column_statistics
+            .iter()
+            .map(|column| {
+                size_of::<ColumnStatistics>()
+                    + scalar_memory_size(&column.max_value)
+                    + scalar_memory_size(&column.min_value)
+                    + scalar_memory_size(&column.sum_value)
+            })
+            .sum::<usize>()
+}
 
 /// Collected statistics for files
 /// Cache is invalided when file size or last modification has changed
+/// The number of entries and their memory can be bounded with [`CacheLimits`].
 #[derive(Default)]
 pub struct DefaultFileStatisticsCache {
-    statistics: DashMap<Path, (ObjectMeta, Arc<Statistics>)>,
+    cache: LruCache<Path, (ObjectMeta, Arc<Statistics>)>,
+}
+
+impl DefaultFileStatisticsCache {
+    /// Create a cache of files statistics bounded by `limits`
+    pub fn new_with_limits(limits: CacheLimits) -> Self {
+        Self {
+            cache: LruCache::new(limits),
+        }
+    }
+
+    /// Return the limits of the cache
+    pub fn limits(&self) -> CacheLimits {
+        self.cache.limits
+    }
 }
 
 impl CacheAccessor<Path, Arc<Statistics>> for DefaultFileStatisticsCache {
@@ -37,28 +391,20 @@ impl CacheAccessor<Path, Arc<Statistics>> for DefaultFileStatisticsCache {
 
     /// Get `Statistics` for file location.
     fn get(&self, k: &Path) -> Option<Arc<Statistics>> {
-        self.statistics
-            .get(k)
-            .map(|s| Some(Arc::clone(&s.value().1)))
-            .unwrap_or(None)
+        self.cache
+            .get(k, |(_, statistics)| Some(Arc::clone(statistics)))
     }
 
     /// Get `Statistics` for file location. Returns None if file has changed or not found.
     fn get_with_extra(&self, k: &Path, e: &Self::Extra) -> Option<Arc<Statistics>> {
-        self.statistics
-            .get(k)
-            .map(|s| {
-                let (saved_meta, statistics) = s.value();
-                if saved_meta.size != e.size
-                    || saved_meta.last_modified != e.last_modified
-                {
-                    // file has changed
-                    None
-                } else {
-                    Some(Arc::clone(statistics))
-                }
-            })
-            .unwrap_or(None)
+        self.cache.get(k, |(saved_meta, statistics)| {
+            if saved_meta.size != e.size || saved_meta.last_modified != e.last_modified {
+                // file has changed
+                None
+            } else {
+                Some(Arc::clone(statistics))
+            }
+        })
     }
 
     /// Save collected file statistics
@@ -72,43 +418,71 @@ impl CacheAccessor<Path, Arc<Statistics>> for DefaultFileStatisticsCache {
         value: Arc<Statistics>,
         e: &Self::Extra,
     ) -> Option<Arc<Statistics>> {
-        self.statistics
-            .insert(key.clone(), (e.clone(), value))
+        let memory_size = key.as_ref().len()
+            + object_meta_memory_size(e)
+            + statistics_memory_size(&value);
+        self.cache
+            .put(key, (e.clone(), value), memory_size)
             .map(|x| x.1)
     }
 
     fn remove(&mut self, k: &Path) -> Option<Arc<Statistics>> {
-        self.statistics.remove(k).map(|x| x.1 .1)
+        self.cache.remove(k).map(|x| x.1)
     }
 
     fn contains_key(&self, k: &Path) -> bool {
-        self.statistics.contains_key(k)
+        self.cache.contains_key(k)
     }
 
     fn len(&self) -> usize {
-        self.statistics.len()
+        self.cache.len()
     }
 
     fn clear(&self) {
-        self.statistics.clear()
+        self.cache.clear()
     }
+
     fn name(&self) -> String {
         "DefaultFileStatisticsCache".to_string()
     }
+
+    fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    fn list_entries(&self) -> Vec<CacheEntry<Path>> {
+        self.cache.list_entries()
+    }
 }
 
 /// Collected files metadata for listing files.
-/// Cache will not invalided until user call remove or clear.
+/// Cache will not invalided until user call remove or clear, or until the
+/// entries expire, see [`CacheLimits::ttl`].
+/// The number of entries and their memory can be bounded with [`CacheLimits`].
 #[derive(Default)]
 pub struct DefaultListFilesCache {
-    statistics: DashMap<Path, Arc<Vec<ObjectMeta>>>,
+    cache: LruCache<Path, Arc<Vec<ObjectMeta>>>,
+}
+
+impl DefaultListFilesCache {
+    /// Create a cache of listed files bounded by `limits`
+    pub fn new_with_limits(limits: CacheLimits) -> Self {
+        Self {
+            cache: LruCache::new(limits),
+        }
+    }
+
+    /// Return the limits of the cache
+    pub fn limits(&self) -> CacheLimits {
+        self.cache.limits
+    }
 }
 
 impl CacheAccessor<Path, Arc<Vec<ObjectMeta>>> for DefaultListFilesCache {
     type Extra = ObjectMeta;
 
     fn get(&self, k: &Path) -> Option<Arc<Vec<ObjectMeta>>> {
-        self.statistics.get(k).map(|x| Arc::clone(x.value()))
+        self.cache.get(k, |metas| Some(Arc::clone(metas)))
     }
 
     fn get_with_extra(
@@ -124,7 +498,10 @@ impl CacheAccessor<Path, Arc<Vec<ObjectMeta>>> for DefaultListFilesCache {
         key: &Path,
         value: Arc<Vec<ObjectMeta>>,
     ) -> Option<Arc<Vec<ObjectMeta>>> {
-        self.statistics.insert(key.clone(), value)
+        let memory_size = key.as_ref().len()
+            + size_of::<Vec<ObjectMeta>>()
+            + value.iter().map(object_meta_memory_size).sum::<usize>();
+        self.cache.put(key, value, memory_size)
     }
 
     fn put_with_extra(
@@ -137,30 +514,149 @@ impl CacheAccessor<Path, Arc<Vec<ObjectMeta>>> for DefaultListFilesCache {
     }
 
     fn remove(&mut self, k: &Path) -> Option<Arc<Vec<ObjectMeta>>> {
-        self.statistics.remove(k).map(|x| x.1)
+        self.cache.remove(k)
     }
 
     fn contains_key(&self, k: &Path) -> bool {
-        self.statistics.contains_key(k)
+        self.cache.contains_key(k)
     }
 
     fn len(&self) -> usize {
-        self.statistics.len()
+        self.cache.len()
     }
 
     fn clear(&self) {
-        self.statistics.clear()
+        self.cache.clear()
     }
 
     fn name(&self) -> String {
         "DefaultListFilesCache".to_string()
     }
+
+    fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    fn list_entries(&self) -> Vec<CacheEntry<Path>> {
+        self.cache.list_entries()
+    }
+}
+
+/// Collected files metadata, such as the footers and page indexes of Parquet
+/// files, for reading files.
+/// Cache is invalided when file size, last modification or e_tag has changed.
+/// Once the metadata uses more than its memory limit, the least recently used
+/// entries are evicted.
+pub struct DefaultFilesMetadataCache {
+    cache: LruCache<Path, (ObjectMeta, Arc<dyn FileMetadata>)>,
+}
+
+impl DefaultFilesMetadataCache {
+    /// Create a cache of files metadata using at most `memory_limit` bytes
+    pub fn new(memory_limit: usize) -> Self {
+        Self::new_with_limits(CacheLimits::default().with_memory_limit(memory_limit))
+    }
+
+    /// Create a cache of files metadata bounded by `limits`
+    pub fn new_with_limits(limits: CacheLimits) -> Self {
+        Self {
+            cache: LruCache::new(limits),
+        }
+    }
+
+    /// Return the limits of the cache
+    pub fn limits(&self) -> CacheLimits {
+        self.cache.limits
+    }
+}
+
+impl CacheAccessor<Path, Arc<dyn FileMetadata>> for DefaultFilesMetadataCache {
+    type Extra = ObjectMeta;
+
+    /// Get the metadata of a file location.
+    fn get(&self, k: &Path) -> Option<Arc<dyn FileMetadata>> {
+        self.cache
+            .get(k, |(_, metadata)| Some(Arc::clone(metadata)))
+    }
+
+    /// Get the metadata of a file location. Returns None if file has changed or not found.
+    fn get_with_extra(&self, k: &Path, e: &Self::Extra) -> Option<Arc<dyn FileMetadata>> {
+        self.cache.get(k, |(saved_meta, metadata)| {
+            if saved_meta.size != e.size
+                || saved_meta.last_modified != e.last_modified
+                || saved_meta.e_tag != e.e_tag
+            {
+                // file has changed
+                None
+            } else {
+                Some(Arc::clone(metadata))
+            }
+        })
+    }
+
+    /// Save the metadata of a file
+    fn put(
+        &self,
+        _key: &Path,
+        _value: Arc<dyn FileMetadata>,
+    ) -> Option<Arc<dyn FileMetadata>> {
+        panic!("Put cache in DefaultFilesMetadataCache without Extra not supported.")
+    }
+
+    fn put_with_extra(
+        &self,
+        key: &Path,
+        value: Arc<dyn FileMetadata>,
+        e: &Self::Extra,
+    ) -> Option<Arc<dyn FileMetadata>> {
+        let memory_size =
+            key.as_ref().len() + object_meta_memory_size(e) + value.memory_size();
+        self.cache
+            .put(key, (e.clone(), value), memory_size)
+            .map(|x| x.1)
+    }
+
+    fn remove(&mut self, k: &Path) -> Option<Arc<dyn FileMetadata>> {
+        self.cache.remove(k).map(|x| x.1)
+    }
+
+    fn contains_key(&self, k: &Path) -> bool {
+        self.cache.contains_key(k)
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn clear(&self) {
+        self.cache.clear()
+    }
+
+    fn name(&self) -> String {
+        "DefaultFilesMetadataCache".to_string()
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    fn list_entries(&self) -> Vec<CacheEntry<Path>> {
+        self.cache.list_entries()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::cache::cache_unit::{DefaultFileStatisticsCache, DefaultListFilesCache};
-    use crate::cache::CacheAccessor;
+    use std::any::Any;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::cache::cache_manager::FileMetadata;
+    use crate::cache::cache_unit::{
+        CacheLimits, DefaultFileStatisticsCache, DefaultFilesMetadataCache,
+        DefaultListFilesCache,
+    };
+    use crate::cache::{CacheAccessor, CacheStats};
     use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
     use chrono::DateTime;
     use datafusion_common::Statistics;
@@ -232,4 +728,216 @@ mod tests {
             meta.clone()
         );
     }
+
+    struct TestFileMetadata {
+        size: usize,
+    }
+
+    impl FileMetadata for TestFileMetadata {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn memory_size(&self) -> usize {
+            self.size
+        }
+    }
+
+    fn object_meta(location: &str) -> ObjectMeta {
+        ObjectMeta {
+            location: Path::from(location),
+            last_modified: DateTime::parse_from_rfc3339("2022-09-27T22:36:00+02:00")
+                .unwrap()
+                .into(),
+            size: 1024,
+            e_tag: Some("1".to_string()),
+            version: None,
+        }
+    }
+
+    fn statistics() -> Arc<Statistics> {
+        Arc::new(Statistics::new_unknown(&Schema::new(vec![Field::new(
+            "test_column",
+            DataType::Int64,
+            false,
+        )])))
+    }
+
+    #[test]
+    fn test_files_metadata_cache() {
+        let meta = object_meta("test");
+        let cache = DefaultFilesMetadataCache::new(1000);
+        assert!(cache.get_with_extra(&meta.location, &meta).is_none());
+
+        cache.put_with_extra(
+            &meta.location,
+            Arc::new(TestFileMetadata { size: 100 }),
+            &meta,
+        );
+        let metadata = cache.get_with_extra(&meta.location, &meta).unwrap();
+        assert!(metadata.as_any().is::<TestFileMetadata>());
+        let memory_used = cache.stats().memory_used;
+        assert!(memory_used > 100);
+
+        // file e_tag changed
+        let mut meta2 = meta.clone();
+        meta2.e_tag = Some("2".to_string());
+        assert!(cache.get_with_extra(&meta2.location, &meta2).is_none());
+
+        // file size changed
+        let mut meta2 = meta.clone();
+        meta2.size = 2048;
+        assert!(cache.get_with_extra(&meta2.location, &meta2).is_none());
+
+        // replacing an entry releases its memory
+        cache.put_with_extra(
+            &meta.location,
+            Arc::new(TestFileMetadata { size: 200 }),
+            &meta,
+        );
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.stats().memory_used, memory_used + 100);
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 3,
+                evictions: 0,
+                memory_used: memory_used + 100,
+            }
+        );
+    }
+
+    #[test]
+    fn test_files_metadata_cache_eviction() {
+        let cache = DefaultFilesMetadataCache::new(1000);
+        let metas = ["a", "b", "c"].map(object_meta);
+        for meta in &metas {
+            cache.put_with_extra(
+                &meta.location,
+                Arc::new(TestFileMetadata { size: 300 }),
+                meta,
+            );
+        }
+        // "a" was evicted
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+        assert!(!cache.contains_key(&metas[0].location));
+        let memory_used = cache.stats().memory_used;
+
+        // "b" is used, so "c" is evicted
+        assert!(cache
+            .get_with_extra(&metas[1].location, &metas[1])
+            .is_some());
+        cache.put_with_extra(
+            &metas[0].location,
+            Arc::new(TestFileMetadata { size: 300 }),
+            &metas[0],
+        );
+        assert!(cache.contains_key(&metas[0].location));
+        assert!(cache.contains_key(&metas[1].location));
+        assert!(!cache.contains_key(&metas[2].location));
+
+        // metadata larger than the limit is not cached
+        cache.put_with_extra(
+            &metas[2].location,
+            Arc::new(TestFileMetadata { size: 2000 }),
+            &metas[2],
+        );
+        assert!(!cache.contains_key(&metas[2].location));
+        assert_eq!(cache.stats().memory_used, memory_used);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().memory_used, 0);
+    }
+
+    #[test]
+    fn test_statistics_cache_max_entries() {
+        let cache = DefaultFileStatisticsCache::new_with_limits(
+            CacheLimits::default().with_max_entries(2),
+        );
+        let metas = ["a", "b", "c"].map(object_meta);
+        cache.put_with_extra(&metas[0].location, statistics(), &metas[0]);
+        cache.put_with_extra(&metas[1].location, statistics(), &metas[1]);
+        assert!(cache.get(&metas[0].location).is_some());
+        cache.put_with_extra(&metas[2].location, statistics(), &metas[2]);
+
+        // "b" is the least recently used entry
+        let entries = cache.list_entries();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.key.as_ref())
+                .collect::<Vec<_>>(),
+            ["a", "c"]
+        );
+        assert_eq!(entries[0].hits, 1);
+        assert_eq!(entries[1].hits, 0);
+        assert_eq!(
+            cache.stats().memory_used,
+            entries.iter().map(|entry| entry.memory_size).sum::<usize>()
+        );
+        assert!(cache.get(&metas[1].location).is_none());
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(cache.stats().misses, 1);
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_statistics_cache_concurrent_access() {
+        let cache = Arc::new(DefaultFileStatisticsCache::new_with_limits(
+            CacheLimits::default().with_max_entries(10),
+        ));
+        let handles = (0..4)
+            .map(|thread| {
+                let cache = Arc::clone(&cache);
+                std::thread::spawn(move || {
+                    for i in 0..100 {
+                        let meta = object_meta(&format!("{thread}/{i}"));
+                        cache.put_with_extra(&meta.location, statistics(), &meta);
+                        cache.get(&meta.location);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let entries = cache.list_entries();
+        assert_eq!(entries.len(), 10);
+        assert_eq!(cache.len(), 10);
+        let stats = cache.stats();
+        assert_eq!(stats.hits + stats.misses, 400);
+        assert_eq!(stats.evictions, 390);
+        assert_eq!(
+            stats.memory_used,
+            entries.iter().map(|entry| entry.memory_size).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_list_files_cache_ttl() {
+        let meta = object_meta("test");
+
+        let cache = DefaultListFilesCache::new_with_limits(
+            CacheLimits::default().with_ttl(Duration::from_secs(3600)),
+        );
+        cache.put(&meta.location, vec![meta.clone()].into());
+        assert!(cache.get(&meta.location).is_some());
+        assert_eq!(cache.list_entries().len(), 1);
+
+        // entries expire immediately
+        let cache = DefaultListFilesCache::new_with_limits(
+            CacheLimits::default().with_ttl(Duration::ZERO),
+        );
+        cache.put(&meta.location, vec![meta.clone()].into());
+        assert!(!cache.contains_key(&meta.location));
+        assert!(cache.list_entries().is_empty());
+        assert!(cache.get(&meta.location).is_none());
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().misses, 1);
+        assert_eq!(cache.stats().memory_used, 0);
+    }
 }
@@ -48,4 +48,41 @@ pub trait CacheAccessor<K, V>: Send + Sync {
     fn clear(&self);
     /// Return the cache name.
     fn name(&self) -> String;
+    /// Return the hit and miss counters and the memory use of the cache.
+    ///
+    /// The default implementation returns empty statistics.
+    fn stats(&self) -> CacheStats {
+        CacheStats::default()
+    }
+    /// Return the entries of the cache, from the least to the most recently
+    /// used.
+    ///
+    /// The default implementation returns no entries.
+    fn list_entries(&self) -> Vec<CacheEntry<K>> {
+        vec![]
+    }
+}
+
+/// Statistics of a cache, returned by [`CacheAccessor::stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of lookups that found a valid entry
+    pub hits: usize,
+    /// Number of lookups that found no entry, or a stale or expired one
+    pub misses: usize,
+    /// Number of entries evicted to respect the limits of the cache
+    pub evictions: usize,
+    /// Estimated memory used by the entries, in bytes
+    pub memory_used: usize,
+}
+
+/// An entry of a cache, returned by [`CacheAccessor::list_entries`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry<K> {
+    /// The key of the entry
+    pub key: K,
+    /// Estimated memory used by the entry, in bytes
+    pub memory_size: usize,
+    /// Number of lookups that found the entry
+    pub hits: usize,
 }
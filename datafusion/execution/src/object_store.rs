@@ -19,11 +19,27 @@
 //! This allows the user to extend DataFusion with different storage systems such as S3 or HDFS
 //! and query data inside these systems.
 
+use crate::cache::cache_unit::{CacheLimits, LruCache};
+use crate::cache::CacheStats;
+use crate::disk_manager::{DiskManager, RefCountedTempFile};
+use async_trait::async_trait;
+use bytes::Bytes;
 use dashmap::DashMap;
 use datafusion_common::{exec_err, DataFusionError, Result};
+use datafusion_common_runtime::SpawnedTask;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use log::debug;
 #[cfg(not(target_arch = "wasm32"))]
 use object_store::local::LocalFileSystem;
-use object_store::ObjectStore;
+use object_store::path::Path;
+use object_store::{
+    coalesce_ranges, GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta,
+    ObjectStore, PutMultipartOpts, PutOptions, PutPayload, PutResult,
+    OBJECT_STORE_COALESCE_DEFAULT,
+};
+use std::io::Write;
+use std::ops::Range;
 use std::sync::Arc;
 use url::Url;
 
@@ -245,6 +261,500 @@ impl ObjectStoreRegistry for DefaultObjectStoreRegistry {
 
 /// Get the key of a url for object store registration.
 /// The credential info will be removed
+/// An [`ObjectStoreRegistry`] wrapping the object stores registered with
+/// [`ObjectStoreRegistry::register_store`] in [`DiskCachingObjectStore`]s,
+/// sharing the same [`ObjectStoreRangeCache`].
+///
+/// The object stores registered by the inner registry itself, such as the
+/// local file system of [`DefaultObjectStoreRegistry`], are not cached.
+///
+/// See [`RuntimeEnvBuilder::with_object_store_cache`] to enable it.
+///
+/// [`RuntimeEnvBuilder::with_object_store_cache`]: crate::runtime_env::RuntimeEnvBuilder::with_object_store_cache
+#[derive(Debug)]
+pub struct CachingObjectStoreRegistry {
+    inner: Arc<dyn ObjectStoreRegistry>,
+    cache: Arc<ObjectStoreRangeCache>,
+}
+
+impl CachingObjectStoreRegistry {
+    /// Create a registry caching the byte ranges of the object stores
+    /// registered with `inner` in `cache`
+    pub fn new(
+        inner: Arc<dyn ObjectStoreRegistry>,
+        cache: Arc<ObjectStoreRangeCache>,
+    ) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Return the cache of the byte ranges of the registered object stores
+    pub fn cache(&self) -> &Arc<ObjectStoreRangeCache> {
+        &self.cache
+    }
+}
+
+impl ObjectStoreRegistry for CachingObjectStoreRegistry {
+    fn register_store(
+        &self,
+        url: &Url,
+        store: Arc<dyn ObjectStore>,
+    ) -> Option<Arc<dyn ObjectStore>> {
+        let store = Arc::new(DiskCachingObjectStore::new(
+            store,
+            get_url_key(url),
+            Arc::clone(&self.cache),
+        ));
+        self.inner.register_store(url, store)
+    }
+
+    fn get_store(&self, url: &Url) -> Result<Arc<dyn ObjectStore>> {
+        self.inner.get_store(url)
+    }
+}
+
+/// Key of a byte range cached by an [`ObjectStoreRangeCache`]: the url of the
+/// object store, the location of the object and the range
+type RangeKey = (Arc<str>, Path, Range<usize>);
+
+/// Key of an object whose e_tag is known by an [`ObjectStoreRangeCache`]: the
+/// url of the object store and the location of the object
+type ObjectKey = (Arc<str>, Path);
+
+/// Default maximum number of e_tags known by an [`ObjectStoreRangeCache`]
+const DEFAULT_MAX_E_TAGS: usize = 10_000;
+
+/// A byte range cached in a temporary file, deleted once evicted
+struct CachedRange {
+    /// e_tag of the object the range was fetched from
+    e_tag: String,
+    file: Arc<RefCountedTempFile>,
+}
+
+/// Byte ranges of objects, cached in temporary files of a [`DiskManager`].
+///
+/// Once the cached ranges exceed `max_size` bytes, the least recently used
+/// ones are evicted. Ranges are cached as they are fetched: a range is only
+/// read from the cache when exactly the same range is fetched again from an
+/// object with the same e_tag.
+///
+/// The cache also keeps the last e_tag returned for the objects, of which the
+/// least recently used are evicted once there are more than `max_e_tags`,
+/// see [`Self::with_max_e_tags`].
+pub struct ObjectStoreRangeCache {
+    disk_manager: Arc<DiskManager>,
+    max_size: usize,
+    ranges: LruCache<RangeKey, CachedRange>,
+    max_e_tags: usize,
+    /// Last e_tag returned by the object stores for each object
+    e_tags: LruCache<ObjectKey, String>,
+}
+
+impl ObjectStoreRangeCache {
+    /// Create a cache of byte ranges using at most `max_size` bytes of the
+    /// temporary files of `disk_manager`
+    pub fn new(disk_manager: Arc<DiskManager>, max_size: usize) -> Self {
+        Self {
+            disk_manager,
+            max_size,
+            ranges: LruCache::new(CacheLimits::default().with_memory_limit(max_size)),
+            max_e_tags: DEFAULT_MAX_E_TAGS,
+            e_tags: LruCache::new(
+                CacheLimits::default().with_max_entries(DEFAULT_MAX_E_TAGS),
+            ),
+        }
+    }
+
+    /// Keep the e_tags of at most `max_e_tags` objects. Reading the ranges of
+    /// an object whose e_tag is unknown requires a request to the object store.
+    pub fn with_max_e_tags(mut self, max_e_tags: usize) -> Self {
+        self.max_e_tags = max_e_tags;
+        self.e_tags = LruCache::new(CacheLimits::default().with_max_entries(max_e_tags));
+        self
+    }
+
+    /// Return the maximum number of e_tags kept by the cache
+    pub fn max_e_tags(&self) -> usize {
+        self.max_e_tags
+    }
+
+    /// Return the maximum size of the cached ranges, in bytes
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Return the number of cached ranges
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Return true if no range is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the hit and miss counters of the cache, whose memory is the
+    /// size of the cached ranges on disk
+    pub fn stats(&self) -> CacheStats {
+        self.ranges.stats()
+    }
+
+    /// Remove all the cached ranges and e_tags
+    pub fn clear(&self) {
+        self.ranges.clear();
+        self.e_tags.clear();
+    }
+
+    /// Return the last e_tag recorded for `key`
+    fn e_tag(&self, key: &ObjectKey) -> Option<String> {
+        self.e_tags.get(key, |e_tag| Some(e_tag.clone()))
+    }
+
+    /// Record the last e_tag returned for `key`, forgetting it if `None`
+    fn set_e_tag(&self, key: &ObjectKey, e_tag: Option<&str>) {
+        match e_tag {
+            Some(e_tag) => {
+                self.e_tags.put(key, e_tag.to_string(), e_tag.len());
+            }
+            None => {
+                self.e_tags.remove(key);
+            }
+        }
+    }
+
+    /// Get the bytes of `key` if they were fetched from an object whose
+    /// e_tag was `e_tag`. An unknown e_tag matches no cached range.
+    async fn get(&self, key: &RangeKey, e_tag: Option<&str>) -> Option<Bytes> {
+        let file = self.ranges.get(key, |range| {
+            (Some(range.e_tag.as_str()) == e_tag).then(|| Arc::clone(&range.file))
+        })?;
+        let read = SpawnedTask::spawn_blocking(move || std::fs::read(file.path()))
+            .join_unwind()
+            .await;
+        match read {
+            Ok(Ok(bytes)) => Some(bytes.into()),
+            Ok(Err(e)) => {
+                debug!("Failed to read cached range {key:?}: {e}");
+                self.ranges.remove(key);
+                None
+            }
+            Err(e) => {
+                debug!("Failed to read cached range {key:?}: {e}");
+                None
+            }
+        }
+    }
+
+    /// Cache the bytes of `key`, fetched from an object whose e_tag is
+    /// `e_tag`. Failures to write the bytes to disk are ignored.
+    async fn put(&self, key: RangeKey, e_tag: &str, bytes: Bytes) {
+        if bytes.len() > self.max_size {
+            return;
+        }
+        let disk_manager = Arc::clone(&self.disk_manager);
+        let size = bytes.len();
+        let write = SpawnedTask::spawn_blocking(move || {
+            let file = disk_manager.create_tmp_file("ObjectStoreRangeCache")?;
+            file.inner()
+                .as_file()
+                .write_all(&bytes)
+                .map_err(DataFusionError::IoError)?;
+            Ok::<_, DataFusionError>(file)
+        })
+        .join_unwind()
+        .await;
+        match write {
+            Ok(Ok(file)) => {
+                let range = CachedRange {
+                    e_tag: e_tag.to_string(),
+                    file: Arc::new(file),
+                };
+                self.ranges.put(&key, range, size);
+            }
+            Ok(Err(e)) => debug!("Failed to cache range {key:?}: {e}"),
+            Err(e) => debug!("Failed to cache range {key:?}: {e}"),
+        }
+    }
+}
+
+impl std::fmt::Debug for ObjectStoreRangeCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ObjectStoreRangeCache")
+            .field("max_size", &self.max_size)
+            .field("max_e_tags", &self.max_e_tags)
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An [`ObjectStore`] caching the byte ranges fetched from an inner object
+/// store in an [`ObjectStoreRangeCache`].
+///
+/// The cached ranges of an object are validated against the last e_tag of the
+/// object returned by the inner object store, such as by the `list` requests
+/// of the file listing, so that reading a cached range requires no request.
+/// The missing ranges are fetched on condition that the object still matches
+/// this e_tag. The ranges of objects without e_tag are not cached.
+#[derive(Debug)]
+pub struct DiskCachingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    /// Url of the inner object store, part of the keys of the cached ranges
+    url: Arc<str>,
+    cache: Arc<ObjectStoreRangeCache>,
+}
+
+impl DiskCachingObjectStore {
+    /// Create an object store caching the byte ranges fetched from `inner`,
+    /// registered for `url`, in `cache`
+    pub fn new(
+        inner: Arc<dyn ObjectStore>,
+        url: impl Into<Arc<str>>,
+        cache: Arc<ObjectStoreRangeCache>,
+    ) -> Self {
+        Self {
+            inner,
+            url: url.into(),
+            cache,
+        }
+    }
+
+    /// Return the inner object store
+    pub fn inner(&self) -> &Arc<dyn ObjectStore> {
+        &self.inner
+    }
+
+    /// Record the e_tag of an object returned by the inner object store
+    fn observe(&self, location: &Path, e_tag: Option<&str>) {
+        self.cache
+            .set_e_tag(&(Arc::clone(&self.url), location.clone()), e_tag);
+    }
+
+    /// Fetch `ranges` of the object at `location`, reading the ranges cached
+    /// for `e_tag` from the cache. Returns [`object_store::Error::Precondition`]
+    /// if the object no longer matches `e_tag`.
+    async fn get_cached_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+        e_tag: Option<String>,
+    ) -> object_store::Result<Vec<Bytes>> {
+        let key = |range: &Range<usize>| {
+            (Arc::clone(&self.url), location.clone(), range.clone())
+        };
+
+        let mut cached = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            cached.push(self.cache.get(&key(range), e_tag.as_deref()).await);
+        }
+        let missing = ranges
+            .iter()
+            .zip(&cached)
+            .filter(|(_, bytes)| bytes.is_none())
+            .map(|(range, _)| range.clone())
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(cached.into_iter().flatten().collect());
+        }
+
+        // Without known e_tag, the first missing range returns it
+        let (e_tag, mut fetched) = match e_tag {
+            Some(e_tag) => (Some(e_tag), vec![]),
+            None => {
+                let options = GetOptions {
+                    range: Some(missing[0].clone().into()),
+                    ..Default::default()
+                };
+                let result = self.inner.get_opts(location, options).await?;
+                self.observe(location, result.meta.e_tag.as_deref());
+                (result.meta.e_tag.clone(), vec![result.bytes().await?])
+            }
+        };
+        let rest = &missing[fetched.len()..];
+        match &e_tag {
+            Some(e_tag) => {
+                let fetch = |range: Range<usize>| async move {
+                    let options = GetOptions {
+                        range: Some(range.into()),
+                        if_match: Some(e_tag.clone()),
+                        ..Default::default()
+                    };
+                    self.inner.get_opts(location, options).await?.bytes().await
+                };
+                fetched.extend(
+                    coalesce_ranges(rest, fetch, OBJECT_STORE_COALESCE_DEFAULT).await?,
+                );
+            }
+            None => fetched.extend(self.inner.get_ranges(location, rest).await?),
+        }
+
+        let mut fetched = fetched.into_iter();
+        let mut result = Vec::with_capacity(ranges.len());
+        for (range, bytes) in ranges.iter().zip(cached) {
+            let bytes = match bytes {
+                Some(bytes) => bytes,
+                None => {
+                    let Some(bytes) = fetched.next() else {
+                        return Err(object_store::Error::Generic {
+                            store: "DiskCachingObjectStore",
+                            source: format!(
+                                "{} returned fewer ranges than requested for {location}",
+                                self.inner
+                            )
+                            .into(),
+                        });
+                    };
+                    if let Some(e_tag) = &e_tag {
+                        self.cache.put(key(range), e_tag, bytes.clone()).await;
+                    }
+                    bytes
+                }
+            };
+            result.push(bytes);
+        }
+        Ok(result)
+    }
+}
+
+impl std::fmt::Display for DiskCachingObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "DiskCaching({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for DiskCachingObjectStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.observe(location, None);
+        let result = self.inner.put_opts(location, payload, opts).await?;
+        self.observe(location, result.e_tag.as_deref());
+        Ok(result)
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.observe(location, None);
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let result = self.inner.get_opts(location, options).await?;
+        self.observe(location, result.meta.e_tag.as_deref());
+        Ok(result)
+    }
+
+    async fn get_range(
+        &self,
+        location: &Path,
+        range: Range<usize>,
+    ) -> object_store::Result<Bytes> {
+        let mut bytes = self.get_ranges(location, &[range]).await?;
+        Ok(bytes.pop().unwrap_or_default())
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        let e_tag = self.cache.e_tag(&(Arc::clone(&self.url), location.clone()));
+        if e_tag.is_none() {
+            return self.get_cached_ranges(location, ranges, None).await;
+        }
+        match self.get_cached_ranges(location, ranges, e_tag).await {
+            // The object changed since its e_tag was returned
+            Err(object_store::Error::Precondition { .. }) => {
+                self.observe(location, None);
+                self.get_cached_ranges(location, ranges, None).await
+            }
+            result => result,
+        }
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        let meta = self.inner.head(location).await?;
+        self.observe(location, meta.e_tag.as_deref());
+        Ok(meta)
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.observe(location, None);
+        self.inner.delete(location).await
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> BoxStream<'_, object_store::Result<ObjectMeta>> {
+        self.inner
+            .list(prefix)
+            .inspect_ok(|meta| self.observe(&meta.location, meta.e_tag.as_deref()))
+            .boxed()
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'_, object_store::Result<ObjectMeta>> {
+        self.inner
+            .list_with_offset(prefix, offset)
+            .inspect_ok(|meta| self.observe(&meta.location, meta.e_tag.as_deref()))
+            .boxed()
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        let result = self.inner.list_with_delimiter(prefix).await?;
+        for meta in &result.objects {
+            self.observe(&meta.location, meta.e_tag.as_deref());
+        }
+        Ok(result)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.observe(to, None);
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.observe(from, None);
+        self.observe(to, None);
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.observe(to, None);
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.observe(from, None);
+        self.observe(to, None);
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
 fn get_url_key(url: &Url) -> String {
     format!(
         "{}://{}",
@@ -256,6 +766,9 @@ fn get_url_key(url: &Url) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::disk_manager::DiskManagerConfig;
+    use crate::runtime_env::RuntimeEnvBuilder;
+    use object_store::memory::InMemory;
 
     #[test]
     fn test_object_store_url() {
@@ -299,4 +812,122 @@ mod tests {
         let key = get_url_key(&url.url);
         assert_eq!(key.as_str(), "s3://host:123");
     }
+
+    #[tokio::test]
+    async fn test_disk_caching_object_store() {
+        let inner = Arc::new(InMemory::new());
+        let location = Path::from("data");
+        inner
+            .put(&location, Bytes::from_static(b"0123456789").into())
+            .await
+            .unwrap();
+        let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs).unwrap();
+        let cache = Arc::new(ObjectStoreRangeCache::new(disk_manager, 8));
+        let store = DiskCachingObjectStore::new(
+            Arc::clone(&inner) as _,
+            "memory://",
+            Arc::clone(&cache),
+        );
+
+        assert_eq!(store.get_range(&location, 0..4).await.unwrap(), "0123");
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.stats().misses, 1);
+
+        // 0..4 is read from the cache
+        let bytes = store.get_ranges(&location, &[0..4, 4..8]).await.unwrap();
+        assert_eq!(bytes, vec!["0123", "4567"]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(cache.stats().memory_used, 8);
+
+        // 0..4 is the least recently used range
+        assert_eq!(store.get_range(&location, 8..10).await.unwrap(), "89");
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+        assert_eq!(cache.stats().memory_used, 6);
+
+        // The changed object no longer matches the e_tag of the cached ranges,
+        // whose new e_tag is returned by the fetch of the missing range
+        inner
+            .put(&location, Bytes::from_static(b"abcdefghij").into())
+            .await
+            .unwrap();
+        assert_eq!(store.get_range(&location, 0..2).await.unwrap(), "ab");
+        assert_eq!(store.get_range(&location, 4..8).await.unwrap(), "efgh");
+        assert_eq!(cache.stats().hits, 1);
+
+        // Ranges larger than the cache are not cached
+        assert_eq!(
+            store.get_range(&location, 0..10).await.unwrap(),
+            "abcdefghij"
+        );
+        assert!(!cache.ranges.contains_key(&(
+            Arc::from("memory://"),
+            location.clone(),
+            0..10
+        )));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disk_caching_object_store_max_e_tags() {
+        let inner = Arc::new(InMemory::new());
+        for name in ["a", "b", "c"] {
+            inner
+                .put(&Path::from(name), Bytes::from_static(b"0123").into())
+                .await
+                .unwrap();
+        }
+        let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs).unwrap();
+        let cache =
+            Arc::new(ObjectStoreRangeCache::new(disk_manager, 1024).with_max_e_tags(2));
+        let store = DiskCachingObjectStore::new(
+            Arc::clone(&inner) as _,
+            "memory://",
+            Arc::clone(&cache),
+        );
+
+        // The e_tag of `a` is evicted as the least recently used one
+        for name in ["a", "b", "c"] {
+            store.head(&Path::from(name)).await.unwrap();
+        }
+        assert_eq!(cache.e_tags.len(), 2);
+        let key = |name: &str| (Arc::from("memory://"), Path::from(name));
+        assert!(!cache.e_tags.contains_key(&key("a")));
+
+        // The ranges of `a` are still read, fetching its e_tag again
+        assert_eq!(store.get_range(&Path::from("a"), 0..2).await.unwrap(), "01");
+        assert!(cache.e_tags.contains_key(&key("a")));
+        assert_eq!(cache.e_tags.len(), 2);
+
+        cache.clear();
+        assert_eq!(cache.e_tags.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_caching_object_store_registry() {
+        let env = RuntimeEnvBuilder::new()
+            .with_object_store_cache(1024)
+            .build()
+            .unwrap();
+        let url = ObjectStoreUrl::local_filesystem();
+        env.register_object_store(url.as_ref(), Arc::new(LocalFileSystem::new()));
+        let store = env.object_store(&url).unwrap();
+        assert_eq!(store.to_string(), "DiskCaching(LocalFileSystem(file:///))");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data");
+        std::fs::write(&path, "0123456789").unwrap();
+        let location = Path::from_filesystem_path(&path).unwrap();
+        assert_eq!(store.get_range(&location, 2..6).await.unwrap(), "2345");
+        assert_eq!(store.get_range(&location, 2..6).await.unwrap(), "2345");
+
+        // The file changes: its e_tag depends on its size and modification
+        std::fs::write(&path, "abcdefghijk").unwrap();
+        // As the file listing before reading it, returns its new e_tag
+        store.head(&location).await.unwrap();
+        assert_eq!(store.get_range(&location, 2..6).await.unwrap(), "cdef");
+    }
 }
@@ -23,7 +23,10 @@ use crate::{
     memory_pool::{
         GreedyMemoryPool, MemoryPool, TrackConsumersPool, UnboundedMemoryPool,
     },
-    object_store::{DefaultObjectStoreRegistry, ObjectStoreRegistry},
+    object_store::{
+        CachingObjectStoreRegistry, DefaultObjectStoreRegistry, ObjectStoreRangeCache,
+        ObjectStoreRegistry,
+    },
 };
 
 use crate::cache::cache_manager::{CacheManager, CacheManagerConfig};
@@ -182,6 +185,11 @@ pub struct RuntimeEnvBuilder {
     pub cache_manager: CacheManagerConfig,
     /// ObjectStoreRegistry to get object store based on url
     pub object_store_registry: Arc<dyn ObjectStoreRegistry>,
+    /// Maximum size of the byte ranges of the registered object stores cached
+    /// in temporary files, see [`CachingObjectStoreRegistry`]
+    ///
+    /// Defaults to no cache if `None`
+    object_store_cache_size: Option<usize>,
 }
 
 impl Default for RuntimeEnvBuilder {
@@ -198,6 +206,7 @@ impl RuntimeEnvBuilder {
             memory_pool: Default::default(),
             cache_manager: Default::default(),
             object_store_registry: Arc::new(DefaultObjectStoreRegistry::default()),
+            object_store_cache_size: None,
         }
     }
 
@@ -228,6 +237,14 @@ impl RuntimeEnvBuilder {
         self
     }
 
+    /// Cache the byte ranges fetched from the object stores registered after
+    /// the runtime is built in temporary files of its disk manager, using at
+    /// most `max_size` bytes of disk, see [`CachingObjectStoreRegistry`]
+    pub fn with_object_store_cache(mut self, max_size: usize) -> Self {
+        self.object_store_cache_size = Some(max_size);
+        self
+    }
+
     /// Specify the total memory to use while running the DataFusion
     /// plan to `max_memory * memory_fraction` in bytes.
     ///
@@ -254,13 +271,26 @@ impl RuntimeEnvBuilder {
             memory_pool,
             cache_manager,
             object_store_registry,
+            object_store_cache_size,
         } = self;
         let memory_pool =
             memory_pool.unwrap_or_else(|| Arc::new(UnboundedMemoryPool::default()));
+        let disk_manager = DiskManager::try_new(disk_manager)?;
+        let object_store_registry = match object_store_cache_size {
+            Some(max_size) => {
+                let cache =
+                    ObjectStoreRangeCache::new(Arc::clone(&disk_manager), max_size);
+                Arc::new(CachingObjectStoreRegistry::new(
+                    object_store_registry,
+                    Arc::new(cache),
+                ))
+            }
+            None => object_store_registry,
+        };
 
         Ok(RuntimeEnv {
             memory_pool,
-            disk_manager: DiskManager::try_new(disk_manager)?,
+            disk_manager,
             cache_manager: CacheManager::try_new(&cache_manager)?,
             object_store_registry,
         })
@@ -31,18 +31,18 @@ use crate::expr_rewriter::{
     rewrite_sort_cols_by_aggs,
 };
 use crate::logical_plan::{
-    Aggregate, Analyze, Distinct, DistinctOn, EmptyRelation, Explain, Filter, Join,
-    JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-    Projection, Repartition, Sort, SubqueryAlias, TableScan, Union, Unnest, Values,
-    Window,
+    Aggregate, Analyze, Distinct, DistinctOn, EmptyRelation, Explain, Extension, Filter,
+    Join, JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
+    Projection, Repartition, Sort, SortWithinPartitions, SubqueryAlias, TableScan, Union,
+    Unnest, Values, Window,
 };
 use crate::utils::{
     can_hash, columnize_expr, compare_sort_expr, expr_to_columns,
     find_valid_equijoin_key_pair, group_window_expr_by_sort_keys,
 };
 use crate::{
-    and, binary_expr, lit, DmlStatement, Expr, ExprSchemable, Operator, RecursiveQuery,
-    Statement, TableProviderFilterPushDown, TableSource, WriteOp,
+    and, binary_expr, cast, lit, DmlStatement, Expr, ExprSchemable, Operator,
+    RecursiveQuery, Statement, TableProviderFilterPushDown, TableSource, WriteOp,
 };
 
 use super::dml::InsertOp;
@@ -669,6 +669,29 @@ impl LogicalPlanBuilder {
         self,
         sorts: impl IntoIterator<Item = impl Into<SortExpr>> + Clone,
         fetch: Option<usize>,
+    ) -> Result<Self> {
+        self.sort_impl(sorts, |expr, input| {
+            LogicalPlan::Sort(Sort { expr, input, fetch })
+        })
+    }
+
+    /// Apply a sort of the rows within each partition of the input, e.g. for
+    /// `SORT BY`. See [`SortWithinPartitions`]
+    pub fn sort_within_partitions(
+        self,
+        sorts: impl IntoIterator<Item = impl Into<SortExpr>> + Clone,
+    ) -> Result<Self> {
+        self.sort_impl(sorts, |expr, input| {
+            LogicalPlan::Extension(Extension {
+                node: Arc::new(SortWithinPartitions::new(expr, input)),
+            })
+        })
+    }
+
+    fn sort_impl(
+        self,
+        sorts: impl IntoIterator<Item = impl Into<SortExpr>> + Clone,
+        sort_plan: impl FnOnce(Vec<SortExpr>, Arc<LogicalPlan>) -> LogicalPlan,
     ) -> Result<Self> {
         let sorts = rewrite_sort_cols_by_aggs(sorts, &self.plan)?;
 
@@ -690,11 +713,10 @@ impl LogicalPlanBuilder {
         })?;
 
         if missing_cols.is_empty() {
-            return Ok(Self::new(LogicalPlan::Sort(Sort {
-                expr: normalize_sorts(sorts, &self.plan)?,
-                input: self.plan,
-                fetch,
-            })));
+            return Ok(Self::new(sort_plan(
+                normalize_sorts(sorts, &self.plan)?,
+                self.plan,
+            )));
         }
 
         // remove pushed down sort columns
@@ -706,11 +728,7 @@ impl LogicalPlanBuilder {
             &missing_cols,
             is_distinct,
         )?;
-        let sort_plan = LogicalPlan::Sort(Sort {
-            expr: normalize_sorts(sorts, &plan)?,
-            input: Arc::new(plan),
-            fetch,
-        });
+        let sort_plan = sort_plan(normalize_sorts(sorts, &plan)?, Arc::new(plan));
 
         Projection::try_new(new_expr, Arc::new(sort_plan))
             .map(LogicalPlan::Projection)
@@ -732,6 +750,27 @@ impl LogicalPlanBuilder {
         )))))
     }
 
+    /// Apply a union by name, preserving duplicate rows
+    ///
+    /// Unlike [`Self::union`], the columns of the inputs are matched by name
+    /// rather than by position. See [`union_by_name`] for details.
+    pub fn union_by_name(self, plan: LogicalPlan) -> Result<Self> {
+        union_by_name(Arc::unwrap_or_clone(self.plan), plan).map(Self::new)
+    }
+
+    /// Apply a union by name, removing duplicate rows
+    ///
+    /// Unlike [`Self::union_distinct`], the columns of the inputs are matched
+    /// by name rather than by position. See [`union_by_name`] for details.
+    pub fn union_by_name_distinct(self, plan: LogicalPlan) -> Result<Self> {
+        let left_plan: LogicalPlan = Arc::unwrap_or_clone(self.plan);
+        let right_plan: LogicalPlan = plan;
+
+        Ok(Self::new(LogicalPlan::Distinct(Distinct::All(Arc::new(
+            union_by_name(left_plan, right_plan)?,
+        )))))
+    }
+
     /// Apply deduplication: Only distinct (different) values are returned)
     pub fn distinct(self) -> Result<Self> {
         Ok(Self::new(LogicalPlan::Distinct(Distinct::All(self.plan))))
@@ -1055,11 +1094,47 @@ impl LogicalPlanBuilder {
     }
 
     /// Repartition
+    ///
+    /// Columns referenced by the partitioning expressions that are missing in
+    /// the input are added to it and projected away after the repartitioning.
     pub fn repartition(self, partitioning_scheme: Partitioning) -> Result<Self> {
-        Ok(Self::new(LogicalPlan::Repartition(Repartition {
-            input: self.plan,
+        let schema = self.plan.schema();
+
+        // Collect partitioning columns that are missing in the input plan's schema
+        let missing_cols: IndexSet<Column> = match &partitioning_scheme {
+            Partitioning::Hash(exprs, _) | Partitioning::DistributeBy(exprs) => exprs
+                .iter()
+                .flat_map(|expr| expr.column_refs())
+                .filter(|c| !schema.has_column(c))
+                .cloned()
+                .collect(),
+            Partitioning::RoundRobinBatch(_) => IndexSet::new(),
+        };
+
+        if missing_cols.is_empty() {
+            return Ok(Self::new(LogicalPlan::Repartition(Repartition {
+                input: self.plan,
+                partitioning_scheme,
+            })));
+        }
+
+        // remove pushed down partitioning columns
+        let new_expr = schema.columns().into_iter().map(Expr::Column).collect();
+
+        let is_distinct = false;
+        let plan = Self::add_missing_columns(
+            Arc::unwrap_or_clone(self.plan),
+            &missing_cols,
+            is_distinct,
+        )?;
+        let repartition_plan = LogicalPlan::Repartition(Repartition {
+            input: Arc::new(plan),
             partitioning_scheme,
-        })))
+        });
+
+        Projection::try_new(new_expr, Arc::new(repartition_plan))
+            .map(LogicalPlan::Projection)
+            .map(Self::new)
     }
 
     /// Apply a window functions to extend the schema
@@ -1405,7 +1480,7 @@ pub fn build_join_schema(
                 .collect::<Vec<_>>();
             left_fields.into_iter().chain(right_fields).collect()
         }
-        JoinType::Left => {
+        JoinType::Left | JoinType::LeftAsof => {
             // left then right, right set to nullable in case of not matched scenario
             let left_fields = left_fields
                 .map(|(q, f)| (q.cloned(), Arc::clone(f)))
@@ -1540,6 +1615,74 @@ pub fn union(left_plan: LogicalPlan, right_plan: LogicalPlan) -> Result<LogicalP
     ])?))
 }
 
+/// Union two [`LogicalPlan`]s, matching their columns by name.
+///
+/// The output contains the columns of `left_plan`, followed by the columns
+/// that only appear in `right_plan`. Columns that are missing from an input
+/// are filled with `NULL`s of the type of the column in the other input.
+///
+/// # Errors
+/// This function errors if an input has several columns with the same name.
+pub fn union_by_name(
+    left_plan: LogicalPlan,
+    right_plan: LogicalPlan,
+) -> Result<LogicalPlan> {
+    let left_names = unique_field_names(&left_plan)?;
+    let right_names = unique_field_names(&right_plan)?;
+
+    // The columns of the left input, followed by the columns only in the right input
+    let mut fields = left_plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| (field.name().clone(), field.data_type().clone()))
+        .collect::<Vec<_>>();
+    fields.extend(
+        right_plan
+            .schema()
+            .fields()
+            .iter()
+            .filter(|field| !left_names.contains_key(field.name()))
+            .map(|field| (field.name().clone(), field.data_type().clone())),
+    );
+
+    let align = |plan: LogicalPlan, names: &HashMap<String, usize>| {
+        let aligned = plan.schema().fields().len() == fields.len()
+            && fields
+                .iter()
+                .enumerate()
+                .all(|(i, (name, _))| names.get(name) == Some(&i));
+        if aligned {
+            return Ok(plan);
+        }
+        let exprs = fields
+            .iter()
+            .map(|(name, data_type)| match names.get(name) {
+                Some(i) => Expr::Column(Column::from(plan.schema().qualified_field(*i))),
+                None => cast(lit(ScalarValue::Null), data_type.clone()).alias(name),
+            })
+            .collect::<Vec<_>>();
+        project(plan, exprs)
+    };
+    let left_plan = align(left_plan, &left_names)?;
+    let right_plan = align(right_plan, &right_names)?;
+    union(left_plan, right_plan)
+}
+
+/// Returns the index of each field of `plan` by name, erroring on duplicates
+fn unique_field_names(plan: &LogicalPlan) -> Result<HashMap<String, usize>> {
+    let mut names = HashMap::new();
+    for (i, field) in plan.schema().fields().iter().enumerate() {
+        if names.insert(field.name().clone(), i).is_some() {
+            return plan_err!(
+                "UNION BY NAME input has multiple columns named {}",
+                field.name()
+            );
+        }
+    }
+    Ok(names)
+}
+
 /// Create Projection
 /// # Errors
 /// This function errors under any of the following conditions:
@@ -1801,6 +1944,14 @@ pub fn get_unnested_columns(
     Ok(qualified_columns)
 }
 
+/// Returns the ordinality column emitted after the unnested list column
+/// `col_name` if [`UnnestOptions::with_ordinality`] is set
+pub fn get_ordinality_column(col_name: &str) -> (Column, Arc<Field>) {
+    let name = format!("{col_name}_ordinality");
+    let field = Field::new(&name, DataType::Int64, true);
+    (Column::from_name(name), Arc::new(field))
+}
+
 /// Create a [`LogicalPlan::Unnest`] plan with options
 /// This function receive a list of columns to be unnested
 /// because multiple unnest can be performed on the same column (e.g unnest with different depth)
@@ -1871,26 +2022,30 @@ pub fn unnest_with_options(
                         .iter()
                         .filter(|p| -> bool { &p.input_column == *column_to_unnest })
                         .collect::<Vec<_>>();
-                    let mut transformed_columns = recursions_on_column
-                        .iter()
-                        .map(|r| {
-                            list_columns.push((
-                                index,
-                                ColumnUnnestList {
-                                    output_column: r.output_column.clone(),
-                                    depth: r.depth,
-                                },
-                            ));
-                            Ok(get_unnested_columns(
+                    let mut transformed_columns = vec![];
+                    for r in recursions_on_column {
+                        list_columns.push((
+                            index,
+                            ColumnUnnestList {
+                                output_column: r.output_column.clone(),
+                                depth: r.depth,
+                            },
+                        ));
+                        // because unnesting a list column always result into one result
+                        transformed_columns.extend(
+                            get_unnested_columns(
                                 &r.output_column.name,
                                 original_field.data_type(),
                                 r.depth,
                             )?
                             .into_iter()
-                            .next()
-                            .unwrap()) // because unnesting a list column always result into one result
-                        })
-                        .collect::<Result<Vec<(Column, Arc<Field>)>>>()?;
+                            .next(),
+                        );
+                        if options.with_ordinality {
+                            transformed_columns
+                                .push(get_ordinality_column(&r.output_column.name));
+                        }
+                    }
                     if transformed_columns.is_empty() {
                         transformed_columns = get_unnested_columns(
                             &column_to_unnest.name,
@@ -1913,6 +2068,11 @@ pub fn unnest_with_options(
                                         depth: 1,
                                     },
                                 ));
+                                if options.with_ordinality {
+                                    transformed_columns.push(get_ordinality_column(
+                                        &column_to_unnest.name,
+                                    ));
+                                }
                             }
                             _ => {}
                         };
@@ -2090,6 +2250,54 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_union_by_name() -> Result<()> {
+        let left =
+            table_scan(Some("employee_csv"), &employee_schema(), Some(vec![3, 4]))?
+                .build()?;
+        let right =
+            table_scan(Some("employee_csv"), &employee_schema(), Some(vec![4, 0]))?
+                .build()?;
+
+        let plan = LogicalPlanBuilder::from(left.clone())
+            .union_by_name(right.clone())?
+            .build()?;
+
+        let expected = "Union\
+        \n  Projection: employee_csv.state, employee_csv.salary, CAST(NULL AS Int32) AS id\
+        \n    TableScan: employee_csv projection=[state, salary]\
+        \n  Projection: CAST(NULL AS Utf8) AS state, employee_csv.salary, employee_csv.id\
+        \n    TableScan: employee_csv projection=[salary, id]";
+        assert_eq!(expected, format!("{plan}"));
+
+        // Inputs that are already aligned are not projected
+        let plan = LogicalPlanBuilder::from(left.clone())
+            .union_by_name_distinct(left)?
+            .build()?;
+
+        let expected = "Distinct:\
+        \n  Union\
+        \n    TableScan: employee_csv projection=[state, salary]\
+        \n    TableScan: employee_csv projection=[state, salary]";
+        assert_eq!(expected, format!("{plan}"));
+
+        // Column names must be unique in each input
+        let other =
+            table_scan(Some("other"), &employee_schema(), Some(vec![4]))?.build()?;
+        let duplicate = LogicalPlanBuilder::from(right.clone())
+            .cross_join(other)?
+            .build()?;
+        let err = LogicalPlanBuilder::from(right)
+            .union_by_name(duplicate)
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: UNION BY NAME input has multiple columns named salary"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn plan_builder_simple_distinct() -> Result<()> {
         let plan =
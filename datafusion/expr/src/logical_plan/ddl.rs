@@ -313,6 +313,14 @@ pub struct CreateMemoryTable {
     pub column_defaults: Vec<(String, Expr)>,
     /// Whether the table is `TableType::Temporary`
     pub temporary: bool,
+    /// Expressions to hash partition the table data by
+    pub partition_by: Vec<Expr>,
+    /// The order the table data is sorted by within each partition
+    pub sort_order: Vec<SortExpr>,
+    /// Comment on the table
+    pub comment: Option<String>,
+    /// Comments on columns
+    pub column_comments: Vec<(String, String)>,
 }
 
 /// Creates a view.
@@ -150,8 +150,26 @@ pub enum WriteOp {
     Delete,
     Update,
     Ctas,
+    /// Merges a source relation into the table (`MERGE INTO`).
+    ///
+    /// The input of the [`DmlStatement`] produces the new contents of the
+    /// table, plus the rows deleted by the merge. Its columns are the columns
+    /// of the table followed by a Utf8 [`MERGE_ACTION_COLUMN`] describing
+    /// what happened to each row:
+    ///
+    /// * `"insert"`: the row was inserted
+    /// * `"update"`: the row was updated
+    /// * `"delete"`: the row was deleted, it must not be kept in the table
+    /// * `"conflict"`: the target row matched more than one source row, the
+    ///   merge must fail with a cardinality violation
+    /// * `NULL`: the row was left unchanged
+    Merge,
 }
 
+/// Name of the column describing the action applied to each row in the input
+/// of a [`WriteOp::Merge`] statement
+pub const MERGE_ACTION_COLUMN: &str = "__merge_action";
+
 impl WriteOp {
     /// Return a descriptive name of this [`WriteOp`]
     pub fn name(&self) -> &str {
@@ -160,6 +178,7 @@ impl WriteOp {
             WriteOp::Delete => "Delete",
             WriteOp::Update => "Update",
             WriteOp::Ctas => "Ctas",
+            WriteOp::Merge => "Merge",
         }
     }
 }
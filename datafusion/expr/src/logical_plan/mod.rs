@@ -23,6 +23,7 @@ mod extension;
 pub(crate) mod invariants;
 pub use invariants::{assert_expected_schema, check_subquery_expr, InvariantLevel};
 mod plan;
+mod sort_within_partitions;
 mod statement;
 pub mod tree_node;
 
@@ -43,6 +44,7 @@ pub use plan::{
     RecursiveQuery, Repartition, SkipType, Sort, StringifiedPlan, Subquery,
     SubqueryAlias, TableScan, ToStringifiedPlan, Union, Unnest, Values, Window,
 };
+pub use sort_within_partitions::SortWithinPartitions;
 pub use statement::{
     Deallocate, Execute, Prepare, SetVariable, Statement, TransactionAccessMode,
     TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
@@ -553,9 +553,10 @@ impl LogicalPlan {
                         left.head_output_expr()
                     }
                 }
-                JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
-                    left.head_output_expr()
-                }
+                JoinType::LeftSemi
+                | JoinType::LeftAnti
+                | JoinType::LeftMark
+                | JoinType::LeftAsof => left.head_output_expr(),
                 JoinType::RightSemi | JoinType::RightAnti => right.head_output_expr(),
             },
             LogicalPlan::RecursiveQuery(RecursiveQuery { static_term, .. }) => {
@@ -975,6 +976,10 @@ impl LogicalPlan {
                 or_replace,
                 column_defaults,
                 temporary,
+                partition_by,
+                sort_order,
+                comment,
+                column_comments,
                 ..
             })) => {
                 self.assert_no_expressions(expr)?;
@@ -988,6 +993,10 @@ impl LogicalPlan {
                         or_replace: *or_replace,
                         column_defaults: column_defaults.clone(),
                         temporary: *temporary,
+                        partition_by: partition_by.clone(),
+                        sort_order: sort_order.clone(),
+                        comment: comment.clone(),
+                        column_comments: column_comments.clone(),
                     },
                 )))
             }
@@ -1330,9 +1339,10 @@ impl LogicalPlan {
                         (left_max, right_max, _) => Some(left_max * right_max),
                     }
                 }
-                JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
-                    left.max_rows()
-                }
+                JoinType::LeftSemi
+                | JoinType::LeftAnti
+                | JoinType::LeftMark
+                | JoinType::LeftAsof => left.max_rows(),
                 JoinType::RightSemi | JoinType::RightAnti => right.max_rows(),
             },
             LogicalPlan::Repartition(Repartition { input, .. }) => input.max_rows(),
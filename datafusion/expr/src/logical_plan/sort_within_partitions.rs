@@ -0,0 +1,114 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SortWithinPartitions`] logical plan node
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::expr::Sort as SortExpr;
+use crate::{Expr, LogicalPlan, UserDefinedLogicalNodeCore};
+
+use datafusion_common::{internal_err, DFSchemaRef, Result};
+
+/// Sorts the rows within each partition of its input, e.g. for `SORT BY`.
+///
+/// Unlike [`Sort`](crate::logical_plan::Sort), the output is not sorted as a
+/// whole and keeps the partitioning of the input (e.g. of a preceding
+/// `DISTRIBUTE BY`). It is planned as a
+/// [`LogicalPlan::Extension`] so that it can be used wherever user defined
+/// nodes are supported.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct SortWithinPartitions {
+    expr: Vec<SortExpr>,
+    input: Arc<LogicalPlan>,
+}
+
+impl SortWithinPartitions {
+    /// Create a new sort of the rows within each partition of `input`
+    pub fn new(expr: Vec<SortExpr>, input: Arc<LogicalPlan>) -> Self {
+        Self { expr, input }
+    }
+
+    /// The sort expressions
+    pub fn expr(&self) -> &[SortExpr] {
+        &self.expr
+    }
+
+    /// The incoming logical plan
+    pub fn input(&self) -> &Arc<LogicalPlan> {
+        &self.input
+    }
+}
+
+impl UserDefinedLogicalNodeCore for SortWithinPartitions {
+    fn name(&self) -> &str {
+        "SortWithinPartitions"
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.input.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        self.expr.iter().map(|sort| sort.expr.clone()).collect()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SortWithinPartitions: ")?;
+        for (i, sort) in self.expr.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{sort}")?;
+        }
+        Ok(())
+    }
+
+    fn with_exprs_and_inputs(
+        &self,
+        exprs: Vec<Expr>,
+        mut inputs: Vec<LogicalPlan>,
+    ) -> Result<Self> {
+        if inputs.len() != 1 || exprs.len() != self.expr.len() {
+            return internal_err!(
+                "SortWithinPartitions expects one input and {} expressions",
+                self.expr.len()
+            );
+        }
+        let expr = self
+            .expr
+            .iter()
+            .zip(exprs)
+            .map(|(sort, expr)| sort.with_expr(expr))
+            .collect();
+        Ok(Self::new(expr, Arc::new(inputs.swap_remove(0))))
+    }
+
+    fn necessary_children_exprs(
+        &self,
+        output_columns: &[usize],
+    ) -> Option<Vec<Vec<usize>>> {
+        // The output columns are the input columns, the columns of the sort
+        // expressions are added by the caller
+        Some(vec![output_columns.to_vec()])
+    }
+}
@@ -266,6 +266,10 @@ impl TreeNode for LogicalPlan {
                         or_replace,
                         column_defaults,
                         temporary,
+                        partition_by,
+                        sort_order,
+                        comment,
+                        column_comments,
                     }) => input.map_elements(f)?.update_data(|input| {
                         DdlStatement::CreateMemoryTable(CreateMemoryTable {
                             name,
@@ -275,6 +279,10 @@ impl TreeNode for LogicalPlan {
                             or_replace,
                             column_defaults,
                             temporary,
+                            partition_by,
+                            sort_order,
+                            comment,
+                            column_comments,
                         })
                     }),
                     DdlStatement::CreateView(CreateView {
@@ -279,6 +279,11 @@ impl AggregateUDF {
         self.inner.default_value(data_type)
     }
 
+    /// See [`AggregateUDFImpl::is_ordered_set_aggregate`] for more details.
+    pub fn is_ordered_set_aggregate(&self) -> bool {
+        self.inner.is_ordered_set_aggregate()
+    }
+
     /// Returns the documentation for this Aggregate UDF.
     ///
     /// Documentation can be accessed programmatically as well as
@@ -631,6 +636,18 @@ pub trait AggregateUDFImpl: Debug + Send + Sync {
         ScalarValue::try_from(data_type)
     }
 
+    /// Returns true if this is an ordered-set aggregate function, such as
+    /// `percentile_cont`, which is called with a `WITHIN GROUP (ORDER BY expr)`
+    /// clause.
+    ///
+    /// For ordered-set aggregates the SQL planner passes the `WITHIN GROUP`
+    /// expression as the first argument of the function, followed by the
+    /// direct arguments, e.g. `percentile_cont(0.5) WITHIN GROUP (ORDER BY x)`
+    /// is planned as `percentile_cont(x, 0.5)` with an ordering of `x`.
+    fn is_ordered_set_aggregate(&self) -> bool {
+        false
+    }
+
     /// Returns the documentation for this Aggregate UDF.
     ///
     /// Documentation can be accessed programmatically as well as
@@ -791,6 +808,10 @@ impl AggregateUDFImpl for AliasedAggregateUDFImpl {
         self.inner.is_descending()
     }
 
+    fn is_ordered_set_aggregate(&self) -> bool {
+        self.inner.is_ordered_set_aggregate()
+    }
+
     fn documentation(&self) -> Option<&Documentation> {
         self.inner.documentation()
     }
@@ -0,0 +1,230 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `mode` ordered-set aggregate function
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::mem::{size_of, size_of_val};
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, AsArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, UInt64Type};
+
+use datafusion_common::{not_impl_err, HashMap, Result, ScalarValue};
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::{format_state_name, AggregateOrderSensitivity};
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Signature, Volatility,
+};
+use datafusion_macros::user_doc;
+
+make_udaf_expr_and_func!(
+    Mode,
+    mode,
+    expression,
+    "Computes the most frequent value of a set of values",
+    mode_udaf
+);
+
+#[user_doc(
+    doc_section(label = "General Functions"),
+    description = "Returns the most frequent input value. If several values are equally frequent, the first one in the WITHIN GROUP ordering is returned.",
+    syntax_example = "mode() WITHIN GROUP (ORDER BY expression)",
+    sql_example = r#"```sql
+> SELECT mode() WITHIN GROUP (ORDER BY column_name) FROM table_name;
++--------------------------------------------+
+| mode() WITHIN GROUP (ORDER BY column_name) |
++--------------------------------------------+
+| 3                                          |
++--------------------------------------------+
+```"#,
+    standard_argument(name = "expression", prefix = "The")
+)]
+/// MODE aggregate expression. This keeps a count for every distinct value in
+/// memory until the result is computed.
+pub struct Mode {
+    signature: Signature,
+}
+
+impl Debug for Mode {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Mode")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mode {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for Mode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "mode"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        let value_field = Field::new_list_field(args.input_types[0].clone(), true);
+        let count_field = Field::new_list_field(DataType::UInt64, true);
+        Ok(vec![
+            Field::new(
+                format_state_name(args.name, "mode_values"),
+                DataType::List(Arc::new(value_field)),
+                true,
+            ),
+            Field::new(
+                format_state_name(args.name, "mode_counts"),
+                DataType::List(Arc::new(count_field)),
+                true,
+            ),
+        ])
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        if acc_args.is_distinct {
+            return not_impl_err!("DISTINCT is not supported for {}", self.name());
+        }
+        let descending = acc_args
+            .ordering_req
+            .first()
+            .is_some_and(|sort| sort.options.descending);
+        Ok(Box::new(ModeAccumulator {
+            data_type: acc_args.exprs[0].data_type(acc_args.schema)?,
+            counts: HashMap::new(),
+            descending,
+        }))
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        // Ties are broken by comparing the values, the WITHIN GROUP ordering
+        // only determines the direction
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn is_ordered_set_aggregate(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+/// Counts the occurrences of every distinct non-null input value
+#[derive(Debug)]
+struct ModeAccumulator {
+    data_type: DataType,
+    counts: HashMap<ScalarValue, u64>,
+    /// Whether the WITHIN GROUP ordering is descending
+    descending: bool,
+}
+
+impl ModeAccumulator {
+    fn add(&mut self, values: &ArrayRef, counts: Option<&UInt64Array>) -> Result<()> {
+        for index in 0..values.len() {
+            if values.is_null(index) {
+                continue;
+            }
+            let value = ScalarValue::try_from_array(values, index)?;
+            let count = counts.map_or(1, |counts| counts.value(index));
+            *self.counts.entry(value).or_default() += count;
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ModeAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let (values, counts): (Vec<_>, Vec<_>) = self
+            .counts
+            .iter()
+            .map(|(value, count)| (value.clone(), ScalarValue::UInt64(Some(*count))))
+            .unzip();
+        Ok(vec![
+            ScalarValue::List(ScalarValue::new_list_nullable(&values, &self.data_type)),
+            ScalarValue::List(ScalarValue::new_list_nullable(&counts, &DataType::UInt64)),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.add(&values[0], None)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let values = states[0].as_list::<i32>();
+        let counts = states[1].as_list::<i32>();
+        for (values, counts) in values.iter().zip(counts.iter()) {
+            if let (Some(values), Some(counts)) = (values, counts) {
+                self.add(&values, Some(counts.as_primitive::<UInt64Type>()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let mode = self.counts.iter().max_by(|(v1, c1), (v2, c2)| {
+            // Prefer the value that comes first in the ordering among equally
+            // frequent values
+            let ordering = v1.partial_cmp(v2).unwrap_or(Ordering::Equal);
+            let ordering = if self.descending {
+                ordering
+            } else {
+                ordering.reverse()
+            };
+            c1.cmp(c2).then(ordering)
+        });
+        match mode {
+            Some((value, _)) => Ok(value.clone()),
+            None => ScalarValue::try_from(&self.data_type),
+        }
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+            + self.counts.capacity() * size_of::<(ScalarValue, u64)>()
+            + self
+                .counts
+                .keys()
+                .map(|value| value.size() - size_of_val(value))
+                .sum::<usize>()
+    }
+}
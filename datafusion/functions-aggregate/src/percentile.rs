@@ -0,0 +1,445 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the exact `percentile_cont` and `percentile_disc` ordered-set
+//! aggregate functions
+
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::mem::{size_of, size_of_val};
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, AsArray, ListArray, RecordBatch};
+use arrow::buffer::OffsetBuffer;
+use arrow::compute::{concat, filter, is_not_null, sort_to_indices, SortOptions};
+use arrow::datatypes::{DataType, Field, Float64Type, Schema};
+
+use datafusion_common::{
+    internal_err, not_impl_datafusion_err, not_impl_err, plan_err, Result, ScalarValue,
+};
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::{format_state_name, AggregateOrderSensitivity};
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, ColumnarValue, Documentation, Signature, Volatility,
+};
+use datafusion_macros::user_doc;
+use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+
+make_udaf_expr_and_func!(
+    PercentileCont,
+    percentile_cont,
+    expression percentile,
+    "Computes the exact percentile of a set of numbers, interpolating between adjacent values",
+    percentile_cont_udaf
+);
+
+make_udaf_expr_and_func!(
+    PercentileDisc,
+    percentile_disc,
+    expression percentile,
+    "Computes the exact percentile of a set of values, returning one of the input values",
+    percentile_disc_udaf
+);
+
+#[user_doc(
+    doc_section(label = "General Functions"),
+    description = "Returns the exact percentile of input values, interpolating between adjacent input values if needed.",
+    syntax_example = "percentile_cont(percentile) WITHIN GROUP (ORDER BY expression)",
+    sql_example = r#"```sql
+> SELECT percentile_cont(0.75) WITHIN GROUP (ORDER BY column_name) FROM table_name;
++-----------------------------------------------------------+
+| percentile_cont(0.75) WITHIN GROUP (ORDER BY column_name) |
++-----------------------------------------------------------+
+| 45.5                                                      |
++-----------------------------------------------------------+
+```"#,
+    standard_argument(name = "expression", prefix = "The"),
+    argument(
+        name = "percentile",
+        description = "Percentile to compute. Must be a float value between 0 and 1 (inclusive)."
+    )
+)]
+/// PERCENTILE_CONT aggregate expression. Like MEDIAN, all values are kept in
+/// memory until the result is computed. If an approximation is sufficient then
+/// APPROX_PERCENTILE_CONT provides a much more efficient solution.
+pub struct PercentileCont {
+    signature: Signature,
+}
+
+impl Debug for PercentileCont {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("PercentileCont")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for PercentileCont {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PercentileCont {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::exact(
+                vec![DataType::Float64, DataType::Float64],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl AggregateUDFImpl for PercentileCont {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "percentile_cont"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        Ok(percentile_state_fields(&args))
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(PercentileAccumulator::try_new(
+            acc_args,
+            self.name(),
+            true,
+        )?))
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        // The input is sorted by the accumulator itself, the WITHIN GROUP
+        // ordering only determines the direction
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn is_ordered_set_aggregate(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+#[user_doc(
+    doc_section(label = "General Functions"),
+    description = "Returns the exact percentile of input values, which is the first input value whose position in the ordering equals or exceeds the specified percentile.",
+    syntax_example = "percentile_disc(percentile) WITHIN GROUP (ORDER BY expression)",
+    sql_example = r#"```sql
+> SELECT percentile_disc(0.75) WITHIN GROUP (ORDER BY column_name) FROM table_name;
++-----------------------------------------------------------+
+| percentile_disc(0.75) WITHIN GROUP (ORDER BY column_name) |
++-----------------------------------------------------------+
+| 45                                                        |
++-----------------------------------------------------------+
+```"#,
+    standard_argument(name = "expression", prefix = "The"),
+    argument(
+        name = "percentile",
+        description = "Percentile to compute. Must be a float value between 0 and 1 (inclusive)."
+    )
+)]
+/// PERCENTILE_DISC aggregate expression. All values are kept in memory until
+/// the result is computed.
+pub struct PercentileDisc {
+    signature: Signature,
+}
+
+impl Debug for PercentileDisc {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("PercentileDisc")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for PercentileDisc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PercentileDisc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for PercentileDisc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "percentile_disc"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        let [value_type, _] = arg_types else {
+            return plan_err!(
+                "percentile_disc expects 2 arguments, got {}",
+                arg_types.len()
+            );
+        };
+        Ok(vec![value_type.clone(), DataType::Float64])
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        Ok(percentile_state_fields(&args))
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(PercentileAccumulator::try_new(
+            acc_args,
+            self.name(),
+            false,
+        )?))
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        // The input is sorted by the accumulator itself, the WITHIN GROUP
+        // ordering only determines the direction
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn is_ordered_set_aggregate(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+/// The intermediate state is a list of all the non-null values collected so far
+fn percentile_state_fields(args: &StateFieldsArgs) -> Vec<Field> {
+    let field = Field::new_list_field(args.input_types[0].clone(), true);
+    vec![Field::new(
+        format_state_name(args.name, "percentile"),
+        DataType::List(Arc::new(field)),
+        true,
+    )]
+}
+
+fn validate_percentile_expr(expr: &Arc<dyn PhysicalExpr>, name: &str) -> Result<f64> {
+    let batch = RecordBatch::new_empty(Arc::new(Schema::empty()));
+    let percentile = match expr.evaluate(&batch) {
+        Ok(ColumnarValue::Scalar(ScalarValue::Float64(Some(value)))) => value,
+        Ok(ColumnarValue::Scalar(sv)) => {
+            return not_impl_err!(
+            "Percentile value for '{name}' must be a Float64 literal (got data type {})",
+            sv.data_type()
+        )
+        }
+        _ => {
+            return Err(not_impl_datafusion_err!(
+                "Percentile value for '{name}' must be a literal, got: {expr}"
+            ))
+        }
+    };
+
+    if !(0.0..=1.0).contains(&percentile) {
+        return plan_err!(
+            "Percentile value must be between 0.0 and 1.0 inclusive, {percentile} is invalid"
+        );
+    }
+    Ok(percentile)
+}
+
+/// Accumulates all non-null input values, which are sorted when the
+/// percentile is evaluated
+#[derive(Debug)]
+struct PercentileAccumulator {
+    data_type: DataType,
+    values: Vec<ArrayRef>,
+    percentile: f64,
+    /// Whether the WITHIN GROUP ordering is descending
+    descending: bool,
+    /// Whether to interpolate between adjacent values (`percentile_cont`) or
+    /// return an input value (`percentile_disc`)
+    interpolate: bool,
+}
+
+impl PercentileAccumulator {
+    fn try_new(args: AccumulatorArgs, name: &str, interpolate: bool) -> Result<Self> {
+        if args.is_distinct {
+            return not_impl_err!("DISTINCT is not supported for {name}");
+        }
+        if args.exprs.len() != 2 {
+            return internal_err!(
+                "{name} should have 2 args, but found num args:{}",
+                args.exprs.len()
+            );
+        }
+        let percentile = validate_percentile_expr(&args.exprs[1], name)?;
+        let descending = args
+            .ordering_req
+            .first()
+            .is_some_and(|sort| sort.options.descending);
+        Ok(Self {
+            data_type: args.exprs[0].data_type(args.schema)?,
+            values: vec![],
+            percentile,
+            descending,
+            interpolate,
+        })
+    }
+
+    fn push(&mut self, values: &ArrayRef) -> Result<()> {
+        let values = if values.null_count() > 0 {
+            filter(values, &is_not_null(values)?)?
+        } else {
+            Arc::clone(values)
+        };
+        if !values.is_empty() {
+            self.values.push(values);
+        }
+        Ok(())
+    }
+
+    /// Returns all accumulated values as one array, which is kept as the state
+    /// for the following calls (e.g. when evaluated for a sliding window)
+    fn concat_values(&mut self) -> Result<ArrayRef> {
+        match self.values.as_slice() {
+            [] => Ok(arrow::array::new_empty_array(&self.data_type)),
+            [values] => Ok(Arc::clone(values)),
+            values => {
+                let arrays = values.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
+                let values = concat(&arrays)?;
+                self.values = vec![Arc::clone(&values)];
+                Ok(values)
+            }
+        }
+    }
+}
+
+impl Accumulator for PercentileAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let values = self.concat_values()?;
+        let list_array = ListArray::new(
+            Arc::new(Field::new_list_field(self.data_type.clone(), true)),
+            OffsetBuffer::from_lengths([values.len()]),
+            values,
+            None,
+        );
+        Ok(vec![ScalarValue::List(Arc::new(list_array))])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.push(&values[0])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let array = states[0].as_list::<i32>();
+        for values in array.iter().flatten() {
+            self.push(&values)?;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let values = self.concat_values()?;
+        if values.is_empty() {
+            return ScalarValue::try_from(&self.data_type);
+        }
+        let options = SortOptions {
+            descending: self.descending,
+            nulls_first: false,
+        };
+        let indices = sort_to_indices(&values, Some(options), None)?;
+        let position = self.percentile * (values.len() - 1) as f64;
+
+        if !self.interpolate {
+            // The first value whose cumulative distribution is at least the percentile
+            let index = ((self.percentile * values.len() as f64).ceil() as usize)
+                .saturating_sub(1);
+            return ScalarValue::try_from_array(&values, indices.value(index) as usize);
+        }
+
+        let values = values.as_primitive::<Float64Type>();
+        let lower = values.value(indices.value(position.floor() as usize) as usize);
+        let upper = values.value(indices.value(position.ceil() as usize) as usize);
+        let result = lower + (upper - lower) * (position - position.floor());
+        Ok(ScalarValue::Float64(Some(result)))
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+            + self.values.capacity() * size_of::<ArrayRef>()
+            + self
+                .values
+                .iter()
+                .map(|values| values.get_array_memory_size())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::Float64Array;
+
+    #[test]
+    fn percentile_evaluate_repeatedly() -> Result<()> {
+        let mut accumulator = PercentileAccumulator {
+            data_type: DataType::Float64,
+            values: vec![],
+            percentile: 0.5,
+            descending: false,
+            interpolate: true,
+        };
+        accumulator.update_batch(&[Arc::new(Float64Array::from(vec![1.0, 4.0]))])?;
+        accumulator.update_batch(&[Arc::new(Float64Array::from(vec![2.0, 3.0]))])?;
+
+        // evaluating or retrieving the state must not consume the values
+        assert_eq!(accumulator.evaluate()?, ScalarValue::Float64(Some(2.5)));
+        let state = accumulator.state()?;
+        assert_eq!(accumulator.state()?, state);
+        assert_eq!(accumulator.evaluate()?, ScalarValue::Float64(Some(2.5)));
+
+        accumulator.update_batch(&[Arc::new(Float64Array::from(vec![5.0]))])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::Float64(Some(3.0)));
+        Ok(())
+    }
+}
@@ -33,8 +33,8 @@ use datafusion_expr::utils::{
     collect_subquery_cols, conjunction, find_join_exprs, split_conjunction,
 };
 use datafusion_expr::{
-    expr, lit, BinaryExpr, Cast, EmptyRelation, Expr, FetchType, LogicalPlan,
-    LogicalPlanBuilder, Operator,
+    expr, lit, BinaryExpr, Cast, EmptyRelation, Expr, ExprFunctionExt, FetchType,
+    LogicalPlan, LogicalPlanBuilder, Operator, SkipType, Sort, WindowUDF,
 };
 use datafusion_physical_expr::execution_props::ExecutionProps;
 
@@ -65,6 +65,11 @@ pub struct PullUpCorrelatedExpr {
     pub collected_count_expr_map: HashMap<LogicalPlan, ExprResultMap>,
     /// pull up having expr, which must be evaluated after the Join
     pub pull_up_having_expr: Option<Expr>,
+    /// Is this the subquery of a LATERAL join. Defaults to **FALSE**
+    pub lateral_join: bool,
+    /// `row_number` window function used to apply a correlated `LIMIT` of a
+    /// LATERAL join subquery to each outer row
+    row_number: Option<Arc<WindowUDF>>,
 }
 
 impl Default for PullUpCorrelatedExpr {
@@ -85,6 +90,8 @@ impl PullUpCorrelatedExpr {
             need_handle_count_bug: false,
             collected_count_expr_map: HashMap::new(),
             pull_up_having_expr: None,
+            lateral_join: false,
+            row_number: None,
         }
     }
 
@@ -108,8 +115,23 @@ impl PullUpCorrelatedExpr {
         self.exists_sub_query = exists_sub_query;
         self
     }
+
+    /// Set that this is the subquery of a LATERAL join.
+    ///
+    /// A correlated `LIMIT` in such a subquery can only be pulled up if the
+    /// `row_number` window function is provided, which is used to number the
+    /// rows of each outer row separately.
+    pub fn with_lateral_join(mut self, row_number: Option<Arc<WindowUDF>>) -> Self {
+        self.lateral_join = true;
+        self.row_number = row_number;
+        self
+    }
 }
 
+/// Name of the row number column used to pull up a correlated `LIMIT` of a
+/// LATERAL join subquery
+pub const LATERAL_ROW_NUMBER: &str = "__lateral_row_number";
+
 /// Used to indicate the unmatched rows from the inner(subquery) table after the left out Join
 /// This is used to handle [the Count bug]
 ///
@@ -127,6 +149,26 @@ impl TreeNodeRewriter for PullUpCorrelatedExpr {
     fn f_down(&mut self, plan: LogicalPlan) -> Result<Transformed<LogicalPlan>> {
         match plan {
             LogicalPlan::Filter(_) => Ok(Transformed::no(plan)),
+            // The order of the rows of a Sort with a fetch is lost once it is
+            // rewritten, so a Limit above it can not be evaluated per outer row
+            LogicalPlan::Limit(ref limit)
+                if self.lateral_join
+                    && matches!(
+                        limit.input.as_ref(),
+                        LogicalPlan::Sort(Sort { fetch: Some(_), .. })
+                    )
+                    && !plan.all_out_ref_exprs().is_empty() =>
+            {
+                self.can_pull_up = false;
+                Ok(Transformed::new(plan, false, TreeNodeRecursion::Jump))
+            }
+            // Sort and Limit of a LATERAL join subquery are evaluated per outer
+            // row, see `pull_up_lateral_limit`
+            LogicalPlan::Sort(_) | LogicalPlan::Limit(_)
+                if self.lateral_join && !plan.contains_outer_reference() =>
+            {
+                Ok(Transformed::no(plan))
+            }
             LogicalPlan::Union(_) | LogicalPlan::Sort(_) | LogicalPlan::Extension(_) => {
                 let plan_hold_outer = !plan.all_out_ref_exprs().is_empty();
                 if plan_hold_outer {
@@ -278,6 +320,11 @@ impl TreeNodeRewriter for PullUpCorrelatedExpr {
                 if !is_distinct {
                     self.can_pull_up = self.can_pull_up && self.can_pull_over_aggregation;
                 }
+                // An aggregation without grouping produces a row for every outer
+                // row of a LATERAL join, even if no input rows match it
+                if self.lateral_join && aggregate.group_expr.is_empty() {
+                    self.can_pull_up = false;
+                }
                 let mut local_correlated_cols = BTreeSet::new();
                 collect_local_correlated_cols(
                     &plan,
@@ -339,6 +386,33 @@ impl TreeNodeRewriter for PullUpCorrelatedExpr {
                 }
                 Ok(Transformed::no(plan))
             }
+            LogicalPlan::Limit(_) | LogicalPlan::Sort(Sort { fetch: Some(_), .. })
+                if self.lateral_join =>
+            {
+                let mut local_correlated_cols = BTreeSet::new();
+                collect_local_correlated_cols(
+                    &plan,
+                    &self.correlated_subquery_cols_map,
+                    &mut local_correlated_cols,
+                );
+                if local_correlated_cols.is_empty() {
+                    return Ok(Transformed::no(plan));
+                }
+                // The rows correlated with an outer row are the rows with the
+                // same correlated column values only if all the correlated
+                // predicates are equalities
+                if !self.can_pull_over_aggregation {
+                    self.can_pull_up = false;
+                    return Ok(Transformed::no(plan));
+                }
+                match self.pull_up_lateral_limit(&plan, &local_correlated_cols)? {
+                    Some(new_plan) => Ok(Transformed::yes(new_plan)),
+                    None => {
+                        self.can_pull_up = false;
+                        Ok(Transformed::no(plan))
+                    }
+                }
+            }
             LogicalPlan::Limit(limit) => {
                 let input_expr_map = self
                     .collected_count_expr_map
@@ -402,6 +476,76 @@ impl PullUpCorrelatedExpr {
         }
         Ok(missing_exprs)
     }
+
+    /// Rewrites the `LIMIT` of a LATERAL join subquery, or a `Sort` with a
+    /// fetch, which applies to the rows of each outer row, into a filter on the
+    /// row number within the group of rows correlated with the same outer row:
+    ///
+    /// ```text
+    /// Projection: <input columns>
+    ///   Filter: __lateral_row_number > <skip> AND __lateral_row_number <= <skip + fetch>
+    ///     WindowAggr: row_number() PARTITION BY <correlated columns> ORDER BY <sort> AS __lateral_row_number
+    ///       <input>
+    /// ```
+    ///
+    /// Returns `None` if the limit can not be rewritten.
+    fn pull_up_lateral_limit(
+        &self,
+        plan: &LogicalPlan,
+        correlated_cols: &BTreeSet<Column>,
+    ) -> Result<Option<LogicalPlan>> {
+        let Some(row_number) = &self.row_number else {
+            return Ok(None);
+        };
+        let (skip, fetch, input, order_by) = match plan {
+            LogicalPlan::Limit(limit) => {
+                let (SkipType::Literal(skip), FetchType::Literal(fetch)) =
+                    (limit.get_skip_type()?, limit.get_fetch_type()?)
+                else {
+                    return Ok(None);
+                };
+                match limit.input.as_ref() {
+                    LogicalPlan::Sort(sort) if sort.fetch.is_none() => {
+                        (skip, fetch, sort.input.as_ref(), sort.expr.clone())
+                    }
+                    LogicalPlan::Sort(_) => return Ok(None),
+                    input => (skip, fetch, input, vec![]),
+                }
+            }
+            LogicalPlan::Sort(sort) => {
+                (0, sort.fetch, sort.input.as_ref(), sort.expr.clone())
+            }
+            _ => return Ok(None),
+        };
+
+        let row_number_col = Expr::Column(Column::from_name(LATERAL_ROW_NUMBER));
+        let mut predicates = vec![];
+        if skip > 0 {
+            predicates.push(row_number_col.clone().gt(lit(skip as u64)));
+        }
+        if let Some(fetch) = fetch {
+            predicates.push(row_number_col.lt_eq(lit((skip + fetch) as u64)));
+        }
+        let Some(predicate) = conjunction(predicates) else {
+            return Ok(Some(input.clone()));
+        };
+
+        let row_number_expr = Expr::WindowFunction(expr::WindowFunction::new(
+            Arc::clone(row_number),
+            vec![],
+        ))
+        .partition_by(correlated_cols.iter().cloned().map(Expr::Column).collect())
+        .order_by(order_by)
+        .build()?
+        .alias(LATERAL_ROW_NUMBER);
+        let input_columns = input.schema().columns().into_iter().map(Expr::Column);
+        LogicalPlanBuilder::from(input.clone())
+            .window(vec![row_number_expr])?
+            .filter(predicate)?
+            .project(input_columns)?
+            .build()
+            .map(Some)
+    }
 }
 
 fn can_pullup_over_aggregation(expr: &Expr) -> bool {
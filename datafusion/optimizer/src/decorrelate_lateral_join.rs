@@ -0,0 +1,254 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`DecorrelateLateralJoin`] decorrelates LATERAL join subqueries
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use crate::decorrelate::PullUpCorrelatedExpr;
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{Column, Result};
+use datafusion_expr::expr_rewriter::replace_col;
+use datafusion_expr::logical_plan::{Join, JoinType, SubqueryAlias};
+use datafusion_expr::utils::conjunction;
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder};
+
+/// Optimizer rule for rewriting LATERAL join subqueries, whose correlated
+/// predicates are pulled up into the join condition.
+///
+/// ```text
+/// SELECT t1.id, s.v FROM t1 JOIN LATERAL (SELECT t2.v FROM t2 WHERE t2.id = t1.id LIMIT 3) s ON true
+/// ```
+///
+/// is rewritten to
+///
+/// ```text
+/// Projection: t1.id, s.v
+///   Inner Join:  Filter: s.id = t1.id
+///     TableScan: t1
+///     SubqueryAlias: s
+///       Projection: t2.v, t2.id
+///         Filter: __lateral_row_number <= UInt64(3)
+///           WindowAggr: windowExpr=[[row_number() PARTITION BY [t2.id] AS __lateral_row_number]]
+///             TableScan: t2
+/// ```
+///
+/// A `LIMIT` in the subquery is only supported if the `row_number` window
+/// function is registered.
+#[derive(Default, Debug)]
+pub struct DecorrelateLateralJoin {}
+
+impl DecorrelateLateralJoin {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OptimizerRule for DecorrelateLateralJoin {
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Join(join) = &plan else {
+            return Ok(Transformed::no(plan));
+        };
+        match decorrelate_lateral_join(join, config)? {
+            Some(new_plan) => Ok(Transformed::yes(new_plan)),
+            None => Ok(Transformed::no(plan)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "decorrelate_lateral_join"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+}
+
+/// Returns the join with its correlated right input rewritten to a plain
+/// input, or `None` if the join is not a LATERAL join that can be decorrelated
+fn decorrelate_lateral_join(
+    join: &Join,
+    config: &dyn OptimizerConfig,
+) -> Result<Option<LogicalPlan>> {
+    if !matches!(join.join_type, JoinType::Inner | JoinType::Left) {
+        return Ok(None);
+    }
+    let (subquery, alias) = match join.right.as_ref() {
+        LogicalPlan::Subquery(subquery) => (subquery, None),
+        LogicalPlan::SubqueryAlias(SubqueryAlias { input, alias, .. }) => {
+            match input.as_ref() {
+                LogicalPlan::Subquery(subquery) => (subquery, Some(alias)),
+                _ => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+
+    let row_number = config
+        .function_registry()
+        .and_then(|registry| registry.udwf("row_number").ok());
+    let mut pull_up = PullUpCorrelatedExpr::new().with_lateral_join(row_number);
+    let new_subquery = subquery
+        .subquery
+        .as_ref()
+        .clone()
+        .rewrite(&mut pull_up)
+        .data()?;
+    if !pull_up.can_pull_up
+        || pull_up.pull_up_having_expr.is_some()
+        || !new_subquery.all_out_ref_exprs().is_empty()
+    {
+        return Ok(None);
+    }
+
+    let lateral_filter = conjunction(pull_up.join_filters);
+    let (right, lateral_filter) = match alias {
+        Some(alias) => {
+            // The correlated columns are referenced through the alias of the subquery
+            let mut correlated_cols = BTreeSet::new();
+            pull_up
+                .correlated_subquery_cols_map
+                .values()
+                .for_each(|cols| correlated_cols.extend(cols.iter().cloned()));
+            let alias_cols = correlated_cols
+                .iter()
+                .map(|col| Column::new(Some(alias.clone()), &col.name))
+                .collect::<Vec<_>>();
+            let replace_map: HashMap<&Column, &Column> =
+                correlated_cols.iter().zip(alias_cols.iter()).collect();
+            let lateral_filter = lateral_filter
+                .map(|filter| replace_col(filter, &replace_map))
+                .transpose()?;
+            let right = LogicalPlanBuilder::from(new_subquery)
+                .alias(alias.clone())?
+                .build()?;
+            (right, lateral_filter)
+        }
+        None => (new_subquery, lateral_filter),
+    };
+
+    let filters = join
+        .on
+        .iter()
+        .map(|(left, right)| left.clone().eq(right.clone()))
+        .chain(join.filter.clone())
+        .chain(lateral_filter);
+    let new_plan = LogicalPlanBuilder::from(Arc::unwrap_or_clone(Arc::clone(&join.left)))
+        .join_on(right, join.join_type, filters)?
+        .build()?;
+
+    // The correlated columns pulled up into the subquery output are not part
+    // of the join output
+    if new_plan.schema().fields().len() == join.schema.fields().len() {
+        return Ok(Some(new_plan));
+    }
+    let columns = join.schema.columns().into_iter().map(Expr::Column);
+    LogicalPlanBuilder::from(new_plan)
+        .project(columns)?
+        .build()
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+
+    use arrow::datatypes::DataType;
+    use datafusion_expr::test::function_stub::sum;
+    use datafusion_expr::{col, lit, out_ref_col, Subquery};
+
+    fn lateral_join(subquery: LogicalPlan) -> Result<LogicalPlan> {
+        let outer_ref_columns = subquery.all_out_ref_exprs();
+        let right = LogicalPlanBuilder::from(LogicalPlan::Subquery(Subquery {
+            subquery: Arc::new(subquery),
+            outer_ref_columns,
+        }))
+        .alias("o")?
+        .build()?;
+        LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .join_on(right, JoinType::Inner, vec![lit(true)])?
+            .build()
+    }
+
+    /// Test a lateral subquery with a correlated filter
+    #[test]
+    fn lateral_join_correlated_filter() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .project(vec![col("orders.o_orderkey")])?
+            .build()?;
+        let plan = lateral_join(subquery)?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, o.o_orderkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64]\
+        \n  Inner Join:  Filter: Boolean(true) AND o.o_custkey = customer.c_custkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64, o_custkey:Int64]\
+        \n    TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n    SubqueryAlias: o [o_orderkey:Int64, o_custkey:Int64]\
+        \n      Projection: orders.o_orderkey, orders.o_custkey [o_orderkey:Int64, o_custkey:Int64]\
+        \n        TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateLateralJoin::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    /// An ungrouped aggregate returns a row for every outer row, which a join
+    /// cannot express, so the subquery is left as is
+    #[test]
+    fn lateral_join_ungrouped_aggregate() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .aggregate(Vec::<Expr>::new(), vec![sum(col("orders.o_totalprice"))])?
+            .build()?;
+        let plan = lateral_join(subquery)?;
+
+        let expected = "Inner Join:  Filter: Boolean(true) [c_custkey:Int64, c_name:Utf8, sum(orders.o_totalprice):Float64;N]\
+        \n  TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n  SubqueryAlias: o [sum(orders.o_totalprice):Float64;N]\
+        \n    Subquery: [sum(orders.o_totalprice):Float64;N]\
+        \n      Aggregate: groupBy=[[]], aggr=[[sum(orders.o_totalprice)]] [sum(orders.o_totalprice):Float64;N]\
+        \n        Filter: orders.o_custkey = outer_ref(customer.c_custkey) [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n          TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateLateralJoin::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+}
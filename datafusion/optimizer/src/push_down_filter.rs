@@ -160,7 +160,7 @@ pub struct PushDownFilter {}
 pub(crate) fn lr_is_preserved(join_type: JoinType) -> (bool, bool) {
     match join_type {
         JoinType::Inner => (true, true),
-        JoinType::Left => (true, false),
+        JoinType::Left | JoinType::LeftAsof => (true, false),
         JoinType::Right => (false, true),
         JoinType::Full => (false, false),
         // No columns from the right side of the join can be referenced in output
@@ -184,7 +184,7 @@ pub(crate) fn lr_is_preserved(join_type: JoinType) -> (bool, bool) {
 pub(crate) fn on_lr_is_preserved(join_type: JoinType) -> (bool, bool) {
     match join_type {
         JoinType::Inner => (true, true),
-        JoinType::Left => (false, true),
+        JoinType::Left | JoinType::LeftAsof => (false, true),
         JoinType::Right => (true, false),
         JoinType::Full => (false, false),
         JoinType::LeftSemi | JoinType::RightSemi => (true, true),
@@ -682,7 +682,7 @@ fn infer_join_predicates_from_on_filters(
             on_filters,
             inferred_predicates,
         ),
-        JoinType::Left | JoinType::LeftSemi | JoinType::LeftMark => {
+        JoinType::Left | JoinType::LeftSemi | JoinType::LeftMark | JoinType::LeftAsof => {
             infer_join_predicates_impl::<true, false>(
                 join_col_keys,
                 on_filters,
@@ -0,0 +1,327 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Dynamic filter expression, whose value is updated while executing a plan
+
+use std::any::Any;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::PhysicalExpr;
+use arrow::datatypes::{DataType, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{internal_datafusion_err, Result};
+use datafusion_expr::ColumnarValue;
+
+/// A filter whose predicate is only known while the plan is executed
+///
+/// For example, a hash join knows which join key values can match once it has
+/// collected its build side, and can then use this expression to tell the scans
+/// of its probe side which rows to skip. Until [`Self::update`] is called the
+/// filter evaluates its initial expression, usually `true`.
+///
+/// The predicate is shared by all clones of the expression, including those
+/// created by [`PhysicalExpr::with_new_children`]. The predicate is written
+/// in terms of the `children` the expression was created with: when the
+/// children are rewritten, for example when pushing the filter through a
+/// projection, the rewritten children are substituted into the predicate
+/// by [`Self::current`].
+///
+/// Consumers of the filter that need a fixed predicate, for example to prune
+/// row groups with statistics, should use [`snapshot_physical_expr`], and
+/// [`dynamic_filter_generation`] to know when the predicate changed.
+#[derive(Debug)]
+pub struct DynamicFilterPhysicalExpr {
+    /// The expressions the predicate is written in terms of
+    children: Vec<Arc<dyn PhysicalExpr>>,
+    /// The rewritten `children`, if they have been rewritten
+    remapped_children: Option<Vec<Arc<dyn PhysicalExpr>>>,
+    /// The current predicate, shared with all clones
+    inner: Arc<RwLock<Arc<dyn PhysicalExpr>>>,
+    /// The number of times the predicate was updated, shared with all clones
+    generation: Arc<AtomicU64>,
+    /// Whether the predicate is tightened many times while executing, as the
+    /// filter of a TopK is, rather than updated once
+    incremental: bool,
+}
+
+impl DynamicFilterPhysicalExpr {
+    /// Create a new dynamic filter over `children`, initially evaluating `inner`
+    pub fn new(
+        children: Vec<Arc<dyn PhysicalExpr>>,
+        inner: Arc<dyn PhysicalExpr>,
+    ) -> Self {
+        Self {
+            children,
+            remapped_children: None,
+            inner: Arc::new(RwLock::new(inner)),
+            generation: Arc::new(AtomicU64::new(0)),
+            incremental: false,
+        }
+    }
+
+    /// Mark this filter as tightened many times while executing, so that
+    /// consumers check it again while scanning, see [`Self::is_incremental`]
+    pub fn with_incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Whether the predicate is tightened many times while executing, as the
+    /// filter of a TopK is, rather than updated once
+    pub fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+
+    /// The number of times the predicate of this filter was updated
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Replace the predicate of this filter and of all its clones.
+    ///
+    /// `new_expr` must be written in terms of the children the filter was
+    /// created with.
+    pub fn update(&self, new_expr: Arc<dyn PhysicalExpr>) -> Result<()> {
+        let mut inner = self
+            .inner
+            .write()
+            .map_err(|_| internal_datafusion_err!("Dynamic filter lock poisoned"))?;
+        *inner = new_expr;
+        self.generation.fetch_add(1, Ordering::Release);
+        Ok(())
+    }
+
+    /// Return the current predicate of this filter, in terms of its
+    /// (possibly rewritten) children
+    pub fn current(&self) -> Result<Arc<dyn PhysicalExpr>> {
+        let expr = Arc::clone(
+            &*self
+                .inner
+                .read()
+                .map_err(|_| internal_datafusion_err!("Dynamic filter lock poisoned"))?,
+        );
+        let Some(remapped_children) = &self.remapped_children else {
+            return Ok(expr);
+        };
+        expr.transform_down(|expr| {
+            match self.children.iter().position(|child| child.eq(&expr)) {
+                Some(idx) => Ok(Transformed::new(
+                    Arc::clone(&remapped_children[idx]),
+                    true,
+                    TreeNodeRecursion::Jump,
+                )),
+                None => Ok(Transformed::no(expr)),
+            }
+        })
+        .data()
+    }
+}
+
+impl PartialEq for DynamicFilterPhysicalExpr {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+            && self.children == other.children
+            && self.remapped_children == other.remapped_children
+    }
+}
+
+impl Eq for DynamicFilterPhysicalExpr {}
+
+impl Hash for DynamicFilterPhysicalExpr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.inner).hash(state);
+        self.children.hash(state);
+        self.remapped_children.hash(state);
+    }
+}
+
+impl fmt::Display for DynamicFilterPhysicalExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.current() {
+            Ok(expr) => write!(f, "DynamicFilterPhysicalExpr [ {expr} ]"),
+            Err(_) => write!(f, "DynamicFilterPhysicalExpr [ <error> ]"),
+        }
+    }
+}
+
+impl PhysicalExpr for DynamicFilterPhysicalExpr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, input_schema: &Schema) -> Result<DataType> {
+        self.current()?.data_type(input_schema)
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> Result<bool> {
+        self.current()?.nullable(input_schema)
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+        self.current()?.evaluate(batch)
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn PhysicalExpr>> {
+        self.remapped_children
+            .as_ref()
+            .unwrap_or(&self.children)
+            .iter()
+            .collect()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(Arc::new(Self {
+            children: self.children.clone(),
+            remapped_children: Some(children),
+            inner: Arc::clone(&self.inner),
+            generation: Arc::clone(&self.generation),
+            incremental: self.incremental,
+        }))
+    }
+}
+
+/// Replace every [`DynamicFilterPhysicalExpr`] in `expr` with its current predicate
+pub fn snapshot_physical_expr(
+    expr: Arc<dyn PhysicalExpr>,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    expr.transform_up(|expr| {
+        match expr.as_any().downcast_ref::<DynamicFilterPhysicalExpr>() {
+            Some(filter) => Ok(Transformed::yes(filter.current()?)),
+            None => Ok(Transformed::no(expr)),
+        }
+    })
+    .data()
+}
+
+/// Returns the sum of the generations of every [`DynamicFilterPhysicalExpr`]
+/// in `expr`, which changes whenever one of their predicates is updated
+pub fn dynamic_filter_generation(expr: &Arc<dyn PhysicalExpr>) -> Result<u64> {
+    let mut generation = 0;
+    expr.apply(|expr| {
+        if let Some(filter) = expr.as_any().downcast_ref::<DynamicFilterPhysicalExpr>() {
+            generation += filter.generation();
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    Ok(generation)
+}
+
+/// Returns true if `expr` contains an incremental [`DynamicFilterPhysicalExpr`],
+/// see [`DynamicFilterPhysicalExpr::is_incremental`]
+pub fn has_incremental_dynamic_filter(expr: &Arc<dyn PhysicalExpr>) -> Result<bool> {
+    expr.exists(|expr| {
+        Ok(expr
+            .as_any()
+            .downcast_ref::<DynamicFilterPhysicalExpr>()
+            .is_some_and(|filter| filter.is_incremental()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{binary, col, lit, Column};
+    use arrow::array::{AsArray, BooleanArray, Int32Array};
+    use arrow::datatypes::Field;
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::Operator;
+
+    #[test]
+    fn dynamic_filter_update_is_shared() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let a = col("a", &schema)?;
+        let filter = Arc::new(DynamicFilterPhysicalExpr::new(
+            vec![Arc::clone(&a)],
+            lit(true),
+        ));
+        // The filter pushed below a projection that moved `a` to index 1
+        let remapped = Arc::clone(&filter)
+            .with_new_children(vec![Arc::new(Column::new("a", 1)) as _])?;
+        assert_eq!(remapped.to_string(), "DynamicFilterPhysicalExpr [ true ]");
+
+        filter.update(binary(a, Operator::Gt, lit(1), &schema)?)?;
+        assert_eq!(filter.to_string(), "DynamicFilterPhysicalExpr [ a@0 > 1 ]");
+        assert_eq!(
+            remapped.to_string(),
+            "DynamicFilterPhysicalExpr [ a@1 > 1 ]"
+        );
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(Int32Array::from(vec![3, 1, 2])),
+            ],
+        )?;
+        let result = remapped.evaluate(&batch)?.into_array(batch.num_rows())?;
+        assert_eq!(
+            result.as_boolean(),
+            &BooleanArray::from(vec![true, false, true])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_filter_generation_is_shared() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let a = col("a", &schema)?;
+        let filter = Arc::new(
+            DynamicFilterPhysicalExpr::new(vec![Arc::clone(&a)], lit(true))
+                .with_incremental(true),
+        );
+        let expr = binary(a, Operator::Lt, lit(10), &schema)?;
+        let expr = binary(expr, Operator::And, Arc::clone(&filter) as _, &schema)?;
+        let remapped = Arc::clone(&filter)
+            .with_new_children(vec![Arc::new(Column::new("a", 1)) as _])?;
+        assert_eq!(dynamic_filter_generation(&expr)?, 0);
+        assert!(has_incremental_dynamic_filter(&expr)?);
+        assert!(has_incremental_dynamic_filter(&remapped)?);
+
+        filter.update(lit(ScalarValue::Boolean(Some(false))))?;
+        assert_eq!(dynamic_filter_generation(&expr)?, 1);
+        assert_eq!(dynamic_filter_generation(&remapped)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn snapshot_dynamic_filter() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let a = col("a", &schema)?;
+        let filter = Arc::new(DynamicFilterPhysicalExpr::new(
+            vec![Arc::clone(&a)],
+            lit(true),
+        ));
+        let expr = binary(Arc::clone(&a), Operator::Lt, lit(10), &schema)?;
+        let expr = binary(expr, Operator::And, Arc::clone(&filter) as _, &schema)?;
+
+        filter.update(lit(ScalarValue::Boolean(Some(false))))?;
+        let snapshot = snapshot_physical_expr(expr)?;
+        assert_eq!(snapshot.to_string(), "a@0 < 10 AND false");
+        Ok(())
+    }
+}
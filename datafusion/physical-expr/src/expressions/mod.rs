@@ -22,6 +22,7 @@ mod binary;
 mod case;
 mod cast;
 mod column;
+mod dynamic_filters;
 mod in_list;
 mod is_not_null;
 mod is_null;
@@ -42,6 +43,10 @@ pub use case::{case, CaseExpr};
 pub use cast::{cast, CastExpr};
 pub use column::{col, with_new_schema, Column};
 pub use datafusion_expr::utils::format_state_name;
+pub use dynamic_filters::{
+    dynamic_filter_generation, has_incremental_dynamic_filter, snapshot_physical_expr,
+    DynamicFilterPhysicalExpr,
+};
 pub use in_list::{in_list, InListExpr};
 pub use is_not_null::{is_not_null, IsNotNullExpr};
 pub use is_null::{is_null, IsNullExpr};
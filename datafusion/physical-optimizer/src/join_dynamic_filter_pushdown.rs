@@ -0,0 +1,293 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`JoinDynamicFilterPushdown`] pushes dynamic filters on the join keys of
+//! hash joins down to the scans of their probe side.
+
+use std::sync::Arc;
+
+use crate::PhysicalOptimizerRule;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{JoinType, Result};
+use datafusion_physical_expr::expressions::{lit, Column, DynamicFilterPhysicalExpr};
+use datafusion_physical_expr::utils::collect_columns;
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_plan::coalesce_batches::CoalesceBatchesExec;
+use datafusion_physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion_physical_plan::filter::FilterExec;
+use datafusion_physical_plan::joins::{HashJoinExec, PartitionMode};
+use datafusion_physical_plan::projection::ProjectionExec;
+use datafusion_physical_plan::repartition::RepartitionExec;
+use datafusion_physical_plan::sorts::sort::SortExec;
+use datafusion_physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use datafusion_physical_plan::source::DataSourceExec;
+use datafusion_physical_plan::ExecutionPlan;
+
+/// Pushes a dynamic filter on the probe side join keys of [`HashJoinExec`]s
+/// down to the scans of their probe side.
+///
+/// Once a hash join has collected its build side, it knows which join key
+/// values the probe side rows may match, and updates the filter accordingly
+/// (see [`DynamicFilterPhysicalExpr`]). Scans supporting dynamic filters, such
+/// as parquet scans, then use the filter to skip row groups, pages and rows
+/// that can not match any build side row. This is especially useful for
+/// selective joins of a large fact table with small dimension tables.
+///
+/// The filter is only pushed down for join types that drop unmatched probe
+/// side rows, and through operators that neither change nor limit the rows of
+/// their input, such as repartitions, filters, projections and the inputs of
+/// other inner hash joins. If the filter can not reach any scan supporting it,
+/// the plan is left unchanged.
+#[derive(Default, Debug)]
+pub struct JoinDynamicFilterPushdown {}
+
+impl JoinDynamicFilterPushdown {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for JoinDynamicFilterPushdown {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_join_dynamic_filter_pushdown {
+            return Ok(plan);
+        }
+        plan.transform_up(|plan| {
+            let Some(hash_join) = plan.as_any().downcast_ref::<HashJoinExec>() else {
+                return Ok(Transformed::no(plan));
+            };
+            if hash_join.mode == PartitionMode::Auto
+                || hash_join.null_equals_null
+                || hash_join.dynamic_filter().is_some()
+                || !drops_unmatched_probe_rows(hash_join.join_type)
+            {
+                return Ok(Transformed::no(plan));
+            }
+
+            let on_right = hash_join
+                .on()
+                .iter()
+                .map(|(_, right)| Arc::clone(right))
+                .collect();
+            let dynamic_filter =
+                Arc::new(DynamicFilterPhysicalExpr::new(on_right, lit(true)));
+            let filter = Arc::clone(&dynamic_filter) as Arc<dyn PhysicalExpr>;
+            let Some(right) = push_down_dynamic_filter(hash_join.right(), &filter)?
+            else {
+                return Ok(Transformed::no(plan));
+            };
+
+            let hash_join = HashJoinExec::try_new(
+                Arc::clone(hash_join.left()),
+                right,
+                hash_join.on().to_vec(),
+                hash_join.filter().cloned(),
+                hash_join.join_type(),
+                hash_join.projection.clone(),
+                hash_join.mode,
+                hash_join.null_equals_null,
+            )?
+            .with_dynamic_filter(Some(dynamic_filter));
+            Ok(Transformed::yes(Arc::new(hash_join) as _))
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "JoinDynamicFilterPushdown"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Whether the probe side rows that do not match any build side row are
+/// dropped by a hash join of type `join_type`
+fn drops_unmatched_probe_rows(join_type: JoinType) -> bool {
+    matches!(
+        join_type,
+        JoinType::Inner
+            | JoinType::Left
+            | JoinType::LeftSemi
+            | JoinType::LeftAnti
+            | JoinType::LeftMark
+            | JoinType::RightSemi
+    )
+}
+
+/// Push `filter`, written in terms of the output columns of `plan`, down to the
+/// scans of `plan`.
+///
+/// Returns the rewritten plan, or `None` if the filter could not be pushed
+/// down to any scan.
+pub(crate) fn push_down_dynamic_filter(
+    plan: &Arc<dyn ExecutionPlan>,
+    filter: &Arc<dyn PhysicalExpr>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    let any = plan.as_any();
+    if let Some(data_source) = any.downcast_ref::<DataSourceExec>() {
+        if data_source.fetch().is_some() {
+            return Ok(None);
+        }
+        return Ok(data_source
+            .try_pushdown_dynamic_filter(filter)
+            .map(|data_source| Arc::new(data_source) as _));
+    }
+    if let Some(hash_join) = any.downcast_ref::<HashJoinExec>() {
+        return push_down_dynamic_filter_through_hash_join(hash_join, filter);
+    }
+
+    // The rows of these operators are the rows of their input, unless they
+    // have a fetch, in which case filtering their input changes which rows
+    // they output
+    let child_filter = if any.is::<RepartitionExec>()
+        || any.is::<CoalesceBatchesExec>()
+        || any.is::<CoalescePartitionsExec>()
+        || any.is::<SortExec>()
+        || any.is::<SortPreservingMergeExec>()
+    {
+        plan.fetch().is_none().then(|| Arc::clone(filter))
+    } else if let Some(filter_exec) = any.downcast_ref::<FilterExec>() {
+        match filter_exec.projection() {
+            Some(projection) => remap_columns(filter, |column| {
+                Some(Column::new(column.name(), projection[column.index()]))
+            })?,
+            None => Some(Arc::clone(filter)),
+        }
+    } else if let Some(projection) = any.downcast_ref::<ProjectionExec>() {
+        remap_columns(filter, |column| {
+            projection.expr()[column.index()]
+                .0
+                .as_any()
+                .downcast_ref::<Column>()
+                .cloned()
+        })?
+    } else {
+        None
+    };
+
+    let Some(child_filter) = child_filter else {
+        return Ok(None);
+    };
+    let child = Arc::clone(plan.children()[0]);
+    match push_down_dynamic_filter(&child, &child_filter)? {
+        Some(child) => Ok(Some(Arc::clone(plan).with_new_children(vec![child])?)),
+        None => Ok(None),
+    }
+}
+
+/// Push `filter` through an inner `hash_join` to the input that all the
+/// columns of the filter come from
+fn push_down_dynamic_filter_through_hash_join(
+    hash_join: &HashJoinExec,
+    filter: &Arc<dyn PhysicalExpr>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    if hash_join.join_type != JoinType::Inner {
+        return Ok(None);
+    }
+
+    let left_columns = hash_join.left().schema().fields().len();
+    // Index of an output column in the schema of the join, which is the
+    // schema of its left input followed by the schema of its right input
+    let join_index = |index: usize| {
+        hash_join
+            .projection
+            .as_ref()
+            .map_or(index, |projection| projection[index])
+    };
+    let columns = collect_columns(filter);
+    let (left, right) = if columns
+        .iter()
+        .all(|column| join_index(column.index()) < left_columns)
+    {
+        let Some(left_filter) = remap_columns(filter, |column| {
+            Some(Column::new(column.name(), join_index(column.index())))
+        })?
+        else {
+            return Ok(None);
+        };
+        let Some(left) = push_down_dynamic_filter(hash_join.left(), &left_filter)? else {
+            return Ok(None);
+        };
+        (left, Arc::clone(hash_join.right()))
+    } else if columns
+        .iter()
+        .all(|column| join_index(column.index()) >= left_columns)
+    {
+        let Some(right_filter) = remap_columns(filter, |column| {
+            Some(Column::new(
+                column.name(),
+                join_index(column.index()) - left_columns,
+            ))
+        })?
+        else {
+            return Ok(None);
+        };
+        let Some(right) = push_down_dynamic_filter(hash_join.right(), &right_filter)?
+        else {
+            return Ok(None);
+        };
+        (Arc::clone(hash_join.left()), right)
+    } else {
+        return Ok(None);
+    };
+
+    let hash_join = HashJoinExec::try_new(
+        left,
+        right,
+        hash_join.on().to_vec(),
+        hash_join.filter().cloned(),
+        hash_join.join_type(),
+        hash_join.projection.clone(),
+        hash_join.mode,
+        hash_join.null_equals_null,
+    )?
+    .with_dynamic_filter(hash_join.dynamic_filter().cloned());
+    Ok(Some(Arc::new(hash_join)))
+}
+
+/// Replace the columns of `filter` by the result of `remap`, or return `None`
+/// if `remap` returns `None` for any of them
+fn remap_columns(
+    filter: &Arc<dyn PhysicalExpr>,
+    remap: impl Fn(&Column) -> Option<Column>,
+) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+    let mut remapped_all = true;
+    let filter = Arc::clone(filter)
+        .transform_up(|expr| {
+            let Some(column) = expr.as_any().downcast_ref::<Column>() else {
+                return Ok(Transformed::no(expr));
+            };
+            match remap(column) {
+                Some(column) => Ok(Transformed::yes(Arc::new(column) as _)),
+                None => {
+                    remapped_all = false;
+                    Ok(Transformed::no(expr))
+                }
+            }
+        })
+        .data()?;
+    Ok(remapped_all.then_some(filter))
+}
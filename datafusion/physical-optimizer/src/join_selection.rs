@@ -33,13 +33,14 @@ use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
 use datafusion_common::{internal_err, JoinSide, JoinType};
 use datafusion_expr_common::sort_properties::SortProperties;
 use datafusion_physical_expr::expressions::Column;
-use datafusion_physical_expr::LexOrdering;
+use datafusion_physical_expr::{LexOrdering, PhysicalExpr};
 use datafusion_physical_plan::execution_plan::EmissionType;
 use datafusion_physical_plan::joins::utils::{ColumnIndex, JoinFilter};
 use datafusion_physical_plan::joins::{
-    CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode,
+    CrossJoinExec, HashJoinExec, IEJoinExec, NestedLoopJoinExec, PartitionMode,
     StreamJoinPartitionMode, SymmetricHashJoinExec,
 };
+use datafusion_physical_plan::projection::ProjectionExec;
 use datafusion_physical_plan::{ExecutionPlan, ExecutionPlanProperties};
 
 /// The [`JoinSelection`] rule tries to modify a given plan so that it can
@@ -182,7 +183,7 @@ impl PhysicalOptimizerRule for JoinSelection {
         let config = &config.optimizer;
         let collect_threshold_byte_size = config.hash_join_single_partition_threshold;
         let collect_threshold_num_rows = config.hash_join_single_partition_threshold_rows;
-        new_plan
+        let new_plan = new_plan
             .transform_up(|plan| {
                 statistical_join_selection_subrule(
                     plan,
@@ -190,7 +191,15 @@ impl PhysicalOptimizerRule for JoinSelection {
                     collect_threshold_num_rows,
                 )
             })
-            .data()
+            .data()?;
+        // Finally, nested loop joins whose filter contains two range conditions
+        // between the join inputs are replaced by inequality joins, now that
+        // their build side has been decided.
+        if config.enable_ie_join {
+            new_plan.transform_up(ie_join_selection_subrule).data()
+        } else {
+            Ok(new_plan)
+        }
     }
 
     fn name(&self) -> &str {
@@ -365,6 +374,51 @@ fn statistical_join_selection_subrule(
     })
 }
 
+/// This subrule replaces a [`NestedLoopJoinExec`] with an [`IEJoinExec`] if its
+/// filter contains two range conditions between the join inputs. Any projection
+/// of the nested loop join is kept as a [`ProjectionExec`] on top of the
+/// inequality join.
+fn ie_join_selection_subrule(
+    plan: Arc<dyn ExecutionPlan>,
+) -> Result<Transformed<Arc<dyn ExecutionPlan>>> {
+    let Some(nl_join) = plan.as_any().downcast_ref::<NestedLoopJoinExec>() else {
+        return Ok(Transformed::no(plan));
+    };
+    let Some(filter) = nl_join.filter() else {
+        return Ok(Transformed::no(plan));
+    };
+    let left = nl_join.left();
+    let right = nl_join.right();
+    if matches!(nl_join.join_type(), JoinType::LeftMark | JoinType::LeftAsof)
+        || IEJoinExec::range_conditions(filter, &left.schema(), &right.schema())?
+            .is_none()
+    {
+        return Ok(Transformed::no(plan));
+    }
+
+    let ie_join: Arc<dyn ExecutionPlan> = Arc::new(IEJoinExec::try_new(
+        Arc::clone(left),
+        Arc::clone(right),
+        filter.clone(),
+        nl_join.join_type(),
+    )?);
+    let Some(projection) = nl_join.projection() else {
+        return Ok(Transformed::yes(ie_join));
+    };
+    let schema = ie_join.schema();
+    let exprs = projection
+        .iter()
+        .map(|index| {
+            let name = schema.field(*index).name().to_string();
+            let column: Arc<dyn PhysicalExpr> = Arc::new(Column::new(&name, *index));
+            (column, name)
+        })
+        .collect();
+    Ok(Transformed::yes(Arc::new(ProjectionExec::try_new(
+        exprs, ie_join,
+    )?)))
+}
+
 /// Pipeline-fixing join selection subrule.
 pub type PipelineFixerSubrule =
     dyn Fn(Arc<dyn ExecutionPlan>, &ConfigOptions) -> Result<Arc<dyn ExecutionPlan>>;
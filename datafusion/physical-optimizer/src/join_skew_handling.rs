@@ -0,0 +1,206 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`HashJoinSkewHandling`] spreads the heavy hitters of partitioned hash
+//! joins over all their partitions.
+
+use std::sync::Arc;
+
+use crate::PhysicalOptimizerRule;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{JoinType, Result};
+use datafusion_physical_expr::{physical_exprs_equal, PhysicalExprRef};
+use datafusion_physical_plan::coalesce_batches::CoalesceBatchesExec;
+use datafusion_physical_plan::joins::{HashJoinExec, PartitionMode};
+use datafusion_physical_plan::repartition::{JoinSkew, JoinSkewSide, RepartitionExec};
+use datafusion_physical_plan::union::InterleaveExec;
+use datafusion_physical_plan::{Distribution, ExecutionPlan, Partitioning};
+
+/// Makes partitioned [`HashJoinExec`]s spread the rows of their most frequent
+/// probe side join keys (heavy hitters) over all their partitions.
+///
+/// A partitioned hash join sends all the rows of a join key to the same
+/// partition, so a join key found in a large fraction of the probe side rows
+/// makes a single partition process them all. With this rule, the
+/// [`RepartitionExec`]s of both sides of the join sample the probe side to
+/// find its heavy hitters, then send the build side rows of heavy hitters to
+/// all the partitions and the probe side rows of heavy hitters round robin to
+/// the partitions (see [`RepartitionExec::with_join_skew`]).
+///
+/// The rule only applies to joins whose inputs are both hash partitioned on
+/// their join keys by [`RepartitionExec`]s, and which do not output unmatched
+/// build side rows, as they would be output by all the partitions. As the
+/// output of the join is not partitioned by its join keys anymore, the rule
+/// does not apply to joins whose parent relies on their output partitioning.
+///
+/// The rule is enabled by `datafusion.execution.enable_hash_join_skew_handling`.
+#[derive(Default, Debug)]
+pub struct HashJoinSkewHandling {}
+
+impl HashJoinSkewHandling {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for HashJoinSkewHandling {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.execution.enable_hash_join_skew_handling {
+            return Ok(plan);
+        }
+        handle_join_skew(plan, false)
+    }
+
+    fn name(&self) -> &str {
+        "HashJoinSkewHandling"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Handles the skew of the hash joins of `plan`, whose output partitioning
+/// must not change if `preserve_partitioning` is set
+#[cfg_attr(feature = "recursive_protection", recursive::recursive)]
+fn handle_join_skew(
+    plan: Arc<dyn ExecutionPlan>,
+    preserve_partitioning: bool,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let is_exchange = plan.as_any().is::<RepartitionExec>();
+    let is_interleave = plan.as_any().is::<InterleaveExec>();
+    let mut changed = false;
+    let children = plan
+        .children()
+        .into_iter()
+        .zip(plan.required_input_distribution())
+        .map(|(child, distribution)| {
+            let preserve_child_partitioning = match distribution {
+                Distribution::HashPartitioned(_) => true,
+                Distribution::SinglePartition => false,
+                // Operators passing their input partitioning through
+                Distribution::UnspecifiedDistribution => {
+                    is_interleave || (preserve_partitioning && !is_exchange)
+                }
+            };
+            let new_child =
+                handle_join_skew(Arc::clone(child), preserve_child_partitioning)?;
+            changed |= !Arc::ptr_eq(child, &new_child);
+            Ok(new_child)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let plan = if changed {
+        plan.with_new_children(children)?
+    } else {
+        plan
+    };
+
+    if preserve_partitioning {
+        return Ok(plan);
+    }
+    let Some(hash_join) = plan.as_any().downcast_ref::<HashJoinExec>() else {
+        return Ok(plan);
+    };
+    if hash_join.mode != PartitionMode::Partitioned
+        || hash_join.null_equals_null
+        || hash_join.join_skew().is_some()
+        || !matches!(
+            hash_join.join_type,
+            JoinType::Inner | JoinType::Right | JoinType::RightSemi | JoinType::RightAnti
+        )
+    {
+        return Ok(plan);
+    }
+
+    let (on_left, on_right): (Vec<_>, Vec<_>) = hash_join
+        .on()
+        .iter()
+        .map(|(left, right)| (Arc::clone(left), Arc::clone(right)))
+        .unzip();
+    let join_skew = Arc::new(JoinSkew::new());
+    let (Some(left), Some(right)) = (
+        with_join_skew(hash_join.left(), &on_left, JoinSkewSide::Build, &join_skew)?,
+        with_join_skew(
+            hash_join.right(),
+            &on_right,
+            JoinSkewSide::Probe,
+            &join_skew,
+        )?,
+    ) else {
+        return Ok(plan);
+    };
+
+    let hash_join = HashJoinExec::try_new(
+        left,
+        right,
+        hash_join.on().to_vec(),
+        hash_join.filter().cloned(),
+        hash_join.join_type(),
+        hash_join.projection.clone(),
+        hash_join.mode,
+        hash_join.null_equals_null,
+    )?
+    .with_dynamic_filter(hash_join.dynamic_filter().cloned())
+    .with_join_skew(Some(join_skew));
+    Ok(Arc::new(hash_join))
+}
+
+/// Returns `input` partitioning the `side` of a skew-aware hash join, if
+/// `input` is a [`RepartitionExec`] partitioning by hash on the join keys `on`,
+/// optionally under a [`CoalesceBatchesExec`]
+fn with_join_skew(
+    input: &Arc<dyn ExecutionPlan>,
+    on: &[PhysicalExprRef],
+    side: JoinSkewSide,
+    join_skew: &Arc<JoinSkew>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    if let Some(coalesce) = input.as_any().downcast_ref::<CoalesceBatchesExec>() {
+        let Some(repartition) = with_join_skew(coalesce.input(), on, side, join_skew)?
+        else {
+            return Ok(None);
+        };
+        return Arc::clone(input)
+            .with_new_children(vec![repartition])
+            .map(Some);
+    }
+
+    let Some(repartition) = input.as_any().downcast_ref::<RepartitionExec>() else {
+        return Ok(None);
+    };
+    let Partitioning::Hash(exprs, _) = repartition.partitioning() else {
+        return Ok(None);
+    };
+    if repartition.preserve_order()
+        || repartition.join_skew().is_some()
+        || !physical_exprs_equal(exprs, on)
+    {
+        return Ok(None);
+    }
+
+    let repartition = RepartitionExec::try_new(
+        Arc::clone(repartition.input()),
+        repartition.partitioning().clone(),
+    )?
+    .with_join_skew(side, Arc::clone(join_skew));
+    Ok(Some(Arc::new(repartition)))
+}
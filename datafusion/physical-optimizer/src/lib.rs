@@ -23,7 +23,9 @@ pub mod coalesce_batches;
 pub mod combine_partial_final_agg;
 pub mod enforce_distribution;
 pub mod enforce_sorting;
+pub mod join_dynamic_filter_pushdown;
 pub mod join_selection;
+pub mod join_skew_handling;
 pub mod limit_pushdown;
 pub mod limited_distinct_aggregation;
 pub mod optimizer;
@@ -32,6 +34,7 @@ pub mod projection_pushdown;
 pub mod pruning;
 pub mod sanity_checker;
 pub mod topk_aggregation;
+pub mod topk_dynamic_filter_pushdown;
 pub mod update_aggr_exprs;
 pub mod utils;
 
@@ -297,9 +297,13 @@ pub(crate) fn pushdown_limits(
 }
 
 /// Transforms the [`ExecutionPlan`] into a [`LimitExec`] if it is a
-/// [`GlobalLimitExec`] or a [`LocalLimitExec`].
+/// [`GlobalLimitExec`] or a [`LocalLimitExec`]. A [`GlobalLimitExec`] whose
+/// skip or fetch is only known at execution time is not extracted.
 fn extract_limit(plan: &Arc<dyn ExecutionPlan>) -> Option<LimitExec> {
     if let Some(global_limit) = plan.as_any().downcast_ref::<GlobalLimitExec>() {
+        if global_limit.has_limit_exprs() {
+            return None;
+        }
         Some(LimitExec::Global(GlobalLimitExec::new(
             Arc::clone(global_limit.input()),
             global_limit.skip(),
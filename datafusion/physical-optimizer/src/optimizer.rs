@@ -25,13 +25,16 @@ use crate::coalesce_batches::CoalesceBatches;
 use crate::combine_partial_final_agg::CombinePartialFinalAggregate;
 use crate::enforce_distribution::EnforceDistribution;
 use crate::enforce_sorting::EnforceSorting;
+use crate::join_dynamic_filter_pushdown::JoinDynamicFilterPushdown;
 use crate::join_selection::JoinSelection;
+use crate::join_skew_handling::HashJoinSkewHandling;
 use crate::limit_pushdown::LimitPushdown;
 use crate::limited_distinct_aggregation::LimitedDistinctAggregation;
 use crate::output_requirements::OutputRequirements;
 use crate::projection_pushdown::ProjectionPushdown;
 use crate::sanity_checker::SanityCheckPlan;
 use crate::topk_aggregation::TopKAggregation;
+use crate::topk_dynamic_filter_pushdown::TopKDynamicFilterPushdown;
 use crate::update_aggr_exprs::OptimizeAggregateOrder;
 
 use datafusion_common::config::ConfigOptions;
@@ -132,6 +135,20 @@ impl PhysicalOptimizer {
             // replacing operators with fetching variants, or adding limits
             // past operators that support limit pushdown.
             Arc::new(LimitPushdown::new()),
+            // The JoinDynamicFilterPushdown rule pushes dynamic filters of hash
+            // joins down to the scans of their probe side. It should run after
+            // all the rules that may change the join mode, move the scans or
+            // push limits into them.
+            Arc::new(JoinDynamicFilterPushdown::new()),
+            // The TopKDynamicFilterPushdown rule pushes dynamic filters of
+            // sorts with a fetch down to the scans of their input. Like the
+            // rule above, it should run after the limits are pushed down.
+            Arc::new(TopKDynamicFilterPushdown::new()),
+            // The HashJoinSkewHandling rule makes partitioned hash joins spread
+            // their heavy hitters over all their partitions. It changes the
+            // output partitioning of the joins, so it should run after all the
+            // rules relying on it.
+            Arc::new(HashJoinSkewHandling::new()),
             // The SanityCheckPlan rule checks whether the order and
             // distribution requirements of each node in the plan
             // is satisfied. It will also reject non-runnable query
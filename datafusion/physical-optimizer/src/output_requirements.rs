@@ -34,10 +34,11 @@ use datafusion_physical_expr::{Distribution, LexRequirement, PhysicalSortRequire
 use datafusion_physical_plan::projection::{
     make_with_child, update_expr, ProjectionExec,
 };
+use datafusion_physical_plan::repartition::RepartitionExec;
 use datafusion_physical_plan::sorts::sort::SortExec;
 use datafusion_physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use datafusion_physical_plan::{
-    DisplayAs, DisplayFormatType, ExecutionPlan, SendableRecordBatchStream,
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
 };
 use datafusion_physical_plan::{ExecutionPlanProperties, PlanProperties};
 
@@ -281,6 +282,10 @@ impl PhysicalOptimizerRule for OutputRequirements {
 /// global requirements are not lost during optimization.
 fn require_top_ordering(plan: Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
     let (new_plan, is_changed) = require_top_ordering_helper(plan)?;
+    if is_changed {
+        return Ok(new_plan);
+    }
+    let (new_plan, is_changed) = require_top_distribution_helper(new_plan)?;
     if is_changed {
         Ok(new_plan)
     } else {
@@ -308,7 +313,14 @@ fn require_top_ordering_helper(
         // In case of constant columns, output ordering of SortExec would give an empty set.
         // Therefore; we check the sort expression field of the SortExec to assign the requirements.
         let req_ordering = sort_exec.expr();
-        let req_dist = sort_exec.required_input_distribution()[0].clone();
+        // A sort within the partitions of a hash partitioned input (e.g. for
+        // `DISTRIBUTE BY ... SORT BY`) also keeps the partitioning
+        let req_dist = match sort_exec.input().output_partitioning() {
+            Partitioning::Hash(exprs, _) if sort_exec.preserve_partitioning() => {
+                Distribution::HashPartitioned(exprs.clone())
+            }
+            _ => sort_exec.required_input_distribution()[0].clone(),
+        };
         let reqs = LexRequirement::from(req_ordering.clone());
         Ok((
             Arc::new(OutputRequirementExec::new(plan, Some(reqs), req_dist)) as _,
@@ -340,4 +352,31 @@ fn require_top_ordering_helper(
     }
 }
 
+/// Helper function that adds an ancillary `OutputRequirementExec` requiring the
+/// hash partitioning of a top-level [`RepartitionExec`] (e.g. planned for
+/// `DISTRIBUTE BY`), which would otherwise be removed as no operator requires
+/// it. First entry in the tuple is resulting plan, second entry indicates
+/// whether any `OutputRequirementExec` is added to the plan.
+fn require_top_distribution_helper(
+    plan: Arc<dyn ExecutionPlan>,
+) -> Result<(Arc<dyn ExecutionPlan>, bool)> {
+    if let Some(repartition) = plan.as_any().downcast_ref::<RepartitionExec>() {
+        if let Partitioning::Hash(exprs, _) = repartition.partitioning() {
+            let req_dist = Distribution::HashPartitioned(exprs.clone());
+            return Ok((
+                Arc::new(OutputRequirementExec::new(plan, None, req_dist)) as _,
+                true,
+            ));
+        }
+        Ok((plan, false))
+    } else if plan.as_any().is::<ProjectionExec>() {
+        // Projections keep the partitioning of their input
+        let (new_child, is_changed) =
+            require_top_distribution_helper(Arc::clone(plan.children()[0]))?;
+        Ok((plan.with_new_children(vec![new_child])?, is_changed))
+    } else {
+        Ok((plan, false))
+    }
+}
+
 // See tests in datafusion/core/tests/physical_optimizer
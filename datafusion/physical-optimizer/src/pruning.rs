@@ -25,7 +25,7 @@ use std::sync::Arc;
 use arrow::array::AsArray;
 use arrow::{
     array::{new_null_array, ArrayRef, BooleanArray},
-    datatypes::{DataType, Field, Schema, SchemaRef},
+    datatypes::{DataType, Field, FieldRef, Schema, SchemaRef},
     record_batch::{RecordBatch, RecordBatchOptions},
 };
 use log::trace;
@@ -39,8 +39,13 @@ use datafusion_common::{
 };
 use datafusion_common::{Column, DFSchema};
 use datafusion_expr_common::operator::Operator;
-use datafusion_physical_expr::utils::{collect_columns, Guarantee, LiteralGuarantee};
-use datafusion_physical_expr::{expressions as phys_expr, PhysicalExprRef};
+use datafusion_expr_common::signature::Volatility;
+use datafusion_physical_expr::utils::{
+    collect_columns, split_conjunction, Guarantee, LiteralGuarantee,
+};
+use datafusion_physical_expr::{
+    expressions as phys_expr, PhysicalExprRef, ScalarFunctionExpr,
+};
 use datafusion_physical_plan::{ColumnarValue, PhysicalExpr};
 
 /// A source of runtime statistical information to [`PruningPredicate`]s.
@@ -53,6 +58,8 @@ use datafusion_physical_plan::{ColumnarValue, PhysicalExpr};
 ///
 /// 3. Whether the values in a column are contained in a set of literals
 ///
+/// 4. The distinct values of columns
+///
 /// # Vectorized Interface
 ///
 /// Information for containers / files are returned as Arrow [`ArrayRef`], so
@@ -141,6 +148,32 @@ pub trait PruningStatistics {
         column: &Column,
         values: &HashSet<ScalarValue>,
     ) -> Option<BooleanArray>;
+
+    /// Return the set of distinct values of the named column in each
+    /// container, if known.
+    ///
+    /// For example, the dictionary of a Parquet column chunk whose data pages
+    /// are all dictionary encoded holds all the distinct values of the column
+    /// in its Row Group.
+    ///
+    /// The returned `Vec` has one element per container, which is either
+    /// * `Some(array)`: an array holding (at least) all the non null values
+    ///   of `column` in the container. It may hold values that are not in the
+    ///   container, but never lacks one that is.
+    /// * `None`: the distinct values of `column` are not known.
+    ///
+    /// Predicates on a single column, such as `a LIKE 'foo%'` or `a <> 'x'`,
+    /// are evaluated on these values to prove that no row of a container can
+    /// match them. As containers may hold nulls, the predicates are evaluated
+    /// on a null value as well.
+    ///
+    /// If these statistics can not determine the distinct values of `column`
+    /// for any container, return `None` (the default).
+    ///
+    /// Note: the returned `Vec` must contain [`Self::num_containers`] elements
+    fn distinct_values(&self, _column: &Column) -> Option<Vec<Option<ArrayRef>>> {
+        None
+    }
 }
 
 /// Used to prove that arbitrary predicates (boolean expression) can not
@@ -471,6 +504,12 @@ pub struct PruningPredicate {
     ///
     /// See [`PruningPredicate::literal_guarantees`] for more details.
     literal_guarantees: Vec<LiteralGuarantee>,
+    /// Single column conjuncts of the predicate, evaluated on the distinct
+    /// values of their column to try and prove the predicate can not possibly
+    /// evaluate to `true`.
+    ///
+    /// See [`PruningStatistics::distinct_values`] for more details.
+    distinct_values_predicates: Vec<DistinctValuesPredicate>,
 }
 
 /// Rewrites predicates that [`PredicateRewriter`] can not handle, e.g. certain
@@ -539,6 +578,8 @@ impl PruningPredicate {
         );
 
         let literal_guarantees = LiteralGuarantee::analyze(&expr);
+        let distinct_values_predicates =
+            DistinctValuesPredicate::analyze(&expr, schema.as_ref());
 
         Ok(Self {
             schema,
@@ -546,6 +587,7 @@ impl PruningPredicate {
             required_columns,
             orig_expr: expr,
             literal_guarantees,
+            distinct_values_predicates,
         })
     }
 
@@ -598,6 +640,21 @@ impl PruningPredicate {
             }
         }
 
+        // Next, try to prove the predicate can't be true for the containers based
+        // on the distinct values of their columns
+        for predicate in &self.distinct_values_predicates {
+            if let Some(values) = statistics.distinct_values(&predicate.column) {
+                let results = values
+                    .iter()
+                    .map(|values| values.as_ref().and_then(|v| predicate.evaluate(v)))
+                    .collect::<BooleanArray>();
+                builder.combine_array(&results);
+                if builder.check_all_pruned() {
+                    return Ok(builder.build());
+                }
+            }
+        }
+
         // Next, try to prove the predicate can't be true for the containers based
         // on min/max values
 
@@ -642,6 +699,9 @@ impl PruningPredicate {
     /// literal_guarantees is empty.
     ///
     /// This can happen when a predicate is simplified to a constant `true`
+    ///
+    /// Note such a predicate may still prune containers using their distinct
+    /// values, see [`Self::distinct_value_columns`].
     pub fn always_true(&self) -> bool {
         is_always_true(&self.predicate_expr) && self.literal_guarantees.is_empty()
     }
@@ -669,6 +729,115 @@ impl PruningPredicate {
             .map(|s| s.to_string())
             .collect()
     }
+
+    /// Names of the columns whose distinct values may be passed to
+    /// [`PruningStatistics::distinct_values`] during pruning.
+    ///
+    /// Like [`Self::literal_columns`], this is useful to avoid fetching
+    /// statistics, such as dictionaries, that will not be used.
+    pub fn distinct_value_columns(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.distinct_values_predicates
+            .iter()
+            .map(|p| &p.column.name)
+            // avoid duplicates
+            .filter(|name| seen.insert(*name))
+            .map(|s| s.to_string())
+            .collect()
+    }
+}
+
+/// A conjunct of a [`PruningPredicate`] referencing a single column, which
+/// can be evaluated on the distinct values of that column (see
+/// [`PruningStatistics::distinct_values`]).
+#[derive(Debug, Clone)]
+struct DistinctValuesPredicate {
+    /// The column referenced by the conjunct
+    column: Column,
+    /// The field of the column
+    field: FieldRef,
+    /// The conjunct, rewritten to reference the column at index 0
+    expr: Arc<dyn PhysicalExpr>,
+}
+
+impl DistinctValuesPredicate {
+    /// Returns the conjuncts of `expr` that can be evaluated on the distinct
+    /// values of the single column of `schema` they reference
+    fn analyze(expr: &Arc<dyn PhysicalExpr>, schema: &Schema) -> Vec<Self> {
+        split_conjunction(expr)
+            .into_iter()
+            .filter(|conjunct| is_distinct_values_expr(conjunct))
+            .filter_map(|conjunct| {
+                let columns = collect_columns(conjunct);
+                if columns.len() != 1 {
+                    return None;
+                }
+                let column = columns.into_iter().next()?;
+                let field = schema.field_with_name(column.name()).ok()?;
+                let expr = Arc::clone(conjunct)
+                    .transform(|expr| {
+                        let Some(column) =
+                            expr.as_any().downcast_ref::<phys_expr::Column>()
+                        else {
+                            return Ok(Transformed::no(expr));
+                        };
+                        let column = phys_expr::Column::new(column.name(), 0);
+                        Ok(Transformed::yes(Arc::new(column) as _))
+                    })
+                    .data()
+                    .ok()?;
+                Some(Self {
+                    column: Column::from_name(column.name()),
+                    field: Arc::new(field.clone().with_nullable(true)),
+                    expr,
+                })
+            })
+            .collect()
+    }
+
+    /// Evaluates the conjunct on the distinct `values` of its column and on a
+    /// null value, returning `Some(false)` if it is `true` for none of them,
+    /// and `None` if it might be `true` or can not be evaluated
+    fn evaluate(&self, values: &ArrayRef) -> Option<bool> {
+        let data_type = self.field.data_type();
+        let values = if values.data_type() == data_type {
+            Arc::clone(values)
+        } else {
+            arrow::compute::cast(values, data_type).ok()?
+        };
+        let values =
+            arrow::compute::concat(&[&values, &new_null_array(data_type, 1)]).ok()?;
+        let schema = Arc::new(Schema::new(vec![Arc::clone(&self.field)]));
+        let batch = RecordBatch::try_new(schema, vec![values]).ok()?;
+        let result = match self.expr.evaluate(&batch) {
+            Ok(result) => result.into_array(batch.num_rows()).ok()?,
+            Err(e) => {
+                trace!("Error evaluating distinct values predicate: {e}");
+                return None;
+            }
+        };
+        let result = result.as_boolean_opt()?;
+        (result.true_count() == 0).then_some(false)
+    }
+}
+
+/// Returns true if `expr` only consists of expressions which can be evaluated
+/// on the distinct values of a column: the result of deterministic
+/// expressions on each value of a column only depends on that value
+fn is_distinct_values_expr(expr: &Arc<dyn PhysicalExpr>) -> bool {
+    let any = expr.as_any();
+    let supported = any.is::<phys_expr::Column>()
+        || any.is::<phys_expr::Literal>()
+        || any.is::<phys_expr::BinaryExpr>()
+        || any.is::<phys_expr::LikeExpr>()
+        || any.is::<phys_expr::InListExpr>()
+        || any.is::<phys_expr::NotExpr>()
+        || any.is::<phys_expr::CastExpr>()
+        || any.is::<phys_expr::TryCastExpr>()
+        || any
+            .downcast_ref::<ScalarFunctionExpr>()
+            .is_some_and(|f| f.fun().signature().volatility == Volatility::Immutable);
+    supported && expr.children().iter().all(|e| is_distinct_values_expr(e))
 }
 
 /// Builds the return `Vec` for [`PruningPredicate::prune`].
@@ -1841,6 +2010,8 @@ mod tests {
         /// (value, contained)
         /// If present, all BooleanArrays must be the same size as min/max
         contained: Vec<(HashSet<ScalarValue>, BooleanArray)>,
+        /// Optional distinct values (e.g. mimic a dictionary)
+        distinct_values: Option<Vec<Option<ArrayRef>>>,
     }
 
     impl ContainerStats {
@@ -2018,6 +2189,18 @@ mod tests {
             self
         }
 
+        /// Add distinct values. There must be the same number of elements as
+        /// there are containers
+        fn with_distinct_values(
+            mut self,
+            values: impl IntoIterator<Item = Option<ArrayRef>>,
+        ) -> Self {
+            let values: Vec<_> = values.into_iter().collect();
+            assert_eq!(values.len(), self.len());
+            self.distinct_values = Some(values);
+            self
+        }
+
         /// get any contained information for the specified values
         fn contained(&self, find_values: &HashSet<ScalarValue>) -> Option<BooleanArray> {
             // find the one with the matching values
@@ -2161,6 +2344,12 @@ mod tests {
                 .get(column)
                 .and_then(|container_stats| container_stats.contained(values))
         }
+
+        fn distinct_values(&self, column: &Column) -> Option<Vec<Option<ArrayRef>>> {
+            self.stats
+                .get(column)
+                .and_then(|container_stats| container_stats.distinct_values.clone())
+        }
     }
 
     /// Returns the specified min/max container values
@@ -4207,6 +4396,129 @@ mod tests {
         // TODO: add other negative test for other case and op
     }
 
+    /// Returns statistics for column `s1` of 4 containers, with unknown
+    /// min/max values and the following distinct values
+    fn utf8_distinct_values_statistics() -> TestStatistics {
+        let values = |values: &[&str]| -> Option<ArrayRef> {
+            Some(Arc::new(StringArray::from(values.to_vec())))
+        };
+        TestStatistics::new().with(
+            "s1",
+            ContainerStats::new_utf8(vec![None; 4], vec![None; 4]).with_distinct_values(
+                [
+                    // container 0 only contains "foo"
+                    values(&["foo"]),
+                    // container 1 contains "foo" and "bar"
+                    values(&["foo", "bar"]),
+                    // container 2 contains "barista" and "baz"
+                    values(&["barista", "baz"]),
+                    // container 3 has unknown values
+                    None,
+                ],
+            ),
+        )
+    }
+
+    #[test]
+    fn prune_with_distinct_values() {
+        let schema = Arc::new(Schema::new(vec![Field::new("s1", DataType::Utf8, true)]));
+        let statistics = utf8_distinct_values_statistics();
+
+        prune_with_expr(
+            col("s1").like(lit("%ar%")),
+            &schema,
+            &statistics,
+            &[false, true, true, true],
+        );
+        prune_with_expr(
+            col("s1").not_like(lit("ba%")),
+            &schema,
+            &statistics,
+            &[true, true, false, true],
+        );
+        prune_with_expr(
+            col("s1").not_eq(lit("foo")),
+            &schema,
+            &statistics,
+            &[false, true, true, true],
+        );
+        prune_with_expr(
+            col("s1").in_list(vec![lit("bar"), lit("baz")], false),
+            &schema,
+            &statistics,
+            &[false, true, true, true],
+        );
+        prune_with_expr(
+            col("s1").in_list(vec![lit("foo"), lit("bar")], true),
+            &schema,
+            &statistics,
+            &[false, false, true, true],
+        );
+        prune_with_expr(
+            col("s1").like(lit("%z")).not(),
+            &schema,
+            &statistics,
+            &[true, true, true, true],
+        );
+        prune_with_expr(
+            col("s1").like(lit("%z")).and(col("s1").not_eq(lit("foo"))),
+            &schema,
+            &statistics,
+            &[false, false, true, true],
+        );
+        // `s1 IS NULL` may be true for the nulls of any container
+        prune_with_expr(
+            col("s1").like(lit("%ar%")).or(col("s1").is_null()),
+            &schema,
+            &statistics,
+            &[true, true, true, true],
+        );
+    }
+
+    #[test]
+    fn prune_with_distinct_values_cast() {
+        // the distinct values are cast to the type of the column
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "s1",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            true,
+        )]));
+        let statistics = utf8_distinct_values_statistics();
+
+        prune_with_expr(
+            col("s1").like(lit("%ar%")),
+            &schema,
+            &statistics,
+            &[false, true, true, true],
+        );
+    }
+
+    #[test]
+    fn distinct_value_columns() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("s1", DataType::Utf8, true),
+            Field::new("s2", DataType::Utf8, true),
+            Field::new("i", DataType::Int32, true),
+        ]));
+        let expr = col("s1")
+            .like(lit("a%"))
+            .and(col("s1").not_eq(lit("b")))
+            .and(col("s1").eq(col("s2")))
+            .and(col("s2").is_null())
+            .and(col("i").eq(lit(1)).or(col("i").eq(lit(2))));
+        let expr = logical2physical(&expr, &schema);
+        let p = PruningPredicate::try_new(expr, schema).unwrap();
+        assert_eq!(p.distinct_value_columns(), vec!["s1", "i"]);
+
+        // predicates min/max values can not prune are always true, but can
+        // still be evaluated with the distinct values
+        let schema = Arc::new(Schema::new(vec![Field::new("s1", DataType::Utf8, true)]));
+        let expr = logical2physical(&col("s1").like(lit("%a")), &schema);
+        let p = PruningPredicate::try_new(expr, schema).unwrap();
+        assert!(p.always_true());
+        assert_eq!(p.distinct_value_columns(), vec!["s1"]);
+    }
+
     #[test]
     fn prune_with_contained_one_column() {
         let schema = Arc::new(Schema::new(vec![Field::new("s1", DataType::Utf8, true)]));
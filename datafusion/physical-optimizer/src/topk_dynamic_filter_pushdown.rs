@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`TopKDynamicFilterPushdown`] pushes dynamic filters on the sort keys of
+//! sorts with a fetch down to the scans of their input.
+
+use std::sync::Arc;
+
+use crate::join_dynamic_filter_pushdown::push_down_dynamic_filter;
+use crate::PhysicalOptimizerRule;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::Result;
+use datafusion_physical_expr::expressions::{lit, DynamicFilterPhysicalExpr};
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_plan::sorts::sort::SortExec;
+use datafusion_physical_plan::ExecutionPlan;
+
+/// Pushes a dynamic filter on the sort keys of [`SortExec`]s with a fetch
+/// (TopKs) down to the scans of their input.
+///
+/// Once a TopK has seen `fetch` rows, rows sorting after the last of its
+/// current top rows can not be part of its output, and it updates the filter
+/// accordingly (see [`DynamicFilterPhysicalExpr`]) each time it sees better
+/// rows. Scans supporting dynamic filters, such as parquet scans, then use
+/// the filter to skip row groups, pages and rows that can not change the
+/// result. This is especially useful for queries such as
+/// `ORDER BY ts DESC LIMIT 10` over large datasets.
+///
+/// The filter is shared by all partitions of the sort: the top rows of any
+/// partition bound the top rows of the whole input, which is all that matters
+/// to the merge with a fetch that combines the partitions.
+///
+/// The filter is pushed through the same operators as the filters of
+/// [`JoinDynamicFilterPushdown`]. If the filter can not reach any scan
+/// supporting it, the plan is left unchanged.
+///
+/// [`JoinDynamicFilterPushdown`]: crate::join_dynamic_filter_pushdown::JoinDynamicFilterPushdown
+#[derive(Default, Debug)]
+pub struct TopKDynamicFilterPushdown {}
+
+impl TopKDynamicFilterPushdown {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for TopKDynamicFilterPushdown {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_topk_dynamic_filter_pushdown {
+            return Ok(plan);
+        }
+        plan.transform_up(|plan| {
+            let Some(sort) = plan.as_any().downcast_ref::<SortExec>() else {
+                return Ok(Transformed::no(plan));
+            };
+            if sort.fetch().is_none() || sort.dynamic_filter().is_some() {
+                return Ok(Transformed::no(plan));
+            }
+
+            let sort_exprs = sort
+                .expr()
+                .iter()
+                .map(|sort_expr| Arc::clone(&sort_expr.expr))
+                .collect();
+            // The filter is tightened as the TopK finds better rows
+            let dynamic_filter = Arc::new(
+                DynamicFilterPhysicalExpr::new(sort_exprs, lit(true))
+                    .with_incremental(true),
+            );
+            let filter = Arc::clone(&dynamic_filter) as Arc<dyn PhysicalExpr>;
+            let Some(input) = push_down_dynamic_filter(sort.input(), &filter)? else {
+                return Ok(Transformed::no(plan));
+            };
+
+            let sort = SortExec::new(sort.expr().clone(), input)
+                .with_fetch(sort.fetch())
+                .with_preserve_partitioning(sort.preserve_partitioning())
+                .with_dynamic_filter(Some(dynamic_filter));
+            Ok(Transformed::yes(Arc::new(sort) as _))
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "TopKDynamicFilterPushdown"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
@@ -1354,11 +1354,11 @@ mod tests {
     use crate::RecordBatchStream;
 
     use arrow::array::{
-        DictionaryArray, Float32Array, Float64Array, Int32Array, StructArray,
-        UInt32Array, UInt64Array,
+        AsArray, DictionaryArray, Float32Array, Float64Array, Int32Array, Int64Array,
+        StructArray, UInt32Array, UInt64Array,
     };
     use arrow::compute::{concat_batches, SortOptions};
-    use arrow::datatypes::{DataType, Int32Type};
+    use arrow::datatypes::{DataType, Int32Type, Int64Type, UInt32Type};
     use datafusion_common::{
         assert_batches_eq, assert_batches_sorted_eq, internal_err, DataFusionError,
         ScalarValue,
@@ -2933,4 +2933,92 @@ mod tests {
         run_test_with_spill_pool_if_necessary(20_000, false).await?;
         Ok(())
     }
+
+    /// Aggregates 2000 groups of 5 rows with `SUM`, under memory pressure, with at most
+    /// `merge_degree` spills merged at once. Returns the spill count and the spilled rows
+    async fn run_test_spill_merge_degree(merge_degree: usize) -> Result<(usize, usize)> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::UInt32, false),
+            Field::new("b", DataType::Int64, false),
+        ]));
+        let batches = (0..50)
+            .map(|i| {
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![
+                        Arc::new(UInt32Array::from_iter_values(
+                            (i * 200..(i + 1) * 200).map(|v| v % 2000),
+                        )),
+                        Arc::new(Int64Array::from(vec![1; 200])),
+                    ],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let plan: Arc<dyn ExecutionPlan> =
+            MemorySourceConfig::try_new_exec(&[batches], Arc::clone(&schema), None)?;
+
+        let grouping_set = PhysicalGroupBy::new(
+            vec![(col("a", &schema)?, "a".to_string())],
+            vec![],
+            vec![vec![false]],
+        );
+        let aggregates: Vec<Arc<AggregateFunctionExpr>> = vec![Arc::new(
+            AggregateExprBuilder::new(sum_udaf(), vec![col("b", &schema)?])
+                .schema(Arc::clone(&schema))
+                .alias("SUM(b)")
+                .build()?,
+        )];
+        let single_aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Single,
+            grouping_set,
+            aggregates,
+            vec![None],
+            plan,
+            Arc::clone(&schema),
+        )?);
+
+        let mut session_config = SessionConfig::new().with_batch_size(100);
+        session_config
+            .options_mut()
+            .execution
+            .aggregate_spill_merge_degree = merge_degree;
+        let task_ctx = Arc::new(
+            TaskContext::default()
+                .with_session_config(session_config)
+                .with_runtime(
+                    RuntimeEnvBuilder::new()
+                        .with_memory_pool(Arc::new(FairSpillPool::new(20_000)))
+                        .build_arc()?,
+                ),
+        );
+
+        let result = collect(single_aggregate.execute(0, task_ctx)?).await?;
+        let batch = concat_batches(&result[0].schema(), &result)?;
+        assert_eq!(batch.num_rows(), 2000);
+        let groups = batch.column(0).as_primitive::<UInt32Type>();
+        let sums = batch.column(1).as_primitive::<Int64Type>();
+        // The groups are emitted in sorted order by the merge of the spills
+        assert!(groups.values().iter().copied().eq(0..2000));
+        assert!(sums.values().iter().all(|sum| *sum == 5));
+
+        let metrics = single_aggregate.metrics().unwrap();
+        Ok((
+            metrics.spill_count().unwrap(),
+            metrics.spilled_rows().unwrap(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_spill_merge_degree() -> Result<()> {
+        let (spill_count, spilled_rows) = run_test_spill_merge_degree(10).await?;
+        assert!(spill_count > 4, "{spill_count}");
+        // The spills are first merged 2 at a time, into new spills, which spills the
+        // rows more often
+        let (_, spilled_rows_merge_degree_2) = run_test_spill_merge_degree(2).await?;
+        assert!(
+            spilled_rows_merge_degree_2 > spilled_rows,
+            "{spilled_rows_merge_degree_2}"
+        );
+        Ok(())
+    }
 }
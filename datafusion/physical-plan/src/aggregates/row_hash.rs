@@ -27,19 +27,20 @@ use crate::aggregates::{
     create_schema, evaluate_group_by, evaluate_many, evaluate_optional, AggregateMode,
     PhysicalGroupBy,
 };
-use crate::metrics::{BaselineMetrics, MetricBuilder, RecordOutput};
-use crate::sorts::sort::sort_batch;
+use crate::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricBuilder, RecordOutput,
+};
 use crate::sorts::streaming_merge::StreamingMergeBuilder;
-use crate::spill::{read_spill_as_stream, spill_record_batch_by_size};
+use crate::spill::{read_spill_stream_as_stream, SpillStreamWriter};
 use crate::stream::RecordBatchStreamAdapter;
 use crate::{aggregates, metrics, ExecutionPlan, PhysicalExpr};
 use crate::{RecordBatchStream, SendableRecordBatchStream};
 
 use arrow::array::*;
-use arrow::compute::SortOptions;
+use arrow::compute::{lexsort_to_indices, take_record_batch, SortOptions};
 use arrow::datatypes::SchemaRef;
 use datafusion_common::{internal_err, DataFusionError, Result};
-use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::disk_manager::{DiskManager, RefCountedTempFile};
 use datafusion_execution::memory_pool::proxy::VecAllocExt;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
 use datafusion_execution::runtime_env::RuntimeEnv;
@@ -53,7 +54,7 @@ use super::AggregateExec;
 use datafusion_physical_expr::aggregate::AggregateFunctionExpr;
 use datafusion_physical_expr_common::sort_expr::LexOrdering;
 use futures::ready;
-use futures::stream::{Stream, StreamExt};
+use futures::stream::{Stream, StreamExt, TryStreamExt};
 use log::debug;
 
 #[derive(Debug, Clone)]
@@ -91,6 +92,9 @@ struct SpillState {
     /// GROUP BY expressions for merging spilled data
     merging_group_by: PhysicalGroupBy,
 
+    /// Maximum number of spills merged at once (from `SessionConfig`)
+    merge_degree: usize,
+
     // ========================================================================
     // STATES:
     // Fields changes during execution. Can be buffer, or state flags that
@@ -303,6 +307,15 @@ impl SkipAggregationProbe {
 /// be grouped once spilled onto disk, the read back data needs to be re-grouped again. In addition,
 /// re-grouping may cause out of memory again. Thus, re-grouping has to be a sort based aggregation.
 ///
+/// The groups are spilled in slices of an `aggregate_spill_merge_degree`th of them, each sorted and
+/// written to a spill of its own, so that their partial states are never all emitted at once.
+/// Once the input is read, the remaining groups are emitted in slices as well, which are kept in
+/// memory until it runs out and spilled from then on, and the aggregation switches to a streaming
+/// sort based aggregation over all of them merged, which only keeps the groups of the current sort
+/// key in memory. When there are more spills than `aggregate_spill_merge_degree`, they are first
+/// merged into fewer spills, so that the number of spills merged at once, and the memory used by
+/// their merge, is bounded.
+///
 /// ```text
 /// Partial Aggregation [batch_size = 2] (max memory = 3 rows)
 ///
@@ -551,6 +564,12 @@ impl GroupedHashAggregateStream {
             is_stream_merging: false,
             merging_aggregate_arguments,
             merging_group_by: PhysicalGroupBy::new_single(agg_group_by.expr.clone()),
+            merge_degree: context
+                .session_config()
+                .options()
+                .execution
+                .aggregate_spill_merge_degree
+                .max(2),
             peak_mem_used: MetricBuilder::new(&agg.metrics)
                 .gauge("peak_mem_used", partition),
             spill_count: MetricBuilder::new(&agg.metrics).spill_count(partition),
@@ -995,30 +1014,74 @@ impl GroupedHashAggregateStream {
         Ok(())
     }
 
-    /// Emit all rows, sort them, and store them on disk.
+    /// Emit all rows in slices, sort them, and store them on disk.
+    ///
+    /// Every slice is sorted and stored as a spill of its own, rather than emitting and sorting
+    /// all rows at once, so that spilling only needs memory for one slice besides the groups.
     fn spill(&mut self) -> Result<()> {
-        let Some(emit) = self.emit(EmitTo::All, true)? else {
-            return Ok(());
+        let slice_len = self.spill_slice_len();
+        while let Some(emit) = self.emit_slice(slice_len)? {
+            let indices = self.sort_indices(&emit)?;
+            let spill = self.spill_sorted(&emit, &indices)?;
+            self.spill_state.spills.push(spill);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of groups emitted at once to be spilled or merged: an
+    /// `aggregate_spill_merge_degree`th of the groups, but at least `batch_size` of them.
+    ///
+    /// The groups are emitted from the front, which copies the remaining ones, so the slices
+    /// grow with the groups to keep the cost of emitting all of them, and the number of spills
+    /// they are written to, bounded.
+    fn spill_slice_len(&self) -> usize {
+        self.group_values
+            .len()
+            .div_ceil(self.spill_state.merge_degree)
+            .max(self.batch_size)
+    }
+
+    /// Emits the partial states of the first `slice_len` groups, or of all of them if there
+    /// are fewer
+    fn emit_slice(&mut self, slice_len: usize) -> Result<Option<RecordBatch>> {
+        let emit_to = if self.group_values.len() > slice_len {
+            EmitTo::First(slice_len)
+        } else {
+            EmitTo::All
         };
-        let sorted = sort_batch(&emit, self.spill_state.spill_expr.as_ref(), None)?;
+        self.emit(emit_to, true)
+    }
+
+    /// Stores the rows of the emitted `batch` on disk in the order of `indices`
+    fn spill_sorted(
+        &self,
+        batch: &RecordBatch,
+        indices: &UInt32Array,
+    ) -> Result<RefCountedTempFile> {
         let spillfile = self.runtime.disk_manager.create_tmp_file("HashAggSpill")?;
-        // TODO: slice large `sorted` and write to multiple files in parallel
-        spill_record_batch_by_size(
-            &sorted,
-            spillfile.path().into(),
-            sorted.schema(),
-            self.batch_size,
-        )?;
-        self.spill_state.spills.push(spillfile);
+        let mut writer = SpillStreamWriter::try_new(spillfile, &batch.schema())?;
+        for offset in (0..indices.len()).step_by(self.batch_size) {
+            let len = self.batch_size.min(indices.len() - offset);
+            writer.write(&take_record_batch(batch, &indices.slice(offset, len))?)?;
+        }
 
         // Update metrics
         self.spill_state.spill_count.add(1);
-        self.spill_state
-            .spilled_bytes
-            .add(sorted.get_array_memory_size());
-        self.spill_state.spilled_rows.add(sorted.num_rows());
+        self.spill_state.spilled_bytes.add(writer.num_bytes());
+        self.spill_state.spilled_rows.add(writer.num_rows());
 
-        Ok(())
+        writer.finish()
+    }
+
+    /// Returns the indices of the rows of the emitted `batch` in the order of the spills
+    fn sort_indices(&self, batch: &RecordBatch) -> Result<UInt32Array> {
+        let sort_columns = self
+            .spill_state
+            .spill_expr
+            .iter()
+            .map(|expr| expr.evaluate_to_sort_column(batch))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(lexsort_to_indices(&sort_columns, None)?)
     }
 
     /// Clear memory and shirk capacities to the size of the batch.
@@ -1052,38 +1115,77 @@ impl GroupedHashAggregateStream {
     }
 
     /// At this point, all the inputs are read and there are some spills.
-    /// Emit the remaining rows and create a batch.
-    /// Conduct a streaming merge sort between the batch and spilled data. Since the stream is fully
-    /// sorted, set `self.group_ordering` to Full, then later we can read with [`EmitTo::First`].
+    /// Conduct a streaming merge sort of the spills and of the remaining rows. Since the stream is
+    /// fully sorted, set `self.group_ordering` to Full, then later we can read with
+    /// [`EmitTo::First`].
+    ///
+    /// The remaining rows are emitted in slices, like when spilling, and every sorted slice is
+    /// merged from memory as a run of its own. Once the memory of a slice, and as much again for
+    /// the merge, can not be reserved, it and all the following slices are spilled instead.
     fn update_merged_stream(&mut self) -> Result<()> {
-        let Some(batch) = self.emit(EmitTo::All, true)? else {
-            return Ok(());
-        };
+        let slice_len = self.spill_slice_len();
+        // The runs in memory are merged along with at least one spill
+        let max_in_memory = self.spill_state.merge_degree - 1;
+        let mut in_memory = vec![];
+        let mut memory_exhausted = false;
+        while let Some(emit) = self.emit_slice(slice_len)? {
+            let indices = self.sort_indices(&emit)?;
+            let size = emit.get_array_memory_size() + indices.get_array_memory_size();
+            // Keep as much memory as the slice uses for merging and aggregating the runs
+            let mut reservation = self.reservation.new_empty();
+            memory_exhausted = memory_exhausted
+                || in_memory.len() == max_in_memory
+                || reservation.try_grow(2 * size).is_err();
+            if memory_exhausted {
+                let spill = self.spill_sorted(&emit, &indices)?;
+                self.spill_state.spills.push(spill);
+                continue;
+            }
+            reservation.shrink(size);
+            let batch_size = self.batch_size;
+            let batches = (0..indices.len()).step_by(batch_size).map(move |offset| {
+                // The memory of the rows is released once they are all merged
+                let _reservation = &reservation;
+                let len = batch_size.min(indices.len() - offset);
+                Ok(take_record_batch(&emit, &indices.slice(offset, len))?)
+            });
+            in_memory.push(Box::pin(RecordBatchStreamAdapter::new(
+                Arc::clone(&self.spill_state.spill_schema),
+                futures::stream::iter(batches),
+            )) as SendableRecordBatchStream);
+        }
         // clear up memory for streaming_merge
         self.clear_all();
         self.update_memory_reservation()?;
-        let mut streams: Vec<SendableRecordBatchStream> = vec![];
-        let expr = self.spill_state.spill_expr.clone();
-        let schema = batch.schema();
-        streams.push(Box::pin(RecordBatchStreamAdapter::new(
-            Arc::clone(&schema),
-            futures::stream::once(futures::future::lazy(move |_| {
-                sort_batch(&batch, expr.as_ref(), None)
-            })),
-        )));
-        for spill in self.spill_state.spills.drain(..) {
-            let stream = read_spill_as_stream(spill, Arc::clone(&schema), 2)?;
-            streams.push(stream);
-        }
+
+        let merge = SpillMerge {
+            schema: Arc::clone(&self.spill_state.spill_schema),
+            expr: self.spill_state.spill_expr.clone(),
+            batch_size: self.batch_size,
+            merge_degree: self.spill_state.merge_degree,
+            reservation: self.reservation.new_empty(),
+            disk_manager: Arc::clone(&self.runtime.disk_manager),
+            spill_count: self.spill_state.spill_count.clone(),
+            spilled_bytes: self.spill_state.spilled_bytes.clone(),
+            spilled_rows: self.spill_state.spilled_rows.clone(),
+        };
+        let spills = std::mem::take(&mut self.spill_state.spills);
+        // The runs in memory are part of the final merge
+        let max_spills = merge.merge_degree - in_memory.len();
+        self.input = if spills.len() <= max_spills {
+            merge.merge(spills, in_memory, self.baseline_metrics.clone())?
+        } else {
+            let metrics = self.baseline_metrics.clone();
+            Box::pin(RecordBatchStreamAdapter::new(
+                Arc::clone(&merge.schema),
+                futures::stream::once(async move {
+                    let spills = merge.merge_to(spills, max_spills).await?;
+                    merge.merge(spills, in_memory, metrics)
+                })
+                .try_flatten(),
+            ))
+        };
         self.spill_state.is_stream_merging = true;
-        self.input = StreamingMergeBuilder::new()
-            .with_streams(streams)
-            .with_schema(schema)
-            .with_expressions(self.spill_state.spill_expr.as_ref())
-            .with_metrics(self.baseline_metrics.clone())
-            .with_batch_size(self.batch_size)
-            .with_reservation(self.reservation.new_empty())
-            .build()?;
         self.input_done = false;
         self.group_ordering = GroupOrdering::Full(GroupOrderingFull::new());
         Ok(())
@@ -1181,3 +1283,78 @@ impl GroupedHashAggregateStream {
         Ok(states_batch)
     }
 }
+
+/// Merges the sorted spills of a [`GroupedHashAggregateStream`]
+struct SpillMerge {
+    /// Schema of the spilled batches
+    schema: SchemaRef,
+    /// Sort order of the spilled batches
+    expr: LexOrdering,
+    batch_size: usize,
+    /// Maximum number of spills merged at once
+    merge_degree: usize,
+    reservation: MemoryReservation,
+    disk_manager: Arc<DiskManager>,
+    spill_count: metrics::Count,
+    spilled_bytes: metrics::Count,
+    spilled_rows: metrics::Count,
+}
+
+impl SpillMerge {
+    /// Returns a sorted stream of the rows of `spills` and of the sorted streams `in_memory`
+    fn merge(
+        &self,
+        spills: Vec<RefCountedTempFile>,
+        in_memory: Vec<SendableRecordBatchStream>,
+        metrics: BaselineMetrics,
+    ) -> Result<SendableRecordBatchStream> {
+        let mut streams = spills
+            .into_iter()
+            .map(|spill| read_spill_stream_as_stream(spill, Arc::clone(&self.schema), 2))
+            .collect::<Result<Vec<_>>>()?;
+        streams.extend(in_memory);
+        StreamingMergeBuilder::new()
+            .with_streams(streams)
+            .with_schema(Arc::clone(&self.schema))
+            .with_expressions(self.expr.as_ref())
+            .with_metrics(metrics)
+            .with_batch_size(self.batch_size)
+            .with_reservation(self.reservation.new_empty())
+            .build()
+    }
+
+    /// Merges `spills` into new spills, `merge_degree` at a time, until there are at most
+    /// `max_spills` of them
+    async fn merge_to(
+        &self,
+        mut spills: Vec<RefCountedTempFile>,
+        max_spills: usize,
+    ) -> Result<Vec<RefCountedTempFile>> {
+        // The rows of the intermediate merges are not part of the output
+        let metrics = BaselineMetrics::new(&ExecutionPlanMetricsSet::new(), 0);
+        while spills.len() > max_spills {
+            let mut merged = Vec::with_capacity(spills.len().div_ceil(self.merge_degree));
+            while !spills.is_empty() {
+                let runs = spills
+                    .drain(..self.merge_degree.min(spills.len()))
+                    .collect::<Vec<_>>();
+                if runs.len() == 1 {
+                    merged.extend(runs);
+                    continue;
+                }
+                let mut stream = self.merge(runs, vec![], metrics.clone())?;
+                let spillfile = self.disk_manager.create_tmp_file("HashAggSpill")?;
+                let mut writer = SpillStreamWriter::try_new(spillfile, &self.schema)?;
+                while let Some(batch) = stream.next().await {
+                    writer.write(&batch?)?;
+                }
+                self.spill_count.add(1);
+                self.spilled_bytes.add(writer.num_bytes());
+                self.spilled_rows.add(writer.num_rows());
+                merged.push(writer.finish()?);
+            }
+            spills = merged;
+        }
+        Ok(spills)
+    }
+}
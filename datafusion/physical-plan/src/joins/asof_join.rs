@@ -0,0 +1,1027 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the ASOF join execution plan.
+//! An ASOF join plan consumes two children sorted on the equijoin keys and
+//! the match condition, and joins every left row with the closest right row
+//! satisfying the match condition.
+
+use std::any::Any;
+use std::fmt::Formatter;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::execution_plan::{boundedness_from_children, EmissionType};
+use crate::expressions::PhysicalSortExpr;
+use crate::joins::sort_merge_join::{
+    compare_join_arrays, is_join_arrays_equal, join_arrays,
+};
+use crate::joins::utils::{
+    build_join_schema, check_join_is_valid, estimate_join_statistics,
+    symmetric_join_output_partitioning, JoinOn, JoinOnRef,
+};
+use crate::metrics::{self, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet};
+use crate::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, ExecutionPlanProperties,
+    PlanProperties, RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
+
+use arrow::array::{new_null_array, Array, ArrayRef, RecordBatch, UInt32Array};
+use arrow::compute::{interleave, take_record_batch, SortOptions};
+use arrow::datatypes::SchemaRef;
+use datafusion_common::{internal_err, plan_err, JoinSide, JoinType, Result};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::PhysicalExprRef;
+use datafusion_physical_expr_common::sort_expr::{LexOrdering, LexRequirement};
+
+use futures::{ready, Stream, StreamExt};
+
+/// Join execution plan that joins every row of the left input with the closest
+/// row of the right input that has equal join keys and satisfies the match
+/// condition. Left rows without such a right row are padded with nulls, so
+/// the output has exactly one row per left row (see [`JoinType::LeftAsof`]).
+///
+/// # Join Expressions
+///
+/// Equi-join predicates (e.g. `<col1> = <col2>`) are represented by
+/// [`Self::on`]. The match condition is a single comparison
+/// `<left expr> <op> <right expr>`, where `<op>` is one of `<`, `<=`, `>`
+/// or `>=`. For `>` and `>=` the closest right row is the one with the
+/// greatest right value, for `<` and `<=` the one with the smallest.
+///
+/// For example, `trades.ts >= quotes.ts` picks the latest quote at or before
+/// each trade.
+///
+/// # Sorting
+///
+/// Both inputs are required to be sorted on the equijoin keys followed by the
+/// match expression, ascending for `>`/`>=` and descending for `<`/`<=`. The
+/// join then performs a single merge pass over both inputs, in the same way
+/// as [`SortMergeJoinExec`]: the right input is only ever read forward, and at
+/// most three right batches (the current one, the one holding the last
+/// candidate row and the one holding the last matched row) are kept in memory
+/// at any time. Rows matched in earlier right batches are copied out of them
+/// until the output for the current left batch is built. The buffered right
+/// rows are accounted for in the memory pool.
+///
+/// The order of the left input is preserved in the output.
+///
+/// [`SortMergeJoinExec`]: crate::joins::SortMergeJoinExec
+#[derive(Debug, Clone)]
+pub struct AsofJoinExec {
+    /// Left sorted joining execution plan
+    pub left: Arc<dyn ExecutionPlan>,
+    /// Right sorted joining execution plan
+    pub right: Arc<dyn ExecutionPlan>,
+    /// Set of common columns used to join on
+    pub on: JoinOn,
+    /// Left side expression of the match condition
+    pub match_left: PhysicalExprRef,
+    /// Comparison operator of the match condition
+    pub match_op: Operator,
+    /// Right side expression of the match condition
+    pub match_right: PhysicalExprRef,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// The left SortExpr
+    left_sort_exprs: LexOrdering,
+    /// The right SortExpr
+    right_sort_exprs: LexOrdering,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl AsofJoinExec {
+    /// Tries to create a new [AsofJoinExec].
+    /// # Error
+    /// This function errors when it is not possible to join the left and right
+    /// sides on keys `on`, or when `match_op` is not an ordering comparison.
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        match_left: PhysicalExprRef,
+        match_op: Operator,
+        match_right: PhysicalExprRef,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+
+        check_join_is_valid(&left_schema, &right_schema, &on)?;
+        if !matches!(
+            match_op,
+            Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq
+        ) {
+            return plan_err!(
+                "AsofJoinExec match condition must use <, <=, > or >=, got {match_op}"
+            );
+        }
+
+        let sort_options = Self::sort_options(on.len(), match_op);
+        let (left_sort_exprs, right_sort_exprs): (Vec<_>, Vec<_>) = on
+            .iter()
+            .map(|(l, r)| (l, r))
+            .chain(std::iter::once((&match_left, &match_right)))
+            .zip(sort_options)
+            .map(|((l, r), options)| {
+                let left = PhysicalSortExpr {
+                    expr: Arc::clone(l),
+                    options,
+                };
+                let right = PhysicalSortExpr {
+                    expr: Arc::clone(r),
+                    options,
+                };
+                (left, right)
+            })
+            .unzip();
+
+        let schema = Arc::new(
+            build_join_schema(&left_schema, &right_schema, &JoinType::LeftAsof).0,
+        );
+        let cache = Self::compute_properties(&left, &right, Arc::clone(&schema), &on);
+        Ok(Self {
+            left,
+            right,
+            on,
+            match_left,
+            match_op,
+            match_right,
+            schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+            left_sort_exprs: LexOrdering::new(left_sort_exprs),
+            right_sort_exprs: LexOrdering::new(right_sort_exprs),
+            cache,
+        })
+    }
+
+    /// Sort options of the join keys followed by the match expression.
+    ///
+    /// The match expression is sorted so that the closest right row for a
+    /// left row is always the last right row that sorts before it.
+    fn sort_options(num_keys: usize, match_op: Operator) -> Vec<SortOptions> {
+        let match_options = SortOptions {
+            descending: matches!(match_op, Operator::Lt | Operator::LtEq),
+            nulls_first: true,
+        };
+        let mut sort_options = vec![SortOptions::default(); num_keys];
+        sort_options.push(match_options);
+        sort_options
+    }
+
+    /// Set of common columns used to join on
+    pub fn on(&self) -> &[(PhysicalExprRef, PhysicalExprRef)] {
+        &self.on
+    }
+
+    /// Ref to left execution plan
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// Ref to right execution plan
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Left side expression, operator and right side expression of the match condition
+    pub fn match_condition(&self) -> (&PhysicalExprRef, Operator, &PhysicalExprRef) {
+        (&self.match_left, self.match_op, &self.match_right)
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        join_on: JoinOnRef,
+    ) -> PlanProperties {
+        let join_type = JoinType::LeftAsof;
+        // Calculate equivalence properties:
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            right.equivalence_properties().clone(),
+            &join_type,
+            schema,
+            &[true, false],
+            Some(JoinSide::Left),
+            join_on,
+        );
+
+        let output_partitioning =
+            symmetric_join_output_partitioning(left, right, &join_type);
+
+        PlanProperties::new(
+            eq_properties,
+            output_partitioning,
+            EmissionType::Incremental,
+            boundedness_from_children([left, right]),
+        )
+    }
+}
+
+impl DisplayAs for AsofJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let on = self
+                    .on
+                    .iter()
+                    .map(|(c1, c2)| format!("({}, {})", c1, c2))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "AsofJoin: on=[{}], match_condition={} {} {}",
+                    on, self.match_left, self.match_op, self.match_right
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for AsofJoinExec {
+    fn name(&self) -> &'static str {
+        "AsofJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        if self.on.is_empty() {
+            return vec![Distribution::SinglePartition, Distribution::SinglePartition];
+        }
+        let (left_expr, right_expr) = self
+            .on
+            .iter()
+            .map(|(l, r)| (Arc::clone(l), Arc::clone(r)))
+            .unzip();
+        vec![
+            Distribution::HashPartitioned(left_expr),
+            Distribution::HashPartitioned(right_expr),
+        ]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<LexRequirement>> {
+        vec![
+            Some(LexRequirement::from(self.left_sort_exprs.clone())),
+            Some(LexRequirement::from(self.right_sort_exprs.clone())),
+        ]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true, false]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &children[..] {
+            [left, right] => Ok(Arc::new(AsofJoinExec::try_new(
+                Arc::clone(left),
+                Arc::clone(right),
+                self.on.clone(),
+                Arc::clone(&self.match_left),
+                self.match_op,
+                Arc::clone(&self.match_right),
+            )?)),
+            _ => internal_err!("AsofJoinExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let left_partitions = self.left.output_partitioning().partition_count();
+        let right_partitions = self.right.output_partitioning().partition_count();
+        if left_partitions != right_partitions {
+            return internal_err!(
+                "Invalid AsofJoinExec, partition count mismatch {left_partitions}!={right_partitions},\
+                 consider using RepartitionExec"
+            );
+        }
+
+        let (mut on_left, mut on_right): (Vec<_>, Vec<_>) =
+            self.on.iter().cloned().unzip();
+        on_left.push(Arc::clone(&self.match_left));
+        on_right.push(Arc::clone(&self.match_right));
+
+        let left = self.left.execute(partition, Arc::clone(&context))?;
+        let right = self.right.execute(partition, Arc::clone(&context))?;
+        let reservation = MemoryConsumer::new(format!("AsofJoinStream[{partition}]"))
+            .register(context.memory_pool());
+
+        Ok(Box::pin(AsofJoinStream {
+            schema: Arc::clone(&self.schema),
+            right_schema: self.right.schema(),
+            left,
+            right,
+            on_left,
+            on_right,
+            num_keys: self.on.len(),
+            sort_options: Self::sort_options(self.on.len(), self.match_op),
+            inclusive: matches!(self.match_op, Operator::LtEq | Operator::GtEq),
+            left_batch: None,
+            right_batch: None,
+            right_idx: 0,
+            right_finished: false,
+            candidate: None,
+            reservation,
+            metrics: AsofJoinMetrics::new(partition, &self.metrics),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        estimate_join_statistics(
+            Arc::clone(&self.left),
+            Arc::clone(&self.right),
+            self.on.clone(),
+            &JoinType::LeftAsof,
+            &self.schema,
+        )
+    }
+}
+
+/// Metrics for AsofJoinExec
+struct AsofJoinMetrics {
+    /// Total time for joining left batches to right batches
+    join_time: metrics::Time,
+    /// Number of batches consumed by this operator
+    input_batches: Count,
+    /// Number of rows consumed by this operator
+    input_rows: Count,
+    /// Number of batches produced by this operator
+    output_batches: Count,
+    /// Number of rows produced by this operator
+    output_rows: Count,
+}
+
+impl AsofJoinMetrics {
+    fn new(partition: usize, metrics: &ExecutionPlanMetricsSet) -> Self {
+        let join_time = MetricBuilder::new(metrics).subset_time("join_time", partition);
+        let input_batches =
+            MetricBuilder::new(metrics).counter("input_batches", partition);
+        let input_rows = MetricBuilder::new(metrics).counter("input_rows", partition);
+        let output_batches =
+            MetricBuilder::new(metrics).counter("output_batches", partition);
+        let output_rows = MetricBuilder::new(metrics).output_rows(partition);
+
+        Self {
+            join_time,
+            input_batches,
+            input_rows,
+            output_batches,
+            output_rows,
+        }
+    }
+}
+
+/// A batch of either input together with its evaluated join keys and match
+/// expression (in this order)
+#[derive(Debug)]
+struct JoinBatch {
+    batch: RecordBatch,
+    join_arrays: Vec<ArrayRef>,
+    /// The estimated size of the batch and its join arrays
+    size_estimation: usize,
+}
+
+impl JoinBatch {
+    fn new(batch: RecordBatch, on: &[PhysicalExprRef]) -> Self {
+        let join_arrays = join_arrays(&batch, on);
+        let size_estimation = batch.get_array_memory_size()
+            + join_arrays
+                .iter()
+                .map(|array| array.get_array_memory_size())
+                .sum::<usize>();
+        Self {
+            batch,
+            join_arrays,
+            size_estimation,
+        }
+    }
+
+    /// Whether the row can match at all, i.e. neither the join keys nor the
+    /// match expression are null
+    fn is_valid(&self, row: usize) -> bool {
+        self.join_arrays.iter().all(|array| array.is_valid(row))
+    }
+}
+
+/// The left batch currently being joined
+struct LeftBatchState {
+    batch: JoinBatch,
+    /// Index of the next left row to join
+    idx: usize,
+    /// Index into `matched` followed by `pending_rows` (offset by one, zero
+    /// meaning no match) and row within it for every left row joined so far
+    indices: Vec<(usize, usize)>,
+    /// Right rows matched by this batch, copied out of the right batches that
+    /// no longer hold the last match
+    matched: Vec<RecordBatch>,
+    /// Right batch holding the last matched row
+    pending_batch: Option<Arc<JoinBatch>>,
+    /// Distinct rows of `pending_batch` matched by this batch, in the order
+    /// they were found
+    pending_rows: Vec<u32>,
+}
+
+impl LeftBatchState {
+    fn new(batch: JoinBatch) -> Self {
+        Self {
+            batch,
+            idx: 0,
+            indices: vec![],
+            matched: vec![],
+            pending_batch: None,
+            pending_rows: vec![],
+        }
+    }
+
+    /// Copies the rows matched in `pending_batch` to `matched`, releasing
+    /// the right batch
+    fn flush_pending(&mut self) -> Result<()> {
+        if let Some(pending_batch) = self.pending_batch.take() {
+            let indices = UInt32Array::from(std::mem::take(&mut self.pending_rows));
+            self.matched
+                .push(take_record_batch(&pending_batch.batch, &indices)?);
+        }
+        Ok(())
+    }
+
+    /// The memory used by the copied matched rows
+    fn matched_size(&self) -> usize {
+        self.matched
+            .iter()
+            .map(|batch| batch.get_array_memory_size())
+            .sum()
+    }
+}
+
+/// A stream that performs the ASOF join of two sorted inputs
+struct AsofJoinStream {
+    /// Output schema
+    schema: SchemaRef,
+    /// Schema of the right input
+    right_schema: SchemaRef,
+    /// Left input
+    left: SendableRecordBatchStream,
+    /// Right input
+    right: SendableRecordBatchStream,
+    /// Join keys followed by the match expression of the left input
+    on_left: Vec<PhysicalExprRef>,
+    /// Join keys followed by the match expression of the right input
+    on_right: Vec<PhysicalExprRef>,
+    /// Number of join keys
+    num_keys: usize,
+    /// Sort options of the join keys followed by the match expression
+    sort_options: Vec<SortOptions>,
+    /// Whether a right row with a match value equal to the left one matches
+    inclusive: bool,
+    /// Left batch currently being joined
+    left_batch: Option<LeftBatchState>,
+    /// Right batch the cursor currently points into
+    right_batch: Option<Arc<JoinBatch>>,
+    /// Index of the first right row that sorts after the current left row
+    right_idx: usize,
+    /// Whether the right input is exhausted
+    right_finished: bool,
+    /// The last valid right row that sorts before the current left row
+    candidate: Option<(Arc<JoinBatch>, usize)>,
+    /// Memory reservation for the buffered right batches and matched rows
+    reservation: MemoryReservation,
+    /// Metrics
+    metrics: AsofJoinMetrics,
+}
+
+impl RecordBatchStream for AsofJoinStream {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
+
+impl Stream for AsofJoinStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.poll_next_impl(cx)
+    }
+}
+
+impl AsofJoinStream {
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        loop {
+            let Some(left_batch) = self.left_batch.as_ref() else {
+                match ready!(self.left.poll_next_unpin(cx)) {
+                    None => return Poll::Ready(None),
+                    Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                    Some(Ok(batch)) => {
+                        self.metrics.input_batches.add(1);
+                        self.metrics.input_rows.add(batch.num_rows());
+                        if batch.num_rows() > 0 {
+                            self.left_batch = Some(LeftBatchState::new(JoinBatch::new(
+                                batch,
+                                &self.on_left,
+                            )));
+                        }
+                        continue;
+                    }
+                }
+            };
+            let left_arrays = left_batch.batch.join_arrays.clone();
+            let num_rows = left_batch.batch.batch.num_rows();
+
+            while let Some(left_idx) = self
+                .left_batch
+                .as_ref()
+                .map(|state| state.idx)
+                .filter(|idx| *idx < num_rows)
+            {
+                if left_arrays.iter().all(|array| array.is_valid(left_idx)) {
+                    if let Err(e) = ready!(self.advance_right(cx, &left_arrays, left_idx))
+                    {
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+                if let Err(e) = self.join_left_row(&left_arrays, left_idx) {
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+
+            let state = self.left_batch.take().unwrap();
+            let timer = self.metrics.join_time.timer();
+            let result = self.build_output(state);
+            timer.done();
+            // Release the reservation of the rows matched by the left batch
+            if let Err(e) = self.resize_reservation() {
+                return Poll::Ready(Some(Err(e)));
+            }
+            if let Ok(batch) = &result {
+                self.metrics.output_batches.add(1);
+                self.metrics.output_rows.add(batch.num_rows());
+            }
+            return Poll::Ready(Some(result));
+        }
+    }
+
+    /// Moves the right cursor past all right rows that sort before the given
+    /// left row, remembering the last valid one as the candidate match.
+    fn advance_right(
+        &mut self,
+        cx: &mut Context<'_>,
+        left_arrays: &[ArrayRef],
+        left_idx: usize,
+    ) -> Poll<Result<()>> {
+        loop {
+            let right_batch = match &self.right_batch {
+                Some(batch) if self.right_idx < batch.batch.num_rows() => {
+                    Arc::clone(batch)
+                }
+                _ if self.right_finished => return Poll::Ready(Ok(())),
+                _ => {
+                    match ready!(self.right.poll_next_unpin(cx)) {
+                        None => self.right_finished = true,
+                        Some(Err(e)) => return Poll::Ready(Err(e)),
+                        Some(Ok(batch)) => {
+                            self.metrics.input_batches.add(1);
+                            self.metrics.input_rows.add(batch.num_rows());
+                            self.right_batch =
+                                Some(Arc::new(JoinBatch::new(batch, &self.on_right)));
+                            self.right_idx = 0;
+                            if let Err(e) = self.resize_reservation() {
+                                return Poll::Ready(Err(e));
+                            }
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            let _timer = self.metrics.join_time.timer();
+            while self.right_idx < right_batch.batch.num_rows() {
+                let ordering = compare_join_arrays(
+                    &right_batch.join_arrays,
+                    self.right_idx,
+                    left_arrays,
+                    left_idx,
+                    &self.sort_options,
+                    false,
+                )?;
+                if ordering.is_gt() || (ordering.is_eq() && !self.inclusive) {
+                    return Poll::Ready(Ok(()));
+                }
+                if right_batch.is_valid(self.right_idx) {
+                    self.candidate = Some((Arc::clone(&right_batch), self.right_idx));
+                }
+                self.right_idx += 1;
+            }
+        }
+    }
+
+    /// Records the match of the given left row, which is the current candidate
+    /// if its join keys are equal to the ones of the left row.
+    fn join_left_row(&mut self, left_arrays: &[ArrayRef], left_idx: usize) -> Result<()> {
+        let state = self.left_batch.as_mut().unwrap();
+        state.idx += 1;
+
+        let is_match = match &self.candidate {
+            Some((right_batch, right_idx))
+                if left_arrays.iter().all(|array| array.is_valid(left_idx)) =>
+            {
+                is_join_arrays_equal(
+                    &left_arrays[..self.num_keys],
+                    left_idx,
+                    &right_batch.join_arrays[..self.num_keys],
+                    *right_idx,
+                )?
+            }
+            _ => false,
+        };
+        if !is_match {
+            state.indices.push((0, 0));
+            return Ok(());
+        }
+
+        let (right_batch, right_idx) = self.candidate.as_ref().unwrap();
+        let right_idx = *right_idx as u32;
+        let is_pending = state
+            .pending_batch
+            .as_ref()
+            .is_some_and(|batch| Arc::ptr_eq(batch, right_batch));
+        let is_last_matched = is_pending && state.pending_rows.last() == Some(&right_idx);
+        if !is_pending {
+            state.flush_pending()?;
+            state.pending_batch = Some(Arc::clone(right_batch));
+        }
+        if !is_last_matched {
+            state.pending_rows.push(right_idx);
+        }
+        state
+            .indices
+            .push((state.matched.len() + 1, state.pending_rows.len() - 1));
+        if !is_pending {
+            self.resize_reservation()?;
+        }
+        Ok(())
+    }
+
+    /// Resizes the memory reservation to the buffered right batches and the
+    /// rows matched by the current left batch
+    fn resize_reservation(&mut self) -> Result<()> {
+        let mut buffered: Vec<&Arc<JoinBatch>> = vec![];
+        let batches = [
+            self.right_batch.as_ref(),
+            self.candidate.as_ref().map(|(batch, _)| batch),
+            self.left_batch
+                .as_ref()
+                .and_then(|state| state.pending_batch.as_ref()),
+        ];
+        for batch in batches.into_iter().flatten() {
+            if !buffered.iter().any(|other| Arc::ptr_eq(other, batch)) {
+                buffered.push(batch);
+            }
+        }
+        let size = buffered
+            .iter()
+            .map(|batch| batch.size_estimation)
+            .sum::<usize>()
+            + self
+                .left_batch
+                .as_ref()
+                .map(LeftBatchState::matched_size)
+                .unwrap_or_default();
+        self.reservation.try_resize(size)
+    }
+
+    /// Builds the output batch for a fully joined left batch
+    fn build_output(&self, mut state: LeftBatchState) -> Result<RecordBatch> {
+        state.flush_pending()?;
+        let num_rows = state.batch.batch.num_rows();
+        let mut columns = state.batch.batch.columns().to_vec();
+        if state.matched.is_empty() {
+            columns.extend(
+                self.right_schema
+                    .fields()
+                    .iter()
+                    .map(|field| new_null_array(field.data_type(), num_rows)),
+            );
+        } else {
+            for (i, field) in self.right_schema.fields().iter().enumerate() {
+                let null_row = new_null_array(field.data_type(), 1);
+                let arrays = std::iter::once(null_row.as_ref())
+                    .chain(state.matched.iter().map(|batch| batch.column(i).as_ref()))
+                    .collect::<Vec<_>>();
+                columns.push(interleave(&arrays, &state.indices)?);
+            }
+        }
+        Ok(RecordBatch::try_new(Arc::clone(&self.schema), columns)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Int32Array, RecordBatch};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::{assert_batches_eq, assert_contains, Result};
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_execution::TaskContext;
+    use datafusion_expr::Operator;
+
+    use crate::expressions::Column;
+    use crate::joins::utils::JoinOn;
+    use crate::joins::AsofJoinExec;
+    use crate::memory::MemorySourceConfig;
+    use crate::{common, ExecutionPlan};
+
+    fn build_table(
+        a: (&str, &Vec<Option<i32>>),
+        b: (&str, &Vec<Option<i32>>),
+        batch_size: usize,
+    ) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(a.0, DataType::Int32, true),
+            Field::new(b.0, DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int32Array::from(a.1.clone())),
+                Arc::new(Int32Array::from(b.1.clone())),
+            ],
+        )
+        .unwrap();
+        let batches = (0..batch.num_rows())
+            .step_by(batch_size)
+            .map(|offset| batch.slice(offset, batch_size.min(batch.num_rows() - offset)))
+            .collect::<Vec<_>>();
+        MemorySourceConfig::try_new_exec(&[batches], schema, None).unwrap()
+    }
+
+    async fn asof_join_collect(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        match_op: Operator,
+    ) -> Result<Vec<RecordBatch>> {
+        let join = AsofJoinExec::try_new(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            on,
+            Arc::new(Column::new_with_schema("t1", &left.schema())?),
+            match_op,
+            Arc::new(Column::new_with_schema("t2", &right.schema())?),
+        )?;
+        let stream = join.execute(0, Arc::new(TaskContext::default()))?;
+        common::collect(stream).await
+    }
+
+    fn on_keys(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+    ) -> Result<JoinOn> {
+        Ok(vec![(
+            Arc::new(Column::new_with_schema("k1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("k2", &right.schema())?) as _,
+        )])
+    }
+
+    #[tokio::test]
+    async fn asof_join_greater_equal() -> Result<()> {
+        for batch_size in [1, 2, 10] {
+            let left = build_table(
+                ("k1", &vec![Some(1), Some(1), Some(1), Some(2), Some(3)]),
+                ("t1", &vec![Some(1), Some(5), Some(9), Some(4), Some(7)]),
+                batch_size,
+            );
+            let right = build_table(
+                ("k2", &vec![Some(1), Some(1), Some(1), Some(2), Some(2)]),
+                ("t2", &vec![Some(2), Some(5), Some(6), Some(1), Some(8)]),
+                batch_size,
+            );
+            let on = on_keys(&left, &right)?;
+            let batches = asof_join_collect(left, right, on, Operator::GtEq).await?;
+            let expected = [
+                "+----+----+----+----+",
+                "| k1 | t1 | k2 | t2 |",
+                "+----+----+----+----+",
+                "| 1  | 1  |    |    |",
+                "| 1  | 5  | 1  | 5  |",
+                "| 1  | 9  | 1  | 6  |",
+                "| 2  | 4  | 2  | 1  |",
+                "| 3  | 7  |    |    |",
+                "+----+----+----+----+",
+            ];
+            assert_batches_eq!(expected, &batches);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_greater() -> Result<()> {
+        let left = build_table(
+            ("k1", &vec![Some(1), Some(1), Some(1)]),
+            ("t1", &vec![Some(2), Some(5), Some(9)]),
+            2,
+        );
+        let right = build_table(
+            ("k2", &vec![Some(1), Some(1), Some(1)]),
+            ("t2", &vec![Some(2), Some(5), Some(6)]),
+            2,
+        );
+        let on = on_keys(&left, &right)?;
+        let batches = asof_join_collect(left, right, on, Operator::Gt).await?;
+        let expected = [
+            "+----+----+----+----+",
+            "| k1 | t1 | k2 | t2 |",
+            "+----+----+----+----+",
+            "| 1  | 2  |    |    |",
+            "| 1  | 5  | 1  | 2  |",
+            "| 1  | 9  | 1  | 6  |",
+            "+----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_less_equal() -> Result<()> {
+        // inputs are sorted descending on the match expression
+        let left = build_table(
+            ("k1", &vec![Some(1), Some(1), Some(1)]),
+            ("t1", &vec![Some(9), Some(5), Some(1)]),
+            1,
+        );
+        let right = build_table(
+            ("k2", &vec![Some(1), Some(1), Some(1)]),
+            ("t2", &vec![Some(6), Some(5), Some(2)]),
+            1,
+        );
+        let on = on_keys(&left, &right)?;
+        let batches = asof_join_collect(left, right, on, Operator::LtEq).await?;
+        let expected = [
+            "+----+----+----+----+",
+            "| k1 | t1 | k2 | t2 |",
+            "+----+----+----+----+",
+            "| 1  | 9  |    |    |",
+            "| 1  | 5  | 1  | 5  |",
+            "| 1  | 1  | 1  | 2  |",
+            "+----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_nulls() -> Result<()> {
+        // nulls sort first, and never match
+        let left = build_table(
+            ("k1", &vec![None, Some(1), Some(1), Some(2)]),
+            ("t1", &vec![Some(5), None, Some(5), Some(5)]),
+            3,
+        );
+        let right = build_table(
+            ("k2", &vec![None, Some(1), Some(1), Some(2)]),
+            ("t2", &vec![Some(1), None, Some(1), None]),
+            3,
+        );
+        let on = on_keys(&left, &right)?;
+        let batches = asof_join_collect(left, right, on, Operator::GtEq).await?;
+        let expected = [
+            "+----+----+----+----+",
+            "| k1 | t1 | k2 | t2 |",
+            "+----+----+----+----+",
+            "|    | 5  |    |    |",
+            "| 1  |    |    |    |",
+            "| 1  | 5  | 1  | 1  |",
+            "| 2  | 5  |    |    |",
+            "+----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_without_keys() -> Result<()> {
+        let left = build_table(
+            ("k1", &vec![Some(1), Some(2), Some(3)]),
+            ("t1", &vec![Some(1), Some(4), Some(8)]),
+            2,
+        );
+        let right = build_table(
+            ("k2", &vec![Some(10), Some(20)]),
+            ("t2", &vec![Some(2), Some(4)]),
+            2,
+        );
+        let batches = asof_join_collect(left, right, vec![], Operator::GtEq).await?;
+        let expected = [
+            "+----+----+----+----+",
+            "| k1 | t1 | k2 | t2 |",
+            "+----+----+----+----+",
+            "| 1  | 1  |    |    |",
+            "| 2  | 4  | 20 | 4  |",
+            "| 3  | 8  | 20 | 4  |",
+            "+----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_memory_limit() -> Result<()> {
+        let left = build_table(
+            ("k1", &vec![Some(1), Some(1), Some(2)]),
+            ("t1", &vec![Some(5), Some(9), Some(4)]),
+            2,
+        );
+        let right = build_table(
+            ("k2", &vec![Some(1), Some(1), Some(2)]),
+            ("t2", &vec![Some(2), Some(6), Some(1)]),
+            2,
+        );
+        let on = on_keys(&left, &right)?;
+        let join = AsofJoinExec::try_new(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            on,
+            Arc::new(Column::new_with_schema("t1", &left.schema())?),
+            Operator::GtEq,
+            Arc::new(Column::new_with_schema("t2", &right.schema())?),
+        )?;
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(100, 1.0)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+
+        // the buffered right batches are accounted for in the memory pool
+        let stream = join.execute(0, task_ctx)?;
+        let err = common::collect(stream).await.unwrap_err();
+        assert_contains!(err.to_string(), "Resources exhausted");
+        assert_contains!(err.to_string(), "AsofJoinStream[0]");
+        Ok(())
+    }
+
+    #[test]
+    fn asof_join_invalid_operator() -> Result<()> {
+        let left = build_table(("k1", &vec![Some(1)]), ("t1", &vec![Some(1)]), 1);
+        let right = build_table(("k2", &vec![Some(1)]), ("t2", &vec![Some(1)]), 1);
+        let err = AsofJoinExec::try_new(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            vec![],
+            Arc::new(Column::new_with_schema("t1", &left.schema())?),
+            Operator::Eq,
+            Arc::new(Column::new_with_schema("t2", &right.schema())?),
+        )
+        .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "AsofJoinExec match condition must use <, <=, > or >=, got ="
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,417 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Dynamic filters computed from the build side of a [`HashJoinExec`]
+//!
+//! [`HashJoinExec`]: super::HashJoinExec
+
+use std::any::Any;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::hash_utils::create_hashes;
+
+use arrow::array::{Array, ArrayRef, BooleanArray};
+use arrow::buffer::{BooleanBuffer, NullBuffer};
+use arrow::compute::{concat, filter, sort_to_indices, SortOptions};
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, SortField};
+use datafusion_common::{Result, ScalarValue};
+use datafusion_execution::memory_pool::MemoryReservation;
+use datafusion_expr::{ColumnarValue, Operator};
+use datafusion_physical_expr::expressions::{
+    in_list, lit, BinaryExpr, DynamicFilterPhysicalExpr,
+};
+use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
+use datafusion_physical_expr_common::physical_expr::format_physical_expr_list;
+
+use ahash::RandomState;
+use hashbrown::HashSet;
+use parking_lot::Mutex;
+
+/// Number of bits set in a [`BloomFilterExpr`] for each build side row
+const BLOOM_FILTER_HASH_FUNCTIONS: u64 = 3;
+
+/// Minimum number of bits of a [`BloomFilterExpr`] for each build side row
+const BLOOM_FILTER_BITS_PER_ROW: usize = 8;
+
+/// The dynamic filter of a [`HashJoinExec`], shared by its partitions, and
+/// what is needed to compute it from the collected build side.
+///
+/// Probe side rows whose join key values do not appear on the build side can
+/// not match any build side row. Once the build side is collected, the filter
+/// is updated to reject (most) such rows:
+///
+/// * the values of each join key must lie within the build side range
+/// * join keys with at most `in_list_threshold` distinct build side values must
+///   be one of them
+/// * if any join key has more distinct values, the join keys must pass a bloom
+///   filter of the build side join keys, see [`BloomFilterExpr`]
+///
+/// In `Partitioned` mode, the probe side of each partition may read from all
+/// the partitions of the probe side scans, so the filter can only be computed
+/// once all the partitions have collected their build side. The partitions do
+/// not wait for each other: they start probing as soon as their own build side
+/// is collected, and the filter is updated when the last one reports its
+/// values in [`Self::report`].
+///
+/// [`HashJoinExec`]: super::HashJoinExec
+#[derive(Debug)]
+pub(crate) struct SharedJoinDynamicFilter {
+    /// The filter shared with the probe side scans
+    filter: Arc<DynamicFilterPhysicalExpr>,
+    /// The probe side join keys, which the filter is written in terms of
+    on_right: Vec<PhysicalExprRef>,
+    /// Schema of the probe side
+    probe_schema: SchemaRef,
+    /// Maximum number of distinct values of a join key listed by the filter
+    in_list_threshold: usize,
+    /// State of the hash function, shared with the hash table
+    random_state: RandomState,
+    /// Number of partitions collecting a build side
+    partitions: usize,
+    /// Build side join key values reported so far, and the memory of the filter
+    state: Mutex<SharedJoinDynamicFilterState>,
+}
+
+#[derive(Debug)]
+struct SharedJoinDynamicFilterState {
+    /// Build side join key values of each partition that has reported them,
+    /// `None` for partitions whose build side was spilled to disk
+    values: Vec<Option<Vec<ArrayRef>>>,
+    /// Memory reserved for the bloom filter
+    reservation: MemoryReservation,
+}
+
+impl SharedJoinDynamicFilter {
+    /// Create a new shared dynamic filter for a hash join whose build side is
+    /// collected by `partitions` partitions
+    pub fn new(
+        filter: Arc<DynamicFilterPhysicalExpr>,
+        on_right: Vec<PhysicalExprRef>,
+        probe_schema: SchemaRef,
+        in_list_threshold: usize,
+        random_state: RandomState,
+        partitions: usize,
+        reservation: MemoryReservation,
+    ) -> Self {
+        Self {
+            filter,
+            on_right,
+            probe_schema,
+            in_list_threshold,
+            random_state,
+            partitions,
+            state: Mutex::new(SharedJoinDynamicFilterState {
+                values: Vec::with_capacity(partitions),
+                reservation,
+            }),
+        }
+    }
+
+    /// Report the build side join key `values` of a partition, or `None` if
+    /// its build side was spilled, updating the filter if all the partitions
+    /// have reported their values
+    pub fn report(&self, values: Option<&[ArrayRef]>) -> Result<()> {
+        let mut state = self.state.lock();
+        state.values.push(values.map(<[_]>::to_vec));
+        if state.values.len() < self.partitions {
+            return Ok(());
+        }
+        self.update(&mut state)
+    }
+
+    /// Update the filter from the values reported by all the partitions
+    fn update(&self, state: &mut SharedJoinDynamicFilterState) -> Result<()> {
+        let values = std::mem::take(&mut state.values);
+        // The join keys of spilled build sides are not known
+        let Some(values) = values.into_iter().collect::<Option<Vec<_>>>() else {
+            state.reservation.free();
+            return self.filter.update(lit(true));
+        };
+        let values = (0..self.on_right.len())
+            .map(|idx| {
+                let arrays = values
+                    .iter()
+                    .map(|values| values[idx].as_ref())
+                    .collect::<Vec<_>>();
+                Ok(concat(&arrays)?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let predicate = self.predicate(&values, &mut state.reservation)?;
+        self.filter.update(predicate)
+    }
+
+    /// Return the filter for the build side join key `values`.
+    ///
+    /// The memory of the bloom filter is allocated from `reservation`. If it
+    /// can not be allocated, the bloom filter is left out of the filter.
+    fn predicate(
+        &self,
+        values: &[ArrayRef],
+        reservation: &mut MemoryReservation,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        // Rows with null join keys never match
+        let values = match union_nulls(values) {
+            Some(nulls) if nulls.null_count() > 0 => {
+                let valid = BooleanArray::new(nulls.into_inner(), None);
+                values
+                    .iter()
+                    .map(|values| Ok(filter(values, &valid)?))
+                    .collect::<Result<Vec<_>>>()?
+            }
+            _ => values.to_vec(),
+        };
+        if values.first().map_or(true, |values| values.is_empty()) {
+            reservation.free();
+            return Ok(lit(false));
+        }
+
+        let mut predicates = vec![];
+        let mut needs_bloom_filter = false;
+        for (key, values) in self.on_right.iter().zip(&values) {
+            if let Some((min, max)) = min_max(values) {
+                predicates.push(Arc::new(BinaryExpr::new(
+                    Arc::clone(key),
+                    Operator::GtEq,
+                    lit(min),
+                )) as _);
+                predicates.push(Arc::new(BinaryExpr::new(
+                    Arc::clone(key),
+                    Operator::LtEq,
+                    lit(max),
+                )) as _);
+            }
+            match distinct_values(values, self.in_list_threshold)? {
+                Some(list) => {
+                    let list = list.into_iter().map(lit).collect();
+                    predicates.push(in_list(
+                        Arc::clone(key),
+                        list,
+                        &false,
+                        &self.probe_schema,
+                    )?);
+                }
+                None => needs_bloom_filter = true,
+            }
+        }
+
+        reservation.free();
+        if needs_bloom_filter {
+            let bloom_filter_size = BloomFilterExpr::memory_size(values[0].len());
+            if reservation.try_grow(bloom_filter_size).is_ok() {
+                predicates.push(Arc::new(BloomFilterExpr::try_new(
+                    self.on_right.clone(),
+                    &values,
+                    self.random_state.clone(),
+                )?) as _);
+            }
+        }
+
+        Ok(predicates
+            .into_iter()
+            .reduce(|left, right| Arc::new(BinaryExpr::new(left, Operator::And, right)))
+            .unwrap_or_else(|| lit(true)))
+    }
+}
+
+/// Return the validity of the rows of `arrays`, a row being valid if it is
+/// valid in all of them
+fn union_nulls(arrays: &[ArrayRef]) -> Option<NullBuffer> {
+    arrays.iter().fold(None, |nulls, array| {
+        NullBuffer::union(nulls.as_ref(), array.logical_nulls().as_ref())
+    })
+}
+
+/// Return the minimum and maximum of `values`, which must not contain nulls,
+/// or `None` if they can not be computed for its data type
+fn min_max(values: &ArrayRef) -> Option<(ScalarValue, ScalarValue)> {
+    let extreme = |descending| {
+        let options = SortOptions {
+            descending,
+            nulls_first: false,
+        };
+        let indices = sort_to_indices(values, Some(options), Some(1)).ok()?;
+        ScalarValue::try_from_array(values, indices.value(0) as usize).ok()
+    };
+    Some((extreme(false)?, extreme(true)?))
+}
+
+/// Return the distinct values of `values`, which must not contain nulls, or
+/// `None` if there are more than `limit` of them
+fn distinct_values(values: &ArrayRef, limit: usize) -> Result<Option<Vec<ScalarValue>>> {
+    let fields = vec![SortField::new(values.data_type().clone())];
+    if !RowConverter::supports_fields(&fields) {
+        return Ok(None);
+    }
+    let converter = RowConverter::new(fields)?;
+    let rows = converter.convert_columns(&[Arc::clone(values)])?;
+
+    let mut seen = HashSet::new();
+    let mut indices = vec![];
+    for (idx, row) in rows.iter().enumerate() {
+        if seen.insert(row) {
+            if indices.len() == limit {
+                return Ok(None);
+            }
+            indices.push(idx);
+        }
+    }
+    indices
+        .into_iter()
+        .map(|idx| ScalarValue::try_from_array(values, idx))
+        .collect::<Result<Vec<_>>>()
+        .map(Some)
+}
+
+/// Checks whether the values of a set of join keys may appear on the build
+/// side of a hash join, using a bloom filter of the hashes of the build side
+/// join keys.
+///
+/// Evaluates to `true` for all the rows whose join keys appear on the build
+/// side, and to `true` for a small fraction of the others. Rows with null join
+/// keys evaluate to null.
+#[derive(Debug)]
+pub(crate) struct BloomFilterExpr {
+    /// The join key expressions
+    on: Vec<PhysicalExprRef>,
+    /// State of the hash function, shared with the build side
+    random_state: RandomState,
+    /// The bits of the filter, their number is a power of two
+    bits: Arc<[u64]>,
+}
+
+impl BloomFilterExpr {
+    /// Create a bloom filter of the rows of the build side join key `values`,
+    /// to check the values of the `on` expressions
+    pub fn try_new(
+        on: Vec<PhysicalExprRef>,
+        values: &[ArrayRef],
+        random_state: RandomState,
+    ) -> Result<Self> {
+        let num_rows = values.first().map_or(0, |values| values.len());
+        let mut bits = vec![0u64; Self::memory_size(num_rows) / size_of::<u64>()];
+        let mask = (bits.len() * 64 - 1) as u64;
+
+        let mut hashes = vec![0; num_rows];
+        create_hashes(values, &random_state, &mut hashes)?;
+        for hash in hashes {
+            for bit in Self::bit_positions(hash, mask) {
+                bits[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+
+        Ok(Self {
+            on,
+            random_state,
+            bits: bits.into(),
+        })
+    }
+
+    /// The number of bytes of a bloom filter for `num_rows` rows
+    fn memory_size(num_rows: usize) -> usize {
+        (num_rows * BLOOM_FILTER_BITS_PER_ROW)
+            .next_power_of_two()
+            .max(64)
+            / 8
+    }
+
+    /// The bits set for `hash`, computed with double hashing
+    fn bit_positions(hash: u64, mask: u64) -> impl Iterator<Item = usize> {
+        let step = hash.rotate_left(32) | 1;
+        (0..BLOOM_FILTER_HASH_FUNCTIONS)
+            .map(move |i| (hash.wrapping_add(i.wrapping_mul(step)) & mask) as usize)
+    }
+
+    /// Return whether a row hashed to `hash` may be in the filter
+    fn contains(&self, hash: u64, mask: u64) -> bool {
+        Self::bit_positions(hash, mask)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+}
+
+impl PartialEq for BloomFilterExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.on == other.on && Arc::ptr_eq(&self.bits, &other.bits)
+    }
+}
+
+impl Eq for BloomFilterExpr {}
+
+impl Hash for BloomFilterExpr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.on.hash(state);
+        Arc::as_ptr(&self.bits).hash(state);
+    }
+}
+
+impl fmt::Display for BloomFilterExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bloom_filter({})", format_physical_expr_list(&self.on))
+    }
+}
+
+impl PhysicalExpr for BloomFilterExpr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+        let num_rows = batch.num_rows();
+        let keys = self
+            .on
+            .iter()
+            .map(|key| key.evaluate(batch)?.into_array(num_rows))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut hashes = vec![0; num_rows];
+        create_hashes(&keys, &self.random_state, &mut hashes)?;
+        let mask = (self.bits.len() * 64 - 1) as u64;
+        let values =
+            BooleanBuffer::collect_bool(num_rows, |idx| self.contains(hashes[idx], mask));
+        let nulls = union_nulls(&keys);
+
+        Ok(ColumnarValue::Array(Arc::new(BooleanArray::new(
+            values, nulls,
+        ))))
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn PhysicalExpr>> {
+        self.on.iter().collect()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(Arc::new(Self {
+            on: children,
+            random_state: self.random_state.clone(),
+            bits: Arc::clone(&self.bits),
+        }))
+    }
+}
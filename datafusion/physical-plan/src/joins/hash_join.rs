@@ -20,10 +20,11 @@
 use std::fmt;
 use std::mem::size_of;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::task::Poll;
 use std::{any::Any, vec};
 
+use super::dynamic_filter::SharedJoinDynamicFilter;
 use super::utils::{
     asymmetric_join_output_partitioning, get_final_indices_from_shared_bitmap,
     reorder_output_after_swap, swap_join_projection,
@@ -37,7 +38,11 @@ use crate::projection::{
     try_embed_projection, try_pushdown_through_join, EmbeddedProjection, JoinData,
     ProjectionExec,
 };
-use crate::spill::get_record_batch_memory_size;
+use crate::repartition::JoinSkew;
+use crate::spill::{
+    get_record_batch_memory_size, read_spill_stream_as_stream, SpillStreamWriter,
+};
+use crate::stream::EmptyRecordBatchStream;
 use crate::ExecutionPlanProperties;
 use crate::{
     coalesce_partitions::CoalescePartitionsExec,
@@ -52,7 +57,7 @@ use crate::{
         JoinFilter, JoinHashMap, JoinHashMapOffset, JoinHashMapType, JoinOn, JoinOnRef,
         StatefulStreamResult,
     },
-    metrics::{ExecutionPlanMetricsSet, MetricsSet},
+    metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet},
     DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, Partitioning,
     PlanProperties, RecordBatchStream, SendableRecordBatchStream, Statistics,
 };
@@ -62,7 +67,7 @@ use arrow::array::{
     UInt32Array, UInt64Array,
 };
 use arrow::compute::kernels::cmp::{eq, not_distinct};
-use arrow::compute::{and, concat_batches, take, FilterBuilder};
+use arrow::compute::{and, concat_batches, take, take_record_batch, FilterBuilder};
 use arrow::datatypes::{Schema, SchemaRef};
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
@@ -72,17 +77,19 @@ use datafusion_common::{
     internal_datafusion_err, internal_err, plan_err, project_schema, DataFusionError,
     JoinSide, JoinType, Result,
 };
+use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
 use datafusion_execution::TaskContext;
 use datafusion_expr::Operator;
 use datafusion_physical_expr::equivalence::{
     join_equivalence_properties, ProjectionMapping,
 };
+use datafusion_physical_expr::expressions::DynamicFilterPhysicalExpr;
 use datafusion_physical_expr::PhysicalExprRef;
 use datafusion_physical_expr_common::datum::compare_op_for_nested;
 
 use ahash::RandomState;
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use futures::{ready, Stream, StreamExt};
 use parking_lot::Mutex;
 
 /// HashTable and input data for the left (build side) of a join
@@ -98,11 +105,11 @@ struct JoinLeftData {
     /// Counter of running probe-threads, potentially
     /// able to update `visited_indices_bitmap`
     probe_threads_counter: AtomicUsize,
-    /// We need to keep this field to maintain accurate memory accounting, even though we don't directly use it.
+    /// We need to keep this field to maintain accurate memory accounting.
     /// Without holding onto this reservation, the recorded memory usage would become inconsistent with actual usage.
     /// This could hide potential out-of-memory issues, especially when upstream operators increase their memory consumption.
     /// The MemoryReservation ensures proper tracking of memory resources throughout the join operation's lifecycle.
-    _reservation: MemoryReservation,
+    reservation: MemoryReservation,
 }
 
 impl JoinLeftData {
@@ -121,10 +128,15 @@ impl JoinLeftData {
             values,
             visited_indices_bitmap,
             probe_threads_counter,
-            _reservation: reservation,
+            reservation,
         }
     }
 
+    /// returns the memory reserved for the build side, in bytes
+    fn memory_size(&self) -> usize {
+        self.reservation.size()
+    }
+
     /// return a reference to the hash map
     fn hash_map(&self) -> &JoinHashMap {
         &self.hash_map
@@ -273,6 +285,27 @@ impl JoinLeftData {
 ///
 /// ```
 ///
+/// # Spilling
+///
+/// In [`PartitionMode::Partitioned`] mode each output stream builds its own
+/// hash table. If the build side of a stream does not fit in memory and the
+/// [`DiskManager`] allows temporary files, the stream falls back to a grace
+/// hash join:
+///
+/// 1. the build side is hash partitioned on the join keys into
+///    `SPILL_FANOUT` spill files,
+/// 2. the probe side is partitioned the same way into its own spill files,
+/// 3. every pair of build and probe partitions is then joined separately. A
+///    build partition that still does not fit in memory is partitioned again
+///    (with a different hash seed), up to `MAX_SPILL_DEPTH` times.
+///
+/// Spilling reorders the probe side, so joins that maintain the order of the
+/// probe side never spill. The number of spill files, and the rows and bytes
+/// written to them, are reported in the `spill_count`, `spilled_rows` and
+/// `spilled_bytes` metrics.
+///
+/// [`DiskManager`]: datafusion_execution::DiskManager
+///
 /// # Example "Optimal" Plans
 ///
 /// The differences in the inputs means that for classic "Star Schema Query",
@@ -337,7 +370,7 @@ pub struct HashJoinExec {
     ///
     /// Each output stream waits on the `OnceAsync` to signal the completion of
     /// the hash table creation.
-    left_fut: OnceAsync<JoinLeftData>,
+    left_fut: OnceAsync<BuildSideData>,
     /// Shared the `RandomState` for the hashing algorithm
     random_state: RandomState,
     /// Partitioning mode to use
@@ -355,6 +388,16 @@ pub struct HashJoinExec {
     pub null_equals_null: bool,
     /// Cache holding plan properties like equivalences, output partitioning etc.
     cache: PlanProperties,
+    /// Dynamic filter on the probe side join keys, pushed down to the probe
+    /// side scans and updated once the build side has been collected
+    dynamic_filter: Option<Arc<DynamicFilterPhysicalExpr>>,
+    /// State shared by the output streams to update `dynamic_filter`
+    shared_dynamic_filter: OnceLock<Arc<SharedJoinDynamicFilter>>,
+    /// Heavy hitters shared with the [`RepartitionExec`]s of both sides of a
+    /// skew-aware partitioned join
+    ///
+    /// [`RepartitionExec`]: crate::repartition::RepartitionExec
+    join_skew: Option<Arc<JoinSkew>>,
 }
 
 impl HashJoinExec {
@@ -416,9 +459,62 @@ impl HashJoinExec {
             column_indices,
             null_equals_null,
             cache,
+            dynamic_filter: None,
+            shared_dynamic_filter: OnceLock::new(),
+            join_skew: None,
         })
     }
 
+    /// Return a new [HashJoinExec] which updates the given dynamic filter on
+    /// its probe side join keys once its build side has been collected.
+    ///
+    /// The filter is expected to have been pushed down to the probe side by
+    /// the caller. In [`PartitionMode::Partitioned`], the filter is updated
+    /// once all the partitions have collected their build side, so all the
+    /// partitions of the join must be executed concurrently.
+    pub fn with_dynamic_filter(
+        mut self,
+        dynamic_filter: Option<Arc<DynamicFilterPhysicalExpr>>,
+    ) -> Self {
+        self.dynamic_filter = dynamic_filter;
+        self
+    }
+
+    /// The dynamic filter on the probe side join keys, if any
+    pub fn dynamic_filter(&self) -> Option<&Arc<DynamicFilterPhysicalExpr>> {
+        self.dynamic_filter.as_ref()
+    }
+
+    /// Return a new [HashJoinExec] whose inputs are partitioned by
+    /// [`RepartitionExec`]s sharing the heavy hitters `join_skew`, see
+    /// [`RepartitionExec::with_join_skew`].
+    ///
+    /// As the build side rows of heavy hitters are sent to all the partitions,
+    /// the join must be [`PartitionMode::Partitioned`] and must not output
+    /// unmatched build side rows. As the probe side rows of heavy hitters are
+    /// sent round robin to the partitions, the output is not partitioned by
+    /// the join keys anymore. Each output stream starts executing its probe
+    /// side while collecting its build side, so the probe side can find the
+    /// heavy hitters the build side waits for.
+    ///
+    /// [`RepartitionExec`]: crate::repartition::RepartitionExec
+    /// [`RepartitionExec::with_join_skew`]: crate::repartition::RepartitionExec::with_join_skew
+    pub fn with_join_skew(mut self, join_skew: Option<Arc<JoinSkew>>) -> Self {
+        if join_skew.is_some() {
+            let partition_count = self.cache.output_partitioning().partition_count();
+            self.cache = self
+                .cache
+                .with_partitioning(Partitioning::UnknownPartitioning(partition_count));
+        }
+        self.join_skew = join_skew;
+        self
+    }
+
+    /// The heavy hitters of a skew-aware partitioned join, if any
+    pub fn join_skew(&self) -> Option<&Arc<JoinSkew>> {
+        self.join_skew.as_ref()
+    }
+
     /// left (build) side which gets hashed
     pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
         &self.left
@@ -496,7 +592,7 @@ impl HashJoinExec {
             },
             None => None,
         };
-        Self::try_new(
+        Ok(Self::try_new(
             Arc::clone(&self.left),
             Arc::clone(&self.right),
             self.on.clone(),
@@ -505,7 +601,8 @@ impl HashJoinExec {
             projection,
             self.mode,
             self.null_equals_null,
-        )
+        )?
+        .with_join_skew(self.join_skew.clone()))
     }
 
     /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
@@ -557,6 +654,7 @@ impl HashJoinExec {
                 JoinType::Left
                 | JoinType::LeftAnti
                 | JoinType::LeftMark
+                | JoinType::LeftAsof
                 | JoinType::Full => EmissionType::Both,
             }
         } else {
@@ -666,7 +764,11 @@ impl DisplayAs for HashJoinExec {
                     f,
                     "HashJoinExec: mode={:?}, join_type={:?}, on=[{}]{}{}",
                     self.mode, self.join_type, on, display_filter, display_projections
-                )
+                )?;
+                if self.join_skew.is_some() {
+                    write!(f, ", join_skew=true")?;
+                }
+                Ok(())
             }
         }
     }
@@ -737,16 +839,20 @@ impl ExecutionPlan for HashJoinExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(HashJoinExec::try_new(
-            Arc::clone(&children[0]),
-            Arc::clone(&children[1]),
-            self.on.clone(),
-            self.filter.clone(),
-            &self.join_type,
-            self.projection.clone(),
-            self.mode,
-            self.null_equals_null,
-        )?))
+        Ok(Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(&children[0]),
+                Arc::clone(&children[1]),
+                self.on.clone(),
+                self.filter.clone(),
+                &self.join_type,
+                self.projection.clone(),
+                self.mode,
+                self.null_equals_null,
+            )?
+            .with_dynamic_filter(self.dynamic_filter.clone())
+            .with_join_skew(self.join_skew.clone()),
+        ))
     }
 
     fn execute(
@@ -776,6 +882,46 @@ impl ExecutionPlan for HashJoinExec {
         }
 
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+        let spill_metrics = HashJoinSpillMetrics::new(partition, &self.metrics);
+
+        // Only streams owning their build side may spill it, and only if the
+        // order of the probe side does not need to be preserved
+        let spill_config = (self.mode == PartitionMode::Partitioned
+            && context.runtime_env().disk_manager.tmp_files_enabled()
+            && !(self.right.output_ordering().is_some()
+                && Self::maintains_input_order(self.join_type)[1]))
+            .then(|| HashJoinSpillConfig {
+                context: Arc::clone(&context),
+                partition,
+                on_left: on_left.clone(),
+                left_schema: self.left.schema(),
+                metrics: spill_metrics,
+            });
+
+        let dynamic_filter = self.dynamic_filter.as_ref().map(|filter| {
+            Arc::clone(self.shared_dynamic_filter.get_or_init(|| {
+                // The build side of `CollectLeft` joins is collected once
+                let build_partitions = match self.mode {
+                    PartitionMode::CollectLeft => 1,
+                    _ => left_partitions,
+                };
+                Arc::new(SharedJoinDynamicFilter::new(
+                    Arc::clone(filter),
+                    on_right.clone(),
+                    self.right.schema(),
+                    context
+                        .session_config()
+                        .options()
+                        .execution
+                        .join_dynamic_filter_in_list_threshold,
+                    self.random_state.clone(),
+                    build_partitions,
+                    MemoryConsumer::new("HashJoinDynamicFilter")
+                        .register(context.memory_pool()),
+                ))
+            }))
+        });
+
         let left_fut = match self.mode {
             PartitionMode::CollectLeft => self.left_fut.once(|| {
                 let reservation =
@@ -790,6 +936,8 @@ impl ExecutionPlan for HashJoinExec {
                     reservation,
                     need_produce_result_in_final(self.join_type),
                     self.right().output_partitioning().partition_count(),
+                    None,
+                    dynamic_filter,
                 )
             }),
             PartitionMode::Partitioned => {
@@ -807,6 +955,8 @@ impl ExecutionPlan for HashJoinExec {
                     reservation,
                     need_produce_result_in_final(self.join_type),
                     1,
+                    spill_config.clone(),
+                    dynamic_filter,
                 ))
             }
             PartitionMode::Auto => {
@@ -847,6 +997,11 @@ impl ExecutionPlan for HashJoinExec {
             batch_size,
             hashes_buffer: vec![],
             right_side_ordered: self.right.output_ordering().is_some(),
+            spill_config,
+            spill_state: HashJoinSpillState::default(),
+            spill_depth: 0,
+            start_probe_side: self.join_skew.is_some(),
+            peeked_probe_batch: None,
         }))
     }
 
@@ -894,17 +1049,20 @@ impl ExecutionPlan for HashJoinExec {
             self.schema(),
             self.filter(),
         )? {
-            Ok(Some(Arc::new(HashJoinExec::try_new(
-                Arc::new(projected_left_child),
-                Arc::new(projected_right_child),
-                join_on,
-                join_filter,
-                self.join_type(),
-                // Returned early if projection is not None
-                None,
-                *self.partition_mode(),
-                self.null_equals_null,
-            )?)))
+            Ok(Some(Arc::new(
+                HashJoinExec::try_new(
+                    Arc::new(projected_left_child),
+                    Arc::new(projected_right_child),
+                    join_on,
+                    join_filter,
+                    self.join_type(),
+                    // Returned early if projection is not None
+                    None,
+                    *self.partition_mode(),
+                    self.null_equals_null,
+                )?
+                .with_join_skew(self.join_skew.clone()),
+            )))
         } else {
             try_embed_projection(projection, self)
         }
@@ -913,6 +1071,9 @@ impl ExecutionPlan for HashJoinExec {
 
 /// Reads the left (build) side of the input, buffering it in memory, to build a
 /// hash table (`LeftJoinData`)
+///
+/// If `spill` is set and the build side does not fit in memory, it is hash
+/// partitioned into spill files instead, see [`collect_build_input`]
 #[allow(clippy::too_many_arguments)]
 async fn collect_left_input(
     partition: Option<usize>,
@@ -924,9 +1085,9 @@ async fn collect_left_input(
     reservation: MemoryReservation,
     with_visited_indices_bitmap: bool,
     probe_threads_count: usize,
-) -> Result<JoinLeftData> {
-    let schema = left.schema();
-
+    spill: Option<HashJoinSpillConfig>,
+    dynamic_filter: Option<Arc<SharedJoinDynamicFilter>>,
+) -> Result<BuildSideData> {
     let (left_input, left_input_partition) = if let Some(partition) = partition {
         (left, partition)
     } else if left.output_partitioning().partition_count() != 1 {
@@ -936,37 +1097,119 @@ async fn collect_left_input(
     };
 
     // Depending on partition argument load single partition or whole left side in memory
-    let stream = left_input.execute(left_input_partition, Arc::clone(&context))?;
-
-    // This operation performs 2 steps at once:
-    // 1. creates a [JoinHashMap] of all batches from the stream
-    // 2. stores the batches in a vector.
-    let initial = (Vec::new(), 0, metrics, reservation);
-    let (batches, num_rows, metrics, mut reservation) = stream
-        .try_fold(initial, |mut acc, batch| async {
-            let batch_size = get_record_batch_memory_size(&batch);
-            // Reserve memory for incoming batch
-            acc.3.try_grow(batch_size)?;
-            // Update metrics
-            acc.2.build_mem_used.add(batch_size);
-            acc.2.build_input_batches.add(1);
-            acc.2.build_input_rows.add(batch.num_rows());
-            // Update row count
-            acc.1 += batch.num_rows();
-            // Push batch to output
-            acc.0.push(batch);
-            Ok(acc)
-        })
-        .await?;
+    let data = match left_input.execute(left_input_partition, Arc::clone(&context)) {
+        Ok(stream) => {
+            collect_build_input(
+                stream,
+                on_left,
+                random_state,
+                metrics,
+                reservation,
+                with_visited_indices_bitmap,
+                probe_threads_count,
+                0,
+                spill,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+
+    if let Some(dynamic_filter) = dynamic_filter {
+        let values = match &data {
+            Ok(BuildSideData::InMemory(data)) => Some(data.values()),
+            Ok(BuildSideData::Spilled(_)) | Err(_) => None,
+        };
+        dynamic_filter.report(values)?;
+    }
+    data
+}
+
+/// Buffers the build side `stream` in memory and builds a hash table from it.
+///
+/// If memory can not be reserved for the build side and `spill` is set, the
+/// buffered batches and the rest of `stream` are hash partitioned into spill
+/// files instead. `spill_depth` is the number of times `stream` has already
+/// been partitioned, `0` for the original input.
+#[allow(clippy::too_many_arguments)]
+async fn collect_build_input(
+    mut stream: SendableRecordBatchStream,
+    on_left: Vec<PhysicalExprRef>,
+    random_state: RandomState,
+    metrics: BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+    with_visited_indices_bitmap: bool,
+    probe_threads_count: usize,
+    spill_depth: usize,
+    spill: Option<HashJoinSpillConfig>,
+) -> Result<BuildSideData> {
+    let schema = stream.schema();
+
+    let mut batches = Vec::new();
+    let mut num_rows = 0;
+    while let Some(batch) = stream.next().await.transpose()? {
+        let batch_size = get_record_batch_memory_size(&batch);
+        if spill_depth == 0 {
+            metrics.build_input_batches.add(1);
+            metrics.build_input_rows.add(batch.num_rows());
+        }
+        // Reserve memory for incoming batch
+        if let Err(e) = reservation.try_grow(batch_size) {
+            return match spill {
+                Some(spill) => {
+                    batches.push(batch);
+                    spill_build_input(
+                        batches,
+                        stream,
+                        spill,
+                        spill_depth,
+                        metrics,
+                        reservation,
+                    )
+                    .await
+                }
+                None => Err(e),
+            };
+        }
+        // Update metrics
+        metrics.build_mem_used.add(batch_size);
+        // Update row count
+        num_rows += batch.num_rows();
+        // Push batch to output
+        batches.push(batch);
+    }
 
     // Estimation of memory size, required for hashtable, prior to allocation.
     // Final result can be verified using `RawTable.allocation_info()`
     let fixed_size = size_of::<JoinHashMap>();
     let estimated_hashtable_size =
         estimate_memory_size::<(u64, u64)>(num_rows, fixed_size)?;
+    // Memory for the visited indices bitmap
+    let bitmap_size = if with_visited_indices_bitmap {
+        bit_util::ceil(num_rows, 8)
+    } else {
+        0
+    };
 
-    reservation.try_grow(estimated_hashtable_size)?;
-    metrics.build_mem_used.add(estimated_hashtable_size);
+    if let Err(e) = reservation.try_grow(estimated_hashtable_size + bitmap_size) {
+        return match spill {
+            Some(spill) => {
+                spill_build_input(
+                    batches,
+                    stream,
+                    spill,
+                    spill_depth,
+                    metrics,
+                    reservation,
+                )
+                .await
+            }
+            None => Err(e),
+        };
+    }
+    metrics
+        .build_mem_used
+        .add(estimated_hashtable_size + bitmap_size);
 
     let mut hashmap = JoinHashMap::with_capacity(num_rows);
     let mut hashes_buffer = Vec::new();
@@ -992,12 +1235,8 @@ async fn collect_left_input(
     // Merge all batches into a single batch, so we can directly index into the arrays
     let single_batch = concat_batches(&schema, batches_iter)?;
 
-    // Reserve additional memory for visited indices bitmap and create shared builder
+    // Create shared builder for visited indices bitmap
     let visited_indices_bitmap = if with_visited_indices_bitmap {
-        let bitmap_size = bit_util::ceil(single_batch.num_rows(), 8);
-        reservation.try_grow(bitmap_size)?;
-        metrics.build_mem_used.add(bitmap_size);
-
         let mut bitmap_buffer = BooleanBufferBuilder::new(single_batch.num_rows());
         bitmap_buffer.append_n(num_rows, false);
         bitmap_buffer
@@ -1022,7 +1261,52 @@ async fn collect_left_input(
         reservation,
     );
 
-    Ok(data)
+    Ok(BuildSideData::InMemory(Arc::new(data)))
+}
+
+/// Hash partitions the buffered build side `batches`, followed by the rest of
+/// `stream`, into spill files, releasing the memory held by `reservation`
+async fn spill_build_input(
+    batches: Vec<RecordBatch>,
+    mut stream: SendableRecordBatchStream,
+    spill: HashJoinSpillConfig,
+    spill_depth: usize,
+    metrics: BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+) -> Result<BuildSideData> {
+    let mut partitioner = SpillPartitioner::new(
+        spill.on_left.clone(),
+        stream.schema(),
+        spill_depth,
+        &spill,
+    );
+    for batch in batches {
+        partitioner.write(&batch)?;
+    }
+    metrics.build_mem_used.sub(reservation.size());
+    reservation.free();
+
+    while let Some(batch) = stream.next().await.transpose()? {
+        if spill_depth == 0 {
+            metrics.build_input_batches.add(1);
+            metrics.build_input_rows.add(batch.num_rows());
+        }
+        partitioner.write(&batch)?;
+    }
+
+    Ok(BuildSideData::Spilled(Mutex::new(partitioner.finish()?)))
+}
+
+/// Reads back a spill file written by [`SpillPartitioner`], or an empty stream
+/// if the partition has no rows
+fn read_spill_file(
+    file: Option<RefCountedTempFile>,
+    schema: SchemaRef,
+) -> Result<SendableRecordBatchStream> {
+    match file {
+        Some(file) => read_spill_stream_as_stream(file, schema, 2),
+        None => Ok(Box::pin(EmptyRecordBatchStream::new(schema))),
+    }
 }
 
 /// Updates `hash_map` with new entries from `batch` evaluated against the expressions `on`
@@ -1083,7 +1367,7 @@ enum BuildSide {
 /// Container for BuildSide::Initial related data
 struct BuildSideInitialState {
     /// Future for building hash table from build-side input
-    left_fut: OnceFut<JoinLeftData>,
+    left_fut: OnceFut<BuildSideData>,
 }
 
 /// Container for BuildSide::Ready related data
@@ -1121,21 +1405,195 @@ impl BuildSide {
     }
 }
 
+/// Collected build side of a [`HashJoinStream`]
+enum BuildSideData {
+    /// The build side fits in memory, and its hash table has been built
+    InMemory(Arc<JoinLeftData>),
+    /// The build side did not fit in memory and has been hash partitioned into
+    /// [`SPILL_FANOUT`] spill files, `None` for empty partitions. Only streams
+    /// owning their build side spill, and take the files out once collected
+    Spilled(Mutex<Vec<Option<RefCountedTempFile>>>),
+}
+
+/// Number of partitions a spilling [`HashJoinStream`] splits its build and
+/// probe sides into
+const SPILL_FANOUT: usize = 16;
+
+/// Maximum number of times the build side of a [`HashJoinStream`] is
+/// partitioned before failing with the memory reservation error
+const MAX_SPILL_DEPTH: usize = 4;
+
+/// Metrics for spilling the build and probe sides of [`HashJoinExec`]
+#[derive(Clone, Debug)]
+struct HashJoinSpillMetrics {
+    /// Number of spill files written
+    spill_count: Count,
+    /// Total number of bytes written to spill files
+    spilled_bytes: Count,
+    /// Total number of rows written to spill files
+    spilled_rows: Count,
+}
+
+impl HashJoinSpillMetrics {
+    fn new(partition: usize, metrics: &ExecutionPlanMetricsSet) -> Self {
+        Self {
+            spill_count: MetricBuilder::new(metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
+            spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
+        }
+    }
+}
+
+/// Everything a [`HashJoinStream`] needs to spill its inputs and to join them
+/// back from the spill files
+#[derive(Clone)]
+struct HashJoinSpillConfig {
+    /// Task context, providing the disk manager and the memory pool
+    context: Arc<TaskContext>,
+    /// Output partition of the stream
+    partition: usize,
+    /// equijoin columns from the left (build side)
+    on_left: Vec<PhysicalExprRef>,
+    /// Schema of the left (build side)
+    left_schema: SchemaRef,
+    /// Spill metrics
+    metrics: HashJoinSpillMetrics,
+}
+
+/// Hash partitions batches on their join keys into [`SPILL_FANOUT`] spill
+/// files, creating each file on the first write to its partition
+struct SpillPartitioner {
+    /// Join key expressions
+    on: Vec<PhysicalExprRef>,
+    /// Random state used to hash the join keys. Differs from the one of the
+    /// join, and between spill depths, so that partitions are split again
+    random_state: RandomState,
+    /// Schema of the partitioned batches
+    schema: SchemaRef,
+    /// Task context, providing the disk manager
+    context: Arc<TaskContext>,
+    /// Spill file writer for each partition
+    files: Vec<Option<SpillStreamWriter>>,
+    /// Scratch space for computing hashes
+    hashes_buffer: Vec<u64>,
+    /// Spill metrics
+    metrics: HashJoinSpillMetrics,
+}
+
+impl SpillPartitioner {
+    fn new(
+        on: Vec<PhysicalExprRef>,
+        schema: SchemaRef,
+        spill_depth: usize,
+        config: &HashJoinSpillConfig,
+    ) -> Self {
+        let seed = spill_depth as u64 + 1;
+        Self {
+            on,
+            random_state: RandomState::with_seeds(seed, seed, seed, seed),
+            schema,
+            context: Arc::clone(&config.context),
+            files: (0..SPILL_FANOUT).map(|_| None).collect(),
+            hashes_buffer: vec![],
+            metrics: config.metrics.clone(),
+        }
+    }
+
+    /// Appends the rows of `batch` to the spill files of their partitions
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let keys_values = self
+            .on
+            .iter()
+            .map(|c| c.evaluate(batch)?.into_array(batch.num_rows()))
+            .collect::<Result<Vec<_>>>()?;
+        self.hashes_buffer.clear();
+        self.hashes_buffer.resize(batch.num_rows(), 0);
+        create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
+
+        let mut indices = vec![vec![]; SPILL_FANOUT];
+        for (row, hash) in self.hashes_buffer.iter().enumerate() {
+            indices[(*hash % SPILL_FANOUT as u64) as usize].push(row as u32);
+        }
+
+        for (partition, indices) in indices.into_iter().enumerate() {
+            if indices.is_empty() {
+                continue;
+            }
+            let partition_batch = take_record_batch(batch, &UInt32Array::from(indices))?;
+            let writer = match &mut self.files[partition] {
+                Some(writer) => writer,
+                slot @ None => {
+                    let file = self
+                        .context
+                        .runtime_env()
+                        .disk_manager
+                        .create_tmp_file("HashJoinSpill")?;
+                    slot.insert(SpillStreamWriter::try_new(file, &self.schema)?)
+                }
+            };
+            writer.write(&partition_batch)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes all spill files, returning them by partition
+    fn finish(self) -> Result<Vec<Option<RefCountedTempFile>>> {
+        self.files
+            .into_iter()
+            .map(|writer| {
+                writer
+                    .map(|writer| {
+                        self.metrics.spill_count.add(1);
+                        self.metrics.spilled_rows.add(writer.num_rows());
+                        self.metrics.spilled_bytes.add(writer.num_bytes());
+                        writer.finish()
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+}
+
+/// A pair of build and probe side spill files, to be joined together
+struct SpilledPartition {
+    /// Build side spill file, `None` if the partition has no build side rows
+    build: Option<RefCountedTempFile>,
+    /// Probe side spill file, `None` if the partition has no probe side rows
+    probe: Option<RefCountedTempFile>,
+    /// Number of times the rows of the partition have been partitioned
+    spill_depth: usize,
+}
+
+/// Spilling progress of a [`HashJoinStream`]
+#[derive(Default)]
+struct HashJoinSpillState {
+    /// Build side spill files, while the probe side is being spilled
+    build_files: Vec<Option<RefCountedTempFile>>,
+    /// Partitioner for the probe side, while it is being spilled
+    probe_partitioner: Option<SpillPartitioner>,
+    /// Partitions left to join
+    pending: Vec<SpilledPartition>,
+}
+
 /// Represents state of HashJoinStream
 ///
 /// Expected state transitions performed by HashJoinStream are:
 ///
 /// ```text
 ///
-///       WaitBuildSide
-///             │
-///             ▼
-///  ┌─► FetchProbeBatch ───► ExhaustedProbeSide ───► Completed
+///       WaitBuildSide ───────────────────► SpillProbeSide
+///             │  ▲                               │
+///             │  └─────── LoadSpilledPartition ◄─┤
+///             ▼                     ▲            │
+///  ┌─► FetchProbeBatch ───► ExhaustedProbeSide ──┴─► Completed
 ///  │          │
 ///  │          ▼
 ///  └─ ProcessProbeBatch
 ///
 /// ```
+///
+/// `SpillProbeSide` and `LoadSpilledPartition` are only reached when the
+/// build side does not fit in memory and has been spilled.
 #[derive(Debug, Clone)]
 enum HashJoinStreamState {
     /// Initial state for HashJoinStream indicating that build-side data not collected yet
@@ -1146,6 +1604,11 @@ enum HashJoinStreamState {
     ProcessProbeBatch(ProcessProbeBatchState),
     /// Indicates that probe-side has been fully processed
     ExhaustedProbeSide,
+    /// Indicates that build-side has been spilled, and probe-side is being spilled
+    /// with the same partitioning
+    SpillProbeSide,
+    /// Indicates that the next spilled partition is ready to be loaded
+    LoadSpilledPartition,
     /// Indicates that HashJoinStream execution is completed
     Completed,
 }
@@ -1220,6 +1683,18 @@ struct HashJoinStream {
     hashes_buffer: Vec<u64>,
     /// Specifies whether the right side has an ordering to potentially preserve
     right_side_ordered: bool,
+    /// Spilling configuration, `None` if the stream can not spill
+    spill_config: Option<HashJoinSpillConfig>,
+    /// Spill files of the stream
+    spill_state: HashJoinSpillState,
+    /// Number of times the current build and probe sides have been partitioned,
+    /// `0` for the original inputs
+    spill_depth: usize,
+    /// Whether to poll the probe side before collecting the build side, so a
+    /// skew-aware join finds the heavy hitters the build side waits for
+    start_probe_side: bool,
+    /// Probe side result polled before collecting the build side
+    peeked_probe_batch: Option<Option<Result<RecordBatch>>>,
 }
 
 impl RecordBatchStream for HashJoinStream {
@@ -1389,6 +1864,12 @@ impl HashJoinStream {
                 HashJoinStreamState::ExhaustedProbeSide => {
                     handle_state!(self.process_unmatched_build_batch())
                 }
+                HashJoinStreamState::SpillProbeSide => {
+                    handle_state!(ready!(self.spill_probe_batch(cx)))
+                }
+                HashJoinStreamState::LoadSpilledPartition => {
+                    handle_state!(self.load_spilled_partition())
+                }
                 HashJoinStreamState::Completed => Poll::Ready(None),
             };
         }
@@ -1396,26 +1877,169 @@ impl HashJoinStream {
 
     /// Collects build-side data by polling `OnceFut` future from initialized build-side
     ///
-    /// Updates build-side to `Ready`, and state to `FetchProbeSide`
+    /// Updates build-side to `Ready`, and state to `FetchProbeSide`. If build-side
+    /// has been spilled, updates state to `SpillProbeSide` instead
     fn collect_build_side(
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        if std::mem::take(&mut self.start_probe_side) {
+            if let Poll::Ready(next) = self.right.poll_next_unpin(cx) {
+                self.peeked_probe_batch = Some(next);
+            }
+        }
+
         let build_timer = self.join_metrics.build_time.timer();
         // build hash table from left (build) side, if not yet done
-        let left_data = ready!(self
+        let build_side_data = ready!(self
             .build_side
             .try_as_initial_mut()?
             .left_fut
             .get_shared(cx))?;
         build_timer.done();
 
-        self.state = HashJoinStreamState::FetchProbeBatch;
-        self.build_side = BuildSide::Ready(BuildSideReadyState { left_data });
+        match build_side_data.as_ref() {
+            BuildSideData::InMemory(left_data) => {
+                self.state = HashJoinStreamState::FetchProbeBatch;
+                self.build_side = BuildSide::Ready(BuildSideReadyState {
+                    left_data: Arc::clone(left_data),
+                });
+            }
+            BuildSideData::Spilled(build_files) => {
+                let Some(spill_config) = &self.spill_config else {
+                    return Poll::Ready(internal_err!(
+                        "Build side spilled by a hash join stream that can not spill"
+                    ));
+                };
+                self.spill_state.build_files = std::mem::take(&mut build_files.lock());
+                self.spill_state.probe_partitioner = Some(SpillPartitioner::new(
+                    self.on_right.clone(),
+                    self.right.schema(),
+                    self.spill_depth,
+                    spill_config,
+                ));
+                self.state = HashJoinStreamState::SpillProbeSide;
+            }
+        }
+
+        Poll::Ready(Ok(StatefulStreamResult::Continue))
+    }
+
+    /// Fetches next batch from probe-side and appends it to the probe-side spill files
+    ///
+    /// Once probe-side is exhausted, queues the spilled partitions for joining and
+    /// updates state to `LoadSpilledPartition`
+    fn spill_probe_batch(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        match ready!(self.poll_probe_batch(cx)) {
+            Some(Ok(batch)) => {
+                let Some(partitioner) = self.spill_state.probe_partitioner.as_mut()
+                else {
+                    return Poll::Ready(internal_err!("Expected probe side partitioner"));
+                };
+                if self.spill_depth == 0 {
+                    self.join_metrics.input_batches.add(1);
+                    self.join_metrics.input_rows.add(batch.num_rows());
+                }
+                partitioner.write(&batch)?;
+            }
+            Some(Err(err)) => return Poll::Ready(Err(err)),
+            None => {
+                let probe_files = self
+                    .spill_state
+                    .probe_partitioner
+                    .take()
+                    .map_or_else(|| Ok(vec![]), SpillPartitioner::finish)?;
+                let build_files = std::mem::take(&mut self.spill_state.build_files);
+                // Partitions are popped from the back, so push them in reverse order
+                let partitions = build_files
+                    .into_iter()
+                    .zip(probe_files)
+                    .filter(|(build, probe)| build.is_some() || probe.is_some())
+                    .map(|(build, probe)| SpilledPartition {
+                        build,
+                        probe,
+                        spill_depth: self.spill_depth + 1,
+                    })
+                    .rev();
+                self.spill_state.pending.extend(partitions);
+                self.state = self.build_side_completed_state();
+            }
+        };
 
         Poll::Ready(Ok(StatefulStreamResult::Continue))
     }
 
+    /// Replaces build and probe sides with the next spilled partition
+    ///
+    /// Updates build-side to `Initial`, and state to `WaitBuildSide`
+    fn load_spilled_partition(
+        &mut self,
+    ) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
+        let (Some(partition), Some(spill_config)) =
+            (self.spill_state.pending.pop(), self.spill_config.clone())
+        else {
+            return internal_err!("Expected spilled partition to load");
+        };
+
+        // Release the memory of the previous partition
+        if let BuildSide::Ready(build_side) = &self.build_side {
+            self.join_metrics
+                .build_mem_used
+                .sub(build_side.left_data.memory_size());
+        }
+
+        self.right = read_spill_file(partition.probe, self.right.schema())?;
+        let left =
+            read_spill_file(partition.build, Arc::clone(&spill_config.left_schema))?;
+        let reservation =
+            MemoryConsumer::new(format!("HashJoinInput[{}]", spill_config.partition))
+                .register(spill_config.context.memory_pool());
+        let spill =
+            (partition.spill_depth < MAX_SPILL_DEPTH).then(|| spill_config.clone());
+        let left_fut = OnceFut::new(collect_build_input(
+            left,
+            spill_config.on_left,
+            self.random_state.clone(),
+            self.join_metrics.clone(),
+            reservation,
+            need_produce_result_in_final(self.join_type),
+            1,
+            partition.spill_depth,
+            spill,
+        ));
+
+        self.spill_depth = partition.spill_depth;
+        self.build_side = BuildSide::Initial(BuildSideInitialState { left_fut });
+        self.state = HashJoinStreamState::WaitBuildSide;
+
+        Ok(StatefulStreamResult::Continue)
+    }
+
+    /// Returns the state following the processing of the current build-side:
+    /// `LoadSpilledPartition` if spilled partitions are left, `Completed` otherwise
+    fn build_side_completed_state(&self) -> HashJoinStreamState {
+        if self.spill_state.pending.is_empty() {
+            HashJoinStreamState::Completed
+        } else {
+            HashJoinStreamState::LoadSpilledPartition
+        }
+    }
+
+    /// Polls the next batch from probe-side, or returns the one polled before
+    /// collecting the build side
+    fn poll_probe_batch(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        match self.peeked_probe_batch.take() {
+            Some(next) => Poll::Ready(next),
+            None => self.right.poll_next_unpin(cx),
+        }
+    }
+
     /// Fetches next batch from probe-side
     ///
     /// If non-empty batch has been fetched, updates state to `ProcessProbeBatchState`,
@@ -1424,7 +2048,7 @@ impl HashJoinStream {
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
-        match ready!(self.right.poll_next_unpin(cx)) {
+        match ready!(self.poll_probe_batch(cx)) {
             None => {
                 self.state = HashJoinStreamState::ExhaustedProbeSide;
             }
@@ -1440,8 +2064,10 @@ impl HashJoinStream {
                 self.hashes_buffer.resize(batch.num_rows(), 0);
                 create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
 
-                self.join_metrics.input_batches.add(1);
-                self.join_metrics.input_rows.add(batch.num_rows());
+                if self.spill_depth == 0 {
+                    self.join_metrics.input_batches.add(1);
+                    self.join_metrics.input_rows.add(batch.num_rows());
+                }
 
                 self.state =
                     HashJoinStreamState::ProcessProbeBatch(ProcessProbeBatchState {
@@ -1569,20 +2195,21 @@ impl HashJoinStream {
 
     /// Processes unmatched build-side rows for certain join types and produces output batch
     ///
-    /// Updates state to `Completed`
+    /// Updates state to `Completed`, or to `LoadSpilledPartition` if spilled partitions
+    /// are left
     fn process_unmatched_build_batch(
         &mut self,
     ) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
         let timer = self.join_metrics.join_time.timer();
 
         if !need_produce_result_in_final(self.join_type) {
-            self.state = HashJoinStreamState::Completed;
+            self.state = self.build_side_completed_state();
             return Ok(StatefulStreamResult::Continue);
         }
 
         let build_side = self.build_side.try_as_ready()?;
         if !build_side.left_data.report_probe_completed() {
-            self.state = HashJoinStreamState::Completed;
+            self.state = self.build_side_completed_state();
             return Ok(StatefulStreamResult::Continue);
         }
 
@@ -1612,7 +2239,7 @@ impl HashJoinStream {
         }
         timer.done();
 
-        self.state = HashJoinStreamState::Completed;
+        self.state = self.build_side_completed_state();
 
         Ok(StatefulStreamResult::Ready(Some(result?)))
     }
@@ -1639,22 +2266,26 @@ impl EmbeddedProjection for HashJoinExec {
 mod tests {
     use super::*;
     use crate::memory::MemorySourceConfig;
+    use crate::repartition::JoinSkewSide;
     use crate::{
         common, expressions::Column, repartition::RepartitionExec, test::build_table_i32,
         test::exec::MockExec,
     };
 
+    use arrow::array::AsArray;
     use arrow::array::{Date32Array, Int32Array, StructArray};
     use arrow::buffer::NullBuffer;
     use arrow::datatypes::{DataType, Field};
+    use arrow::util::pretty::pretty_format_batches;
     use datafusion_common::{
         assert_batches_eq, assert_batches_sorted_eq, assert_contains, exec_err,
         ScalarValue,
     };
     use datafusion_execution::config::SessionConfig;
-    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_execution::disk_manager::DiskManagerConfig;
+    use datafusion_execution::runtime_env::{RuntimeEnv, RuntimeEnvBuilder};
     use datafusion_expr::Operator;
-    use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
+    use datafusion_physical_expr::expressions::{lit, BinaryExpr, Literal};
     use datafusion_physical_expr::PhysicalExpr;
     use hashbrown::raw::RawTable;
     use rstest::*;
@@ -1883,6 +2514,84 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn partitioned_join_with_join_skew(
+        #[values(
+            JoinType::Inner,
+            JoinType::Right,
+            JoinType::RightSemi,
+            JoinType::RightAnti
+        )]
+        join_type: JoinType,
+    ) -> Result<()> {
+        let task_ctx = prepare_task_ctx(8);
+        let left = build_table(
+            ("a1", &vec![1, 2, 3, 4]),
+            ("b1", &vec![1, 1, 2, 4]),
+            ("c1", &vec![7, 8, 9, 10]),
+        );
+        // Most rows of the probe side have the join key 1
+        let mut b2 = vec![1; 20];
+        b2.extend([2, 3]);
+        let right = build_table(
+            ("a2", &(0..22).collect()),
+            ("b2", &b2),
+            ("c2", &(0..22).map(|i| i * 10).collect()),
+        );
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+        )];
+
+        let (_, expected) = partitioned_join_collect(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            on.clone(),
+            &join_type,
+            false,
+            Arc::clone(&task_ctx),
+        )
+        .await?;
+
+        let join_skew = Arc::new(JoinSkew::new());
+        let repartition = |input, expr, side| {
+            RepartitionExec::try_new(input, Partitioning::Hash(vec![expr], 4))
+                .map(|exec| Arc::new(exec.with_join_skew(side, Arc::clone(&join_skew))))
+        };
+        let join = HashJoinExec::try_new(
+            repartition(left, Arc::clone(&on[0].0), JoinSkewSide::Build)?,
+            repartition(right, Arc::clone(&on[0].1), JoinSkewSide::Probe)?,
+            on,
+            None,
+            &join_type,
+            None,
+            PartitionMode::Partitioned,
+            false,
+        )?
+        .with_join_skew(Some(Arc::clone(&join_skew)));
+        assert_eq!(join.properties().output_partitioning().partition_count(), 4);
+        assert!(!matches!(
+            join.properties().output_partitioning(),
+            Partitioning::Hash(_, _)
+        ));
+
+        let mut batches = vec![];
+        for partition in 0..4 {
+            let stream = join.execute(partition, Arc::clone(&task_ctx))?;
+            batches.extend(common::collect(stream).await?);
+        }
+        // The heavy hitter was spread over the partitions
+        let heavy_hitters = join_skew.heavy_hitters().await?;
+        assert_eq!(heavy_hitters.len(), 1);
+
+        let expected = pretty_format_batches(&expected)?.to_string();
+        let expected = expected.lines().collect::<Vec<_>>();
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
     #[apply(batch_sizes)]
     #[tokio::test]
     async fn partitioned_join_inner_one(batch_size: usize) -> Result<()> {
@@ -4068,8 +4777,10 @@ mod tests {
         ];
 
         for join_type in join_types {
+            // Disable spilling, so that the build side must fit in memory
             let runtime = RuntimeEnvBuilder::new()
                 .with_memory_limit(100, 1.0)
+                .with_disk_manager(DiskManagerConfig::Disabled)
                 .build_arc()?;
             let session_config = SessionConfig::default().with_batch_size(50);
             let task_ctx = TaskContext::default()
@@ -4107,6 +4818,129 @@ mod tests {
         Ok(())
     }
 
+    /// Builds a table of `num_batches` batches of 100 rows, with join keys
+    /// `b` computed by `key` from the row number
+    fn build_spill_table(
+        prefix: &str,
+        num_batches: i32,
+        key: impl Fn(i32) -> i32,
+    ) -> Arc<dyn ExecutionPlan> {
+        let batches = (0..num_batches)
+            .map(|batch| {
+                let rows = (batch * 100..(batch + 1) * 100).collect::<Vec<_>>();
+                build_table_i32(
+                    (&format!("a{prefix}"), &rows),
+                    (
+                        &format!("b{prefix}"),
+                        &rows.iter().map(|i| key(*i)).collect(),
+                    ),
+                    (&format!("c{prefix}"), &rows),
+                )
+            })
+            .collect::<Vec<_>>();
+        let schema = batches[0].schema();
+        MemorySourceConfig::try_new_exec(&[batches], schema, None).unwrap()
+    }
+
+    /// Runs a partitioned hash join of `left` and `right` with `runtime`,
+    /// returning its sorted output lines and its spill count
+    async fn partitioned_join_with_runtime(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        join_type: JoinType,
+        runtime: Arc<RuntimeEnv>,
+    ) -> Result<(Vec<String>, usize)> {
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+        )];
+        let join = HashJoinExec::try_new(
+            Arc::clone(left),
+            Arc::clone(right),
+            on,
+            None,
+            &join_type,
+            None,
+            PartitionMode::Partitioned,
+            false,
+        )?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        let batches = common::collect(join.execute(0, task_ctx)?).await?;
+
+        let mut lines = pretty_format_batches(&batches)?
+            .to_string()
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        lines.sort_unstable();
+        let spill_count = join.metrics().unwrap().spill_count().unwrap();
+        Ok((lines, spill_count))
+    }
+
+    #[tokio::test]
+    async fn partitioned_join_spill() -> Result<()> {
+        // Keys of the build side repeat twice, and only partially overlap with the
+        // keys of the probe side
+        let left = build_spill_table("1", 20, |i| i % 1000);
+        let right = build_spill_table("2", 20, |i| i % 1500 + 500);
+
+        let join_types = vec![
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+            JoinType::LeftMark,
+        ];
+
+        for join_type in join_types {
+            let (expected, spill_count) = partitioned_join_with_runtime(
+                &left,
+                &right,
+                join_type,
+                Arc::new(RuntimeEnv::default()),
+            )
+            .await?;
+            assert_eq!(spill_count, 0);
+
+            let runtime = RuntimeEnvBuilder::new()
+                .with_memory_limit(16 * 1024, 1.0)
+                .with_disk_manager(DiskManagerConfig::NewOs)
+                .build_arc()?;
+            let (actual, spill_count) =
+                partitioned_join_with_runtime(&left, &right, join_type, runtime).await?;
+            assert!(spill_count > 0, "{join_type} join did not spill");
+            assert_eq!(expected, actual, "{join_type} join results differ");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn partitioned_join_spill_skewed_keys() -> Result<()> {
+        // All rows have the same key, so partitioning never makes the build side smaller
+        let left = build_spill_table("1", 20, |_| 1);
+        let right = build_spill_table("2", 1, |_| 1);
+
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(16 * 1024, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+        let err = partitioned_join_with_runtime(&left, &right, JoinType::Inner, runtime)
+            .await
+            .unwrap_err();
+
+        assert_contains!(
+            err.to_string(),
+            "Resources exhausted: Additional allocation failed with top memory consumers (across reservations) as: HashJoinInput[0]"
+        );
+
+        Ok(())
+    }
+
     fn build_table_struct(
         struct_name: &str,
         field_name_and_values: (&str, &Vec<Option<i32>>),
@@ -4205,6 +5039,132 @@ mod tests {
         Ok(())
     }
 
+    /// Joins two tables on `b1` in `mode`, and returns the dynamic filter the
+    /// join updated, evaluated on probe side values 3 to 8. If `sequential`,
+    /// the partitions of the join are executed one after the other.
+    async fn join_dynamic_filter_result(
+        mode: PartitionMode,
+        sequential: bool,
+        task_ctx: Arc<TaskContext>,
+    ) -> Result<(String, BooleanArray)> {
+        let left = build_table(
+            ("a1", &vec![1, 2, 3, 4]),
+            ("b1", &vec![4, 5, 5, 7]),
+            ("c1", &vec![7, 8, 9, 10]),
+        );
+        let right = build_table(
+            ("a2", &vec![10, 20, 30]),
+            ("b1", &vec![4, 6, 7]),
+            ("c2", &vec![70, 80, 90]),
+        );
+        let on_left = Arc::new(Column::new_with_schema("b1", &left.schema())?) as _;
+        let on_right = Arc::new(Column::new_with_schema("b1", &right.schema())?) as _;
+        let (left, right) = match mode {
+            PartitionMode::Partitioned => (
+                Arc::new(RepartitionExec::try_new(
+                    left,
+                    Partitioning::Hash(vec![Arc::clone(&on_left)], 2),
+                )?) as _,
+                Arc::new(RepartitionExec::try_new(
+                    right,
+                    Partitioning::Hash(vec![Arc::clone(&on_right)], 2),
+                )?) as _,
+            ),
+            _ => (left, right),
+        };
+        let probe_schema = right.schema();
+
+        let filter = Arc::new(DynamicFilterPhysicalExpr::new(
+            vec![Arc::clone(&on_right)],
+            lit(true),
+        ));
+        let join = HashJoinExec::try_new(
+            left,
+            right,
+            vec![(on_left, on_right)],
+            None,
+            &JoinType::Inner,
+            None,
+            mode,
+            false,
+        )?
+        .with_dynamic_filter(Some(Arc::clone(&filter)));
+        let join = Arc::new(join);
+        let batches = if sequential {
+            let mut batches = vec![];
+            for partition in 0..join.properties().output_partitioning().partition_count()
+            {
+                let stream = join.execute(partition, Arc::clone(&task_ctx))?;
+                batches.extend(common::collect(stream).await?);
+            }
+            batches
+        } else {
+            crate::collect(join, task_ctx).await?
+        };
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+
+        let probe = RecordBatch::try_new(
+            probe_schema,
+            vec![
+                Arc::new(Int32Array::from(vec![0; 6])),
+                Arc::new(Int32Array::from(vec![3, 4, 5, 6, 7, 8])),
+                Arc::new(Int32Array::from(vec![0; 6])),
+            ],
+        )?;
+        let result = filter.evaluate(&probe)?.into_array(probe.num_rows())?;
+        Ok((filter.current()?.to_string(), result.as_boolean().clone()))
+    }
+
+    #[tokio::test]
+    async fn join_dynamic_filter() -> Result<()> {
+        for mode in [PartitionMode::CollectLeft, PartitionMode::Partitioned] {
+            // The partitions must not wait for each other to update the filter
+            for sequential in [false, true] {
+                let (filter, result) = join_dynamic_filter_result(
+                    mode,
+                    sequential,
+                    Arc::new(TaskContext::default()),
+                )
+                .await?;
+                assert_contains!(
+                    &filter,
+                    "b1@1 >= 4 AND b1@1 <= 7 AND Use b1@1 IN (SET)"
+                );
+                assert_eq!(
+                    result,
+                    BooleanArray::from(vec![false, true, true, false, true, false]),
+                    "{mode:?}, sequential: {sequential}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_dynamic_filter_bloom_filter() -> Result<()> {
+        let mut session_config = SessionConfig::default();
+        session_config
+            .options_mut()
+            .execution
+            .join_dynamic_filter_in_list_threshold = 2;
+        for mode in [PartitionMode::CollectLeft, PartitionMode::Partitioned] {
+            let task_ctx = Arc::new(
+                TaskContext::default().with_session_config(session_config.clone()),
+            );
+            let (filter, result) =
+                join_dynamic_filter_result(mode, false, task_ctx).await?;
+            assert_contains!(&filter, "b1@1 >= 4 AND b1@1 <= 7 AND bloom_filter([b1@1])");
+            // The bloom filter may let 6 through, but not 3 and 8, which are
+            // out of range
+            assert_eq!(
+                [0, 1, 2, 4, 5].map(|idx| result.value(idx)),
+                [false, true, true, true, false],
+                "{mode:?}"
+            );
+        }
+        Ok(())
+    }
+
     /// Returns the column names on the schema
     fn columns(schema: &Schema) -> Vec<String> {
         schema.fields().iter().map(|f| f.name().clone()).collect()
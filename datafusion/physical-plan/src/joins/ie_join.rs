@@ -0,0 +1,1227 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`IEJoinExec`]: joins whose filter contains two range conditions
+//! (inequality predicates) between the join inputs.
+
+use std::any::Any;
+use std::fmt::Formatter;
+use std::future::Future;
+use std::ops::Range;
+use std::sync::Arc;
+use std::task::Poll;
+
+use super::nested_loop_join::{
+    collect_left_input, JoinLeftData, NestedLoopJoinStreamState,
+};
+use super::utils::{
+    asymmetric_join_output_partitioning, get_final_indices_from_shared_bitmap,
+    need_produce_result_in_final, BatchSplitter, BatchTransformer, NoopBatchTransformer,
+    StatefulStreamResult,
+};
+use crate::execution_plan::{boundedness_from_children, EmissionType};
+use crate::joins::utils::{
+    adjust_indices_by_join_type, apply_join_filter_to_indices, build_batch_from_indices,
+    build_join_schema, check_join_is_valid, estimate_join_statistics,
+    BuildProbeJoinMetrics, ColumnIndex, JoinFilter, OnceAsync, OnceFut,
+};
+use crate::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use crate::{
+    handle_state, DisplayAs, DisplayFormatType, Distribution, ExecutionPlan,
+    ExecutionPlanProperties, PhysicalExpr, PlanProperties, RecordBatchStream,
+    SendableRecordBatchStream,
+};
+
+use arrow::array::{ArrayRef, UInt32Array, UInt64Array};
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{
+    exec_datafusion_err, internal_err, plan_err, JoinSide, Result, Statistics,
+};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::{JoinType, Operator};
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column};
+use datafusion_physical_expr::utils::{collect_columns, split_conjunction};
+use datafusion_physical_expr::PhysicalExprRef;
+
+use futures::{ready, Stream, StreamExt};
+
+/// A range condition `<left> <op> <right>` of an [`IEJoinExec`], where `<left>`
+/// and `<right>` are expressions over the left and right input respectively,
+/// and `<op>` is one of `<`, `<=`, `>` or `>=`.
+#[derive(Debug, Clone)]
+pub struct IEJoinCondition {
+    /// Expression over the left input
+    pub left: PhysicalExprRef,
+    /// Comparison operator
+    pub op: Operator,
+    /// Expression over the right input
+    pub right: PhysicalExprRef,
+}
+
+impl IEJoinCondition {
+    /// Whether a left value comparing to a right value with `ordering`
+    /// satisfies this condition
+    fn is_satisfied(&self, ordering: std::cmp::Ordering) -> bool {
+        match self.op {
+            Operator::Lt => ordering.is_lt(),
+            Operator::LtEq => ordering.is_le(),
+            Operator::Gt => ordering.is_gt(),
+            _ => ordering.is_ge(),
+        }
+    }
+}
+
+/// Left (build-side) data: the data collected as for [`NestedLoopJoinExec`],
+/// and the index of its values of both range conditions
+///
+/// [`NestedLoopJoinExec`]: crate::joins::NestedLoopJoinExec
+struct IEJoinLeftData {
+    /// Build-side data collected to single batch, and its visited rows
+    data: JoinLeftData,
+    /// Sorted build-side values of both range conditions
+    index: IEJoinIndex,
+    /// Memory reservation for tracking index
+    /// Cleared on `IEJoinLeftData` drop
+    _reservation: MemoryReservation,
+}
+
+/// Build-side values of the two range conditions, in the sort orders used by
+/// the IEJoin algorithm. Rows with a null value in either condition can never
+/// match and are not part of the index.
+struct IEJoinIndex {
+    /// Row-format values of the first condition, for all build-side rows
+    first_values: Rows,
+    /// Build-side rows ordered ascending on the first condition
+    first_order: Vec<u32>,
+    /// Row-format values of the second condition, for all build-side rows
+    second_values: Rows,
+    /// Build-side rows ordered ascending on the second condition
+    second_order: Vec<u32>,
+    /// Position of every build-side row in `second_order`
+    second_rank: Vec<u32>,
+}
+
+impl IEJoinIndex {
+    fn try_new(batch: &RecordBatch, conditions: &[IEJoinCondition; 2]) -> Result<Self> {
+        let [first, second] = conditions;
+        let num_rows = batch.num_rows();
+        let first_array = first.left.evaluate(batch)?.into_array(num_rows)?;
+        let second_array = second.left.evaluate(batch)?.into_array(num_rows)?;
+        let first_values = convert_values(&first_array)?;
+        let second_values = convert_values(&second_array)?;
+
+        let valid_rows = (0..num_rows as u32)
+            .filter(|row| {
+                first_array.is_valid(*row as usize)
+                    && second_array.is_valid(*row as usize)
+            })
+            .collect::<Vec<_>>();
+        let mut first_order = valid_rows.clone();
+        first_order.sort_unstable_by(|a, b| {
+            first_values
+                .row(*a as usize)
+                .cmp(&first_values.row(*b as usize))
+        });
+        let mut second_order = valid_rows;
+        second_order.sort_unstable_by(|a, b| {
+            second_values
+                .row(*a as usize)
+                .cmp(&second_values.row(*b as usize))
+        });
+        let mut second_rank = vec![0; num_rows];
+        for (rank, row) in second_order.iter().enumerate() {
+            second_rank[*row as usize] = rank as u32;
+        }
+
+        Ok(Self {
+            first_values,
+            first_order,
+            second_values,
+            second_order,
+            second_rank,
+        })
+    }
+
+    /// Size of the index in bytes
+    fn size(&self) -> usize {
+        self.first_values.size()
+            + self.second_values.size()
+            + (self.first_order.capacity()
+                + self.second_order.capacity()
+                + self.second_rank.capacity())
+                * size_of::<u32>()
+    }
+}
+
+/// Converts the values of a range condition to the row format, so that values
+/// of any comparable type can be compared with each other.
+fn convert_values(array: &ArrayRef) -> Result<Rows> {
+    let converter = RowConverter::new(vec![SortField::new(array.data_type().clone())])?;
+    Ok(converter.convert_columns(&[Arc::clone(array)])?)
+}
+
+/// A bitmap over the positions of build-side rows in the order of the second
+/// range condition, with a summary bit for every 64 words which allows
+/// skipping empty regions when iterating over set bits.
+struct RankBitmap {
+    words: Vec<u64>,
+    summary: Vec<u64>,
+}
+
+impl RankBitmap {
+    fn new(len: usize) -> Self {
+        let num_words = len.div_ceil(64);
+        Self {
+            words: vec![0; num_words],
+            summary: vec![0; num_words.div_ceil(64)],
+        }
+    }
+
+    fn clear(&mut self) {
+        self.words.fill(0);
+        self.summary.fill(0);
+    }
+
+    fn set(&mut self, position: usize) {
+        let word = position / 64;
+        self.words[word] |= 1 << (position % 64);
+        self.summary[word / 64] |= 1 << (word % 64);
+    }
+
+    /// Calls `f` for every set position in `range`, in ascending order
+    fn for_each_set(&self, range: Range<usize>, mut f: impl FnMut(usize)) {
+        if range.is_empty() {
+            return;
+        }
+        let first_word = range.start / 64;
+        let last_word = (range.end - 1) / 64;
+        let mut word_idx = first_word;
+        while word_idx <= last_word {
+            if word_idx % 64 == 0 && self.summary[word_idx / 64] == 0 {
+                word_idx += 64;
+                continue;
+            }
+            let mut word = self.words[word_idx];
+            if word_idx == first_word {
+                word &= u64::MAX << (range.start % 64);
+            }
+            if word_idx == last_word && range.end % 64 != 0 {
+                word &= u64::MAX >> (64 - range.end % 64);
+            }
+            while word != 0 {
+                f(word_idx * 64 + word.trailing_zeros() as usize);
+                word &= word - 1;
+            }
+            word_idx += 1;
+        }
+    }
+}
+
+#[allow(rustdoc::private_intra_doc_links)]
+/// IEJoinExec is a build-probe join operator for joins without equijoin
+/// conditions, whose filter contains (at least) two range conditions between
+/// the join inputs, e.g. `l.ts >= r.start AND l.ts < r.end`. It produces the
+/// same result as a [`NestedLoopJoinExec`] with the same filter, but avoids
+/// evaluating the filter on the Cartesian product of its inputs.
+///
+/// [`NestedLoopJoinExec`]: crate::joins::NestedLoopJoinExec
+///
+/// Execution consists of following phases:
+///
+/// #### 1. Build phase
+/// Collecting build-side (LEFT) data into a single batch shared across all
+/// threads, like [`NestedLoopJoinExec`] does, and sorting the build-side values
+/// of both range conditions.
+///
+/// #### 2. Probe phase
+/// For every probe-side batch, the probe rows are sorted on the first range
+/// condition and visited in the order in which the set of build-side rows
+/// satisfying the first condition only grows. These build-side rows are marked
+/// in a bitmap ordered on the second range condition, so that the build-side
+/// rows satisfying both conditions for a probe row are the set bits of a
+/// contiguous range of this bitmap. This is the IEJoin algorithm described in
+/// [Lightning Fast and Space Efficient Inequality Joins]. The complete join
+/// filter is then evaluated on the candidate pairs only, after which visited
+/// build-side rows and join type specific output are handled in the same way
+/// as in [`NestedLoopJoinExec`].
+///
+/// #### 3. Producing unmatched build-side data
+/// As for [`NestedLoopJoinExec`], the last probe thread to complete produces
+/// the unmatched build-side rows if required by the join type.
+///
+/// [Lightning Fast and Space Efficient Inequality Joins]: https://vldb.org/pvldb/vol8/p2074-khayyat.pdf
+///
+/// # Clone / Shared State
+///
+/// Note this structure includes a [`OnceAsync`] that is used to coordinate the
+/// loading of the left side with the processing in each output stream.
+/// Therefore it can not be [`Clone`]
+#[derive(Debug)]
+pub struct IEJoinExec {
+    /// left side
+    pub(crate) left: Arc<dyn ExecutionPlan>,
+    /// right side
+    pub(crate) right: Arc<dyn ExecutionPlan>,
+    /// Filter which is applied while finding matching rows
+    pub(crate) filter: JoinFilter,
+    /// The two range conditions of `filter` used to find candidate rows
+    pub(crate) conditions: [IEJoinCondition; 2],
+    /// How the join is performed
+    pub(crate) join_type: JoinType,
+    /// The schema once the join is applied
+    join_schema: SchemaRef,
+    /// Future that consumes left input and sorts it on the range conditions
+    ///
+    /// This structure is *shared* across all output streams.
+    inner_table: OnceAsync<IEJoinLeftData>,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl IEJoinExec {
+    /// Try to create a new [`IEJoinExec`]
+    ///
+    /// # Error
+    /// This function errors when the filter does not contain two range
+    /// conditions between the inputs (see [`Self::range_conditions`]), or when
+    /// the join type is not supported.
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        filter: JoinFilter,
+        join_type: &JoinType,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        check_join_is_valid(&left_schema, &right_schema, &[])?;
+        if matches!(join_type, JoinType::LeftMark | JoinType::LeftAsof) {
+            return plan_err!("IEJoinExec does not support {join_type} joins");
+        }
+        let Some(conditions) =
+            Self::range_conditions(&filter, &left_schema, &right_schema)?
+        else {
+            return plan_err!(
+                "IEJoinExec requires a filter with two range conditions between its inputs, got {}",
+                filter.expression()
+            );
+        };
+
+        let (join_schema, column_indices) =
+            build_join_schema(&left_schema, &right_schema, join_type);
+        let join_schema = Arc::new(join_schema);
+        let cache =
+            Self::compute_properties(&left, &right, Arc::clone(&join_schema), *join_type);
+
+        Ok(IEJoinExec {
+            left,
+            right,
+            filter,
+            conditions,
+            join_type: *join_type,
+            join_schema,
+            inner_table: Default::default(),
+            column_indices,
+            metrics: Default::default(),
+            cache,
+        })
+    }
+
+    /// Extracts the first two range conditions between the join inputs from
+    /// the conjuncts of `filter`, i.e. comparisons using `<`, `<=`, `>` or `>=`
+    /// whose one side only references columns of the left input and whose other
+    /// side only references columns of the right input.
+    ///
+    /// Returns `None` if `filter` has less than two such conditions, or if their
+    /// values can not be compared in the row format.
+    pub fn range_conditions(
+        filter: &JoinFilter,
+        left_schema: &Schema,
+        right_schema: &Schema,
+    ) -> Result<Option<[IEJoinCondition; 2]>> {
+        let mut conditions = vec![];
+        for predicate in split_conjunction(filter.expression()) {
+            let Some(binary) = predicate.as_any().downcast_ref::<BinaryExpr>() else {
+                continue;
+            };
+            if !matches!(
+                binary.op(),
+                Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq
+            ) {
+                continue;
+            }
+            let condition = match (
+                expr_side(binary.left(), filter),
+                expr_side(binary.right(), filter),
+            ) {
+                (Some(JoinSide::Left), Some(JoinSide::Right)) => IEJoinCondition {
+                    left: rewrite_to_input(binary.left(), filter)?,
+                    op: *binary.op(),
+                    right: rewrite_to_input(binary.right(), filter)?,
+                },
+                (Some(JoinSide::Right), Some(JoinSide::Left)) => IEJoinCondition {
+                    left: rewrite_to_input(binary.right(), filter)?,
+                    op: binary.op().swap().unwrap(),
+                    right: rewrite_to_input(binary.left(), filter)?,
+                },
+                _ => continue,
+            };
+            let data_type = condition.left.data_type(left_schema)?;
+            if data_type != condition.right.data_type(right_schema)?
+                || !RowConverter::supports_fields(&[SortField::new(data_type)])
+            {
+                continue;
+            }
+            conditions.push(condition);
+            if conditions.len() == 2 {
+                let second = conditions.pop().unwrap();
+                let first = conditions.pop().unwrap();
+                return Ok(Some([first, second]));
+            }
+        }
+        Ok(None)
+    }
+
+    /// left side
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// right side
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Filter applied before join output
+    pub fn filter(&self) -> &JoinFilter {
+        &self.filter
+    }
+
+    /// The two range conditions used to find candidate rows
+    pub fn conditions(&self) -> &[IEJoinCondition; 2] {
+        &self.conditions
+    }
+
+    /// How the join is performed
+    pub fn join_type(&self) -> &JoinType {
+        &self.join_type
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        join_type: JoinType,
+    ) -> PlanProperties {
+        // Calculate equivalence properties:
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            right.equivalence_properties().clone(),
+            &join_type,
+            schema,
+            &Self::maintains_input_order(join_type),
+            None,
+            // No on columns in IEJoin
+            &[],
+        );
+
+        let output_partitioning =
+            asymmetric_join_output_partitioning(left, right, &join_type);
+
+        let emission_type = if left.boundedness().is_unbounded() {
+            EmissionType::Final
+        } else if right.pipeline_behavior() == EmissionType::Incremental {
+            match join_type {
+                // If we only need to generate matched rows from the probe side,
+                // we can emit rows incrementally.
+                JoinType::Inner
+                | JoinType::LeftSemi
+                | JoinType::RightSemi
+                | JoinType::Right
+                | JoinType::RightAnti => EmissionType::Incremental,
+                // If we need to generate unmatched rows from the *build side*,
+                // we need to emit them at the end.
+                JoinType::Left
+                | JoinType::LeftAnti
+                | JoinType::LeftMark
+                | JoinType::LeftAsof
+                | JoinType::Full => EmissionType::Both,
+            }
+        } else {
+            right.pipeline_behavior()
+        };
+
+        PlanProperties::new(
+            eq_properties,
+            output_partitioning,
+            emission_type,
+            boundedness_from_children([left, right]),
+        )
+    }
+
+    /// Returns a vector indicating whether the left and right inputs maintain
+    /// their order. As for `NestedLoopJoinExec`, the right (probe-side) input's
+    /// order is maintained for INNER, RIGHT, RIGHT ANTI, and RIGHT SEMI joins.
+    fn maintains_input_order(join_type: JoinType) -> Vec<bool> {
+        vec![
+            false,
+            matches!(
+                join_type,
+                JoinType::Inner
+                    | JoinType::Right
+                    | JoinType::RightAnti
+                    | JoinType::RightSemi
+            ),
+        ]
+    }
+}
+
+/// Returns the side of the join input referenced by all columns of `expr`,
+/// or `None` if `expr` references no columns or columns of both sides.
+fn expr_side(expr: &Arc<dyn PhysicalExpr>, filter: &JoinFilter) -> Option<JoinSide> {
+    let mut sides = collect_columns(expr)
+        .into_iter()
+        .map(|column| filter.column_indices()[column.index()].side);
+    let side = sides.next()?;
+    sides.all(|s| s == side).then_some(side)
+}
+
+/// Rewrites `expr` over the intermediate schema of `filter` to an expression
+/// over the join input it references.
+fn rewrite_to_input(
+    expr: &Arc<dyn PhysicalExpr>,
+    filter: &JoinFilter,
+) -> Result<PhysicalExprRef> {
+    Arc::clone(expr)
+        .transform(|expr| {
+            if let Some(column) = expr.as_any().downcast_ref::<Column>() {
+                let index = filter.column_indices()[column.index()].index;
+                let column: Arc<dyn PhysicalExpr> =
+                    Arc::new(Column::new(column.name(), index));
+                Ok(Transformed::yes(column))
+            } else {
+                Ok(Transformed::no(expr))
+            }
+        })
+        .data()
+}
+
+impl DisplayAs for IEJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "IEJoinExec: join_type={:?}, filter={}",
+                    self.join_type,
+                    self.filter.expression()
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for IEJoinExec {
+    fn name(&self) -> &'static str {
+        "IEJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![
+            Distribution::SinglePartition,
+            Distribution::UnspecifiedDistribution,
+        ]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        Self::maintains_input_order(self.join_type)
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(IEJoinExec::try_new(
+            Arc::clone(&children[0]),
+            Arc::clone(&children[1]),
+            self.filter.clone(),
+            &self.join_type,
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+
+        // Initialization reservation for load of inner table
+        let load_reservation = MemoryConsumer::new(format!("IEJoinLoad[{partition}]"))
+            .register(context.memory_pool());
+
+        let inner_table = self.inner_table.once(|| {
+            let index_reservation = load_reservation.new_empty();
+            let left_data = collect_left_input(
+                Arc::clone(&self.left),
+                Arc::clone(&context),
+                join_metrics.clone(),
+                load_reservation,
+                need_produce_result_in_final(self.join_type),
+                self.right().output_partitioning().partition_count(),
+            );
+            build_left_index(
+                left_data,
+                self.conditions.clone(),
+                join_metrics.clone(),
+                index_reservation,
+            )
+        });
+        // Reservation for the candidate pairs of each probe-side batch
+        let reservation = MemoryConsumer::new(format!("IEJoinStream[{partition}]"))
+            .register(context.memory_pool());
+
+        let batch_size = context.session_config().batch_size();
+        let enforce_batch_size_in_joins =
+            context.session_config().enforce_batch_size_in_joins();
+
+        let outer_table = self.right.execute(partition, context)?;
+
+        // Right side has an order and it is maintained during operation.
+        let right_side_ordered =
+            self.maintains_input_order()[1] && self.right.output_ordering().is_some();
+
+        if enforce_batch_size_in_joins {
+            Ok(Box::pin(IEJoinStream {
+                schema: self.schema(),
+                filter: self.filter.clone(),
+                conditions: self.conditions.clone(),
+                join_type: self.join_type,
+                outer_table,
+                inner_table,
+                column_indices: self.column_indices.clone(),
+                join_metrics,
+                right_side_ordered,
+                state: NestedLoopJoinStreamState::WaitBuildSide,
+                batch_transformer: BatchSplitter::new(batch_size),
+                left_data: None,
+                bitmap: RankBitmap::new(0),
+                reservation,
+            }))
+        } else {
+            Ok(Box::pin(IEJoinStream {
+                schema: self.schema(),
+                filter: self.filter.clone(),
+                conditions: self.conditions.clone(),
+                join_type: self.join_type,
+                outer_table,
+                inner_table,
+                column_indices: self.column_indices.clone(),
+                join_metrics,
+                right_side_ordered,
+                state: NestedLoopJoinStreamState::WaitBuildSide,
+                batch_transformer: NoopBatchTransformer::new(),
+                left_data: None,
+                bitmap: RankBitmap::new(0),
+                reservation,
+            }))
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        estimate_join_statistics(
+            Arc::clone(&self.left),
+            Arc::clone(&self.right),
+            vec![],
+            &self.join_type,
+            &self.join_schema,
+        )
+    }
+}
+
+/// Sorts the build-side data collected by `left_data` on both range conditions
+async fn build_left_index(
+    left_data: impl Future<Output = Result<JoinLeftData>>,
+    conditions: [IEJoinCondition; 2],
+    join_metrics: BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+) -> Result<IEJoinLeftData> {
+    let data = left_data.await?;
+    let index = IEJoinIndex::try_new(data.batch(), &conditions)?;
+    reservation.try_grow(index.size())?;
+    join_metrics.build_mem_used.add(index.size());
+
+    Ok(IEJoinLeftData {
+        data,
+        index,
+        _reservation: reservation,
+    })
+}
+
+/// A stream that issues [RecordBatch]es as they arrive from the right of the join.
+struct IEJoinStream<T> {
+    /// Input schema
+    schema: Arc<Schema>,
+    /// join filter
+    filter: JoinFilter,
+    /// range conditions of the join filter
+    conditions: [IEJoinCondition; 2],
+    /// type of the join
+    join_type: JoinType,
+    /// the outer table data of the join
+    outer_table: SendableRecordBatchStream,
+    /// the inner table data of the join
+    inner_table: OnceFut<IEJoinLeftData>,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+    /// Join execution metrics
+    join_metrics: BuildProbeJoinMetrics,
+    /// Whether the right side is ordered
+    right_side_ordered: bool,
+    /// Current state of the stream
+    state: NestedLoopJoinStreamState,
+    /// Transforms the output batch before returning.
+    batch_transformer: T,
+    /// Result of the left data future
+    left_data: Option<Arc<IEJoinLeftData>>,
+    /// Build-side rows satisfying the first range condition for the current
+    /// probe row, by their position in the order of the second condition
+    bitmap: RankBitmap,
+    /// Memory reservation for the candidate pairs of the probe-side batch
+    reservation: MemoryReservation,
+}
+
+impl<T: BatchTransformer> IEJoinStream<T> {
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        loop {
+            return match self.state {
+                NestedLoopJoinStreamState::WaitBuildSide => {
+                    handle_state!(ready!(self.collect_build_side(cx)))
+                }
+                NestedLoopJoinStreamState::FetchProbeBatch => {
+                    handle_state!(ready!(self.fetch_probe_batch(cx)))
+                }
+                NestedLoopJoinStreamState::ProcessProbeBatch(_) => {
+                    handle_state!(self.process_probe_batch())
+                }
+                NestedLoopJoinStreamState::ExhaustedProbeSide => {
+                    handle_state!(self.process_unmatched_build_batch())
+                }
+                NestedLoopJoinStreamState::Completed => Poll::Ready(None),
+            };
+        }
+    }
+
+    fn collect_build_side(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        let build_timer = self.join_metrics.build_time.timer();
+        // sort left (build) side, if not yet done
+        let left_data = ready!(self.inner_table.get_shared(cx))?;
+        build_timer.done();
+
+        self.bitmap = RankBitmap::new(left_data.index.second_order.len());
+        self.left_data = Some(left_data);
+        self.state = NestedLoopJoinStreamState::FetchProbeBatch;
+
+        Poll::Ready(Ok(StatefulStreamResult::Continue))
+    }
+
+    /// Fetches next batch from probe-side
+    ///
+    /// If a non-empty batch has been fetched, updates state to
+    /// `ProcessProbeBatchState`, otherwise updates state to `ExhaustedProbeSide`.
+    fn fetch_probe_batch(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        match ready!(self.outer_table.poll_next_unpin(cx)) {
+            None => {
+                self.state = NestedLoopJoinStreamState::ExhaustedProbeSide;
+            }
+            Some(Ok(right_batch)) => {
+                self.state = NestedLoopJoinStreamState::ProcessProbeBatch(right_batch);
+            }
+            Some(Err(err)) => return Poll::Ready(Err(err)),
+        };
+
+        Poll::Ready(Ok(StatefulStreamResult::Continue))
+    }
+
+    /// Joins current probe batch with build-side data and produces batch with
+    /// matched output, updates state to `FetchProbeBatch`.
+    fn process_probe_batch(
+        &mut self,
+    ) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
+        let Some(left_data) = self.left_data.clone() else {
+            return internal_err!(
+                "Expected left_data to be Some in ProcessProbeBatch state"
+            );
+        };
+
+        match self.batch_transformer.next() {
+            None => {
+                let batch = self.state.try_as_process_probe_batch()?.clone();
+                // Setting up timer & updating input metrics
+                self.join_metrics.input_batches.add(1);
+                self.join_metrics.input_rows.add(batch.num_rows());
+                let join_time = self.join_metrics.join_time.clone();
+                let timer = join_time.timer();
+
+                let result = self.join_left_and_right_batch(&left_data, &batch);
+                timer.done();
+
+                self.batch_transformer.set_batch(result?);
+                Ok(StatefulStreamResult::Continue)
+            }
+            Some((batch, last)) => {
+                if last {
+                    self.state = NestedLoopJoinStreamState::FetchProbeBatch;
+                }
+
+                self.join_metrics.output_batches.add(1);
+                self.join_metrics.output_rows.add(batch.num_rows());
+                Ok(StatefulStreamResult::Ready(Some(batch)))
+            }
+        }
+    }
+
+    /// Processes unmatched build-side rows for certain join types and produces
+    /// output batch, updates state to `Completed`.
+    fn process_unmatched_build_batch(
+        &mut self,
+    ) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
+        let Some(left_data) = self.left_data.clone() else {
+            return internal_err!(
+                "Expected left_data to be Some in ExhaustedProbeSide state"
+            );
+        };
+        if need_produce_result_in_final(self.join_type) {
+            // At this stage `visited_left_side` won't be updated, so it's
+            // safe to report about probe completion.
+            if !left_data.data.report_probe_completed() {
+                self.state = NestedLoopJoinStreamState::Completed;
+                return Ok(StatefulStreamResult::Ready(None));
+            };
+
+            // Only setting up timer, input is exhausted
+            let timer = self.join_metrics.join_time.timer();
+            // use the global left bitmap to produce the left indices and right indices
+            let (left_side, right_side) = get_final_indices_from_shared_bitmap(
+                left_data.data.bitmap(),
+                self.join_type,
+            );
+            let empty_right_batch = RecordBatch::new_empty(self.outer_table.schema());
+            // use the left and right indices to produce the batch result
+            let result = build_batch_from_indices(
+                &self.schema,
+                left_data.data.batch(),
+                &empty_right_batch,
+                &left_side,
+                &right_side,
+                &self.column_indices,
+                JoinSide::Left,
+            );
+            self.state = NestedLoopJoinStreamState::Completed;
+
+            // Recording time
+            if result.is_ok() {
+                timer.done();
+            }
+
+            Ok(StatefulStreamResult::Ready(Some(result?)))
+        } else {
+            // end of the join loop
+            self.state = NestedLoopJoinStreamState::Completed;
+            Ok(StatefulStreamResult::Ready(None))
+        }
+    }
+
+    fn join_left_and_right_batch(
+        &mut self,
+        left_data: &IEJoinLeftData,
+        right_batch: &RecordBatch,
+    ) -> Result<RecordBatch> {
+        let (left_side, right_side) = self
+            .build_join_indices(left_data, right_batch)
+            .map_err(|e| {
+                exec_datafusion_err!(
+                    "Fail to build join indices in IEJoinExec, error: {e}"
+                )
+            })?;
+
+        // set the left bitmap
+        // and only full join need the left bitmap
+        if need_produce_result_in_final(self.join_type) {
+            let mut bitmap = left_data.data.bitmap().lock();
+            left_side.values().iter().for_each(|x| {
+                bitmap.set_bit(*x as usize, true);
+            });
+        }
+        // adjust the two side indices base on the join type
+        let (left_side, right_side) = adjust_indices_by_join_type(
+            left_side,
+            right_side,
+            0..right_batch.num_rows(),
+            self.join_type,
+            self.right_side_ordered,
+        )?;
+
+        build_batch_from_indices(
+            &self.schema,
+            left_data.data.batch(),
+            right_batch,
+            &left_side,
+            &right_side,
+            &self.column_indices,
+            JoinSide::Left,
+        )
+    }
+
+    /// Finds the pairs of build-side and probe-side rows satisfying both range
+    /// conditions, and applies the join filter to them. The returned indices
+    /// are ordered by the probe-side row.
+    fn build_join_indices(
+        &mut self,
+        left_data: &IEJoinLeftData,
+        right_batch: &RecordBatch,
+    ) -> Result<(UInt64Array, UInt32Array)> {
+        let [first, second] = &self.conditions;
+        let index = &left_data.index;
+        let num_rows = right_batch.num_rows();
+        let first_array = first.right.evaluate(right_batch)?.into_array(num_rows)?;
+        let second_array = second.right.evaluate(right_batch)?.into_array(num_rows)?;
+        let first_values = convert_values(&first_array)?;
+        let second_values = convert_values(&second_array)?;
+
+        // Visit the probe rows in the order in which the build-side rows
+        // satisfying the first condition only grow: ascending for `<` and `<=`,
+        // descending for `>` and `>=`
+        let ascending = matches!(first.op, Operator::Lt | Operator::LtEq);
+        let mut probe_order = (0..num_rows)
+            .filter(|row| first_array.is_valid(*row) && second_array.is_valid(*row))
+            .collect::<Vec<_>>();
+        probe_order.sort_unstable_by(|a, b| {
+            let ordering = first_values.row(*a).cmp(&first_values.row(*b));
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+
+        self.bitmap.clear();
+        let num_build_rows = index.first_order.len();
+        let mut inserted = 0;
+        // The candidate pairs of (probe-side, build-side) rows
+        let mut pairs = Vec::with_capacity(num_rows);
+        let pair_size = size_of::<(u32, u32)>();
+        self.reservation.try_resize(pairs.capacity() * pair_size)?;
+        for probe_row in probe_order {
+            let first_value = first_values.row(probe_row);
+            while inserted < num_build_rows {
+                let build_row = if ascending {
+                    index.first_order[inserted]
+                } else {
+                    index.first_order[num_build_rows - 1 - inserted]
+                } as usize;
+                let ordering = index.first_values.row(build_row).cmp(&first_value);
+                if !first.is_satisfied(ordering) {
+                    break;
+                }
+                self.bitmap.set(index.second_rank[build_row] as usize);
+                inserted += 1;
+            }
+
+            let second_value = second_values.row(probe_row);
+            let lower_or_equal = index.second_order.partition_point(|build_row| {
+                index.second_values.row(*build_row as usize) <= second_value
+            });
+            let lower =
+                index.second_order[..lower_or_equal].partition_point(|build_row| {
+                    index.second_values.row(*build_row as usize) < second_value
+                });
+            let ranks = match second.op {
+                Operator::Lt => 0..lower,
+                Operator::LtEq => 0..lower_or_equal,
+                Operator::Gt => lower_or_equal..index.second_order.len(),
+                _ => lower..index.second_order.len(),
+            };
+            self.bitmap.for_each_set(ranks, |rank| {
+                pairs.push((probe_row as u32, index.second_order[rank]));
+            });
+            self.reservation.try_resize(pairs.capacity() * pair_size)?;
+        }
+        pairs.sort_unstable();
+
+        let (right_indices, left_indices): (Vec<_>, Vec<_>) = pairs
+            .into_iter()
+            .map(|(right, left)| (right, left as u64))
+            .unzip();
+        self.reservation.free();
+        apply_join_filter_to_indices(
+            left_data.data.batch(),
+            right_batch,
+            UInt64Array::from(left_indices),
+            UInt32Array::from(right_indices),
+            &self.filter,
+            JoinSide::Left,
+        )
+    }
+}
+
+impl<T: BatchTransformer + Unpin + Send> Stream for IEJoinStream<T> {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.poll_next_impl(cx)
+    }
+}
+
+impl<T: BatchTransformer + Unpin + Send> RecordBatchStream for IEJoinStream<T> {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+    use crate::joins::NestedLoopJoinExec;
+    use crate::memory::MemorySourceConfig;
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field};
+    use datafusion_common::{assert_contains, JoinSide};
+    use datafusion_physical_expr::expressions::{col, BinaryExpr};
+
+    fn build_table(
+        a: (&str, &Vec<Option<i32>>),
+        b: (&str, &Vec<Option<i32>>),
+        batch_size: usize,
+    ) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(a.0, DataType::Int32, true),
+            Field::new(b.0, DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int32Array::from(a.1.clone())),
+                Arc::new(Int32Array::from(b.1.clone())),
+            ],
+        )
+        .unwrap();
+        let batches = (0..batch.num_rows())
+            .step_by(batch_size)
+            .map(|offset| batch.slice(offset, batch_size.min(batch.num_rows() - offset)))
+            .collect::<Vec<_>>();
+        MemorySourceConfig::try_new_exec(&[batches], schema, None).unwrap()
+    }
+
+    /// Builds the filter `<left a1> <op1> <right a2> AND <right b2> <op2> <left b1>`
+    /// over the inputs built by [`build_table`]
+    fn range_filter(op1: Operator, op2: Operator) -> JoinFilter {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a1", DataType::Int32, true),
+            Field::new("b1", DataType::Int32, true),
+            Field::new("a2", DataType::Int32, true),
+            Field::new("b2", DataType::Int32, true),
+        ]));
+        let first = Arc::new(BinaryExpr::new(
+            col("a1", &schema).unwrap(),
+            op1,
+            col("a2", &schema).unwrap(),
+        ));
+        let second = Arc::new(BinaryExpr::new(
+            col("b2", &schema).unwrap(),
+            op2,
+            col("b1", &schema).unwrap(),
+        ));
+        let expression = Arc::new(BinaryExpr::new(first, Operator::And, second));
+        JoinFilter::new(
+            expression,
+            vec![
+                ColumnIndex {
+                    index: 0,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 1,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 0,
+                    side: JoinSide::Right,
+                },
+                ColumnIndex {
+                    index: 1,
+                    side: JoinSide::Right,
+                },
+            ],
+            schema,
+        )
+    }
+
+    async fn collect_sorted(plan: Arc<dyn ExecutionPlan>) -> Result<Vec<String>> {
+        let context = Arc::new(TaskContext::default());
+        let batches = common::collect(plan.execute(0, context)?).await?;
+        let formatted = arrow::util::pretty::pretty_format_batches(&batches)?;
+        let mut lines = formatted
+            .to_string()
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        lines.sort();
+        Ok(lines)
+    }
+
+    /// Checks that [`IEJoinExec`] produces the same output as
+    /// [`NestedLoopJoinExec`] for all supported join types and operators
+    #[tokio::test]
+    async fn ie_join_matches_nested_loop_join() -> Result<()> {
+        let a1 = (0..40).map(|i| Some((i * 7) % 13)).collect::<Vec<_>>();
+        let b1 = (0..40)
+            .map(|i| (i % 9 != 0).then_some((i * 5) % 11))
+            .collect::<Vec<_>>();
+        let a2 = (0..30)
+            .map(|i| (i % 7 != 0).then_some((i * 3) % 13))
+            .collect::<Vec<_>>();
+        let b2 = (0..30).map(|i| Some((i * 4) % 11)).collect::<Vec<_>>();
+        let operators = [Operator::Lt, Operator::LtEq, Operator::Gt, Operator::GtEq];
+        let join_types = [
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+        ];
+
+        for op1 in operators {
+            for op2 in operators {
+                for join_type in join_types {
+                    let left = build_table(("a1", &a1), ("b1", &b1), 16);
+                    let right = build_table(("a2", &a2), ("b2", &b2), 7);
+                    let filter = range_filter(op1, op2);
+                    let nested_loop_join = NestedLoopJoinExec::try_new(
+                        Arc::clone(&left),
+                        Arc::clone(&right),
+                        Some(filter.clone()),
+                        &join_type,
+                        None,
+                    )?;
+                    let ie_join = IEJoinExec::try_new(left, right, filter, &join_type)?;
+                    assert_eq!(
+                        collect_sorted(Arc::new(nested_loop_join)).await?,
+                        collect_sorted(Arc::new(ie_join)).await?,
+                        "{op1} {op2} {join_type}"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ie_join_range_conditions() -> Result<()> {
+        let left = build_table(("a1", &vec![Some(1)]), ("b1", &vec![Some(1)]), 1);
+        let right = build_table(("a2", &vec![Some(1)]), ("b2", &vec![Some(1)]), 1);
+        let filter = range_filter(Operator::Lt, Operator::GtEq);
+        let [first, second] =
+            IEJoinExec::range_conditions(&filter, &left.schema(), &right.schema())?
+                .unwrap();
+        assert_eq!(first.left.to_string(), "a1@0");
+        assert_eq!(first.op, Operator::Lt);
+        assert_eq!(first.right.to_string(), "a2@0");
+        // The second condition is normalized to have the left input first
+        assert_eq!(second.left.to_string(), "b1@1");
+        assert_eq!(second.op, Operator::LtEq);
+        assert_eq!(second.right.to_string(), "b2@1");
+
+        let filter = range_filter(Operator::Lt, Operator::Eq);
+        assert!(
+            IEJoinExec::range_conditions(&filter, &left.schema(), &right.schema())?
+                .is_none()
+        );
+        let err = IEJoinExec::try_new(left, right, filter, &JoinType::Inner).unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "IEJoinExec requires a filter with two range conditions between its inputs"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rank_bitmap() {
+        let mut bitmap = RankBitmap::new(10_000);
+        for position in [0, 5, 63, 64, 4095, 4096, 9000, 9999] {
+            bitmap.set(position);
+        }
+        let collect = |bitmap: &RankBitmap, range: Range<usize>| {
+            let mut positions = vec![];
+            bitmap.for_each_set(range, |p| positions.push(p));
+            positions
+        };
+        assert_eq!(
+            collect(&bitmap, 0..10_000),
+            vec![0, 5, 63, 64, 4095, 4096, 9000, 9999]
+        );
+        assert_eq!(collect(&bitmap, 5..64), vec![5, 63]);
+        assert_eq!(collect(&bitmap, 6..63), Vec::<usize>::new());
+        assert_eq!(collect(&bitmap, 64..4097), vec![64, 4095, 4096]);
+        assert_eq!(collect(&bitmap, 100..100), Vec::<usize>::new());
+
+        bitmap.clear();
+        assert_eq!(collect(&bitmap, 0..10_000), Vec::<usize>::new());
+    }
+}
@@ -18,15 +18,20 @@
 //! DataFusion Join implementations
 
 use arrow::array::BooleanBufferBuilder;
+pub use asof_join::AsofJoinExec;
 pub use cross_join::CrossJoinExec;
 pub use hash_join::HashJoinExec;
+pub use ie_join::{IEJoinCondition, IEJoinExec};
 pub use nested_loop_join::NestedLoopJoinExec;
 use parking_lot::Mutex;
 // Note: SortMergeJoin is not used in plans yet
 pub use sort_merge_join::SortMergeJoinExec;
 pub use symmetric_hash_join::SymmetricHashJoinExec;
+mod asof_join;
 mod cross_join;
+mod dynamic_filter;
 mod hash_join;
+mod ie_join;
 mod nested_loop_join;
 mod sort_merge_join;
 mod stream_join_utils;
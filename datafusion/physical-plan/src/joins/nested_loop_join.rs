@@ -65,8 +65,10 @@ use datafusion_physical_expr::equivalence::{
 use futures::{ready, Stream, StreamExt, TryStreamExt};
 use parking_lot::Mutex;
 
-/// Left (build-side) data
-struct JoinLeftData {
+/// Left (build-side) data, also used by [`IEJoinExec`]
+///
+/// [`IEJoinExec`]: crate::joins::IEJoinExec
+pub(crate) struct JoinLeftData {
     /// Build-side data collected to single batch
     batch: RecordBatch,
     /// Shared bitmap builder for visited left indices
@@ -93,17 +95,17 @@ impl JoinLeftData {
         }
     }
 
-    fn batch(&self) -> &RecordBatch {
+    pub(crate) fn batch(&self) -> &RecordBatch {
         &self.batch
     }
 
-    fn bitmap(&self) -> &SharedBitmapBuilder {
+    pub(crate) fn bitmap(&self) -> &SharedBitmapBuilder {
         &self.bitmap
     }
 
     /// Decrements counter of running threads, and returns `true`
     /// if caller is the last running thread
-    fn report_probe_completed(&self) -> bool {
+    pub(crate) fn report_probe_completed(&self) -> bool {
         self.probe_threads_counter.fetch_sub(1, Ordering::Relaxed) == 1
     }
 }
@@ -279,6 +281,7 @@ impl NestedLoopJoinExec {
                 JoinType::Left
                 | JoinType::LeftAnti
                 | JoinType::LeftMark
+                | JoinType::LeftAsof
                 | JoinType::Full => EmissionType::Both,
             }
         } else {
@@ -600,7 +603,7 @@ impl ExecutionPlan for NestedLoopJoinExec {
 }
 
 /// Asynchronously collect input into a single batch, and creates `JoinLeftData` from it
-async fn collect_left_input(
+pub(crate) async fn collect_left_input(
     input: Arc<dyn ExecutionPlan>,
     context: Arc<TaskContext>,
     join_metrics: BuildProbeJoinMetrics,
@@ -659,9 +662,10 @@ async fn collect_left_input(
     ))
 }
 
-/// This enumeration represents various states of the nested loop join algorithm.
+/// This enumeration represents various states of the nested loop join algorithm,
+/// and of the IEJoin algorithm.
 #[derive(Debug, Clone)]
-enum NestedLoopJoinStreamState {
+pub(crate) enum NestedLoopJoinStreamState {
     /// The initial state, indicating that build-side data not collected yet
     WaitBuildSide,
     /// Indicates that build-side has been collected, and stream is ready for
@@ -680,7 +684,7 @@ impl NestedLoopJoinStreamState {
     /// Tries to extract a `ProcessProbeBatchState` from the
     /// `NestedLoopJoinStreamState` enum. Returns an error if state is not
     /// `ProcessProbeBatchState`.
-    fn try_as_process_probe_batch(&mut self) -> Result<&RecordBatch> {
+    pub(crate) fn try_as_process_probe_batch(&mut self) -> Result<&RecordBatch> {
         match self {
             NestedLoopJoinStreamState::ProcessProbeBatch(state) => Ok(state),
             _ => internal_err!("Expected join stream in ProcessProbeBatch state"),
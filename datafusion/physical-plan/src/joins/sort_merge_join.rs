@@ -22,7 +22,7 @@
 
 use std::any::Any;
 use std::cmp::Ordering;
-use std::collections::{HashMap, VecDeque};
+use std::collections::VecDeque;
 use std::fmt::Formatter;
 use std::fs::File;
 use std::io::BufReader;
@@ -46,7 +46,9 @@ use crate::projection::{
     join_allows_pushdown, join_table_borders, new_join_children,
     physical_to_column_exprs, update_join_on, ProjectionExec,
 };
-use crate::spill::spill_record_batches;
+use crate::spill::{
+    get_record_batch_memory_size, spill_record_batches, SpillStreamWriter,
+};
 use crate::{
     metrics, DisplayAs, DisplayFormatType, Distribution, ExecutionPlan,
     ExecutionPlanProperties, PhysicalExpr, PlanProperties, RecordBatchStream,
@@ -57,9 +59,9 @@ use arrow::array::{types::UInt64Type, *};
 use arrow::compute::{
     self, concat_batches, filter_record_batch, is_not_null, take, SortOptions,
 };
-use arrow::datatypes::{DataType, SchemaRef, TimeUnit};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
 use arrow::error::ArrowError;
-use arrow::ipc::reader::FileReader;
+use arrow::ipc::reader::{FileReader, StreamReader};
 use datafusion_common::{
     exec_err, internal_err, not_impl_err, plan_err, DataFusionError, HashSet, JoinSide,
     JoinType, Result,
@@ -95,7 +97,12 @@ use futures::{Stream, StreamExt};
 /// # "Streamed" vs "Buffered"
 ///
 /// The number of record batches of streamed input currently present in the memory will depend
-/// on the output batch size of the execution plan. There is no spilling support for streamed input.
+/// on the output batch size of the execution plan. For outer, semi and anti joins with a join
+/// filter, the joined rows of a streamed row are staged until the filter outcome of all of them
+/// is known, which for a streamed row joined with a large buffered group can exceed the memory
+/// limit. If spilling is enabled, the staged joined rows are then spilled to disk along with
+/// their filter outcomes, and both are read back one batch at a time when the filter is applied.
+/// If spilling is disabled, the execution will fail under the same conditions.
 /// The comparisons are performed from values of join keys in streamed input with the values of
 /// join keys in buffered input. One row in streamed record batch could be matched with multiple rows in
 /// buffered input batches. The streamed input is managed through the states in `StreamedState`
@@ -112,6 +119,10 @@ use futures::{Stream, StreamExt};
 /// from memory/disk. The buffered input is managed through the states in `BufferedState`
 /// and buffered input batches are represented by `BufferedBatch`.
 ///
+/// For full joins with a join filter, the buffered rows joined with streamed rows but never
+/// satisfying the filter are tracked to be joined with nulls when their batch is released.
+/// The tracking of spilled buffered batches is spilled to disk along with them.
+///
 /// Depending on the type of join, left or right input may be selected as streamed or buffered
 /// respectively. For example, in a left-outer join, the left execution plan will be selected as
 /// streamed input while in a right-outer join, the right execution plan will be selected as the
@@ -232,7 +243,8 @@ impl SortMergeJoinExec {
             | JoinType::Full
             | JoinType::LeftAnti
             | JoinType::LeftSemi
-            | JoinType::LeftMark => JoinSide::Left,
+            | JoinType::LeftMark
+            | JoinType::LeftAsof => JoinSide::Left,
         }
     }
 
@@ -758,11 +770,13 @@ struct BufferedBatch {
     pub null_joined: Vec<usize>,
     /// Size estimation used for reserving / releasing memory
     pub size_estimation: usize,
-    /// The indices of buffered batch that the join filter doesn't satisfy.
-    /// This is a map between right row index and a boolean value indicating whether all joined row
-    /// of the right row does not satisfy the filter .
-    /// When dequeuing the buffered batch, we need to produce null joined rows for these indices.
-    pub join_filter_not_matched_map: HashMap<u64, bool>,
+    /// The rows of buffered batch joined with at least one streamed row on the join key.
+    /// Empty unless the join filter is evaluated on the rows of this batch.
+    pub join_filter_joined: BooleanBufferBuilder,
+    /// The rows of buffered batch for which at least one joined row satisfies the join filter.
+    /// When dequeuing the buffered batch, we need to produce null joined rows for the rows
+    /// which are joined but never satisfy the filter.
+    pub join_filter_matched: BooleanBufferBuilder,
     /// Current buffered batch number of rows. Equal to batch.num_rows()
     /// but if batch is spilled to disk this property is preferable
     /// and less expensive
@@ -771,6 +785,10 @@ struct BufferedBatch {
     /// None by default
     /// Some(fileName) if the batch spilled to the disk
     pub spill_file: Option<RefCountedTempFile>,
+    /// The temp spill file of `join_filter_joined` and `join_filter_matched`
+    /// if the batch spilled to the disk. They are then only read in memory
+    /// while being updated or used.
+    pub join_filter_spill_file: Option<RefCountedTempFile>,
 }
 
 impl BufferedBatch {
@@ -785,6 +803,7 @@ impl BufferedBatch {
         //   inner batch size
         // + join keys size
         // + worst case null_joined (as vector capacity * element size)
+        // + join filter bitmaps
         // + Range size
         // + size of this estimation
         let size_estimation = batch.get_array_memory_size()
@@ -793,6 +812,7 @@ impl BufferedBatch {
                 .map(|arr| arr.get_array_memory_size())
                 .sum::<usize>()
             + batch.num_rows().next_power_of_two() * size_of::<usize>()
+            + 2 * batch.num_rows().div_ceil(8)
             + size_of::<Range<usize>>()
             + size_of::<usize>();
 
@@ -803,10 +823,58 @@ impl BufferedBatch {
             join_arrays,
             null_joined: vec![],
             size_estimation,
-            join_filter_not_matched_map: HashMap::new(),
+            join_filter_joined: BooleanBufferBuilder::new(0),
+            join_filter_matched: BooleanBufferBuilder::new(0),
             num_rows,
             spill_file: None,
+            join_filter_spill_file: None,
+        }
+    }
+
+    /// Reads `join_filter_joined` and `join_filter_matched` back from disk,
+    /// if they were spilled
+    fn read_join_filter_bitmaps(&mut self) -> Result<()> {
+        let Some(spill_file) = &self.join_filter_spill_file else {
+            return Ok(());
+        };
+        let file = BufReader::new(File::open(spill_file.path())?);
+        for batch in FileReader::try_new(file, None)? {
+            let batch = batch?;
+            self.join_filter_joined
+                .append_buffer(batch.column(0).as_boolean().values());
+            self.join_filter_matched
+                .append_buffer(batch.column(1).as_boolean().values());
+        }
+        Ok(())
+    }
+
+    /// Spills `join_filter_joined` and `join_filter_matched` to disk if the
+    /// batch spilled, so that they are not kept in memory along with the other
+    /// buffered batches
+    fn spill_join_filter_bitmaps(&mut self, runtime_env: &RuntimeEnv) -> Result<()> {
+        if self.spill_file.is_none() {
+            return Ok(());
         }
+        let spill_file = match self.join_filter_spill_file.take() {
+            Some(spill_file) => spill_file,
+            None => runtime_env
+                .disk_manager
+                .create_tmp_file("sort_merge_join_buffered_filter_spill")?,
+        };
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("joined", DataType::Boolean, false),
+            Field::new("matched", DataType::Boolean, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(BooleanArray::new(self.join_filter_joined.finish(), None)),
+                Arc::new(BooleanArray::new(self.join_filter_matched.finish(), None)),
+            ],
+        )?;
+        spill_record_batches(vec![batch], spill_file.path().into(), schema)?;
+        self.join_filter_spill_file = Some(spill_file);
+        Ok(())
     }
 }
 
@@ -851,6 +919,9 @@ struct SortMergeJoinStream {
     pub filter: Option<JoinFilter>,
     /// Staging output array builders
     pub staging_output_record_batches: JoinedRecordBatches,
+    /// Staged joined batches being filtered and output, when part of them were
+    /// spilled to disk
+    pub spilled_output: Option<SpilledJoinedBatches>,
     /// Output buffer. Currently used by filtering as it requires double buffering
     /// to avoid small/empty batches. Non-filtered join outputs directly from `staging_output_record_batches.batches`
     pub output: RecordBatch,
@@ -873,9 +944,18 @@ struct SortMergeJoinStream {
 }
 
 /// Joined batches with attached join filter information
+#[derive(Default)]
 struct JoinedRecordBatches {
     /// Joined batches. Each batch is already joined columns from left and right sources
     pub batches: Vec<RecordBatch>,
+    /// Joined batches spilled to disk, which precede `batches`
+    pub spill_files: Vec<RefCountedTempFile>,
+    /// Number of rows in `spill_files`
+    pub spilled_rows: usize,
+    /// Memory reserved for the first `reserved_batches` of `batches`
+    pub reserved_size: usize,
+    /// Number of `batches` covered by `reserved_size`
+    pub reserved_batches: usize,
     /// Filter match mask for each row(matched/non-matched)
     pub filter_mask: BooleanBuilder,
     /// Row indices to glue together rows in `batches` and `filter_mask`
@@ -884,200 +964,363 @@ struct JoinedRecordBatches {
     /// It is necessary to differentiate rows that are distributed the way when they point to the same
     /// row index but in not the same batches
     pub batch_ids: Vec<usize>,
+    /// Filter match masks of the rows spilled to disk along with `spill_files`,
+    /// which precede `filter_mask`
+    pub filter_mask_spill_files: Vec<RefCountedTempFile>,
+    /// Number of rows in `filter_mask_spill_files`
+    pub spilled_filter_mask_rows: usize,
+    /// Number of rows in `filter_mask_spill_files` with a null row index
+    pub spilled_null_row_indices: usize,
+    /// Corrector of the filter mask in `filter_mask_spill_files`, whose row
+    /// indices and batch ids are not kept
+    pub spilled_filter_mask_corrector: Option<FilterMaskCorrector>,
 }
 
 impl JoinedRecordBatches {
     fn clear(&mut self) {
         self.batches.clear();
+        self.spill_files.clear();
+        self.spilled_rows = 0;
+        self.reserved_size = 0;
+        self.reserved_batches = 0;
         self.batch_ids.clear();
         self.filter_mask = BooleanBuilder::new();
         self.row_indices = UInt64Builder::new();
+        self.filter_mask_spill_files.clear();
+        self.spilled_filter_mask_rows = 0;
+        self.spilled_null_row_indices = 0;
+        self.spilled_filter_mask_corrector = None;
     }
-}
-impl RecordBatchStream for SortMergeJoinStream {
-    fn schema(&self) -> SchemaRef {
-        Arc::clone(&self.schema)
+
+    fn is_empty(&self) -> bool {
+        self.batches.is_empty() && self.spill_files.is_empty()
+    }
+
+    fn num_rows(&self) -> usize {
+        self.spilled_rows
+            + self
+                .batches
+                .iter()
+                .map(|batch| batch.num_rows())
+                .sum::<usize>()
     }
 }
 
-/// True if next index refers to either:
-/// - another batch id
-/// - another row index within same batch id
-/// - end of row indices
-#[inline(always)]
-fn last_index_for_row(
-    row_index: usize,
-    indices: &UInt64Array,
-    batch_ids: &[usize],
-    indices_len: usize,
-) -> bool {
-    row_index == indices_len - 1
-        || batch_ids[row_index] != batch_ids[row_index + 1]
-        || indices.value(row_index) != indices.value(row_index + 1)
+/// Joined batches staged for filtering, part of which were spilled to disk.
+/// They are read back, filtered and output one batch at a time.
+struct SpilledJoinedBatches {
+    /// Spill files not read yet
+    spill_files: VecDeque<RefCountedTempFile>,
+    /// The spill file currently read and its reader
+    reader: Option<(RefCountedTempFile, StreamReader<BufReader<File>>)>,
+    /// Batches staged in memory after the last spill
+    batches: VecDeque<RecordBatch>,
+    /// Filter mask of all staged rows, `None` if the rows are output as is
+    filter_mask: Option<SpilledFilterMask>,
+    /// Memory reserved for `batches`
+    reserved_size: usize,
 }
 
-// Returns a corrected boolean bitmask for the given join type
-// Values in the corrected bitmask can be: true, false, null
-// `true` - the row found its match and sent to the output
-// `null` - the row ignored, no output
-// `false` - the row sent as NULL joined row
-fn get_corrected_filter_mask(
-    join_type: JoinType,
-    row_indices: &UInt64Array,
-    batch_ids: &[usize],
-    filter_mask: &BooleanArray,
-    expected_size: usize,
-) -> Option<BooleanArray> {
-    let row_indices_length = row_indices.len();
-    let mut corrected_mask: BooleanBuilder =
-        BooleanBuilder::with_capacity(row_indices_length);
-    let mut seen_true = false;
-
-    match join_type {
-        JoinType::Left | JoinType::Right => {
-            for i in 0..row_indices_length {
-                let last_index =
-                    last_index_for_row(i, row_indices, batch_ids, row_indices_length);
-                if filter_mask.value(i) {
-                    seen_true = true;
-                    corrected_mask.append_value(true);
-                } else if seen_true || !filter_mask.value(i) && !last_index {
-                    corrected_mask.append_null(); // to be ignored and not set to output
-                } else {
-                    corrected_mask.append_value(false); // to be converted to null joined row
+impl SpilledJoinedBatches {
+    /// Returns the next staged batch with its part of the corrected filter mask
+    fn next_batch(&mut self) -> Result<Option<(RecordBatch, Option<BooleanArray>)>> {
+        let batch = loop {
+            if let Some((_, reader)) = &mut self.reader {
+                if let Some(batch) = reader.next() {
+                    break batch?;
                 }
+                self.reader = None;
+            }
+            if let Some(spill_file) = self.spill_files.pop_front() {
+                let file = BufReader::new(File::open(spill_file.path())?);
+                self.reader = Some((spill_file, StreamReader::try_new(file, None)?));
+            } else if let Some(batch) = self.batches.pop_front() {
+                break batch;
+            } else {
+                return Ok(None);
+            }
+        };
+        let mask = self
+            .filter_mask
+            .as_mut()
+            .map(|filter_mask| filter_mask.next_corrected(batch.num_rows()))
+            .transpose()?;
+        Ok(Some((batch, mask)))
+    }
+}
 
-                if last_index {
-                    seen_true = false;
+/// Filter mask of staged joined batches, part of which were spilled to disk.
+/// It is read back and corrected one batch at a time.
+struct SpilledFilterMask {
+    /// Corrector of the filter mask, whose runs were all pushed
+    corrector: FilterMaskCorrector,
+    /// Spill files not read yet
+    spill_files: VecDeque<RefCountedTempFile>,
+    /// The spill file currently read and its reader
+    reader: Option<(RefCountedTempFile, StreamReader<BufReader<File>>)>,
+    /// Filter mask in memory after the last spill
+    filter_mask: Option<BooleanArray>,
+    /// Number of rows with a null filter mask after `filter_mask`
+    null_rows: usize,
+    /// Filter mask read but not corrected yet
+    pending: BooleanArray,
+}
+
+impl SpilledFilterMask {
+    /// Returns the next part of the filter mask, of at most `max_len` rows
+    fn next_part(&mut self, max_len: usize) -> Result<Option<BooleanArray>> {
+        loop {
+            if let Some((_, reader)) = &mut self.reader {
+                if let Some(batch) = reader.next() {
+                    return Ok(Some(batch?.column(0).as_boolean().clone()));
                 }
+                self.reader = None;
+            }
+            if let Some(spill_file) = self.spill_files.pop_front() {
+                let file = BufReader::new(File::open(spill_file.path())?);
+                self.reader = Some((spill_file, StreamReader::try_new(file, None)?));
+            } else if let Some(filter_mask) = self.filter_mask.take() {
+                return Ok(Some(filter_mask));
+            } else if self.null_rows > 0 {
+                let len = self.null_rows.min(max_len);
+                self.null_rows -= len;
+                return Ok(Some(BooleanArray::new_null(len)));
+            } else {
+                return Ok(None);
             }
-
-            // Generate null joined rows for records which have no matching join key
-            corrected_mask.append_n(expected_size - corrected_mask.len(), false);
-            Some(corrected_mask.finish())
         }
-        JoinType::LeftMark => {
-            for i in 0..row_indices_length {
-                let last_index =
-                    last_index_for_row(i, row_indices, batch_ids, row_indices_length);
-                if filter_mask.value(i) && !seen_true {
-                    seen_true = true;
-                    corrected_mask.append_value(true);
-                } else if seen_true || !filter_mask.value(i) && !last_index {
-                    corrected_mask.append_null(); // to be ignored and not set to output
-                } else {
-                    corrected_mask.append_value(false); // to be converted to null joined row
-                }
+    }
 
-                if last_index {
-                    seen_true = false;
+    /// Returns the corrected filter mask of the next `num_rows` staged rows
+    fn next_corrected(&mut self, num_rows: usize) -> Result<BooleanArray> {
+        let mut corrected_mask = BooleanBuilder::with_capacity(num_rows);
+        while corrected_mask.len() < num_rows {
+            let remaining = num_rows - corrected_mask.len();
+            if self.pending.is_empty() {
+                match self.next_part(remaining)? {
+                    Some(filter_mask) => self.pending = filter_mask,
+                    None => break,
                 }
             }
+            let len = self.pending.len().min(remaining);
+            self.corrector
+                .correct(&self.pending.slice(0, len), &mut corrected_mask);
+            self.pending = self.pending.slice(len, self.pending.len() - len);
+        }
 
-            // Generate null joined rows for records which have no matching join key
-            corrected_mask.append_n(expected_size - corrected_mask.len(), false);
-            Some(corrected_mask.finish())
+        // Generate null joined rows for records which have no matching join key
+        if let Some(value) = self.corrector.unmatched_value() {
+            corrected_mask.append_n(num_rows - corrected_mask.len(), value);
         }
-        JoinType::LeftSemi => {
-            for i in 0..row_indices_length {
-                let last_index =
-                    last_index_for_row(i, row_indices, batch_ids, row_indices_length);
-                if filter_mask.value(i) && !seen_true {
-                    seen_true = true;
-                    corrected_mask.append_value(true);
-                } else {
-                    corrected_mask.append_null(); // to be ignored and not set to output
-                }
+        Ok(corrected_mask.finish())
+    }
+}
+impl RecordBatchStream for SortMergeJoinStream {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
 
-                if last_index {
-                    seen_true = false;
-                }
-            }
+/// A run of consecutive staged joined rows with the same batch id and row
+/// index, i.e. joined from the same streamed row, whose join filter outcomes
+/// are corrected together
+#[derive(Debug, Clone, Copy)]
+struct FilterMaskRun {
+    /// The batch id and row index of the rows of the run
+    key: (usize, u64),
+    /// Number of rows of the run
+    len: usize,
+    /// Offset in the run of the first row satisfying the join filter
+    first_true: Option<usize>,
+}
+
+/// Corrects the join filter mask of staged joined rows for outer, semi and
+/// anti joins, see [`get_corrected_filter_mask`].
+///
+/// The runs of the rows are first pushed with [`Self::push_rows`], then their
+/// filter mask is corrected in order, possibly in several chunks, with
+/// [`Self::correct`]. This allows to correct the filter mask of staged rows
+/// spilled to disk without reading them all in memory.
+#[derive(Debug)]
+struct FilterMaskCorrector {
+    join_type: JoinType,
+    /// Runs of the rows not corrected yet
+    runs: VecDeque<FilterMaskRun>,
+    /// Offset of the next row to correct in the first run
+    offset: usize,
+    /// Whether a row of the first run before `offset` satisfies the join filter
+    seen_true: bool,
+    /// Whether a row of the first run before `offset` does not satisfy the
+    /// join filter (full joins only)
+    seen_false: bool,
+}
 
-            Some(corrected_mask.finish())
+impl FilterMaskCorrector {
+    fn new(join_type: JoinType) -> Self {
+        Self {
+            join_type,
+            runs: VecDeque::new(),
+            offset: 0,
+            seen_true: false,
+            seen_false: false,
         }
-        JoinType::LeftAnti | JoinType::RightAnti => {
-            for i in 0..row_indices_length {
-                let last_index =
-                    last_index_for_row(i, row_indices, batch_ids, row_indices_length);
+    }
 
-                if filter_mask.value(i) {
-                    seen_true = true;
+    /// Appends the staged rows with `row_indices`, `batch_ids` and join filter
+    /// outcomes `filter_mask` to the runs to correct
+    fn push_rows(
+        &mut self,
+        row_indices: &UInt64Array,
+        batch_ids: &[usize],
+        filter_mask: &BooleanArray,
+    ) {
+        for (i, batch_id) in batch_ids.iter().enumerate().take(row_indices.len()) {
+            let key = (*batch_id, row_indices.value(i));
+            let run = match self.runs.back_mut() {
+                Some(run) if run.key == key => run,
+                _ => {
+                    self.runs.push_back(FilterMaskRun {
+                        key,
+                        len: 0,
+                        first_true: None,
+                    });
+                    self.runs.back_mut().unwrap()
                 }
+            };
+            if run.first_true.is_none() && filter_mask.value(i) {
+                run.first_true = Some(run.len);
+            }
+            run.len += 1;
+        }
+    }
 
-                if last_index {
-                    if !seen_true {
-                        corrected_mask.append_value(true);
+    /// Appends to `corrected_mask` the corrected filter mask of the next
+    /// `filter_mask.len()` rows, whose runs were pushed
+    fn correct(
+        &mut self,
+        filter_mask: &BooleanArray,
+        corrected_mask: &mut BooleanBuilder,
+    ) {
+        for i in 0..filter_mask.len() {
+            let Some(run) = self.runs.front() else {
+                return;
+            };
+            let last_index = self.offset + 1 == run.len;
+            let value = filter_mask.value(i);
+            let corrected = match self.join_type {
+                JoinType::Left | JoinType::Right => {
+                    if value {
+                        self.seen_true = true;
+                        Some(true)
+                    } else if self.seen_true || !last_index {
+                        None // to be ignored and not set to output
                     } else {
-                        corrected_mask.append_null();
+                        Some(false) // to be converted to null joined row
                     }
-
-                    seen_true = false;
-                } else {
-                    corrected_mask.append_null();
                 }
-            }
-            // Generate null joined rows for records which have no matching join key,
-            // for LeftAnti non-matched considered as true
-            corrected_mask.append_n(expected_size - corrected_mask.len(), true);
-            Some(corrected_mask.finish())
-        }
-        JoinType::Full => {
-            let mut mask: Vec<Option<bool>> = vec![Some(true); row_indices_length];
-            let mut last_true_idx = 0;
-            let mut first_row_idx = 0;
-            let mut seen_false = false;
-
-            for i in 0..row_indices_length {
-                let last_index =
-                    last_index_for_row(i, row_indices, batch_ids, row_indices_length);
-                let val = filter_mask.value(i);
-                let is_null = filter_mask.is_null(i);
-
-                if val {
-                    // memoize the first seen matched row
-                    if !seen_true {
-                        last_true_idx = i;
+                JoinType::LeftMark => {
+                    if value && !self.seen_true {
+                        self.seen_true = true;
+                        Some(true)
+                    } else if self.seen_true || !last_index {
+                        None
+                    } else {
+                        Some(false)
                     }
-                    seen_true = true;
                 }
-
-                if is_null || val {
-                    mask[i] = Some(true);
-                } else if !is_null && !val && (seen_true || seen_false) {
-                    mask[i] = None;
-                } else {
-                    mask[i] = Some(false);
+                JoinType::LeftSemi => {
+                    if value && !self.seen_true {
+                        self.seen_true = true;
+                        Some(true)
+                    } else {
+                        None
+                    }
                 }
-
-                if !is_null && !val {
-                    seen_false = true;
+                JoinType::LeftAnti | JoinType::RightAnti => {
+                    self.seen_true |= value;
+                    (last_index && !self.seen_true).then_some(true)
                 }
-
-                if last_index {
-                    // If the left row seen as true its needed to output it once
-                    // To do that we mark all other matches for same row as null to avoid the output
-                    if seen_true {
-                        #[allow(clippy::needless_range_loop)]
-                        for j in first_row_idx..last_true_idx {
-                            mask[j] = None;
-                        }
-                    }
-
-                    seen_true = false;
-                    seen_false = false;
-                    last_true_idx = 0;
-                    first_row_idx = i + 1;
+                JoinType::Full => {
+                    let is_null = filter_mask.is_null(i);
+                    // If the streamed row is seen as true its needed to output it once,
+                    // so all its other joined rows before the first matched one are ignored
+                    let corrected = if run.first_true.is_some_and(|t| self.offset < t) {
+                        None
+                    } else if is_null || value {
+                        Some(true)
+                    } else if self.seen_true || self.seen_false {
+                        None
+                    } else {
+                        Some(false)
+                    };
+                    self.seen_true |= value;
+                    self.seen_false |= !is_null && !value;
+                    corrected
                 }
+                _ => Some(value),
+            };
+            corrected_mask.append_option(corrected);
+
+            if last_index {
+                self.runs.pop_front();
+                self.offset = 0;
+                self.seen_true = false;
+                self.seen_false = false;
+            } else {
+                self.offset += 1;
             }
+        }
+    }
 
-            Some(BooleanArray::from(mask))
+    /// The corrected filter mask of the staged rows without join filter
+    /// outcome, which have no matching join key, if any
+    fn unmatched_value(&self) -> Option<bool> {
+        match self.join_type {
+            JoinType::Left | JoinType::Right | JoinType::LeftMark => Some(false),
+            // for anti joins non-matched rows are considered as true
+            JoinType::LeftAnti | JoinType::RightAnti => Some(true),
+            _ => None,
         }
-        // Only outer joins needs to keep track of processed rows and apply corrected filter mask
-        _ => None,
     }
 }
 
+// Returns a corrected boolean bitmask for the given join type
+// Values in the corrected bitmask can be: true, false, null
+// `true` - the row found its match and sent to the output
+// `null` - the row ignored, no output
+// `false` - the row sent as NULL joined row
+fn get_corrected_filter_mask(
+    join_type: JoinType,
+    row_indices: &UInt64Array,
+    batch_ids: &[usize],
+    filter_mask: &BooleanArray,
+    expected_size: usize,
+) -> Option<BooleanArray> {
+    // Only outer joins needs to keep track of processed rows and apply corrected filter mask
+    if !matches!(
+        join_type,
+        JoinType::Left
+            | JoinType::Right
+            | JoinType::LeftMark
+            | JoinType::LeftSemi
+            | JoinType::LeftAnti
+            | JoinType::RightAnti
+            | JoinType::Full
+    ) {
+        return None;
+    }
+    let mut corrector = FilterMaskCorrector::new(join_type);
+    corrector.push_rows(row_indices, batch_ids, filter_mask);
+    let mut corrected_mask = BooleanBuilder::with_capacity(expected_size);
+    corrector.correct(filter_mask, &mut corrected_mask);
+
+    // Generate null joined rows for records which have no matching join key
+    if let Some(value) = corrector.unmatched_value() {
+        corrected_mask.append_n(expected_size - corrected_mask.len(), value);
+    }
+    Some(corrected_mask.finish())
+}
+
 impl Stream for SortMergeJoinStream {
     type Item = Result<RecordBatch>;
 
@@ -1088,6 +1331,43 @@ impl Stream for SortMergeJoinStream {
         let join_time = self.join_metrics.join_time.clone();
         let _timer = join_time.timer();
         loop {
+            // Output the staged joined batches which were spilled to disk
+            // before making any further progress
+            if let Some(next_batch) = self
+                .spilled_output
+                .as_mut()
+                .map(SpilledJoinedBatches::next_batch)
+                .transpose()?
+            {
+                match next_batch {
+                    Some((batch, mask)) => {
+                        let filtered_batch = match mask {
+                            Some(mask) => {
+                                self.filter_record_batch_by_join_type(batch, &mask)?
+                            }
+                            None => batch,
+                        };
+                        self.output = concat_batches(
+                            &self.schema(),
+                            vec![&self.output, &filtered_batch],
+                        )?;
+                        if self.output.num_rows() >= self.batch_size {
+                            let schema = self.output.schema();
+                            let record_batch = std::mem::replace(
+                                &mut self.output,
+                                RecordBatch::new_empty(schema),
+                            );
+                            return Poll::Ready(Some(Ok(record_batch)));
+                        }
+                    }
+                    None => {
+                        if let Some(spilled_output) = self.spilled_output.take() {
+                            self.reservation.shrink(spilled_output.reserved_size);
+                        }
+                    }
+                }
+                continue;
+            }
             match &self.state {
                 SortMergeJoinState::Init => {
                     let streamed_exhausted =
@@ -1116,11 +1396,18 @@ impl Stream for SortMergeJoinStream {
 
                                         // If join is filtered and there is joined tuples waiting
                                         // to be filtered
-                                        if !self
-                                            .staging_output_record_batches
-                                            .batches
-                                            .is_empty()
+                                        if !self.staging_output_record_batches.is_empty()
                                         {
+                                            // Filter joined tuples spilled to disk batch by batch
+                                            if !self
+                                                .staging_output_record_batches
+                                                .spill_files
+                                                .is_empty()
+                                            {
+                                                self.start_spilled_output()?;
+                                                continue;
+                                            }
+
                                             // Apply filter on joined tuples and get filtered batch
                                             let out_filtered_batch =
                                                 self.filter_joined_batch()?;
@@ -1222,6 +1509,14 @@ impl Stream for SortMergeJoinStream {
                             }
 
                             return Poll::Ready(Some(Ok(record_batch)));
+                        } else if !self
+                            .staging_output_record_batches
+                            .spill_files
+                            .is_empty()
+                        {
+                            // All joined tuples waiting to be filtered were spilled to disk
+                            self.output_size = 0;
+                            continue;
                         }
                         return Poll::Pending;
                     }
@@ -1230,7 +1525,11 @@ impl Stream for SortMergeJoinStream {
                     self.freeze_all()?;
 
                     // if there is still something not processed
-                    if !self.staging_output_record_batches.batches.is_empty() {
+                    if !self.staging_output_record_batches.is_empty() {
+                        if !self.staging_output_record_batches.spill_files.is_empty() {
+                            self.start_spilled_output()?;
+                            continue;
+                        }
                         if self.filter.is_some()
                             && matches!(
                                 self.join_type,
@@ -1304,12 +1603,8 @@ impl SortMergeJoinStream {
             on_streamed,
             on_buffered,
             filter,
-            staging_output_record_batches: JoinedRecordBatches {
-                batches: vec![],
-                filter_mask: BooleanBuilder::new(),
-                row_indices: UInt64Builder::new(),
-                batch_ids: vec![],
-            },
+            staging_output_record_batches: JoinedRecordBatches::default(),
+            spilled_output: None,
             output: RecordBatch::new_empty(schema),
             output_size: 0,
             batch_size,
@@ -1717,7 +2012,7 @@ impl SortMergeJoinStream {
             }
             buffered_batch.null_joined.clear();
         }
-        Ok(())
+        self.reserve_staged_batches()
     }
 
     fn produce_buffered_not_matched(
@@ -1728,16 +2023,19 @@ impl SortMergeJoinStream {
             return Ok(());
         }
 
+        buffered_batch.read_join_filter_bitmaps()?;
+        buffered_batch.join_filter_spill_file = None;
+
         // For buffered row which is joined with streamed side rows but all joined rows
         // don't satisfy the join filter
-        let not_matched_buffered_indices = buffered_batch
-            .join_filter_not_matched_map
-            .iter()
-            .filter_map(|(idx, failed)| if *failed { Some(*idx) } else { None })
-            .collect::<Vec<_>>();
-
-        let buffered_indices =
-            UInt64Array::from_iter_values(not_matched_buffered_indices.iter().copied());
+        let buffered_indices = UInt64Array::from_iter_values(
+            (0..buffered_batch.join_filter_joined.len())
+                .filter(|idx| {
+                    buffered_batch.join_filter_joined.get_bit(*idx)
+                        && !buffered_batch.join_filter_matched.get_bit(*idx)
+                })
+                .map(|idx| idx as u64),
+        );
 
         if let Some(record_batch) = produce_buffered_null_batch(
             &self.schema,
@@ -1761,9 +2059,10 @@ impl SortMergeJoinStream {
                 .batches
                 .push(record_batch);
         }
-        buffered_batch.join_filter_not_matched_map.clear();
+        buffered_batch.join_filter_joined = BooleanBufferBuilder::new(0);
+        buffered_batch.join_filter_matched = BooleanBufferBuilder::new(0);
 
-        Ok(())
+        self.reserve_staged_batches()
     }
 
     // Produces and stages record batch for all output indices found
@@ -1924,6 +2223,12 @@ impl SortMergeJoinStream {
                     if matches!(self.join_type, JoinType::Full) {
                         let buffered_batch = &mut self.buffered_data.batches
                             [chunk.buffered_batch_idx.unwrap()];
+                        buffered_batch.read_join_filter_bitmaps()?;
+                        if buffered_batch.join_filter_joined.is_empty() {
+                            let num_rows = buffered_batch.num_rows;
+                            buffered_batch.join_filter_joined.append_n(num_rows, false);
+                            buffered_batch.join_filter_matched.append_n(num_rows, false);
+                        }
 
                         for i in 0..pre_mask.len() {
                             // If the buffered row is not joined with streamed side,
@@ -1932,17 +2237,17 @@ impl SortMergeJoinStream {
                                 continue;
                             }
 
-                            let buffered_index = right_indices.value(i);
-
-                            buffered_batch.join_filter_not_matched_map.insert(
-                                buffered_index,
-                                *buffered_batch
-                                    .join_filter_not_matched_map
-                                    .get(&buffered_index)
-                                    .unwrap_or(&true)
-                                    && !pre_mask.value(i),
-                            );
+                            let buffered_index = right_indices.value(i) as usize;
+                            buffered_batch
+                                .join_filter_joined
+                                .set_bit(buffered_index, true);
+                            if pre_mask.value(i) {
+                                buffered_batch
+                                    .join_filter_matched
+                                    .set_bit(buffered_index, true);
+                            }
                         }
+                        buffered_batch.spill_join_filter_bitmaps(&self.runtime_env)?;
                     }
                 } else {
                     self.staging_output_record_batches
@@ -1958,7 +2263,7 @@ impl SortMergeJoinStream {
 
         self.streamed_batch.output_indices.clear();
 
-        Ok(())
+        self.reserve_staged_batches()
     }
 
     fn output_record_batch_and_reset(&mut self) -> Result<RecordBatch> {
@@ -1997,42 +2302,200 @@ impl SortMergeJoinStream {
     fn filter_joined_batch(&mut self) -> Result<RecordBatch> {
         let record_batch =
             concat_batches(&self.schema, &self.staging_output_record_batches.batches)?;
-        let mut out_indices = self.staging_output_record_batches.row_indices.finish();
-        let mut out_mask = self.staging_output_record_batches.filter_mask.finish();
-        let mut batch_ids = &self.staging_output_record_batches.batch_ids;
-        let default_batch_ids = vec![0; record_batch.num_rows()];
+        let filtered_record_batch =
+            match self.corrected_filter_mask(record_batch.num_rows()) {
+                Some(corrected_mask) => {
+                    self.filter_record_batch_by_join_type(record_batch, &corrected_mask)?
+                }
+                None => record_batch,
+            };
+        self.reservation
+            .shrink(self.staging_output_record_batches.reserved_size);
+        self.staging_output_record_batches.clear();
+
+        Ok(filtered_record_batch)
+    }
+
+    /// Returns the corrected filter mask of the `num_rows` staged joined rows, or
+    /// `None` if the staged rows are output as is
+    fn corrected_filter_mask(&mut self, num_rows: usize) -> Option<BooleanArray> {
+        let staging = &mut self.staging_output_record_batches;
+        let mut out_indices = staging.row_indices.finish();
+        let mut out_mask = staging.filter_mask.finish();
+        let mut batch_ids = &staging.batch_ids;
+        let default_batch_ids = vec![0; num_rows];
 
         // If only nulls come in and indices sizes doesn't match with expected record batch count
         // generate missing indices
         // Happens for null joined batches for Full Join
-        if out_indices.null_count() == out_indices.len()
-            && out_indices.len() != record_batch.num_rows()
+        if out_indices.null_count() == out_indices.len() && out_indices.len() != num_rows
         {
-            out_mask = BooleanArray::from(vec![None; record_batch.num_rows()]);
-            out_indices = UInt64Array::from(vec![None; record_batch.num_rows()]);
+            out_mask = BooleanArray::from(vec![None; num_rows]);
+            out_indices = UInt64Array::from(vec![None; num_rows]);
             batch_ids = &default_batch_ids;
         }
 
         if out_mask.is_empty() {
-            self.staging_output_record_batches.batches.clear();
-            return Ok(record_batch);
+            return None;
         }
 
-        let maybe_corrected_mask = get_corrected_filter_mask(
+        let corrected_mask = get_corrected_filter_mask(
             self.join_type,
             &out_indices,
             batch_ids,
             &out_mask,
-            record_batch.num_rows(),
+            num_rows,
         );
+        Some(corrected_mask.unwrap_or(out_mask))
+    }
 
-        let corrected_mask = if let Some(ref filtered_join_mask) = maybe_corrected_mask {
-            filtered_join_mask
-        } else {
-            &out_mask
+    /// Starts filtering and outputting the staged joined batches one batch at a
+    /// time, after part of them were spilled to disk along with their filter
+    /// mask
+    fn start_spilled_output(&mut self) -> Result<()> {
+        let num_rows = self.staging_output_record_batches.num_rows();
+        let staging = &mut self.staging_output_record_batches;
+        let row_indices = staging.row_indices.finish();
+        let filter_mask = staging.filter_mask.finish();
+        let filter_mask_rows = staging.spilled_filter_mask_rows + filter_mask.len();
+        let null_row_indices =
+            staging.spilled_null_row_indices + row_indices.null_count();
+
+        let filter_mask = match staging.spilled_filter_mask_corrector.take() {
+            Some(mut corrector) => {
+                // If only nulls come in and indices sizes doesn't match with expected record batch count
+                // all the rows are corrected as a single run of null joined rows
+                // Happens for null joined batches for Full Join
+                if null_row_indices == filter_mask_rows && filter_mask_rows != num_rows {
+                    corrector.runs = VecDeque::from([FilterMaskRun {
+                        key: (0, 0),
+                        len: num_rows,
+                        first_true: None,
+                    }]);
+                    staging.filter_mask_spill_files.clear();
+                    Some(SpilledFilterMask {
+                        corrector,
+                        spill_files: VecDeque::new(),
+                        reader: None,
+                        filter_mask: None,
+                        null_rows: num_rows,
+                        pending: BooleanArray::new_null(0),
+                    })
+                } else {
+                    corrector.push_rows(&row_indices, &staging.batch_ids, &filter_mask);
+                    Some(SpilledFilterMask {
+                        corrector,
+                        spill_files: std::mem::take(&mut staging.filter_mask_spill_files)
+                            .into(),
+                        reader: None,
+                        filter_mask: Some(filter_mask),
+                        null_rows: 0,
+                        pending: BooleanArray::new_null(0),
+                    })
+                }
+            }
+            None => None,
         };
+        self.spilled_output = Some(SpilledJoinedBatches {
+            spill_files: std::mem::take(&mut staging.spill_files).into(),
+            reader: None,
+            batches: std::mem::take(&mut staging.batches).into(),
+            filter_mask,
+            reserved_size: staging.reserved_size,
+        });
+        staging.clear();
+        Ok(())
+    }
+
+    /// Whether the joined batches are staged until the join filter outcome of
+    /// all rows joined with a streamed row is known
+    fn is_filter_deferred(&self) -> bool {
+        self.filter.is_some()
+            && matches!(
+                self.join_type,
+                JoinType::Left
+                    | JoinType::LeftSemi
+                    | JoinType::Right
+                    | JoinType::LeftAnti
+                    | JoinType::RightAnti
+                    | JoinType::LeftMark
+                    | JoinType::Full
+            )
+    }
+
+    /// Reserves memory for the joined batches staged since the last call. If the
+    /// reservation can not grow, all staged batches in memory are spilled to disk.
+    ///
+    /// Only applies when the join filter is deferred, as otherwise the staged
+    /// batches are output as soon as they reach the target batch size.
+    fn reserve_staged_batches(&mut self) -> Result<()> {
+        if !self.is_filter_deferred() {
+            return Ok(());
+        }
+        let staging = &mut self.staging_output_record_batches;
+        let size = staging.batches[staging.reserved_batches..]
+            .iter()
+            .map(get_record_batch_memory_size)
+            .sum::<usize>();
+        match self.reservation.try_grow(size) {
+            Ok(_) => {
+                staging.reserved_size += size;
+                staging.reserved_batches = staging.batches.len();
+                self.join_metrics
+                    .peak_mem_used
+                    .set_max(self.reservation.size());
+                Ok(())
+            }
+            Err(_) if self.runtime_env.disk_manager.tmp_files_enabled() => {
+                // spill staged batches to disk
+                let spill_file = self
+                    .runtime_env
+                    .disk_manager
+                    .create_tmp_file("sort_merge_join_streamed_spill")?;
+                let mut writer = SpillStreamWriter::try_new(spill_file, &self.schema)?;
+                for batch in staging.batches.drain(..) {
+                    writer.write(&batch)?;
+                }
 
-        self.filter_record_batch_by_join_type(record_batch, corrected_mask)
+                // update metrics to register spill
+                self.join_metrics.spill_count.add(1);
+                self.join_metrics.spilled_bytes.add(writer.num_bytes());
+                self.join_metrics.spilled_rows.add(writer.num_rows());
+
+                staging.spilled_rows += writer.num_rows();
+                staging.spill_files.push(writer.finish()?);
+
+                // spill the filter mask of the staged batches along with them,
+                // keeping only the runs of their row indices
+                let corrector = staging
+                    .spilled_filter_mask_corrector
+                    .get_or_insert_with(|| FilterMaskCorrector::new(self.join_type));
+                let filter_mask = staging.filter_mask.finish();
+                let row_indices = staging.row_indices.finish();
+                corrector.push_rows(&row_indices, &staging.batch_ids, &filter_mask);
+                staging.batch_ids.clear();
+                staging.spilled_filter_mask_rows += filter_mask.len();
+                staging.spilled_null_row_indices += row_indices.null_count();
+                let spill_file = self
+                    .runtime_env
+                    .disk_manager
+                    .create_tmp_file("sort_merge_join_filter_mask_spill")?;
+                let schema =
+                    Schema::new(vec![Field::new("filter_mask", DataType::Boolean, true)]);
+                let mut writer = SpillStreamWriter::try_new(spill_file, &schema)?;
+                writer.write(&RecordBatch::try_new(
+                    Arc::new(schema),
+                    vec![Arc::new(filter_mask)],
+                )?)?;
+                staging.filter_mask_spill_files.push(writer.finish()?);
+
+                self.reservation.shrink(staging.reserved_size);
+                staging.reserved_size = 0;
+                staging.reserved_batches = 0;
+                Ok(())
+            }
+            Err(e) => exec_err!("{}. Disk spilling disabled.", e.message()),
+        }
     }
 
     fn filter_record_batch_by_join_type(
@@ -2157,8 +2620,6 @@ impl SortMergeJoinStream {
             )?;
         }
 
-        self.staging_output_record_batches.clear();
-
         Ok(filtered_record_batch)
     }
 }
@@ -2353,7 +2814,10 @@ impl BufferedData {
 }
 
 /// Get join array refs of given batch and join columns
-fn join_arrays(batch: &RecordBatch, on_column: &[PhysicalExprRef]) -> Vec<ArrayRef> {
+pub(super) fn join_arrays(
+    batch: &RecordBatch,
+    on_column: &[PhysicalExprRef],
+) -> Vec<ArrayRef> {
     on_column
         .iter()
         .map(|c| {
@@ -2365,7 +2829,7 @@ fn join_arrays(batch: &RecordBatch, on_column: &[PhysicalExprRef]) -> Vec<ArrayR
 }
 
 /// Get comparison result of two rows of join arrays
-fn compare_join_arrays(
+pub(super) fn compare_join_arrays(
     left_arrays: &[ArrayRef],
     left: usize,
     right_arrays: &[ArrayRef],
@@ -2455,7 +2919,7 @@ fn compare_join_arrays(
 
 /// A faster version of compare_join_arrays() that only output whether
 /// the given two rows are equal
-fn is_join_arrays_equal(
+pub(super) fn is_join_arrays_equal(
     left_arrays: &[ArrayRef],
     left: usize,
     right_arrays: &[ArrayRef],
@@ -2525,23 +2989,23 @@ mod tests {
     use std::sync::Arc;
 
     use arrow::array::{
-        builder::{BooleanBuilder, UInt64Builder},
         BooleanArray, Date32Array, Date64Array, Int32Array, RecordBatch, UInt64Array,
     };
     use arrow::compute::{concat_batches, filter_record_batch, SortOptions};
     use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::util::pretty::pretty_format_batches;
 
-    use datafusion_common::JoinSide;
     use datafusion_common::JoinType::*;
     use datafusion_common::{
         assert_batches_eq, assert_batches_sorted_eq, assert_contains, JoinType, Result,
     };
+    use datafusion_common::{JoinSide, ScalarValue};
     use datafusion_execution::config::SessionConfig;
     use datafusion_execution::disk_manager::DiskManagerConfig;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_execution::TaskContext;
     use datafusion_expr::Operator;
-    use datafusion_physical_expr::expressions::BinaryExpr;
+    use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
 
     use crate::expressions::Column;
     use crate::joins::sort_merge_join::{get_corrected_filter_mask, JoinedRecordBatches};
@@ -4132,6 +4596,272 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn overallocation_multi_batch_spill_with_filter() -> Result<()> {
+        // All rows share the same join key, so that every streamed row is joined
+        // with all buffered rows before the join filter outcome is known
+        let left_batches = (0..4)
+            .map(|i| {
+                build_table_i32(
+                    ("a1", &vec![i * 3, i * 3 + 1, i * 3 + 2]),
+                    ("b1", &vec![1, 1, 1]),
+                    ("c1", &vec![i * 30, i * 30 + 10, i * 30 + 20]),
+                )
+            })
+            .collect::<Vec<_>>();
+        let right_batches = (0..4)
+            .map(|i| {
+                build_table_i32(
+                    ("a2", &vec![i * 2, i * 2 + 1]),
+                    ("b2", &vec![1, 1]),
+                    ("c2", &vec![i * 25, i * 25 + 40]),
+                )
+            })
+            .collect::<Vec<_>>();
+        let left = build_table_from_batches(left_batches);
+        let right = build_table_from_batches(right_batches);
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+        )];
+        let filter = JoinFilter::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("c2", 1)),
+                Operator::Gt,
+                Arc::new(Column::new("c1", 0)),
+            )),
+            vec![
+                ColumnIndex {
+                    index: 2,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 2,
+                    side: JoinSide::Right,
+                },
+            ],
+            Arc::new(Schema::new(vec![
+                Field::new("c1", DataType::Int32, true),
+                Field::new("c2", DataType::Int32, true),
+            ])),
+        );
+        let sort_options = vec![SortOptions::default(); on.len()];
+
+        let join_types = [Left, Right, Full, LeftSemi, LeftAnti, RightAnti, LeftMark];
+
+        // Enable DiskManager to allow spilling
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(500, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+
+        for batch_size in [1, 5, 50] {
+            let session_config = SessionConfig::default().with_batch_size(batch_size);
+
+            for join_type in &join_types {
+                let task_ctx = TaskContext::default()
+                    .with_session_config(session_config.clone())
+                    .with_runtime(Arc::clone(&runtime));
+                let join = join_with_filter(
+                    Arc::clone(&left),
+                    Arc::clone(&right),
+                    on.clone(),
+                    filter.clone(),
+                    *join_type,
+                    sort_options.clone(),
+                    false,
+                )?;
+                let stream = join.execute(0, Arc::new(task_ctx))?;
+                let spilled_join_result = common::collect(stream).await?;
+                assert!(join.metrics().unwrap().spill_count().unwrap() > 0);
+                assert!(join.metrics().unwrap().spilled_rows().unwrap() > 0);
+
+                let task_ctx_no_spill =
+                    TaskContext::default().with_session_config(session_config.clone());
+                let join = join_with_filter(
+                    Arc::clone(&left),
+                    Arc::clone(&right),
+                    on.clone(),
+                    filter.clone(),
+                    *join_type,
+                    sort_options.clone(),
+                    false,
+                )?;
+                let stream = join.execute(0, Arc::new(task_ctx_no_spill))?;
+                let no_spilled_join_result = common::collect(stream).await?;
+                assert_eq!(join.metrics().unwrap().spill_count(), Some(0));
+
+                // The order of null joined rows may differ, as spilled joined
+                // rows are filtered one batch at a time
+                let expected =
+                    pretty_format_batches(&no_spilled_join_result)?.to_string();
+                let mut expected = expected.lines().collect::<Vec<_>>();
+                expected.sort_unstable();
+                let actual = pretty_format_batches(&spilled_join_result)?.to_string();
+                let mut actual = actual.lines().collect::<Vec<_>>();
+                actual.sort_unstable();
+                assert_eq!(expected, actual, "{join_type} with batch size {batch_size}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn large_key_group_spill_with_filter() -> Result<()> {
+        // 200 rows on each side share the same join key, so that the joined
+        // rows staged for the join filter exceed the memory limit
+        let build_batches = |a: &str, b: &str, c: &str| {
+            (0..10)
+                .map(|i| {
+                    let values = (i * 20..i * 20 + 20).collect::<Vec<_>>();
+                    build_table_i32((a, &values), (b, &vec![1; 20]), (c, &values))
+                })
+                .collect::<Vec<_>>()
+        };
+        let left = build_table_from_batches(build_batches("a1", "b1", "c1"));
+        let right = build_table_from_batches(build_batches("a2", "b2", "c2"));
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+        )];
+        // c2 > c1
+        let filter = JoinFilter::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("c2", 1)),
+                Operator::Gt,
+                Arc::new(Column::new("c1", 0)),
+            )),
+            vec![
+                ColumnIndex {
+                    index: 2,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 2,
+                    side: JoinSide::Right,
+                },
+            ],
+            Arc::new(Schema::new(vec![
+                Field::new("c1", DataType::Int32, true),
+                Field::new("c2", DataType::Int32, true),
+            ])),
+        );
+
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(5_000, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+        let task_ctx = TaskContext::default().with_runtime(runtime);
+        let join = join_with_filter(
+            left,
+            right,
+            on,
+            filter,
+            Left,
+            vec![SortOptions::default()],
+            false,
+        )?;
+        let stream = join.execute(0, Arc::new(task_ctx))?;
+        let result = common::collect(stream).await?;
+
+        // Each left row c1 is joined with the 199 - c1 right rows with c2 > c1,
+        // and the last left row with a null row
+        let num_rows = result.iter().map(|batch| batch.num_rows()).sum::<usize>();
+        assert_eq!(num_rows, 199 * 200 / 2 + 1);
+        assert!(join.metrics().unwrap().spill_count().unwrap() > 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn large_key_group_spill_with_filter_all_join_types() -> Result<()> {
+        // 100 rows on each side share the same join key, preceded by 20 right
+        // rows and followed by 20 left rows without match
+        let build_batches = |a: &str, b: &str, c: &str, keys: [i32; 3]| {
+            (0..7)
+                .map(|i| {
+                    let key = match i {
+                        0 => keys[0],
+                        6 => keys[2],
+                        _ => keys[1],
+                    };
+                    let values = (i * 20..i * 20 + 20).collect::<Vec<_>>();
+                    build_table_i32((a, &values), (b, &vec![key; 20]), (c, &values))
+                })
+                .collect::<Vec<_>>()
+        };
+        let left_batches = build_batches("a1", "b1", "c1", [1, 1, 2]);
+        let right_batches = build_batches("a2", "b2", "c2", [0, 1, 1]);
+        // c2 > c1 + 10
+        let filter = JoinFilter::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("c2", 1)),
+                Operator::Gt,
+                Arc::new(BinaryExpr::new(
+                    Arc::new(Column::new("c1", 0)),
+                    Operator::Plus,
+                    Arc::new(Literal::new(ScalarValue::Int32(Some(10)))),
+                )),
+            )),
+            vec![
+                ColumnIndex {
+                    index: 2,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 2,
+                    side: JoinSide::Right,
+                },
+            ],
+            Arc::new(Schema::new(vec![
+                Field::new("c1", DataType::Int32, true),
+                Field::new("c2", DataType::Int32, true),
+            ])),
+        );
+
+        for join_type in [Left, Right, Full, LeftSemi, LeftAnti, RightAnti, LeftMark] {
+            let mut results = vec![];
+            for memory_limit in [usize::MAX, 5_000] {
+                let left = build_table_from_batches(left_batches.clone());
+                let right = build_table_from_batches(right_batches.clone());
+                let on = vec![(
+                    Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+                    Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+                )];
+                let runtime = RuntimeEnvBuilder::new()
+                    .with_memory_limit(memory_limit, 1.0)
+                    .with_disk_manager(DiskManagerConfig::NewOs)
+                    .build_arc()?;
+                let task_ctx = TaskContext::default().with_runtime(runtime);
+                let join = join_with_filter(
+                    left,
+                    right,
+                    on,
+                    filter.clone(),
+                    join_type,
+                    vec![SortOptions::default()],
+                    false,
+                )?;
+                let stream = join.execute(0, Arc::new(task_ctx))?;
+                let result = common::collect(stream).await?;
+                let spill_count = join.metrics().unwrap().spill_count().unwrap();
+                assert_eq!(spill_count > 0, memory_limit != usize::MAX, "{join_type}");
+
+                let mut rows = pretty_format_batches(&result)?
+                    .to_string()
+                    .lines()
+                    .map(String::from)
+                    .collect::<Vec<_>>();
+                rows.sort();
+                results.push(rows);
+            }
+            assert_eq!(results[0], results[1], "{join_type}");
+        }
+
+        Ok(())
+    }
+
     fn build_joined_record_batches() -> Result<JoinedRecordBatches> {
         let schema = Arc::new(Schema::new(vec![
             Field::new("a", DataType::Int32, true),
@@ -4140,12 +4870,7 @@ mod tests {
             Field::new("y", DataType::Int32, true),
         ]));
 
-        let mut batches = JoinedRecordBatches {
-            batches: vec![],
-            filter_mask: BooleanBuilder::new(),
-            row_indices: UInt64Builder::new(),
-            batch_ids: vec![],
-        };
+        let mut batches = JoinedRecordBatches::default();
 
         // Insert already prejoined non-filtered rows
         batches.batches.push(RecordBatch::try_new(
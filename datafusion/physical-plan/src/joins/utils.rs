@@ -559,7 +559,7 @@ pub struct ColumnIndex {
 fn output_join_field(old_field: &Field, join_type: &JoinType, is_left: bool) -> Field {
     let force_nullable = match join_type {
         JoinType::Inner => false,
-        JoinType::Left => !is_left, // right input is padded with nulls
+        JoinType::Left | JoinType::LeftAsof => !is_left, // right input is padded with nulls
         JoinType::Right => is_left, // left input is padded with nulls
         JoinType::Full => true,     // both inputs can be padded with nulls
         JoinType::LeftSemi => false, // doesn't introduce nulls
@@ -617,7 +617,11 @@ pub fn build_join_schema(
     };
 
     let (fields, column_indices): (SchemaBuilder, Vec<ColumnIndex>) = match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::Full | JoinType::Right => {
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::Full
+        | JoinType::Right
+        | JoinType::LeftAsof => {
             // left then right
             left_fields().chain(right_fields()).unzip()
         }
@@ -854,6 +858,16 @@ fn estimate_join_cardinality(
                 column_statistics,
             })
         }
+
+        // AsofJoins produce exactly one row for each row of the left input
+        JoinType::LeftAsof => Some(PartialJoinStatistics {
+            num_rows: *left_stats.num_rows.get_value()?,
+            column_statistics: left_stats
+                .column_statistics
+                .into_iter()
+                .chain(right_stats.column_statistics)
+                .collect(),
+        }),
     }
 }
 
@@ -1296,7 +1310,10 @@ pub(crate) fn adjust_indices_by_join_type(
             // the left_indices will not be used later for the `right anti` join
             Ok((left_indices, right_indices))
         }
-        JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
+        JoinType::LeftSemi
+        | JoinType::LeftAnti
+        | JoinType::LeftMark
+        | JoinType::LeftAsof => {
             // matched or unmatched left row will be produced in the end of loop
             // When visit the right batch, we can output the matched left row and don't need to wait the end of loop
             Ok((
@@ -1619,9 +1636,11 @@ pub(crate) fn symmetric_join_output_partitioning(
     let left_partitioning = left.output_partitioning();
     let right_partitioning = right.output_partitioning();
     match join_type {
-        JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
-            left_partitioning.clone()
-        }
+        JoinType::Left
+        | JoinType::LeftSemi
+        | JoinType::LeftAnti
+        | JoinType::LeftMark
+        | JoinType::LeftAsof => left_partitioning.clone(),
         JoinType::RightSemi | JoinType::RightAnti => right_partitioning.clone(),
         JoinType::Inner | JoinType::Right => {
             adjust_right_output_partitioning(right_partitioning, left_columns_len)
@@ -1648,7 +1667,8 @@ pub(crate) fn asymmetric_join_output_partitioning(
         | JoinType::LeftSemi
         | JoinType::LeftAnti
         | JoinType::Full
-        | JoinType::LeftMark => Partitioning::UnknownPartitioning(
+        | JoinType::LeftMark
+        | JoinType::LeftAsof => Partitioning::UnknownPartitioning(
             right.output_partitioning().partition_count(),
         ),
     }
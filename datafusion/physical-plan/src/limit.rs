@@ -27,17 +27,94 @@ use super::{
     DisplayAs, ExecutionPlanProperties, PlanProperties, RecordBatchStream,
     SendableRecordBatchStream, Statistics,
 };
+use crate::common::collect;
 use crate::execution_plan::{Boundedness, CardinalityEffect};
-use crate::{DisplayFormatType, Distribution, ExecutionPlan, Partitioning};
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{DisplayFormatType, Distribution, ExecutionPlan, Partitioning, PhysicalExpr};
 
-use arrow::datatypes::SchemaRef;
+use arrow::array::RecordBatchOptions;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
-use datafusion_common::{internal_err, Result};
+use datafusion_common::{exec_err, internal_err, DataFusionError, Result, ScalarValue};
 use datafusion_execution::TaskContext;
 
-use futures::stream::{Stream, StreamExt};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
 use log::trace;
 
+/// A `LIMIT` or `OFFSET` value that is computed when the plan is executed,
+/// such as `LIMIT (SELECT cfg FROM settings)`.
+///
+/// The expression is evaluated against a single row whose `i`-th column
+/// holds the result of the `i`-th subquery. Each subquery must produce at
+/// most one row with a single column; no rows is treated as `NULL`.
+#[derive(Debug, Clone)]
+pub struct LimitExpr {
+    /// Expression computing the value
+    expr: Arc<dyn PhysicalExpr>,
+    /// Plans of the scalar subqueries referenced by `expr`
+    subqueries: Vec<Arc<dyn ExecutionPlan>>,
+}
+
+impl LimitExpr {
+    /// Create a new `LimitExpr`
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        subqueries: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Self {
+        Self { expr, subqueries }
+    }
+
+    /// Expression computing the value
+    pub fn expr(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.expr
+    }
+
+    /// Plans of the scalar subqueries referenced by the expression
+    pub fn subqueries(&self) -> &[Arc<dyn ExecutionPlan>] {
+        &self.subqueries
+    }
+
+    /// Runs the subqueries and evaluates the expression, returning `None`
+    /// if the value is `NULL`
+    async fn evaluate(
+        &self,
+        clause: &str,
+        context: &Arc<TaskContext>,
+    ) -> Result<Option<usize>> {
+        let mut fields = Vec::with_capacity(self.subqueries.len());
+        let mut columns = Vec::with_capacity(self.subqueries.len());
+        for (i, subquery) in self.subqueries.iter().enumerate() {
+            let stream = subquery.execute(0, Arc::clone(context))?;
+            let batches = collect(stream).await?;
+            let num_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+            if num_rows > 1 {
+                return exec_err!(
+                    "Scalar subquery in {clause} returned more than one row"
+                );
+            }
+            let value = match batches.iter().find(|batch| batch.num_rows() > 0) {
+                Some(batch) => ScalarValue::try_from_array(batch.column(0), 0)?,
+                None => ScalarValue::try_from(subquery.schema().field(0).data_type())?,
+            };
+            fields.push(Field::new(format!("subquery_{i}"), value.data_type(), true));
+            columns.push(value.to_array()?);
+        }
+        let batch = RecordBatch::try_new_with_options(
+            Arc::new(Schema::new(fields)),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(1)),
+        )?;
+        let value = self.expr.evaluate(&batch)?.into_array(1)?;
+        match ScalarValue::try_from_array(&value, 0)?.cast_to(&DataType::Int64)? {
+            ScalarValue::Int64(Some(n)) if n >= 0 => Ok(Some(n as usize)),
+            ScalarValue::Int64(Some(n)) => {
+                exec_err!("{clause} must be >= 0, '{n}' was provided")
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
 /// Limit execution plan
 #[derive(Debug, Clone)]
 pub struct GlobalLimitExec {
@@ -48,6 +125,12 @@ pub struct GlobalLimitExec {
     /// Maximum number of rows to fetch,
     /// `None` means fetching all rows
     fetch: Option<usize>,
+    /// Computes the number of rows to skip at execution time,
+    /// overriding `skip`
+    skip_expr: Option<LimitExpr>,
+    /// Computes the maximum number of rows to fetch at execution time,
+    /// overriding `fetch`
+    fetch_expr: Option<LimitExpr>,
     /// Execution metrics
     metrics: ExecutionPlanMetricsSet,
     cache: PlanProperties,
@@ -61,11 +144,29 @@ impl GlobalLimitExec {
             input,
             skip,
             fetch,
+            skip_expr: None,
+            fetch_expr: None,
             metrics: ExecutionPlanMetricsSet::new(),
             cache,
         }
     }
 
+    /// Compute the number of rows to skip with `skip_expr` when the plan
+    /// is executed
+    pub fn with_skip_expr(mut self, skip_expr: LimitExpr) -> Self {
+        self.skip = 0;
+        self.skip_expr = Some(skip_expr);
+        self
+    }
+
+    /// Compute the maximum number of rows to fetch with `fetch_expr` when
+    /// the plan is executed
+    pub fn with_fetch_expr(mut self, fetch_expr: LimitExpr) -> Self {
+        self.fetch = None;
+        self.fetch_expr = Some(fetch_expr);
+        self
+    }
+
     /// Input execution plan
     pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
         &self.input
@@ -81,6 +182,23 @@ impl GlobalLimitExec {
         self.fetch
     }
 
+    /// Expression computing the number of rows to skip, if it is only
+    /// known at execution time
+    pub fn skip_expr(&self) -> Option<&LimitExpr> {
+        self.skip_expr.as_ref()
+    }
+
+    /// Expression computing the maximum number of rows to fetch, if it is
+    /// only known at execution time
+    pub fn fetch_expr(&self) -> Option<&LimitExpr> {
+        self.fetch_expr.as_ref()
+    }
+
+    /// Whether the skip or fetch of this limit is only known at execution time
+    pub fn has_limit_exprs(&self) -> bool {
+        self.skip_expr.is_some() || self.fetch_expr.is_some()
+    }
+
     /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
     fn compute_properties(input: &Arc<dyn ExecutionPlan>) -> PlanProperties {
         PlanProperties::new(
@@ -101,12 +219,15 @@ impl DisplayAs for GlobalLimitExec {
     ) -> std::fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
-                write!(
-                    f,
-                    "GlobalLimitExec: skip={}, fetch={}",
-                    self.skip,
-                    self.fetch.map_or("None".to_string(), |x| x.to_string())
-                )
+                let skip = match &self.skip_expr {
+                    Some(skip_expr) => skip_expr.expr.to_string(),
+                    None => self.skip.to_string(),
+                };
+                let fetch = match &self.fetch_expr {
+                    Some(fetch_expr) => fetch_expr.expr.to_string(),
+                    None => self.fetch.map_or("None".to_string(), |x| x.to_string()),
+                };
+                write!(f, "GlobalLimitExec: skip={skip}, fetch={fetch}")
             }
         }
     }
@@ -126,31 +247,54 @@ impl ExecutionPlan for GlobalLimitExec {
         &self.cache
     }
 
+    /// The input is followed by the subqueries of `skip_expr` and
+    /// `fetch_expr`, if any
     fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
-        vec![&self.input]
+        let subqueries = [&self.skip_expr, &self.fetch_expr]
+            .into_iter()
+            .flatten()
+            .flat_map(|limit_expr| &limit_expr.subqueries);
+        std::iter::once(&self.input).chain(subqueries).collect()
     }
 
     fn required_input_distribution(&self) -> Vec<Distribution> {
-        vec![Distribution::SinglePartition]
+        vec![Distribution::SinglePartition; self.children().len()]
     }
 
     fn maintains_input_order(&self) -> Vec<bool> {
-        vec![true]
+        let mut maintains = vec![false; self.children().len()];
+        maintains[0] = true;
+        maintains
     }
 
     fn benefits_from_input_partitioning(&self) -> Vec<bool> {
-        vec![false]
+        vec![false; self.children().len()]
     }
 
     fn with_new_children(
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(GlobalLimitExec::new(
-            Arc::clone(&children[0]),
-            self.skip,
-            self.fetch,
-        )))
+        if children.len() != self.children().len() {
+            return internal_err!("GlobalLimitExec wrong number of children");
+        }
+        let mut children = children.into_iter();
+        let mut limit =
+            GlobalLimitExec::new(children.next().unwrap(), self.skip, self.fetch);
+        if let Some(skip_expr) = &self.skip_expr {
+            let subqueries = children.by_ref().take(skip_expr.subqueries.len());
+            limit = limit.with_skip_expr(LimitExpr::new(
+                Arc::clone(&skip_expr.expr),
+                subqueries.collect(),
+            ));
+        }
+        if let Some(fetch_expr) = &self.fetch_expr {
+            limit = limit.with_fetch_expr(LimitExpr::new(
+                Arc::clone(&fetch_expr.expr),
+                children.collect(),
+            ));
+        }
+        Ok(Arc::new(limit))
     }
 
     fn execute(
@@ -173,12 +317,45 @@ impl ExecutionPlan for GlobalLimitExec {
         }
 
         let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
-        let stream = self.input.execute(0, context)?;
-        Ok(Box::pin(LimitStream::new(
+        if !self.has_limit_exprs() {
+            let stream = self.input.execute(0, context)?;
+            return Ok(Box::pin(LimitStream::new(
+                stream,
+                self.skip,
+                self.fetch,
+                baseline_metrics,
+            )));
+        }
+
+        // Compute the skip and fetch before reading the input
+        let input = Arc::clone(&self.input);
+        let skip = self.skip;
+        let fetch = self.fetch;
+        let skip_expr = self.skip_expr.clone();
+        let fetch_expr = self.fetch_expr.clone();
+        let stream = stream::once(async move {
+            let skip = match skip_expr {
+                Some(skip_expr) => {
+                    skip_expr.evaluate("OFFSET", &context).await?.unwrap_or(0)
+                }
+                None => skip,
+            };
+            let fetch = match fetch_expr {
+                Some(fetch_expr) => fetch_expr.evaluate("LIMIT", &context).await?,
+                None => fetch,
+            };
+            let stream = input.execute(0, context)?;
+            Ok::<_, DataFusionError>(LimitStream::new(
+                stream,
+                skip,
+                fetch,
+                baseline_metrics,
+            ))
+        })
+        .try_flatten();
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
             stream,
-            self.skip,
-            self.fetch,
-            baseline_metrics,
         )))
     }
 
@@ -187,13 +364,19 @@ impl ExecutionPlan for GlobalLimitExec {
     }
 
     fn statistics(&self) -> Result<Statistics> {
-        Statistics::with_fetch(
+        let statistics = Statistics::with_fetch(
             self.input.statistics()?,
             self.schema(),
             self.fetch,
             self.skip,
             1,
-        )
+        )?;
+        if self.has_limit_exprs() {
+            // The actual number of rows is only known at execution time
+            Ok(statistics.to_inexact())
+        } else {
+            Ok(statistics)
+        }
     }
 
     fn fetch(&self) -> Option<usize> {
@@ -201,7 +384,8 @@ impl ExecutionPlan for GlobalLimitExec {
     }
 
     fn supports_limit_pushdown(&self) -> bool {
-        true
+        // Limits from above can't be combined with one that is not known yet
+        !self.has_limit_exprs()
     }
 }
 
@@ -480,11 +664,12 @@ mod tests {
     use crate::test;
 
     use crate::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
+    use crate::memory::MemorySourceConfig;
+    use arrow::array::Int64Array;
     use arrow::array::RecordBatchOptions;
     use arrow::datatypes::Schema;
     use datafusion_common::stats::Precision;
-    use datafusion_physical_expr::expressions::col;
-    use datafusion_physical_expr::PhysicalExpr;
+    use datafusion_physical_expr::expressions::{col, lit};
 
     #[tokio::test]
     async fn limit() -> Result<()> {
@@ -674,6 +859,63 @@ mod tests {
         Ok(())
     }
 
+    /// Plan producing a single row holding `value`
+    fn scalar_subquery(value: i64) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![Field::new("v", DataType::Int64, true)]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(Int64Array::from(vec![value]))],
+        )?;
+        MemorySourceConfig::try_new_exec(&[vec![batch]], schema, None)
+            .map(|exec| exec as _)
+    }
+
+    #[tokio::test]
+    async fn skip_and_fetch_exprs() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let csv = test::scan_partitioned(4);
+        let subquery_schema = Schema::new(vec![Field::new("v", DataType::Int64, true)]);
+
+        // OFFSET (SELECT 395) LIMIT 10
+        let skip_expr =
+            LimitExpr::new(col("v", &subquery_schema)?, vec![scalar_subquery(395)?]);
+        let fetch_expr = LimitExpr::new(lit(10_i64), vec![]);
+        let limit: Arc<dyn ExecutionPlan> = Arc::new(
+            GlobalLimitExec::new(Arc::new(CoalescePartitionsExec::new(csv)), 0, None)
+                .with_skip_expr(skip_expr)
+                .with_fetch_expr(fetch_expr),
+        );
+        assert_eq!(limit.children().len(), 2);
+        assert!(!limit.supports_limit_pushdown());
+        assert!(!limit.statistics()?.num_rows.is_exact().unwrap());
+
+        let children = limit.children().into_iter().cloned().collect();
+        let limit = limit.with_new_children(children)?;
+        let batches = collect(limit.execute(0, task_ctx)?).await?;
+        let row_count: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(row_count, 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn negative_fetch_expr() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let subquery_schema = Schema::new(vec![Field::new("v", DataType::Int64, true)]);
+        let fetch_expr =
+            LimitExpr::new(col("v", &subquery_schema)?, vec![scalar_subquery(-1)?]);
+        let limit = GlobalLimitExec::new(test::scan_partitioned(1), 0, None)
+            .with_fetch_expr(fetch_expr);
+
+        let err = collect(limit.execute(0, task_ctx)?).await.unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Execution error: LIMIT must be >= 0, '-1' was provided"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_row_number_statistics_for_global_limit() -> Result<()> {
         let row_count = row_number_statistics_for_global_limit(0, Some(10)).await?;
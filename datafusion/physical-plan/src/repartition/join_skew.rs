@@ -0,0 +1,152 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Heavy hitters of skew-aware hash joins, shared by the [`RepartitionExec`]s
+//! of their build and probe sides
+//!
+//! [`RepartitionExec`]: super::RepartitionExec
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion_common::{DataFusionError, Result};
+use parking_lot::Mutex;
+use tokio::sync::watch;
+
+/// The side of a skew-aware hash join partitioned by a [`RepartitionExec`]
+///
+/// [`RepartitionExec`]: super::RepartitionExec
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinSkewSide {
+    /// The build side rows of heavy hitters are sent to all the partitions
+    Build,
+    /// The probe side rows of heavy hitters are sent round robin to the
+    /// partitions, and the probe side is sampled to find the heavy hitters
+    Probe,
+}
+
+impl fmt::Display for JoinSkewSide {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Build => write!(f, "build"),
+            Self::Probe => write!(f, "probe"),
+        }
+    }
+}
+
+/// Heavy hitters of a skew-aware hash join: the hashes of the join keys of a
+/// large fraction of its probe side rows
+///
+/// Each input partition of the probe side [`RepartitionExec`] samples its
+/// first rows and reports the heavy hitters of its sample with
+/// [`Self::report`]. The build side [`RepartitionExec`] waits for the heavy
+/// hitters reported by all the probe side input partitions with
+/// [`Self::heavy_hitters`], and sends their build side rows to all the
+/// partitions. As a probe side input partition only spreads the rows of the
+/// heavy hitters it reported, each probe side row meets all its matching build
+/// side rows in its partition.
+///
+/// The probe side must start executing while the build side is collected,
+/// which is the case of a [`HashJoinExec`] with a join skew.
+///
+/// [`RepartitionExec`]: super::RepartitionExec
+/// [`HashJoinExec`]: crate::joins::HashJoinExec
+pub struct JoinSkew {
+    /// Heavy hitters reported so far
+    state: Mutex<JoinSkewState>,
+    /// Sends all the heavy hitters, once all the probe side input partitions
+    /// reported theirs
+    sender: watch::Sender<Option<Arc<HashSet<u64>>>>,
+}
+
+#[derive(Default)]
+struct JoinSkewState {
+    /// Number of probe side input partitions yet to report their heavy
+    /// hitters, `None` until the probe side starts executing
+    pending_reports: Option<usize>,
+    heavy_hitters: HashSet<u64>,
+}
+
+impl JoinSkew {
+    /// Create a new [`JoinSkew`], without heavy hitters
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(JoinSkewState::default()),
+            sender: watch::Sender::new(None),
+        }
+    }
+
+    /// Set the number of probe side input partitions reporting heavy hitters
+    pub(crate) fn expect_reports(&self, input_partitions: usize) {
+        let mut state = self.state.lock();
+        state.pending_reports = Some(input_partitions);
+        if input_partitions == 0 {
+            self.sender.send_replace(Some(Arc::default()));
+        }
+    }
+
+    /// Report the heavy hitters of a probe side input partition
+    pub(crate) fn report(&self, heavy_hitters: &HashSet<u64>) {
+        let mut state = self.state.lock();
+        state.heavy_hitters.extend(heavy_hitters);
+        let pending_reports = state.pending_reports.get_or_insert(1);
+        *pending_reports = pending_reports.saturating_sub(1);
+        if *pending_reports == 0 {
+            let heavy_hitters = std::mem::take(&mut state.heavy_hitters);
+            self.sender.send_replace(Some(Arc::new(heavy_hitters)));
+        }
+    }
+
+    /// Wait for the heavy hitters reported by all the probe side input
+    /// partitions
+    pub(crate) async fn heavy_hitters(&self) -> Result<Arc<HashSet<u64>>> {
+        let mut receiver = self.sender.subscribe();
+        let heavy_hitters = receiver
+            .wait_for(Option::is_some)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        Ok(heavy_hitters.as_ref().map(Arc::clone).unwrap_or_default())
+    }
+}
+
+impl Default for JoinSkew {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for JoinSkew {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinSkew").finish_non_exhaustive()
+    }
+}
+
+/// Returns the hashes of `hashes` found more than `ratio` times the number of
+/// `hashes`
+pub(crate) fn find_heavy_hitters(hashes: &[u64], ratio: f64) -> HashSet<u64> {
+    let mut counts = datafusion_common::HashMap::<u64, usize>::new();
+    for hash in hashes {
+        *counts.entry(*hash).or_default() += 1;
+    }
+    let threshold = (hashes.len() as f64 * ratio).max(1.0);
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count as f64 > threshold)
+        .map(|(hash, _)| hash)
+        .collect()
+}
@@ -19,6 +19,7 @@
 //! partitions to M output partitions based on a partitioning scheme, optionally
 //! maintaining the order of the input rows in the output.
 
+use std::collections::HashSet;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -37,6 +38,7 @@ use crate::repartition::distributor_channels::{
     channels, partition_aware_channels, DistributionReceiver, DistributionSender,
 };
 use crate::sorts::streaming_merge::StreamingMergeBuilder;
+use crate::spill::{read_spill_stream_as_stream, SpillStreamWriter};
 use crate::stream::RecordBatchStreamAdapter;
 use crate::{DisplayFormatType, ExecutionPlan, Partitioning, PlanProperties, Statistics};
 
@@ -45,21 +47,59 @@ use arrow::compute::take_arrays;
 use arrow::datatypes::{SchemaRef, UInt32Type};
 use datafusion_common::utils::transpose;
 use datafusion_common::HashMap;
-use datafusion_common::{not_impl_err, DataFusionError, Result};
+use datafusion_common::{internal_err, not_impl_err, DataFusionError, Result};
 use datafusion_common_runtime::SpawnedTask;
+use datafusion_execution::disk_manager::DiskManager;
 use datafusion_execution::memory_pool::MemoryConsumer;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
 use datafusion_physical_expr_common::sort_expr::LexOrdering;
 
-use futures::stream::Stream;
-use futures::{FutureExt, StreamExt, TryStreamExt};
+use futures::stream::{BoxStream, Stream};
+use futures::{ready, FutureExt, StreamExt, TryStreamExt};
 use log::trace;
 use parking_lot::Mutex;
 
 mod distributor_channels;
+mod join_skew;
 
-type MaybeBatch = Option<Result<RecordBatch>>;
+use join_skew::find_heavy_hitters;
+pub use join_skew::{JoinSkew, JoinSkewSide};
+
+/// A batch sent by an input partition to an output partition
+#[derive(Debug)]
+enum RepartitionBatch {
+    /// A batch in memory, accounted for by the memory reservation of the
+    /// output partition
+    Memory(RecordBatch),
+    /// A batch appended to the [`SpillChannel`] of the channel, because the
+    /// memory reservation of the output partition could not grow
+    Spilled,
+}
+
+/// Spill file of the batches sent through a channel to an output partition
+///
+/// The input partitions append the batches they spill to the file, and the
+/// output partition reads them back in the same order. Once the output
+/// partition has read all the batches of the file, it takes the file from the
+/// [`SpillChannel`] so that the next batches are appended to a new file.
+#[derive(Default)]
+struct SpillChannel {
+    /// Writer of the file the next spilled batches are appended to, if any
+    writer: Option<SpillStreamWriter>,
+}
+
+impl std::fmt::Debug for SpillChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpillChannel")
+            .field("spilling", &self.writer.is_some())
+            .finish()
+    }
+}
+
+type SharedSpillChannel = Arc<Mutex<SpillChannel>>;
+
+type MaybeBatch = Option<Result<RepartitionBatch>>;
 type InputPartitionsToCurrentPartitionSender = Vec<DistributionSender<MaybeBatch>>;
 type InputPartitionsToCurrentPartitionReceiver = Vec<DistributionReceiver<MaybeBatch>>;
 
@@ -74,6 +114,7 @@ struct RepartitionExecState {
             InputPartitionsToCurrentPartitionSender,
             InputPartitionsToCurrentPartitionReceiver,
             SharedMemoryReservation,
+            Vec<SharedSpillChannel>,
         ),
     >,
 
@@ -87,19 +128,32 @@ impl RepartitionExecState {
         partitioning: Partitioning,
         metrics: ExecutionPlanMetricsSet,
         preserve_order: bool,
+        join_skew: Option<(JoinSkewSide, Arc<JoinSkew>)>,
         name: String,
         context: Arc<TaskContext>,
     ) -> Self {
         let num_input_partitions = input.output_partitioning().partition_count();
         let num_output_partitions = partitioning.partition_count();
 
-        let (txs, rxs) = if preserve_order {
+        if let Some((JoinSkewSide::Probe, join_skew)) = &join_skew {
+            join_skew.expect_reports(num_input_partitions);
+        }
+
+        // one spill channel per channel, shared by its senders
+        let (txs, rxs, spills) = if preserve_order {
             let (txs, rxs) =
                 partition_aware_channels(num_input_partitions, num_output_partitions);
             // Take transpose of senders and receivers. `state.channels` keeps track of entries per output partition
             let txs = transpose(txs);
             let rxs = transpose(rxs);
-            (txs, rxs)
+            let spills = (0..num_output_partitions)
+                .map(|_| {
+                    (0..num_input_partitions)
+                        .map(|_| SharedSpillChannel::default())
+                        .collect()
+                })
+                .collect::<Vec<_>>();
+            (txs, rxs, spills)
         } else {
             // create one channel per *output* partition
             // note we use a custom channel that ensures there is always data for each receiver
@@ -111,16 +165,22 @@ impl RepartitionExecState {
                 .map(|item| vec![item; num_input_partitions])
                 .collect::<Vec<_>>();
             let rxs = rxs.into_iter().map(|item| vec![item]).collect::<Vec<_>>();
-            (txs, rxs)
+            let spills = (0..num_output_partitions)
+                .map(|_| vec![SharedSpillChannel::default(); num_input_partitions])
+                .collect::<Vec<_>>();
+            (txs, rxs, spills)
         };
 
         let mut channels = HashMap::with_capacity(txs.len());
-        for (partition, (tx, rx)) in txs.into_iter().zip(rxs).enumerate() {
+        for (partition, ((tx, rx), spill)) in
+            txs.into_iter().zip(rxs).zip(spills).enumerate()
+        {
             let reservation = Arc::new(Mutex::new(
                 MemoryConsumer::new(format!("{}[{partition}]", name))
+                    .with_can_spill(true)
                     .register(context.memory_pool()),
             ));
-            channels.insert(partition, (tx, rx, reservation));
+            channels.insert(partition, (tx, rx, reservation, spill));
         }
 
         // launch one async task per *input* partition
@@ -128,8 +188,15 @@ impl RepartitionExecState {
         for i in 0..num_input_partitions {
             let txs: HashMap<_, _> = channels
                 .iter()
-                .map(|(partition, (tx, _rx, reservation))| {
-                    (*partition, (tx[i].clone(), Arc::clone(reservation)))
+                .map(|(partition, (tx, _rx, reservation, spill))| {
+                    (
+                        *partition,
+                        (
+                            tx[i].clone(),
+                            Arc::clone(reservation),
+                            Arc::clone(&spill[i]),
+                        ),
+                    )
                 })
                 .collect();
 
@@ -140,6 +207,7 @@ impl RepartitionExecState {
                 i,
                 txs.clone(),
                 partitioning.clone(),
+                join_skew.clone(),
                 r_metrics,
                 Arc::clone(&context),
             ));
@@ -149,7 +217,7 @@ impl RepartitionExecState {
             let wait_for_task = SpawnedTask::spawn(RepartitionExec::wait_for_task(
                 input_task,
                 txs.into_iter()
-                    .map(|(partition, (tx, _reservation))| (partition, tx))
+                    .map(|(partition, (tx, _reservation, _spill))| (partition, tx))
                     .collect(),
             ));
             spawned_tasks.push(wait_for_task);
@@ -188,6 +256,7 @@ enum BatchPartitionerState {
         exprs: Vec<Arc<dyn PhysicalExpr>>,
         num_partitions: usize,
         hash_buffer: Vec<u64>,
+        skew: Option<PartitionerSkew>,
     },
     RoundRobin {
         num_partitions: usize,
@@ -195,6 +264,14 @@ enum BatchPartitionerState {
     },
 }
 
+/// Heavy hitters of a skew-aware hash join partitioned by a [`BatchPartitioner`]
+struct PartitionerSkew {
+    side: JoinSkewSide,
+    heavy_hitters: Arc<HashSet<u64>>,
+    /// Next partition of the probe side rows of heavy hitters
+    next_idx: usize,
+}
+
 impl BatchPartitioner {
     /// Create a new [`BatchPartitioner`] with the provided [`Partitioning`]
     ///
@@ -213,6 +290,7 @@ impl BatchPartitioner {
                 // Use fixed random hash
                 random_state: ahash::RandomState::with_seeds(0, 0, 0, 0),
                 hash_buffer: vec![],
+                skew: None,
             },
             other => return not_impl_err!("Unsupported repartitioning scheme {other:?}"),
         };
@@ -220,6 +298,64 @@ impl BatchPartitioner {
         Ok(Self { state, timer })
     }
 
+    /// Partition the rows of `heavy_hitters`, the hashes of the join keys of
+    /// a skew-aware hash join, according to `side`: the build side rows are
+    /// sent to all the partitions, and the probe side rows are sent round
+    /// robin to the partitions
+    ///
+    /// This is a no op unless partitioning by hash.
+    pub fn with_join_skew(
+        mut self,
+        side: JoinSkewSide,
+        heavy_hitters: Arc<HashSet<u64>>,
+    ) -> Self {
+        if let BatchPartitionerState::Hash { skew, .. } = &mut self.state {
+            *skew = (!heavy_hitters.is_empty()).then_some(PartitionerSkew {
+                side,
+                heavy_hitters,
+                next_idx: 0,
+            });
+        }
+        self
+    }
+
+    /// Returns the hashes of the rows of `batch` used to partition it by hash
+    fn hash_rows(&mut self, batch: &RecordBatch) -> Result<&[u64]> {
+        match &mut self.state {
+            BatchPartitionerState::Hash {
+                random_state,
+                exprs,
+                hash_buffer,
+                ..
+            } => {
+                Self::hash_batch(batch, exprs, random_state, hash_buffer)?;
+                Ok(hash_buffer)
+            }
+            BatchPartitionerState::RoundRobin { .. } => {
+                internal_err!("Round robin partitioning does not hash rows")
+            }
+        }
+    }
+
+    /// Writes the hashes of `exprs` evaluated against `batch` to `hash_buffer`
+    fn hash_batch(
+        batch: &RecordBatch,
+        exprs: &[Arc<dyn PhysicalExpr>],
+        random_state: &ahash::RandomState,
+        hash_buffer: &mut Vec<u64>,
+    ) -> Result<()> {
+        let arrays = exprs
+            .iter()
+            .map(|expr| expr.evaluate(batch)?.into_array(batch.num_rows()))
+            .collect::<Result<Vec<_>>>()?;
+
+        hash_buffer.clear();
+        hash_buffer.resize(batch.num_rows(), 0);
+
+        create_hashes(&arrays, random_state, hash_buffer)?;
+        Ok(())
+    }
+
     /// Partition the provided [`RecordBatch`] into one or more partitioned [`RecordBatch`]
     /// based on the [`Partitioning`] specified on construction
     ///
@@ -263,26 +399,39 @@ impl BatchPartitioner {
                     exprs,
                     num_partitions: partitions,
                     hash_buffer,
+                    skew,
                 } => {
                     // Tracking time required for distributing indexes across output partitions
                     let timer = self.timer.timer();
 
-                    let arrays = exprs
-                        .iter()
-                        .map(|expr| expr.evaluate(&batch)?.into_array(batch.num_rows()))
-                        .collect::<Result<Vec<_>>>()?;
-
-                    hash_buffer.clear();
-                    hash_buffer.resize(batch.num_rows(), 0);
-
-                    create_hashes(&arrays, random_state, hash_buffer)?;
+                    Self::hash_batch(&batch, exprs, random_state, hash_buffer)?;
 
                     let mut indices: Vec<_> = (0..*partitions)
                         .map(|_| Vec::with_capacity(batch.num_rows()))
                         .collect();
 
-                    for (index, hash) in hash_buffer.iter().enumerate() {
-                        indices[(*hash % *partitions as u64) as usize].push(index as u32);
+                    match skew {
+                        Some(skew) => {
+                            for (index, hash) in hash_buffer.iter().enumerate() {
+                                if !skew.heavy_hitters.contains(hash) {
+                                    indices[(*hash % *partitions as u64) as usize]
+                                        .push(index as u32);
+                                } else if skew.side == JoinSkewSide::Build {
+                                    indices
+                                        .iter_mut()
+                                        .for_each(|indices| indices.push(index as u32));
+                                } else {
+                                    indices[skew.next_idx].push(index as u32);
+                                    skew.next_idx = (skew.next_idx + 1) % *partitions;
+                                }
+                            }
+                        }
+                        None => {
+                            for (index, hash) in hash_buffer.iter().enumerate() {
+                                indices[(*hash % *partitions as u64) as usize]
+                                    .push(index as u32);
+                            }
+                        }
                     }
 
                     // Finished building index-arrays for output partitions
@@ -385,6 +534,16 @@ impl BatchPartitioner {
 /// If any of the input partitions return an error, the error is propagated to
 /// all output partitions and inputs are not polled again.
 ///
+/// # Memory Usage
+///
+/// The batches sent to an output partition are buffered until the output
+/// partition reads them, using memory reserved from the memory pool by the
+/// output partition. When the output partition is slower than the input
+/// partitions, for example because hash partitioning sends it most of the
+/// rows, the reservation may not be able to grow. The batches are then
+/// spilled to disk, in the Arrow IPC format, and read back by the output
+/// partition in the order they were sent.
+///
 /// # Output Ordering
 ///
 /// If more than one stream is being repartitioned, the output will be some
@@ -409,6 +568,9 @@ pub struct RepartitionExec {
     /// Boolean flag to decide whether to preserve ordering. If true means
     /// `SortPreservingRepartitionExec`, false means `RepartitionExec`.
     preserve_order: bool,
+    /// Side of a skew-aware hash join partitioned by this exec, and the heavy
+    /// hitters of the join
+    join_skew: Option<(JoinSkewSide, Arc<JoinSkew>)>,
     /// Cache holding plan properties like equivalences, output partitioning etc.
     cache: PlanProperties,
 }
@@ -423,6 +585,12 @@ struct RepartitionMetrics {
     ///
     /// One metric per output partition.
     send_time: Vec<metrics::Time>,
+    /// Number of spill files written
+    spill_count: metrics::Count,
+    /// Total size of the batches spilled to disk, in bytes
+    spilled_bytes: metrics::Count,
+    /// Total number of rows spilled to disk
+    spilled_rows: metrics::Count,
 }
 
 impl RepartitionMetrics {
@@ -454,6 +622,9 @@ impl RepartitionMetrics {
             fetch_time,
             repartition_time,
             send_time,
+            spill_count: MetricBuilder::new(metrics).spill_count(input_partition),
+            spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(input_partition),
+            spilled_rows: MetricBuilder::new(metrics).spilled_rows(input_partition),
         }
     }
 }
@@ -475,6 +646,14 @@ impl RepartitionExec {
         self.preserve_order
     }
 
+    /// Side of the skew-aware hash join partitioned by this exec, and the
+    /// heavy hitters of the join, if any
+    pub fn join_skew(&self) -> Option<(JoinSkewSide, &Arc<JoinSkew>)> {
+        self.join_skew
+            .as_ref()
+            .map(|(side, join_skew)| (*side, join_skew))
+    }
+
     /// Get name used to display this Exec
     pub fn name(&self) -> &str {
         "RepartitionExec"
@@ -504,6 +683,10 @@ impl DisplayAs for RepartitionExec {
                 if let Some(sort_exprs) = self.sort_exprs() {
                     write!(f, ", sort_exprs={}", sort_exprs.clone())?;
                 }
+
+                if let Some((side, _)) = self.join_skew() {
+                    write!(f, ", join_skew={side}")?;
+                }
                 Ok(())
             }
         }
@@ -539,6 +722,9 @@ impl ExecutionPlan for RepartitionExec {
         if self.preserve_order {
             repartition = repartition.with_preserve_order();
         }
+        if let Some((side, join_skew)) = self.join_skew() {
+            repartition = repartition.with_join_skew(side, Arc::clone(join_skew));
+        }
         Ok(Arc::new(repartition))
     }
 
@@ -566,6 +752,7 @@ impl ExecutionPlan for RepartitionExec {
         let partitioning = self.partitioning().clone();
         let metrics = self.metrics.clone();
         let preserve_order = self.preserve_order;
+        let join_skew = self.join_skew.clone();
         let name = self.name().to_owned();
         let schema = self.schema();
         let schema_captured = Arc::clone(&schema);
@@ -587,6 +774,7 @@ impl ExecutionPlan for RepartitionExec {
                         partitioning,
                         metrics_captured,
                         preserve_order,
+                        join_skew,
                         name_captured,
                         context_captured,
                     ))
@@ -594,18 +782,18 @@ impl ExecutionPlan for RepartitionExec {
                 .await;
 
             // lock scope
-            let (mut rx, reservation, abort_helper) = {
+            let (mut rx, reservation, mut spill, abort_helper) = {
                 // lock mutexes
                 let mut state = state.lock();
 
                 // now return stream for the specified *output* partition which will
                 // read from the channel
-                let (_tx, rx, reservation) = state
+                let (_tx, rx, reservation, spill) = state
                     .channels
                     .remove(&partition)
                     .expect("partition not used yet");
 
-                (rx, reservation, Arc::clone(&state.abort_helper))
+                (rx, reservation, spill, Arc::clone(&state.abort_helper))
             };
 
             trace!(
@@ -618,12 +806,17 @@ impl ExecutionPlan for RepartitionExec {
                 // Store streams from all the input partitions:
                 let input_streams = rx
                     .into_iter()
-                    .map(|receiver| {
+                    .zip(spill)
+                    .map(|(receiver, spill)| {
                         Box::pin(PerPartitionStream {
                             schema: Arc::clone(&schema_captured),
                             receiver,
                             _drop_helper: Arc::clone(&abort_helper),
                             reservation: Arc::clone(&reservation),
+                            spilled: SpillReader::new(
+                                spill,
+                                Arc::clone(&schema_captured),
+                            ),
                         }) as SendableRecordBatchStream
                     })
                     .collect::<Vec<_>>();
@@ -652,6 +845,7 @@ impl ExecutionPlan for RepartitionExec {
                     input: rx.swap_remove(0),
                     _drop_helper: abort_helper,
                     reservation,
+                    spilled: SpillReader::new(spill.swap_remove(0), input.schema()),
                 }) as SendableRecordBatchStream)
             }
         })
@@ -706,10 +900,11 @@ impl ExecutionPlan for RepartitionExec {
             others => others.clone(),
         };
 
-        Ok(Some(Arc::new(RepartitionExec::try_new(
-            new_projection,
-            new_partitioning,
-        )?)))
+        let mut repartition = RepartitionExec::try_new(new_projection, new_partitioning)?;
+        if let Some((side, join_skew)) = self.join_skew() {
+            repartition = repartition.with_join_skew(side, Arc::clone(join_skew));
+        }
+        Ok(Some(Arc::new(repartition)))
     }
 }
 
@@ -729,6 +924,7 @@ impl RepartitionExec {
             state: Default::default(),
             metrics: ExecutionPlanMetricsSet::new(),
             preserve_order,
+            join_skew: None,
             cache,
         })
     }
@@ -792,6 +988,29 @@ impl RepartitionExec {
         self
     }
 
+    /// Partition the `side` of a skew-aware hash join, whose heavy hitters
+    /// are shared by the [`RepartitionExec`]s of both sides of the join.
+    ///
+    /// Each input partition of the probe side samples its first rows, up to
+    /// `hash_join_skew_sample_rows`, and the hashes of the join keys of more
+    /// than `hash_join_skew_heavy_hitter_ratio` of the sampled rows are the
+    /// heavy hitters of the join. The build side waits for the heavy hitters
+    /// before partitioning its input, and sends the rows of heavy hitters to
+    /// all the partitions, while the probe side sends them round robin to the
+    /// partitions. See [`JoinSkew`] for more details.
+    ///
+    /// This is a no op unless partitioning by hash.
+    pub fn with_join_skew(
+        mut self,
+        side: JoinSkewSide,
+        join_skew: Arc<JoinSkew>,
+    ) -> Self {
+        if matches!(self.partitioning(), Partitioning::Hash(_, _)) {
+            self.join_skew = Some((side, join_skew));
+        }
+        self
+    }
+
     /// Return the sort expressions that are used to merge
     fn sort_exprs(&self) -> Option<&LexOrdering> {
         if self.preserve_order {
@@ -810,20 +1029,55 @@ impl RepartitionExec {
         partition: usize,
         mut output_channels: HashMap<
             usize,
-            (DistributionSender<MaybeBatch>, SharedMemoryReservation),
+            (
+                DistributionSender<MaybeBatch>,
+                SharedMemoryReservation,
+                SharedSpillChannel,
+            ),
         >,
         partitioning: Partitioning,
+        join_skew: Option<(JoinSkewSide, Arc<JoinSkew>)>,
         metrics: RepartitionMetrics,
         context: Arc<TaskContext>,
     ) -> Result<()> {
         let mut partitioner =
             BatchPartitioner::try_new(partitioning, metrics.repartition_time.clone())?;
 
+        let disk_manager = Arc::clone(&context.runtime_env().disk_manager);
+        let options = &context.session_config().options().execution;
+        let sample_rows = options.hash_join_skew_sample_rows;
+        let heavy_hitter_ratio = options.hash_join_skew_heavy_hitter_ratio;
+
+        if let Some((JoinSkewSide::Build, join_skew)) = &join_skew {
+            let heavy_hitters = join_skew.heavy_hitters().await?;
+            partitioner = partitioner.with_join_skew(JoinSkewSide::Build, heavy_hitters);
+        }
+
         // execute the child operator
         let timer = metrics.fetch_time.timer();
-        let mut stream = input.execute(partition, context)?;
+        let mut stream = input.execute(partition, context)?.boxed();
         timer.done();
 
+        if let Some((JoinSkewSide::Probe, join_skew)) = &join_skew {
+            let mut sample = vec![];
+            let heavy_hitters = Self::sample_heavy_hitters(
+                &mut stream,
+                &mut partitioner,
+                &mut sample,
+                sample_rows,
+                heavy_hitter_ratio,
+                &metrics,
+            )
+            .await;
+            // Always report, as the build side waits for all the reports
+            join_skew.report(heavy_hitters.as_ref().unwrap_or(&HashSet::new()));
+            partitioner =
+                partitioner.with_join_skew(JoinSkewSide::Probe, Arc::new(heavy_hitters?));
+            stream = futures::stream::iter(sample.into_iter().map(Ok))
+                .chain(stream)
+                .boxed();
+        }
+
         // While there are still outputs to send to, keep pulling inputs
         let mut batches_until_yield = partitioner.num_partitions();
         while !output_channels.is_empty() {
@@ -844,12 +1098,23 @@ impl RepartitionExec {
 
                 let timer = metrics.send_time[partition].timer();
                 // if there is still a receiver, send to it
-                if let Some((tx, reservation)) = output_channels.get_mut(&partition) {
-                    reservation.lock().try_grow(size)?;
+                if let Some((tx, reservation, spill)) =
+                    output_channels.get_mut(&partition)
+                {
+                    let batch = match reservation.lock().try_grow(size) {
+                        Ok(_) => RepartitionBatch::Memory(batch),
+                        Err(_) if disk_manager.tmp_files_enabled() => {
+                            Self::spill(&batch, spill, &disk_manager, &metrics)?
+                        }
+                        Err(e) => return Err(e),
+                    };
+                    let in_memory = matches!(batch, RepartitionBatch::Memory(_));
 
                     if tx.send(Some(Ok(batch))).await.is_err() {
                         // If the other end has hung up, it was an early shutdown (e.g. LIMIT)
-                        reservation.lock().shrink(size);
+                        if in_memory {
+                            reservation.lock().shrink(size);
+                        }
                         output_channels.remove(&partition);
                     }
                 }
@@ -883,6 +1148,57 @@ impl RepartitionExec {
         Ok(())
     }
 
+    /// Reads the first `sample_rows` rows of `stream` to `sample`, and returns
+    /// the hashes of more than `heavy_hitter_ratio` of them
+    async fn sample_heavy_hitters(
+        stream: &mut BoxStream<'static, Result<RecordBatch>>,
+        partitioner: &mut BatchPartitioner,
+        sample: &mut Vec<RecordBatch>,
+        sample_rows: usize,
+        heavy_hitter_ratio: f64,
+        metrics: &RepartitionMetrics,
+    ) -> Result<HashSet<u64>> {
+        let mut hashes = Vec::with_capacity(sample_rows);
+        while hashes.len() < sample_rows {
+            let timer = metrics.fetch_time.timer();
+            let result = stream.next().await;
+            timer.done();
+
+            let Some(batch) = result.transpose()? else {
+                break;
+            };
+            let _timer = metrics.repartition_time.timer();
+            hashes.extend_from_slice(partitioner.hash_rows(&batch)?);
+            sample.push(batch);
+        }
+        Ok(find_heavy_hitters(&hashes, heavy_hitter_ratio))
+    }
+
+    /// Appends `batch` to the file of `spill`, creating a new temporary file
+    /// if the output partition took the previous one
+    fn spill(
+        batch: &RecordBatch,
+        spill: &SharedSpillChannel,
+        disk_manager: &DiskManager,
+        metrics: &RepartitionMetrics,
+    ) -> Result<RepartitionBatch> {
+        let mut spill = spill.lock();
+        let writer = match &mut spill.writer {
+            Some(writer) => writer,
+            None => {
+                let file = disk_manager.create_tmp_file("RepartitionExec spill")?;
+                metrics.spill_count.add(1);
+                spill
+                    .writer
+                    .insert(SpillStreamWriter::try_new(file, &batch.schema())?)
+            }
+        };
+        writer.write(batch)?;
+        metrics.spilled_bytes.add(batch.get_array_memory_size());
+        metrics.spilled_rows.add(batch.num_rows());
+        Ok(RepartitionBatch::Spilled)
+    }
+
     /// Waits for `input_task` which is consuming one of the inputs to
     /// complete. Upon each successful completion, sends a `None` to
     /// each of the output tx channels to signal one of the inputs is
@@ -948,6 +1264,9 @@ struct RepartitionStream {
 
     /// Memory reservation.
     reservation: SharedMemoryReservation,
+
+    /// Reader of the spilled batches
+    spilled: SpillReader,
 }
 
 impl Stream for RepartitionStream {
@@ -958,33 +1277,29 @@ impl Stream for RepartitionStream {
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
         loop {
-            match self.input.recv().poll_unpin(cx) {
-                Poll::Ready(Some(Some(v))) => {
-                    if let Ok(batch) = &v {
-                        self.reservation
-                            .lock()
-                            .shrink(batch.get_array_memory_size());
+            if let Some(batch) = ready!(self.spilled.poll_next(cx)) {
+                return Poll::Ready(Some(batch));
+            }
+            match ready!(self.input.recv().poll_unpin(cx)) {
+                Some(Some(v)) => {
+                    let this = &mut *self;
+                    if let Some(batch) = receive(v, &this.reservation, &mut this.spilled)
+                    {
+                        return Poll::Ready(Some(batch));
                     }
-
-                    return Poll::Ready(Some(v));
                 }
-                Poll::Ready(Some(None)) => {
+                Some(None) => {
                     self.num_input_partitions_processed += 1;
 
                     if self.num_input_partitions == self.num_input_partitions_processed {
                         // all input partitions have finished sending batches
                         return Poll::Ready(None);
-                    } else {
-                        // other partitions still have data to send
-                        continue;
                     }
+                    // other partitions still have data to send
                 }
-                Poll::Ready(None) => {
+                None => {
                     return Poll::Ready(None);
                 }
-                Poll::Pending => {
-                    return Poll::Pending;
-                }
             }
         }
     }
@@ -1011,6 +1326,9 @@ struct PerPartitionStream {
 
     /// Memory reservation.
     reservation: SharedMemoryReservation,
+
+    /// Reader of the spilled batches
+    spilled: SpillReader,
 }
 
 impl Stream for PerPartitionStream {
@@ -1020,21 +1338,21 @@ impl Stream for PerPartitionStream {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        match self.receiver.recv().poll_unpin(cx) {
-            Poll::Ready(Some(Some(v))) => {
-                if let Ok(batch) = &v {
-                    self.reservation
-                        .lock()
-                        .shrink(batch.get_array_memory_size());
-                }
-                Poll::Ready(Some(v))
+        loop {
+            if let Some(batch) = ready!(self.spilled.poll_next(cx)) {
+                return Poll::Ready(Some(batch));
             }
-            Poll::Ready(Some(None)) => {
+            match ready!(self.receiver.recv().poll_unpin(cx)) {
+                Some(Some(v)) => {
+                    let this = &mut *self;
+                    if let Some(batch) = receive(v, &this.reservation, &mut this.spilled)
+                    {
+                        return Poll::Ready(Some(batch));
+                    }
+                }
                 // Input partition has finished sending batches
-                Poll::Ready(None)
+                Some(None) | None => return Poll::Ready(None),
             }
-            Poll::Ready(None) => Poll::Ready(None),
-            Poll::Pending => Poll::Pending,
         }
     }
 }
@@ -1046,6 +1364,86 @@ impl RecordBatchStream for PerPartitionStream {
     }
 }
 
+/// Handles a batch received by an output partition
+///
+/// Returns the batch if it was in memory, releasing its memory from
+/// `reservation`, or leaves it to `spilled` to read back if it was spilled.
+fn receive(
+    batch: Result<RepartitionBatch>,
+    reservation: &SharedMemoryReservation,
+    spilled: &mut SpillReader,
+) -> Option<Result<RecordBatch>> {
+    match batch {
+        Ok(RepartitionBatch::Memory(batch)) => {
+            reservation.lock().shrink(batch.get_array_memory_size());
+            Some(Ok(batch))
+        }
+        Ok(RepartitionBatch::Spilled) => {
+            spilled.pending += 1;
+            None
+        }
+        Err(e) => Some(Err(e)),
+    }
+}
+
+/// Reads back the batches spilled to a [`SpillChannel`] by an output partition,
+/// in the order they were spilled
+struct SpillReader {
+    channel: SharedSpillChannel,
+    schema: SchemaRef,
+    /// Number of spilled batches received but not read back yet
+    pending: usize,
+    /// The spill file being read back, if any
+    stream: Option<SendableRecordBatchStream>,
+}
+
+impl SpillReader {
+    fn new(channel: SharedSpillChannel, schema: SchemaRef) -> Self {
+        Self {
+            channel,
+            schema,
+            pending: 0,
+            stream: None,
+        }
+    }
+
+    /// Polls the next spilled batch received but not read back yet
+    ///
+    /// Returns `Ready(None)` once there is no such batch left to read.
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<RecordBatch>>> {
+        while self.pending > 0 {
+            let stream = match &mut self.stream {
+                Some(stream) => stream,
+                None => match self.next_file() {
+                    Ok(stream) => self.stream.insert(stream),
+                    Err(e) => {
+                        self.pending = 0;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                },
+            };
+            match ready!(stream.poll_next_unpin(cx)) {
+                Some(batch) => {
+                    self.pending -= 1;
+                    return Poll::Ready(Some(batch));
+                }
+                // All the batches of the file were read back
+                None => self.stream = None,
+            }
+        }
+        Poll::Ready(None)
+    }
+
+    /// Takes the file the batches are being spilled to, so that the next
+    /// batches are spilled to a new file, and starts reading it back
+    fn next_file(&mut self) -> Result<SendableRecordBatchStream> {
+        let Some(writer) = self.channel.lock().writer.take() else {
+            return internal_err!("Spilled batch of RepartitionExec not found on disk");
+        };
+        read_spill_stream_as_stream(writer.finish()?, Arc::clone(&self.schema), 1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -1062,10 +1460,11 @@ mod tests {
         {collect, expressions::col, memory::MemorySourceConfig},
     };
 
-    use arrow::array::{ArrayRef, StringArray, UInt32Array};
+    use arrow::array::{ArrayRef, AsArray, StringArray, UInt32Array};
     use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_common::cast::as_string_array;
     use datafusion_common::{arrow_datafusion_err, assert_batches_sorted_eq, exec_err};
+    use datafusion_execution::disk_manager::DiskManagerConfig;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
 
     use tokio::task::JoinSet;
@@ -1542,6 +1941,75 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn hash_repartition_with_join_skew() -> Result<()> {
+        let schema = test_schema();
+        let partitioning = Partitioning::Hash(vec![col("c0", &schema)?], 4);
+        let probe_batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(UInt32Array::from(vec![1, 1, 1, 1, 1, 1, 2, 3]))],
+        )?;
+        let build_batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(UInt32Array::from(vec![1, 2, 3]))],
+        )?;
+
+        let join_skew = Arc::new(JoinSkew::new());
+        let probe = RepartitionExec::try_new(
+            MemorySourceConfig::try_new_exec(
+                &[vec![probe_batch]],
+                Arc::clone(&schema),
+                None,
+            )?,
+            partitioning.clone(),
+        )?
+        .with_join_skew(JoinSkewSide::Probe, Arc::clone(&join_skew));
+        let build = RepartitionExec::try_new(
+            MemorySourceConfig::try_new_exec(&[vec![build_batch]], schema, None)?,
+            partitioning,
+        )?
+        .with_join_skew(JoinSkewSide::Build, join_skew);
+
+        // Both sides are executed concurrently, as the build side waits for
+        // the probe side heavy hitters
+        let task_ctx = Arc::new(TaskContext::default());
+        let outputs = futures::future::try_join_all((0..4).flat_map(|partition| {
+            [&probe, &build].map(|exec| {
+                let stream = exec.execute(partition, Arc::clone(&task_ctx));
+                async move { crate::common::collect(stream?).await }
+            })
+        }))
+        .await?;
+
+        let values = |batches: &[RecordBatch]| {
+            batches
+                .iter()
+                .flat_map(|batch| {
+                    let array = batch.column(0).as_any().downcast_ref::<UInt32Array>();
+                    array.unwrap().values().to_vec()
+                })
+                .collect::<Vec<_>>()
+        };
+        let (probe_outputs, build_outputs): (Vec<_>, Vec<_>) = outputs
+            .chunks(2)
+            .map(|o| (values(&o[0]), values(&o[1])))
+            .unzip();
+
+        // The heavy hitter is spread over the probe side partitions, and sent
+        // to all the build side partitions
+        for (probe_output, build_output) in probe_outputs.iter().zip(&build_outputs) {
+            let heavy_probe_rows = probe_output.iter().filter(|v| **v == 1).count();
+            assert!((1..=2).contains(&heavy_probe_rows), "{probe_output:?}");
+            assert!(build_output.contains(&1), "{build_output:?}");
+            for value in [2, 3] {
+                assert_eq!(probe_output.contains(&value), build_output.contains(&value));
+            }
+        }
+        assert_eq!(probe_outputs.concat().len(), 8);
+        assert_eq!(build_outputs.concat().len(), 6);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn oom() -> Result<()> {
         // define input partitions
@@ -1550,9 +2018,10 @@ mod tests {
         let input_partitions = vec![partition];
         let partitioning = Partitioning::RoundRobinBatch(4);
 
-        // setup up context
+        // setup up context, without spilling
         let runtime = RuntimeEnvBuilder::default()
             .with_memory_limit(1, 1.0)
+            .with_disk_manager(DiskManagerConfig::Disabled)
             .build_arc()?;
 
         let task_ctx = TaskContext::default().with_runtime(runtime);
@@ -1581,6 +2050,92 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn spill() -> Result<()> {
+        // define input partitions
+        let schema = test_schema();
+        let input_partitions = vec![create_vec_batches(50), create_vec_batches(50)];
+        let partitioning = Partitioning::Hash(vec![col("c0", &schema)?], 4);
+
+        // setup up context, spilling all the batches
+        let runtime = RuntimeEnvBuilder::default()
+            .with_memory_limit(1, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+
+        let task_ctx = TaskContext::default().with_runtime(runtime);
+        let task_ctx = Arc::new(task_ctx);
+
+        // create physical plan
+        let exec = MemorySourceConfig::try_new_exec(
+            &input_partitions,
+            Arc::clone(&schema),
+            None,
+        )?;
+        let exec = RepartitionExec::try_new(exec, partitioning)?;
+
+        // pull partitions
+        let mut rows = 0;
+        for i in 0..exec.partitioning().partition_count() {
+            let stream = exec.execute(i, Arc::clone(&task_ctx))?;
+            let batches = crate::common::collect(stream).await?;
+            rows += batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+        }
+        assert_eq!(rows, 800);
+
+        let metrics = exec.metrics().unwrap();
+        assert!(metrics.spill_count().unwrap() > 0);
+        assert_eq!(metrics.spilled_rows().unwrap(), 800);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spill_preserves_batch_order() -> Result<()> {
+        // define input partitions
+        let schema = test_schema();
+        let batches = (0..20)
+            .map(|i| {
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![Arc::new(UInt32Array::from(vec![i; 8]))],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let partitioning = Partitioning::RoundRobinBatch(2);
+
+        // setup up context, spilling all the batches
+        let runtime = RuntimeEnvBuilder::default()
+            .with_memory_limit(1, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+
+        // create physical plan
+        let exec =
+            MemorySourceConfig::try_new_exec(&[batches], Arc::clone(&schema), None)?;
+        let exec = RepartitionExec::try_new(exec, partitioning)?;
+
+        // pull partitions
+        for i in 0..exec.partitioning().partition_count() {
+            let stream = exec.execute(i, Arc::clone(&task_ctx))?;
+            let batches = crate::common::collect(stream).await?;
+            let values = batches
+                .iter()
+                .map(|batch| batch.column(0).as_primitive::<UInt32Type>().value(0))
+                .collect::<Vec<_>>();
+            let expected = (0..20).skip(i).step_by(2).collect::<Vec<_>>();
+            assert_eq!(values, expected);
+        }
+
+        let metrics = exec.metrics().unwrap();
+        assert_eq!(metrics.spilled_rows().unwrap(), 160);
+        // the batches are appended to the spill files of the output partitions
+        assert!(metrics.spill_count().unwrap() < 20);
+
+        Ok(())
+    }
+
     /// Create vector batches
     fn create_vec_batches(n: usize) -> Vec<RecordBatch> {
         let batch = create_batch();
@@ -26,7 +26,7 @@ use std::sync::Arc;
 
 use crate::common::spawn_buffered;
 use crate::execution_plan::{Boundedness, CardinalityEffect, EmissionType};
-use crate::expressions::PhysicalSortExpr;
+use crate::expressions::{DynamicFilterPhysicalExpr, PhysicalSortExpr};
 use crate::limit::LimitStream;
 use crate::metrics::{
     BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
@@ -37,7 +37,7 @@ use crate::spill::{
     get_record_batch_memory_size, read_spill_as_stream, spill_record_batches,
 };
 use crate::stream::RecordBatchStreamAdapter;
-use crate::topk::TopK;
+use crate::topk::{TopK, TopKDynamicFilter};
 use crate::{
     DisplayAs, DisplayFormatType, Distribution, EmptyRecordBatchStream, ExecutionPlan,
     ExecutionPlanProperties, Partitioning, PlanProperties, SendableRecordBatchStream,
@@ -710,6 +710,9 @@ pub struct SortExec {
     preserve_partitioning: bool,
     /// Fetch highest/lowest n results
     fetch: Option<usize>,
+    /// Filter on the sort keys pushed down to the scans of the input,
+    /// tightened by the TopKs of all partitions as they see new rows
+    dynamic_filter: Option<Arc<TopKDynamicFilter>>,
     /// Cache holding plan properties like equivalences, output partitioning etc.
     cache: PlanProperties,
 }
@@ -726,6 +729,7 @@ impl SortExec {
             metrics_set: ExecutionPlanMetricsSet::new(),
             preserve_partitioning,
             fetch: None,
+            dynamic_filter: None,
             cache,
         }
     }
@@ -778,10 +782,32 @@ impl SortExec {
             metrics_set: self.metrics_set.clone(),
             preserve_partitioning: self.preserve_partitioning,
             fetch,
+            dynamic_filter: self.dynamic_filter.clone(),
             cache,
         }
     }
 
+    /// Set the dynamic filter on the sort keys that, when the sort has a
+    /// fetch, is tightened to the k-th row seen so far while executing.
+    ///
+    /// `filter` must be created over the sort expressions of this sort, and
+    /// is usually pushed down to the scans of its input, which can then skip
+    /// rows that can not be part of its output. See
+    /// [`DynamicFilterPhysicalExpr`] for more details.
+    pub fn with_dynamic_filter(
+        mut self,
+        filter: Option<Arc<DynamicFilterPhysicalExpr>>,
+    ) -> Self {
+        self.dynamic_filter =
+            filter.map(|filter| Arc::new(TopKDynamicFilter::new(filter)));
+        self
+    }
+
+    /// The dynamic filter on the sort keys, if any
+    pub fn dynamic_filter(&self) -> Option<&Arc<DynamicFilterPhysicalExpr>> {
+        self.dynamic_filter.as_ref().map(|filter| filter.filter())
+    }
+
     /// Input schema
     pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
         &self.input
@@ -918,9 +944,10 @@ impl ExecutionPlan for SortExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let new_sort = SortExec::new(self.expr.clone(), Arc::clone(&children[0]))
+        let mut new_sort = SortExec::new(self.expr.clone(), Arc::clone(&children[0]))
             .with_fetch(self.fetch)
             .with_preserve_partitioning(self.preserve_partitioning);
+        new_sort.dynamic_filter = self.dynamic_filter.clone();
 
         Ok(Arc::new(new_sort))
     }
@@ -960,7 +987,8 @@ impl ExecutionPlan for SortExec {
                     context.session_config().batch_size(),
                     context.runtime_env(),
                     &self.metrics_set,
-                )?;
+                )?
+                .with_dynamic_filter(self.dynamic_filter.clone());
                 Ok(Box::pin(RecordBatchStreamAdapter::new(
                     self.schema(),
                     futures::stream::once(async move {
@@ -1080,8 +1108,8 @@ mod tests {
     use datafusion_execution::config::SessionConfig;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_execution::RecordBatchStream;
-    use datafusion_physical_expr::expressions::{Column, Literal};
-    use datafusion_physical_expr::EquivalenceProperties;
+    use datafusion_physical_expr::expressions::{lit, Column, Literal};
+    use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
 
     use futures::{FutureExt, Stream};
 
@@ -1353,6 +1381,92 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sort_fetch_dynamic_filter() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let batch = |a: Vec<Option<i32>>, b: Vec<Option<i32>>| {
+            RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![Arc::new(Int32Array::from(a)), Arc::new(Int32Array::from(b))],
+            )
+        };
+        let batches = vec![
+            batch(vec![Some(1), Some(3)], vec![Some(1), Some(1)])?,
+            batch(
+                vec![Some(5), Some(4), Some(5), Some(5)],
+                vec![Some(20), Some(1), Some(10), None],
+            )?,
+        ];
+
+        // ORDER BY a DESC NULLS FIRST, b ASC NULLS LAST LIMIT 2
+        let sort_exprs = LexOrdering::new(vec![
+            PhysicalSortExpr {
+                expr: col("a", &schema)?,
+                options: SortOptions {
+                    descending: true,
+                    nulls_first: true,
+                },
+            },
+            PhysicalSortExpr {
+                expr: col("b", &schema)?,
+                options: SortOptions {
+                    descending: false,
+                    nulls_first: false,
+                },
+            },
+        ]);
+        let filter = Arc::new(DynamicFilterPhysicalExpr::new(
+            sort_exprs.iter().map(|e| Arc::clone(&e.expr)).collect(),
+            lit(true),
+        ));
+        let sort_exec = Arc::new(
+            SortExec::new(
+                sort_exprs,
+                MemorySourceConfig::try_new_exec(&[batches], Arc::clone(&schema), None)?,
+            )
+            .with_fetch(Some(2))
+            .with_dynamic_filter(Some(Arc::clone(&filter))),
+        );
+
+        let result = collect(sort_exec, task_ctx).await?;
+        let expected = [
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 5 | 10 |",
+            "| 5 | 20 |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        // Only rows sorting before the 2nd row of the result pass the filter
+        assert_eq!(
+            filter.current()?.to_string(),
+            "a@0 IS NULL OR a@0 > 5 OR a@0 = 5 AND b@1 < 20"
+        );
+        let input = batch(
+            vec![None, Some(6), Some(5), Some(5), Some(5), Some(4)],
+            vec![Some(1), Some(1), Some(10), Some(20), None, Some(1)],
+        )?;
+        let mask = filter.evaluate(&input)?.into_array(input.num_rows())?;
+        assert_eq!(
+            mask.as_boolean(),
+            &BooleanArray::from(vec![
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(false),
+                None,
+                Some(false)
+            ])
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_sort_metadata() -> Result<()> {
         let task_ctx = Arc::new(TaskContext::default());
@@ -28,7 +28,7 @@ use crate::{DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties};
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::{Constraints, Statistics};
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
-use datafusion_physical_expr::{EquivalenceProperties, Partitioning};
+use datafusion_physical_expr::{EquivalenceProperties, Partitioning, PhysicalExpr};
 use datafusion_physical_expr_common::sort_expr::LexOrdering;
 
 /// Common behaviors in Data Sources for both from Files and Memory.
@@ -62,6 +62,18 @@ pub trait DataSource: Send + Sync {
         &self,
         _projection: &ProjectionExec,
     ) -> datafusion_common::Result<Option<Arc<dyn ExecutionPlan>>>;
+    /// Return a copy of this source that also uses the dynamic `filter`,
+    /// written in terms of the output columns of the source, to skip rows
+    /// that can not pass it, or `None` if the source can not make use of it.
+    ///
+    /// The source must not drop rows that pass the filter, but does not have
+    /// to drop all the rows that do not.
+    fn try_pushdown_dynamic_filter(
+        &self,
+        _filter: &Arc<dyn PhysicalExpr>,
+    ) -> Option<Arc<dyn DataSource>> {
+        None
+    }
 }
 
 impl Debug for dyn DataSource {
@@ -185,6 +197,19 @@ impl DataSourceExec {
         self
     }
 
+    /// Return a copy of this plan whose source also uses the dynamic `filter`
+    /// to skip rows, see [`DataSource::try_pushdown_dynamic_filter`]
+    pub fn try_pushdown_dynamic_filter(
+        &self,
+        filter: &Arc<dyn PhysicalExpr>,
+    ) -> Option<Self> {
+        let source = self.source.try_pushdown_dynamic_filter(filter)?;
+        Some(Self {
+            source,
+            cache: self.cache.clone(),
+        })
+    }
+
     /// Assign constraints
     pub fn with_constraints(mut self, constraints: Constraints) -> Self {
         self.cache = self.cache.with_constraints(constraints);
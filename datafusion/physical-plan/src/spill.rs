@@ -17,14 +17,16 @@
 
 //! Defines the spilling functions
 
+use std::borrow::Borrow;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 use std::ptr::NonNull;
 
 use arrow::array::ArrayData;
-use arrow::datatypes::SchemaRef;
-use arrow::ipc::reader::FileReader;
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::ipc::writer::StreamWriter;
 use arrow::record_batch::RecordBatch;
 use log::debug;
 use tokio::sync::mpsc::Sender;
@@ -77,6 +79,87 @@ pub(crate) fn spill_record_batches(
     Ok(writer.num_rows)
 }
 
+/// Incrementally writes batches to a spill file.
+///
+/// Unlike [`IPCWriter`], batches are written in the Arrow IPC stream format,
+/// which allows the dictionaries of dictionary encoded columns to differ
+/// between batches. Use [`read_spill_stream_as_stream`] to read the file back.
+pub(crate) struct SpillStreamWriter {
+    /// The spill file
+    file: RefCountedTempFile,
+    /// Writer to the spill file
+    writer: StreamWriter<BufWriter<File>>,
+    /// Rows written
+    num_rows: usize,
+    /// Bytes written
+    num_bytes: usize,
+}
+
+impl SpillStreamWriter {
+    /// Creates a writer of batches with `schema` to `file`
+    pub(crate) fn try_new(file: RefCountedTempFile, schema: &Schema) -> Result<Self> {
+        let writer =
+            StreamWriter::try_new(BufWriter::new(File::create(file.path())?), schema)?;
+        Ok(Self {
+            file,
+            writer,
+            num_rows: 0,
+            num_bytes: 0,
+        })
+    }
+
+    /// Appends `batch` to the spill file
+    pub(crate) fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.writer.write(batch)?;
+        self.num_rows += batch.num_rows();
+        self.num_bytes += batch.get_array_memory_size();
+        Ok(())
+    }
+
+    /// Returns the number of rows written so far
+    pub(crate) fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of bytes written so far
+    pub(crate) fn num_bytes(&self) -> usize {
+        self.num_bytes
+    }
+
+    /// Finishes the spill file and returns it
+    pub(crate) fn finish(mut self) -> Result<RefCountedTempFile> {
+        self.writer.finish()?;
+        Ok(self.file)
+    }
+}
+
+/// Read batches written by [`SpillStreamWriter`] from the disk
+///
+/// `file` - temp file, or a shared reference to it to read it several times
+/// `schema` - batches schema, should be the same across batches
+/// `buffer` - internal buffer of capacity batches
+pub(crate) fn read_spill_stream_as_stream(
+    file: impl Borrow<RefCountedTempFile> + Send + 'static,
+    schema: SchemaRef,
+    buffer: usize,
+) -> Result<SendableRecordBatchStream> {
+    let mut builder = RecordBatchReceiverStream::builder(schema, buffer);
+    let sender = builder.tx();
+
+    builder.spawn_blocking(move || {
+        let file = File::open(file.borrow().path())?;
+        let reader = StreamReader::try_new(BufReader::new(file), None)?;
+        for batch in reader {
+            sender
+                .blocking_send(batch.map_err(Into::into))
+                .map_err(|e| exec_datafusion_err!("{e}"))?;
+        }
+        Ok(())
+    });
+
+    Ok(builder.build())
+}
+
 fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
     let file = BufReader::new(File::open(path)?);
     let reader = FileReader::try_new(file, None)?;
@@ -184,8 +267,8 @@ mod tests {
     use super::*;
     use crate::spill::{spill_record_batch_by_size, spill_record_batches};
     use crate::test::build_table_i32;
-    use arrow::array::{Float64Array, Int32Array, ListArray};
-    use arrow::datatypes::{DataType, Field, Int32Type, Schema};
+    use arrow::array::{DictionaryArray, Float64Array, Int32Array, ListArray};
+    use arrow::datatypes::{DataType, Field, Int32Type};
     use arrow::record_batch::RecordBatch;
     use datafusion_common::Result;
     use datafusion_execution::disk_manager::DiskManagerConfig;
@@ -257,6 +340,40 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_spill_stream_with_dictionary_replacement() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new_dictionary(
+            "d",
+            DataType::Int32,
+            DataType::Utf8,
+            false,
+        )]));
+        let batch1 = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(DictionaryArray::<Int32Type>::from_iter([
+                "a", "b",
+            ]))],
+        )?;
+        let batch2 = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(DictionaryArray::<Int32Type>::from_iter(["c"]))],
+        )?;
+
+        let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs)?;
+        let spill_file = disk_manager.create_tmp_file("Test Spill")?;
+        let mut writer = SpillStreamWriter::try_new(spill_file, &schema)?;
+        writer.write(&batch1)?;
+        writer.write(&batch2)?;
+        assert_eq!(writer.num_rows(), 3);
+        let spill_file = writer.finish()?;
+
+        let stream = read_spill_stream_as_stream(spill_file, Arc::clone(&schema), 2)?;
+        let batches = crate::common::collect(stream).await?;
+        assert_eq!(batches, vec![batch1, batch2]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_get_record_batch_memory_size() {
         // Create a simple record batch with two columns
@@ -30,13 +30,18 @@ use crate::{stream::RecordBatchStreamAdapter, SendableRecordBatchStream};
 use arrow::array::{Array, ArrayRef, RecordBatch};
 use arrow::datatypes::SchemaRef;
 use datafusion_common::HashMap;
-use datafusion_common::Result;
+use datafusion_common::{Result, ScalarValue};
 use datafusion_execution::{
     memory_pool::{MemoryConsumer, MemoryReservation},
     runtime_env::RuntimeEnv,
 };
-use datafusion_physical_expr::PhysicalSortExpr;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{
+    lit, BinaryExpr, DynamicFilterPhysicalExpr, IsNotNullExpr, IsNullExpr,
+};
+use datafusion_physical_expr::{PhysicalExpr, PhysicalSortExpr};
 use datafusion_physical_expr_common::sort_expr::LexOrdering;
+use parking_lot::Mutex;
 
 /// Global TopK
 ///
@@ -90,6 +95,8 @@ pub struct TopK {
     scratch_rows: Rows,
     /// stores the top k values and their sort key values, in order
     heap: TopKHeap,
+    /// filter tightened to the current k-th value, see [`TopKDynamicFilter`]
+    dynamic_filter: Option<Arc<TopKDynamicFilter>>,
 }
 
 impl TopK {
@@ -137,9 +144,20 @@ impl TopK {
             row_converter,
             scratch_rows,
             heap: TopKHeap::new(k, batch_size, schema),
+            dynamic_filter: None,
         })
     }
 
+    /// Tighten `dynamic_filter` to the k-th value seen so far as new values
+    /// are inserted
+    pub(crate) fn with_dynamic_filter(
+        mut self,
+        dynamic_filter: Option<Arc<TopKDynamicFilter>>,
+    ) -> Self {
+        self.dynamic_filter = dynamic_filter;
+        self
+    }
+
     /// Insert `batch`, remembering if any of its values are among
     /// the top k seen so far.
     pub fn insert_batch(&mut self, batch: RecordBatch) -> Result<()> {
@@ -183,6 +201,43 @@ impl TopK {
 
         // update memory reservation
         self.reservation.try_resize(self.size())?;
+
+        self.update_dynamic_filter()
+    }
+
+    /// Tighten the dynamic filter, if any, to only accept rows sorting
+    /// before the current max of the heap
+    fn update_dynamic_filter(&self) -> Result<()> {
+        let (Some(dynamic_filter), Some(max)) = (&self.dynamic_filter, self.heap.max())
+        else {
+            return Ok(());
+        };
+        let mut threshold = dynamic_filter.threshold.lock();
+        if threshold
+            .as_ref()
+            .is_some_and(|threshold| threshold.as_slice() <= max.row())
+        {
+            return Ok(());
+        }
+
+        let entry = self
+            .heap
+            .store
+            .get(max.batch_id)
+            .expect("invalid stored batch id");
+        let row = entry.batch.slice(max.index, 1);
+        let values = self
+            .expr
+            .iter()
+            .map(|expr| {
+                let value = expr.expr.evaluate(&row)?.into_array(1)?;
+                ScalarValue::try_from_array(&value, 0)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        dynamic_filter
+            .filter
+            .update(sorts_before(&self.expr, values))?;
+        *threshold = Some(max.row().to_vec());
         Ok(())
     }
 
@@ -197,6 +252,7 @@ impl TopK {
             row_converter: _,
             scratch_rows: _,
             mut heap,
+            dynamic_filter: _,
         } = self;
         let _timer = metrics.baseline.elapsed_compute().timer(); // time updated on drop
 
@@ -231,6 +287,90 @@ impl TopK {
     }
 }
 
+/// A [`DynamicFilterPhysicalExpr`] on the sort keys of a TopK, shared by the
+/// [`TopK`]s of all the partitions of a sort.
+///
+/// Once a [`TopK`] holds k rows, rows sorting after its k-th row can not be
+/// part of its output, nor of the global top k. Every [`TopK`] tightens the
+/// filter to its k-th row whenever it sorts before the rows the filter was
+/// last tightened to, so that scans can skip the data that can not change the
+/// result of the sort.
+#[derive(Debug)]
+pub(crate) struct TopKDynamicFilter {
+    /// The filter pushed down to the scans
+    filter: Arc<DynamicFilterPhysicalExpr>,
+    /// The sort key, in the row format, of the row the filter was last
+    /// tightened to
+    threshold: Mutex<Option<Vec<u8>>>,
+}
+
+impl TopKDynamicFilter {
+    pub(crate) fn new(filter: Arc<DynamicFilterPhysicalExpr>) -> Self {
+        Self {
+            filter,
+            threshold: Mutex::new(None),
+        }
+    }
+
+    /// The filter pushed down to the scans
+    pub(crate) fn filter(&self) -> &Arc<DynamicFilterPhysicalExpr> {
+        &self.filter
+    }
+}
+
+/// Returns a predicate that is true for the rows whose sort key, as
+/// defined by `expr`, sorts strictly before `values`
+fn sorts_before(
+    expr: &[PhysicalSortExpr],
+    values: Vec<ScalarValue>,
+) -> Arc<dyn PhysicalExpr> {
+    let and = |left, right| Arc::new(BinaryExpr::new(left, Operator::And, right)) as _;
+    let or = |left, right| Arc::new(BinaryExpr::new(left, Operator::Or, right)) as _;
+
+    // (e1 before v1) OR (e1 = v1 AND e2 before v2) OR ...
+    let mut predicate: Option<Arc<dyn PhysicalExpr>> = None;
+    let mut equal: Option<Arc<dyn PhysicalExpr>> = None;
+    for (sort_expr, value) in expr.iter().zip(values) {
+        let e = &sort_expr.expr;
+        let nulls_first = sort_expr.options.nulls_first;
+        let (before, equal_to) = if value.is_null() {
+            let before =
+                (!nulls_first).then(|| Arc::new(IsNotNullExpr::new(Arc::clone(e))) as _);
+            (before, Arc::new(IsNullExpr::new(Arc::clone(e))) as _)
+        } else {
+            let op = if sort_expr.options.descending {
+                Operator::Gt
+            } else {
+                Operator::Lt
+            };
+            let value = lit(value);
+            let mut before: Arc<dyn PhysicalExpr> =
+                Arc::new(BinaryExpr::new(Arc::clone(e), op, Arc::clone(&value)));
+            if nulls_first {
+                before = or(Arc::new(IsNullExpr::new(Arc::clone(e))), before);
+            }
+            let equal_to = Arc::new(BinaryExpr::new(Arc::clone(e), Operator::Eq, value));
+            (Some(before), equal_to as _)
+        };
+
+        if let Some(before) = before {
+            let before = match &equal {
+                Some(equal) => and(Arc::clone(equal), before),
+                None => before,
+            };
+            predicate = Some(match predicate {
+                Some(predicate) => or(predicate, before),
+                None => before,
+            });
+        }
+        equal = Some(match equal {
+            Some(equal) => and(equal, equal_to),
+            None => equal_to,
+        });
+    }
+    predicate.unwrap_or_else(|| lit(false))
+}
+
 struct TopKMetrics {
     /// metrics
     pub baseline: BaselineMetrics,
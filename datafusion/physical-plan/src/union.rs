@@ -37,16 +37,16 @@ use crate::execution_plan::{
 };
 use crate::metrics::BaselineMetrics;
 use crate::projection::{make_with_child, ProjectionExec};
-use crate::stream::ObservedStream;
+use crate::stream::{ObservedStream, RecordBatchStreamAdapter};
 
 use arrow::datatypes::{Field, Schema, SchemaRef};
-use arrow::record_batch::RecordBatch;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use datafusion_common::stats::Precision;
 use datafusion_common::{exec_err, internal_err, DataFusionError, Result};
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{calculate_union, EquivalenceProperties};
 
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use itertools::Itertools;
 use log::{debug, trace, warn};
 use tokio::macros::support::thread_rng_n;
@@ -235,7 +235,8 @@ impl ExecutionPlan for UnionExec {
         for input in self.inputs.iter() {
             // Calculate whether partition belongs to the current partition
             if partition < input.output_partitioning().partition_count() {
-                let stream = input.execute(partition, context)?;
+                let stream =
+                    with_union_schema(input.execute(partition, context)?, &self.schema());
                 debug!("Found a Union partition to execute");
                 return Ok(Box::pin(ObservedStream::new(
                     stream,
@@ -441,7 +442,10 @@ impl ExecutionPlan for InterleaveExec {
         let mut input_stream_vec = vec![];
         for input in self.inputs.iter() {
             if partition < input.output_partitioning().partition_count() {
-                input_stream_vec.push(input.execute(partition, Arc::clone(&context))?);
+                input_stream_vec.push(with_union_schema(
+                    input.execute(partition, Arc::clone(&context))?,
+                    &self.schema(),
+                ));
             } else {
                 // Do not find a partition to execute
                 break;
@@ -544,6 +548,33 @@ fn union_schema(inputs: &[Arc<dyn ExecutionPlan>]) -> SchemaRef {
     Arc::new(Schema::new_with_metadata(fields, all_metadata_merged))
 }
 
+/// Returns `stream` emitting its batches with the union `schema`
+///
+/// The fields of the union inputs may differ in nullability and metadata from
+/// the fields of the union, which must be the same for all its batches.
+fn with_union_schema(
+    stream: SendableRecordBatchStream,
+    schema: &SchemaRef,
+) -> SendableRecordBatchStream {
+    if stream.schema() == *schema {
+        return stream;
+    }
+    let schema = Arc::clone(schema);
+    Box::pin(RecordBatchStreamAdapter::new(
+        Arc::clone(&schema),
+        stream.map(move |batch| {
+            let batch = batch?;
+            let options =
+                RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+            Ok(RecordBatch::try_new_with_options(
+                Arc::clone(&schema),
+                batch.columns().to_vec(),
+                &options,
+            )?)
+        }),
+    ))
+}
+
 /// CombinedRecordBatchStream can be used to combine a Vec of SendableRecordBatchStreams into one
 struct CombinedRecordBatchStream {
     /// Schema wrapped by Arc
@@ -403,12 +403,17 @@ pub struct ListUnnest {
 /// - temp_P2(2-dimension) unnest into P2
 /// - colB(1-dimension) unnest into P3 (unnesting of P3 starts from this level)
 ///
+/// If [`UnnestOptions::with_ordinality`] is set, the ordinality of every item
+/// is computed when unnesting the last level (depth = 1), and written to
+/// *temp_ordinality_arrs*
+///
 /// The returned array will has the same size as the input batch
 /// and only contains original columns that are not being unnested.
 fn list_unnest_at_level(
     batch: &[ArrayRef],
     list_type_unnests: &[ListUnnest],
     temp_unnested_arrs: &mut HashMap<ListUnnest, ArrayRef>,
+    temp_ordinality_arrs: &mut HashMap<ListUnnest, ArrayRef>,
     level_to_unnest: usize,
     options: &UnnestOptions,
 ) -> Result<Option<Vec<ArrayRef>>> {
@@ -454,6 +459,21 @@ fn list_unnest_at_level(
     let unnested_temp_arrays =
         unnest_list_arrays(arrs_to_unnest.as_ref(), unnested_length, total_length)?;
 
+    // The ordinality is the position within the innermost lists
+    if options.with_ordinality && level_to_unnest == 1 {
+        let ordinality_arrays = list_ordinality_arrays(
+            arrs_to_unnest.as_ref(),
+            unnested_length,
+            total_length,
+        )?;
+        ordinality_arrays
+            .into_iter()
+            .zip(list_unnest_specs.iter())
+            .for_each(|(ordinality_arr, unnesting)| {
+                temp_ordinality_arrs.insert(*unnesting, ordinality_arr);
+            });
+    }
+
     // Create the take indices array for other columns
     let take_indices = create_take_indices(unnested_length, total_length);
     unnested_temp_arrays
@@ -490,6 +510,7 @@ fn list_unnest_at_level(
 }
 struct UnnestingResult {
     arr: ArrayRef,
+    ordinality: Option<ArrayRef>,
     depth: usize,
 }
 
@@ -560,6 +581,7 @@ fn build_batch(
         0 => flatten_struct_cols(batch.columns(), schema, struct_column_indices),
         _ => {
             let mut temp_unnested_result = HashMap::new();
+            let mut temp_ordinality_result = HashMap::new();
             let max_recursion = list_type_columns
                 .iter()
                 .fold(0, |highest_depth, ListUnnest { depth, .. }| {
@@ -580,6 +602,7 @@ fn build_batch(
                     input,
                     list_type_columns,
                     &mut temp_unnested_result,
+                    &mut temp_ordinality_result,
                     depth,
                     options,
                 )?
@@ -599,9 +622,14 @@ fn build_batch(
                         },
                         flattened_array,
                     )| {
+                        let ordinality = temp_ordinality_result.remove(&ListUnnest {
+                            index_in_input_schema,
+                            depth,
+                        });
                         acc.entry(index_in_input_schema).or_default().push(
                             UnnestingResult {
                                 arr: flattened_array,
+                                ordinality,
                                 depth,
                             },
                         );
@@ -646,28 +674,33 @@ fn build_batch(
                             original_index,
                             unnested_columns
                                 .into_iter()
-                                .map(|result| result.arr)
+                                .flat_map(|result| {
+                                    // The ordinality column follows its unnested column
+                                    std::iter::once(result.arr).chain(result.ordinality)
+                                })
                                 .collect::<Vec<_>>(),
                         )
                     },
                 )
                 .collect::<HashMap<_, _>>();
 
-            let ret = flatten_arrs
-                .into_iter()
-                .enumerate()
-                .flat_map(|(col_idx, arr)| {
-                    // Convert original column into its unnested version(s)
-                    // Plural because one column can be unnested with different recursion level
-                    // and into separate output columns
-                    match multi_unnested_per_original_index.remove(&col_idx) {
-                        Some(unnested_arrays) => unnested_arrays,
-                        None => vec![arr],
-                    }
-                })
-                .collect::<Vec<_>>();
-
-            flatten_struct_cols(&ret, schema, struct_column_indices)
+            let mut ret = Vec::with_capacity(flatten_arrs.len());
+            // Struct columns are shifted by the columns that are unnested before them
+            let mut struct_indices_in_ret = HashSet::new();
+            for (col_idx, arr) in flatten_arrs.into_iter().enumerate() {
+                if struct_column_indices.contains(&col_idx) {
+                    struct_indices_in_ret.insert(ret.len());
+                }
+                // Convert original column into its unnested version(s)
+                // Plural because one column can be unnested with different recursion level
+                // and into separate output columns
+                match multi_unnested_per_original_index.remove(&col_idx) {
+                    Some(unnested_arrays) => ret.extend(unnested_arrays),
+                    None => ret.push(arr),
+                }
+            }
+
+            flatten_struct_cols(&ret, schema, &struct_indices_in_ret)
         }
     }?;
     Ok(Some(transformed))
@@ -769,13 +802,9 @@ impl ListArrayType for FixedSizeListArray {
     }
 }
 
-/// Unnest multiple list arrays according to the length array.
-fn unnest_list_arrays(
-    list_arrays: &[ArrayRef],
-    length_array: &PrimitiveArray<Int64Type>,
-    capacity: usize,
-) -> Result<Vec<ArrayRef>> {
-    let typed_arrays = list_arrays
+/// Downcast list arrays to [`ListArrayType`]
+fn as_list_array_types(list_arrays: &[ArrayRef]) -> Result<Vec<&dyn ListArrayType>> {
+    list_arrays
         .iter()
         .map(|list_array| match list_array.data_type() {
             DataType::List(_) => Ok(list_array.as_list::<i32>() as &dyn ListArrayType),
@@ -787,14 +816,34 @@ fn unnest_list_arrays(
             }
             other => exec_err!("Invalid unnest datatype {other }"),
         })
-        .collect::<Result<Vec<_>>>()?;
+        .collect()
+}
 
-    typed_arrays
+/// Unnest multiple list arrays according to the length array.
+fn unnest_list_arrays(
+    list_arrays: &[ArrayRef],
+    length_array: &PrimitiveArray<Int64Type>,
+    capacity: usize,
+) -> Result<Vec<ArrayRef>> {
+    as_list_array_types(list_arrays)?
         .iter()
         .map(|list_array| unnest_list_array(*list_array, length_array, capacity))
         .collect::<Result<_>>()
 }
 
+/// Compute the ordinality of the unnested values of multiple list arrays
+/// according to the length array.
+fn list_ordinality_arrays(
+    list_arrays: &[ArrayRef],
+    length_array: &PrimitiveArray<Int64Type>,
+    capacity: usize,
+) -> Result<Vec<ArrayRef>> {
+    Ok(as_list_array_types(list_arrays)?
+        .iter()
+        .map(|list_array| list_ordinality(*list_array, length_array, capacity))
+        .collect())
+}
+
 /// Unnest a list array according the target length array.
 ///
 /// Consider a list array like this:
@@ -849,6 +898,45 @@ fn unnest_list_array(
     )?)
 }
 
+/// Compute the 1-based position of every value of a list array after it is
+/// unnested according to the target length array.
+///
+/// Consider a list array like this:
+///
+/// ```ignore
+/// [1], [2, 3, 4], null, [5], [],
+/// ```
+///
+/// and the length array is:
+///
+/// ```ignore
+/// [2, 3, 2, 1, 2]
+/// ```
+///
+/// The padded values have no position, so the ordinality array will look like this:
+///
+/// ```ignore
+/// [1, null, 1, 2, 3, null, null, 1, null, null]
+/// ```
+///
+fn list_ordinality(
+    list_array: &dyn ListArrayType,
+    length_array: &PrimitiveArray<Int64Type>,
+    capacity: usize,
+) -> ArrayRef {
+    let mut builder = PrimitiveArray::<Int64Type>::builder(capacity);
+    for row in 0..list_array.len() {
+        let mut value_length = 0;
+        if !list_array.is_null(row) {
+            let (start, end) = list_array.value_offsets(row);
+            value_length = end - start;
+            (1..=value_length).for_each(|position| builder.append_value(position));
+        }
+        builder.append_nulls((length_array.value(row) - value_length) as usize);
+    }
+    Arc::new(builder.finish())
+}
+
 /// Creates take indices that will be used to expand all columns except for the list type
 /// [`columns`](UnnestExec::list_column_indices) that is being unnested.
 /// Every column value needs to be repeated multiple times according to the length array.
@@ -950,6 +1038,7 @@ fn repeat_arrs_from_indices(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arrow::array::Int32Array;
     use arrow::array::{
         GenericListArray, NullBufferBuilder, OffsetSizeTrait, StringArray,
     };
@@ -1137,6 +1226,7 @@ mod tests {
             &HashSet::default(),
             &UnnestOptions {
                 preserve_nulls: true,
+                with_ordinality: false,
                 recursions: vec![],
             },
         )?
@@ -1217,6 +1307,97 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_list_ordinality() -> Result<()> {
+        // [A, B, C], [], NULL, [D], NULL, [NULL, F]
+        let list_array = make_generic_array::<i32>();
+        let length_array = Int64Array::from(vec![3, 2, 1, 2, 0, 3]);
+        let ordinality = list_ordinality(&list_array, &length_array, 11);
+        let expected = Int64Array::from(vec![
+            Some(1),
+            Some(2),
+            Some(3),
+            None,
+            None,
+            None,
+            Some(1),
+            None,
+            Some(1),
+            Some(2),
+            None,
+        ]);
+        assert_eq!(ordinality.as_primitive::<Int64Type>(), &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_batch_with_ordinality() -> Result<()> {
+        // col1         | col2          | col3
+        // [1, 2]       | [a]           | {x: 1}
+        // null         | [b, c]        | {x: 2}
+        // [3]          | []            | {x: 3}
+        let col1 = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+            Some(vec![Some(3)]),
+        ]);
+        let col2 = GenericListArray::<i32>::new(
+            Arc::new(Field::new_list_field(DataType::Utf8, true)),
+            OffsetBuffer::from_lengths([1, 2, 0]),
+            Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            None,
+        );
+        let x_field = Arc::new(Field::new("x", DataType::Int32, true));
+        let col3 = StructArray::from(vec![(
+            Arc::clone(&x_field),
+            Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef,
+        )]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("col1", Arc::new(col1) as ArrayRef),
+            ("col2", Arc::new(col2) as ArrayRef),
+            ("col3", Arc::new(col3) as ArrayRef),
+        ])?;
+        let out_schema = Arc::new(Schema::new(vec![
+            Field::new("col1", DataType::Int32, true),
+            Field::new("col1_ordinality", DataType::Int64, true),
+            Field::new("col2", DataType::Utf8, true),
+            Field::new("col2_ordinality", DataType::Int64, true),
+            Field::new("col3.x", DataType::Int32, true),
+        ]));
+        let list_type_columns = vec![
+            ListUnnest {
+                index_in_input_schema: 0,
+                depth: 1,
+            },
+            ListUnnest {
+                index_in_input_schema: 1,
+                depth: 1,
+            },
+        ];
+        let ret = build_batch(
+            &batch,
+            &out_schema,
+            &list_type_columns,
+            &HashSet::from([2]),
+            &UnnestOptions::new().with_ordinality(true),
+        )?
+        .unwrap();
+
+        let expected = &[
+            "+------+-----------------+------+-----------------+--------+",
+            "| col1 | col1_ordinality | col2 | col2_ordinality | col3.x |",
+            "+------+-----------------+------+-----------------+--------+",
+            "| 1    | 1               | a    | 1               | 1      |",
+            "| 2    | 2               |      |                 | 1      |",
+            "|      |                 | b    | 1               | 2      |",
+            "|      |                 | c    | 2               | 2      |",
+            "| 3    | 1               |      |                 | 3      |",
+            "+------+-----------------+------+-----------------+--------+",
+        ];
+        assert_batches_eq!(expected, &[ret]);
+        Ok(())
+    }
+
     fn verify_longest_length(
         list_arrays: &[ArrayRef],
         preserve_nulls: bool,
@@ -1224,6 +1405,7 @@ mod tests {
     ) -> Result<()> {
         let options = UnnestOptions {
             preserve_nulls,
+            with_ordinality: false,
             recursions: vec![],
         };
         let longest_length = find_longest_length(list_arrays, &options)?;
@@ -932,6 +932,31 @@ impl Stream for BoundedWindowAggStream {
     }
 }
 
+/// Returns a stream evaluating `window_expr`, whose expressions all use
+/// bounded memory, over `input` sorted on the `PARTITION BY` keys, as
+/// [`BoundedWindowAggExec`] does in [`InputOrderMode::Sorted`] mode
+pub(crate) fn sorted_window_agg_stream(
+    schema: SchemaRef,
+    window_expr: Vec<Arc<dyn WindowExpr>>,
+    input: SendableRecordBatchStream,
+    baseline_metrics: BaselineMetrics,
+    partition_by_sort_keys: LexOrdering,
+    ordered_partition_by_indices: Vec<usize>,
+) -> Result<SendableRecordBatchStream> {
+    let search_mode = Box::new(SortedSearch {
+        partition_by_sort_keys,
+        ordered_partition_by_indices,
+        input_schema: input.schema(),
+    });
+    Ok(Box::pin(BoundedWindowAggStream::new(
+        schema,
+        window_expr,
+        input,
+        baseline_metrics,
+        search_mode,
+    )?))
+}
+
 impl BoundedWindowAggStream {
     /// Create a new BoundedWindowAggStream
     fn new(
@@ -18,13 +18,21 @@
 //! Stream and channel implementations for window function expressions.
 
 use std::any::Any;
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use super::utils::create_schema;
 use crate::execution_plan::EmissionType;
-use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::metrics::{
+    BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
+use crate::spill::{
+    get_record_batch_memory_size, read_spill_stream_as_stream, SpillStreamWriter,
+};
+use crate::stream::RecordBatchStreamAdapter;
+use crate::windows::bounded_window_agg_exec::sorted_window_agg_stream;
 use crate::windows::{
     calc_requirements, get_ordered_partition_by_indices, get_partition_by_sort_exprs,
     window_equivalence_properties,
@@ -36,19 +44,33 @@ use crate::{
 };
 
 use arrow::array::ArrayRef;
-use arrow::compute::{concat, concat_batches};
+use arrow::compute::{concat, concat_batches, SortColumn};
 use arrow::datatypes::SchemaRef;
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 use datafusion_common::stats::Precision;
 use datafusion_common::utils::{evaluate_partition_ranges, transpose};
-use datafusion_common::{internal_err, Result};
+use datafusion_common::{internal_err, DataFusionError, Result, ScalarValue};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_execution::TaskContext;
+use datafusion_physical_expr::aggregate::AggregateFunctionExpr;
+use datafusion_physical_expr::window::PlainAggregateWindowExpr;
 use datafusion_physical_expr_common::sort_expr::{LexOrdering, LexRequirement};
 
-use futures::{ready, Stream, StreamExt};
+use futures::{ready, Stream, StreamExt, TryStreamExt};
 
 /// Window execution plan
+///
+/// Window partitions that do not fit in memory are spilled to disk, and read
+/// back from disk to be evaluated, if all the window expressions either
+/// aggregate their whole window partition, such as `SUM(x) OVER (PARTITION BY
+/// k)`, or use bounded memory, such as `SUM(x) OVER (ORDER BY y ROWS BETWEEN 1
+/// PRECEDING AND CURRENT ROW)`. Other window expressions, such as those with a
+/// `CURRENT ROW AND UNBOUNDED FOLLOWING` frame, need their whole window
+/// partition in memory, so their window partitions are not spilled and the
+/// execution fails if their memory can not be reserved.
 #[derive(Debug, Clone)]
 pub struct WindowAggExec {
     /// Input plan
@@ -246,12 +268,14 @@ impl ExecutionPlan for WindowAggExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
-        let input = self.input.execute(partition, context)?;
+        let input = self.input.execute(partition, Arc::clone(&context))?;
         let stream = Box::pin(WindowAggStream::new(
             Arc::clone(&self.schema),
             self.window_expr.clone(),
             input,
-            BaselineMetrics::new(&self.metrics, partition),
+            partition,
+            &self.metrics,
+            &context,
             self.partition_by_sort_keys()?,
             self.ordered_partition_by_indices.clone(),
         )?);
@@ -292,25 +316,92 @@ fn compute_window_aggregates(
         .collect()
 }
 
+/// Returns whether the window partitions of `window_expr` can be spilled, i.e.
+/// whether they can be evaluated from disk without reading them back in
+/// memory at once, see [`WindowAggStream::evaluate_spilled_partition`]
+fn can_spill(window_expr: &[Arc<dyn WindowExpr>]) -> bool {
+    whole_partition_aggregates(window_expr).is_some()
+        || window_expr.iter().all(|expr| expr.uses_bounded_memory())
+}
+
+/// Returns the aggregates of `window_expr` if all of them aggregate their
+/// whole window partition, such as `SUM(x) OVER (PARTITION BY k)`, in which
+/// case their value is the same for all the rows of a window partition
+fn whole_partition_aggregates(
+    window_expr: &[Arc<dyn WindowExpr>],
+) -> Option<Vec<&AggregateFunctionExpr>> {
+    window_expr
+        .iter()
+        .map(|expr| {
+            let frame = expr.get_window_frame();
+            if !frame.start_bound.is_unbounded() || !frame.end_bound.is_unbounded() {
+                return None;
+            }
+            expr.as_any()
+                .downcast_ref::<PlainAggregateWindowExpr>()
+                .map(|expr| expr.get_aggregate_expr())
+        })
+        .collect()
+}
+
 /// stream for window aggregation plan
+///
+/// The input is sorted on the `PARTITION BY` keys, so the rows of each window
+/// partition are contiguous, and a window partition is evaluated as soon as
+/// the first row of the next one is read. The rows of the window partition
+/// being read are buffered, using memory tracked by a [`MemoryReservation`],
+/// and spilled to disk when the reservation can not grow and the window
+/// expressions can be evaluated from disk. Spilled window partitions are read
+/// back from disk to be evaluated, see [`Self::evaluate_spilled_partition`].
 pub struct WindowAggStream {
     schema: SchemaRef,
     input: SendableRecordBatchStream,
-    batches: Vec<RecordBatch>,
+    /// The window partition being read
+    partition: PartitionBuffer,
+    /// The evaluated window partitions, not yet emitted
+    output: VecDeque<SendableRecordBatchStream>,
     finished: bool,
     window_expr: Vec<Arc<dyn WindowExpr>>,
     partition_by_sort_keys: LexOrdering,
     baseline_metrics: BaselineMetrics,
     ordered_partition_by_indices: Vec<usize>,
+    /// Memory used by the batches buffered in `partition`
+    reservation: MemoryReservation,
+    runtime: Arc<RuntimeEnv>,
+    /// Whether the window partitions can be spilled, see [`can_spill`]
+    can_spill: bool,
+    spill_count: Count,
+    spilled_bytes: Count,
+    spilled_rows: Count,
+}
+
+/// The buffered rows of a window partition
+#[derive(Default)]
+struct PartitionBuffer {
+    /// The values of the `PARTITION BY` keys of the window partition
+    key: Vec<ScalarValue>,
+    /// The batches in memory, following the spilled batches if any
+    batches: Vec<RecordBatch>,
+    /// The batches spilled to disk so far
+    spill: Option<SpillStreamWriter>,
+}
+
+impl PartitionBuffer {
+    fn is_empty(&self) -> bool {
+        self.batches.is_empty() && self.spill.is_none()
+    }
 }
 
 impl WindowAggStream {
     /// Create a new WindowAggStream
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         schema: SchemaRef,
         window_expr: Vec<Arc<dyn WindowExpr>>,
         input: SendableRecordBatchStream,
-        baseline_metrics: BaselineMetrics,
+        partition: usize,
+        metrics: &ExecutionPlanMetricsSet,
+        context: &TaskContext,
         partition_by_sort_keys: LexOrdering,
         ordered_partition_by_indices: Vec<usize>,
     ) -> Result<Self> {
@@ -318,32 +409,41 @@ impl WindowAggStream {
         if window_expr[0].partition_by().len() != ordered_partition_by_indices.len() {
             return internal_err!("All partition by columns should have an ordering");
         }
+        let runtime = context.runtime_env();
+        let can_spill = can_spill(&window_expr);
+        let reservation = MemoryConsumer::new(format!("WindowAggStream[{partition}]"))
+            .with_can_spill(can_spill)
+            .register(&runtime.memory_pool);
         Ok(Self {
             schema,
             input,
-            batches: vec![],
+            partition: PartitionBuffer::default(),
+            output: VecDeque::new(),
             finished: false,
             window_expr,
-            baseline_metrics,
+            baseline_metrics: BaselineMetrics::new(metrics, partition),
             partition_by_sort_keys,
             ordered_partition_by_indices,
+            reservation,
+            runtime,
+            can_spill,
+            spill_count: MetricBuilder::new(metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
+            spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
         })
     }
 
-    fn compute_aggregates(&self) -> Result<Option<RecordBatch>> {
+    /// Evaluates the window expressions over `batch`, whose rows may belong
+    /// to several whole window partitions
+    fn compute_aggregates(&self, batch: &RecordBatch) -> Result<Option<RecordBatch>> {
         // record compute time on drop
         let _timer = self.baseline_metrics.elapsed_compute().timer();
 
-        let batch = concat_batches(&self.input.schema(), &self.batches)?;
         if batch.num_rows() == 0 {
             return Ok(None);
         }
 
-        let partition_by_sort_keys = self
-            .ordered_partition_by_indices
-            .iter()
-            .map(|idx| self.partition_by_sort_keys[*idx].evaluate_to_sort_column(&batch))
-            .collect::<Result<Vec<_>>>()?;
+        let partition_by_sort_keys = self.partition_by_sort_columns(batch)?;
         let partition_points =
             evaluate_partition_ranges(batch.num_rows(), &partition_by_sort_keys)?;
 
@@ -374,6 +474,216 @@ impl WindowAggStream {
             batch_columns,
         )?))
     }
+
+    fn partition_by_sort_columns(&self, batch: &RecordBatch) -> Result<Vec<SortColumn>> {
+        self.ordered_partition_by_indices
+            .iter()
+            .map(|idx| self.partition_by_sort_keys[*idx].evaluate_to_sort_column(batch))
+            .collect()
+    }
+
+    /// Buffers the rows of `batch` that belong to the window partition being
+    /// read, and evaluates the window partitions completed by `batch`
+    fn process_batch(&mut self, batch: RecordBatch) -> Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        let partition_by_sort_keys = self.partition_by_sort_columns(&batch)?;
+        let partition_points =
+            evaluate_partition_ranges(batch.num_rows(), &partition_by_sort_keys)?;
+        let key = |row: usize| {
+            partition_by_sort_keys
+                .iter()
+                .map(|column| ScalarValue::try_from_array(&column.values, row))
+                .collect::<Result<Vec<_>>>()
+        };
+
+        let mut complete_start = 0;
+        if !self.partition.is_empty() {
+            let first = &partition_points[0];
+            if key(first.start)? == self.partition.key {
+                self.buffer(batch.slice(first.start, first.len()))?;
+                if partition_points.len() == 1 {
+                    return Ok(());
+                }
+                complete_start = first.end;
+            }
+            self.finish_partition()?;
+        }
+
+        // The last window partition of the batch may continue in the next batch
+        let last = &partition_points[partition_points.len() - 1];
+        if complete_start < last.start {
+            let complete = batch.slice(complete_start, last.start - complete_start);
+            if let Some(result) = self.compute_aggregates(&complete)? {
+                self.emit(result);
+            }
+        }
+        self.partition.key = key(last.start)?;
+        self.buffer(batch.slice(last.start, last.len()))
+    }
+
+    /// Buffers `batch` in the window partition being read, spilling the
+    /// buffered batches to disk if their memory can not be reserved
+    fn buffer(&mut self, batch: RecordBatch) -> Result<()> {
+        let size = get_record_batch_memory_size(&batch);
+        self.partition.batches.push(batch);
+        match self.reservation.try_grow(size) {
+            Ok(_) => Ok(()),
+            Err(_) if self.can_spill && self.runtime.disk_manager.tmp_files_enabled() => {
+                self.spill()
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Spills the buffered batches of the window partition being read
+    fn spill(&mut self) -> Result<()> {
+        let spill = match &mut self.partition.spill {
+            Some(spill) => spill,
+            None => {
+                let file = self
+                    .runtime
+                    .disk_manager
+                    .create_tmp_file("WindowAggExec spill")?;
+                self.partition
+                    .spill
+                    .insert(SpillStreamWriter::try_new(file, &self.input.schema())?)
+            }
+        };
+        let (rows, bytes) = (spill.num_rows(), spill.num_bytes());
+        for batch in self.partition.batches.drain(..) {
+            spill.write(&batch)?;
+        }
+        self.spill_count.add(1);
+        self.spilled_rows.add(spill.num_rows() - rows);
+        self.spilled_bytes.add(spill.num_bytes() - bytes);
+        self.reservation.free();
+        Ok(())
+    }
+
+    /// Evaluates the window partition being read, which is complete
+    fn finish_partition(&mut self) -> Result<()> {
+        let PartitionBuffer { batches, spill, .. } = std::mem::take(&mut self.partition);
+        match spill {
+            None => {
+                let batch = concat_batches(&self.input.schema(), &batches)?;
+                drop(batches);
+                self.reservation.free();
+                if let Some(result) = self.compute_aggregates(&batch)? {
+                    self.emit(result);
+                }
+            }
+            Some(spill) => {
+                let reservation = self.reservation.take();
+                let output = self.evaluate_spilled_partition(
+                    spill.finish()?,
+                    batches,
+                    reservation,
+                )?;
+                self.output.push_back(output);
+            }
+        }
+        Ok(())
+    }
+
+    fn emit(&mut self, batch: RecordBatch) {
+        // Empty record batches should not be emitted.
+        // They need to be treated as  [`Option<RecordBatch>`]es and handled separately
+        debug_assert!(batch.num_rows() > 0);
+        self.output
+            .push_back(Box::pin(RecordBatchStreamAdapter::new(
+                Arc::clone(&self.schema),
+                futures::stream::iter([Ok(batch)]),
+            )));
+    }
+
+    /// Evaluates a window partition whose first rows were spilled to `file`,
+    /// followed by `batches` in memory, using `reservation`
+    ///
+    /// If all the window expressions aggregate their whole window partition,
+    /// the window partition is read twice: first to compute the aggregates,
+    /// then to emit its rows along with them, so that it is never in memory
+    /// at once. If all the window expressions use bounded memory, such as
+    /// `SUM(x) OVER (ORDER BY y ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)`,
+    /// the window partition is evaluated batch by batch as
+    /// [`BoundedWindowAggExec`] does. Window partitions of other window
+    /// expressions are never spilled, see [`can_spill`].
+    ///
+    /// [`BoundedWindowAggExec`]: crate::windows::BoundedWindowAggExec
+    fn evaluate_spilled_partition(
+        &self,
+        file: RefCountedTempFile,
+        batches: Vec<RecordBatch>,
+        reservation: MemoryReservation,
+    ) -> Result<SendableRecordBatchStream> {
+        let schema = Arc::clone(&self.schema);
+        let input_schema = self.input.schema();
+        let window_expr = self.window_expr.clone();
+        let elapsed_compute = self.baseline_metrics.elapsed_compute().clone();
+        let file = Arc::new(file);
+        let read = move || {
+            let spilled = read_spill_stream_as_stream(
+                Arc::clone(&file),
+                Arc::clone(&input_schema),
+                2,
+            )?;
+            let batches = futures::stream::iter(batches.clone().into_iter().map(Ok));
+            Ok::<_, DataFusionError>(spilled.chain(batches))
+        };
+
+        let Some(aggregates) = whole_partition_aggregates(&self.window_expr) else {
+            let input = read()?.map(move |batch| {
+                // The in-memory batches are released along with the input
+                let _reservation = &reservation;
+                batch
+            });
+            return sorted_window_agg_stream(
+                Arc::clone(&self.schema),
+                self.window_expr.clone(),
+                Box::pin(RecordBatchStreamAdapter::new(self.input.schema(), input)),
+                self.baseline_metrics.intermediate(),
+                self.partition_by_sort_keys.clone(),
+                self.ordered_partition_by_indices.clone(),
+            );
+        };
+
+        let mut accumulators = aggregates
+            .iter()
+            .map(|aggregate| aggregate.create_accumulator())
+            .collect::<Result<Vec<_>>>()?;
+        let output = futures::stream::once(async move {
+            let mut input = read()?;
+            while let Some(batch) = input.next().await {
+                let batch = batch?;
+                let _timer = elapsed_compute.timer();
+                for (expr, accumulator) in window_expr.iter().zip(&mut accumulators) {
+                    accumulator.update_batch(&expr.evaluate_args(&batch)?)?;
+                }
+            }
+            let values = accumulators
+                .iter_mut()
+                .map(|accumulator| accumulator.evaluate())
+                .collect::<Result<Vec<_>>>()?;
+
+            let output = read()?.map(move |batch| {
+                // The in-memory batches are released once they are emitted
+                let _reservation = &reservation;
+                let batch = batch?;
+                let mut columns = batch.columns().to_vec();
+                for value in &values {
+                    columns.push(value.to_array_of_size(batch.num_rows())?);
+                }
+                Ok(RecordBatch::try_new(Arc::clone(&schema), columns)?)
+            });
+            Ok::<_, DataFusionError>(output)
+        })
+        .try_flatten();
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            output,
+        )))
+    }
 }
 
 impl Stream for WindowAggStream {
@@ -394,28 +704,35 @@ impl WindowAggStream {
         &mut self,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<RecordBatch>>> {
-        if self.finished {
-            return Poll::Ready(None);
-        }
-
         loop {
-            return Poll::Ready(Some(match ready!(self.input.poll_next_unpin(cx)) {
-                Some(Ok(batch)) => {
-                    self.batches.push(batch);
-                    continue;
+            if let Some(output) = self.output.front_mut() {
+                match ready!(output.poll_next_unpin(cx)) {
+                    Some(result) => return Poll::Ready(Some(result)),
+                    None => {
+                        self.output.pop_front();
+                        continue;
+                    }
                 }
+            }
+            if self.finished {
+                return Poll::Ready(None);
+            }
+
+            let result = match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => self.process_batch(batch),
                 Some(Err(e)) => Err(e),
                 None => {
-                    let Some(result) = self.compute_aggregates()? else {
-                        return Poll::Ready(None);
-                    };
                     self.finished = true;
-                    // Empty record batches should not be emitted.
-                    // They need to be treated as  [`Option<RecordBatch>`]es and handled separately
-                    debug_assert!(result.num_rows() > 0);
-                    Ok(result)
+                    if self.partition.is_empty() {
+                        Ok(())
+                    } else {
+                        self.finish_partition()
+                    }
                 }
-            }));
+            };
+            if let Err(e) = result {
+                return Poll::Ready(Some(Err(e)));
+            }
         }
     }
 }
@@ -426,3 +743,191 @@ impl RecordBatchStream for WindowAggStream {
         Arc::clone(&self.schema)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect;
+    use crate::expressions::col;
+    use crate::memory::MemorySourceConfig;
+    use crate::source::DataSourceExec;
+    use crate::windows::create_window_expr;
+
+    use arrow::array::{AsArray, Int64Array};
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Int64Type, Schema};
+    use datafusion_execution::disk_manager::DiskManagerConfig;
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_expr::{
+        WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunctionDefinition,
+    };
+    use datafusion_functions_aggregate::sum::sum_udaf;
+    use datafusion_physical_expr::expressions::PhysicalSortExpr;
+
+    /// 3 window partitions of 1000 rows, with keys 0, 1 and 2 and values
+    /// 0..1000, in batches of 100 rows
+    fn sorted_input() -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Int64, false),
+            Field::new("v", DataType::Int64, false),
+        ]));
+        let batches = (0..30)
+            .map(|i| {
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![
+                        Arc::new(Int64Array::from(vec![i / 10; 100])),
+                        Arc::new(Int64Array::from_iter_values(
+                            (i % 10 * 100)..(i % 10 * 100 + 100),
+                        )),
+                    ],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let ordering = LexOrdering::new(vec![PhysicalSortExpr {
+            expr: col("k", &schema)?,
+            options: SortOptions::default(),
+        }]);
+        let source = MemorySourceConfig::try_new(&[batches], schema, None)?
+            .try_with_sort_information(vec![ordering])?;
+        Ok(Arc::new(DataSourceExec::new(Arc::new(source))))
+    }
+
+    fn window_agg_exec(
+        input: Arc<dyn ExecutionPlan>,
+        window_frame: WindowFrame,
+    ) -> Result<Arc<WindowAggExec>> {
+        let schema = input.schema();
+        let order_by = if window_frame.start_bound.is_unbounded() {
+            LexOrdering::default()
+        } else {
+            LexOrdering::new(vec![PhysicalSortExpr {
+                expr: col("v", &schema)?,
+                options: SortOptions::default(),
+            }])
+        };
+        let window_expr = create_window_expr(
+            &WindowFunctionDefinition::AggregateUDF(sum_udaf()),
+            "sum".to_string(),
+            &[col("v", &schema)?],
+            &[col("k", &schema)?],
+            &order_by,
+            Arc::new(window_frame),
+            &schema,
+            false,
+        )?;
+        Ok(Arc::new(WindowAggExec::try_new(
+            vec![window_expr],
+            input,
+            false,
+        )?))
+    }
+
+    fn spilling_task_ctx() -> Result<Arc<TaskContext>> {
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(8 * 1024, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+        Ok(Arc::new(TaskContext::default().with_runtime(runtime)))
+    }
+
+    /// Returns the (k, v, sum) rows of `batches`
+    fn rows(batches: &[RecordBatch]) -> Vec<(i64, i64, i64)> {
+        batches
+            .iter()
+            .flat_map(|batch| {
+                let column = |i: usize| batch.column(i).as_primitive::<Int64Type>();
+                (0..batch.num_rows())
+                    .map(|row| {
+                        (
+                            column(0).value(row),
+                            column(1).value(row),
+                            column(2).value(row),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_window_agg_spill_whole_partition() -> Result<()> {
+        let window_agg_exec = window_agg_exec(sorted_input()?, WindowFrame::new(None))?;
+        let result =
+            collect(Arc::clone(&window_agg_exec) as _, spilling_task_ctx()?).await?;
+
+        let expected = (0..3)
+            .flat_map(|k| (0..1000).map(move |v| (k, v, 999 * 1000 / 2)))
+            .collect::<Vec<_>>();
+        assert_eq!(rows(&result), expected);
+
+        let metrics = window_agg_exec.metrics().unwrap();
+        assert_eq!(metrics.output_rows().unwrap(), 3000);
+        assert!(metrics.spill_count().unwrap() > 0);
+        assert!(metrics.spilled_rows().unwrap() > 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_window_agg_spill_sliding_frame() -> Result<()> {
+        // SUM(v) OVER (PARTITION BY k ORDER BY v ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)
+        let window_frame = WindowFrame::new_bounds(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::Preceding(ScalarValue::UInt64(Some(1))),
+            WindowFrameBound::CurrentRow,
+        );
+        let window_agg_exec = window_agg_exec(sorted_input()?, window_frame)?;
+        let result =
+            collect(Arc::clone(&window_agg_exec) as _, spilling_task_ctx()?).await?;
+
+        let expected = (0..3)
+            .flat_map(|k| (0..1000).map(move |v| (k, v, v + (v - 1).max(0))))
+            .collect::<Vec<_>>();
+        assert_eq!(rows(&result), expected);
+
+        let metrics = window_agg_exec.metrics().unwrap();
+        assert_eq!(metrics.output_rows().unwrap(), 3000);
+        assert!(metrics.spill_count().unwrap() > 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_window_agg_spill_unbounded_following() -> Result<()> {
+        // SUM(v) OVER (PARTITION BY k ORDER BY v ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING)
+        // is evaluated with the whole window partition in memory, so the
+        // window partitions are not spilled
+        let window_frame = WindowFrame::new_bounds(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::CurrentRow,
+            WindowFrameBound::Following(ScalarValue::UInt64(None)),
+        );
+        let window_agg_exec = window_agg_exec(sorted_input()?, window_frame)?;
+        let err = collect(Arc::clone(&window_agg_exec) as _, spilling_task_ctx()?)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err.find_root(), DataFusionError::ResourcesExhausted(_)),
+            "{err}"
+        );
+
+        let metrics = window_agg_exec.metrics().unwrap();
+        assert_eq!(metrics.spill_count().unwrap(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_window_agg_spill_disabled() -> Result<()> {
+        let window_agg_exec = window_agg_exec(sorted_input()?, WindowFrame::new(None))?;
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(8 * 1024, 1.0)
+            .with_disk_manager(DiskManagerConfig::Disabled)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        let err = collect(window_agg_exec, task_ctx).await.unwrap_err();
+        assert!(
+            matches!(err.find_root(), DataFusionError::ResourcesExhausted(_)),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -979,6 +979,7 @@ impl TryFrom<&protobuf::ParquetOptions> for ParquetOptions {
             schema_force_view_types: value.schema_force_view_types,
             binary_as_string: value.binary_as_string,
             skip_arrow_metadata: value.skip_arrow_metadata,
+            dictionary_pruning: value.dictionary_pruning,
         })
     }
 }
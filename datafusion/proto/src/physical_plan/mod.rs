@@ -1264,6 +1264,11 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
         }
 
         if let Some(limit) = plan.downcast_ref::<GlobalLimitExec>() {
+            if limit.has_limit_exprs() {
+                return not_impl_err!(
+                    "Serializing GlobalLimitExec with non-literal LIMIT or OFFSET is not supported"
+                );
+            }
             let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
                 limit.input().to_owned(),
                 extension_codec,
@@ -46,8 +46,8 @@ use datafusion::execution::FunctionRegistry;
 use datafusion::functions_aggregate::count::count_udaf;
 use datafusion::functions_aggregate::expr_fn::{
     approx_median, approx_percentile_cont, approx_percentile_cont_with_weight, count,
-    count_distinct, covar_pop, covar_samp, first_value, grouping, max, median, min,
-    stddev, stddev_pop, sum, var_pop, var_sample,
+    count_distinct, covar_pop, covar_samp, first_value, grouping, max, median, min, mode,
+    percentile_cont, percentile_disc, stddev, stddev_pop, sum, var_pop, var_sample,
 };
 use datafusion::functions_aggregate::min_max::max_udaf;
 use datafusion::functions_nested::map::map;
@@ -413,6 +413,42 @@ async fn roundtrip_logical_plan_dml() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn roundtrip_logical_plan_merge() -> Result<()> {
+    let ctx = SessionContext::new();
+    let schema = Schema::new(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Decimal128(15, 2), true),
+    ]);
+
+    ctx.register_csv(
+        "t1",
+        "tests/testdata/test.csv",
+        CsvReadOptions::default().schema(&schema),
+    )
+    .await?;
+    let query =
+        "MERGE INTO T1 USING (SELECT 1 AS a) s ON T1.a = s.a WHEN MATCHED THEN DELETE";
+    let plan = ctx.sql(query).await?.into_optimized_plan()?;
+
+    // We need more stack space so this doesn't overflow in dev builds
+    let logical_round_trip = std::thread::Builder::new()
+        .stack_size(10_000_000)
+        .spawn({
+            let plan = plan.clone();
+            move || {
+                let bytes = logical_plan_to_bytes(&plan)?;
+                logical_plan_from_bytes(&bytes, &ctx)
+            }
+        })
+        .expect("spawning thread")
+        .join()
+        .expect("joining thread")?;
+    assert_eq!(format!("{plan}"), format!("{logical_round_trip}"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn roundtrip_logical_plan_copy_to_sql_options() -> Result<()> {
     let ctx = SessionContext::new();
@@ -964,6 +1000,9 @@ async fn roundtrip_expr_api() -> Result<()> {
         max(lit(1)),
         median(lit(2)),
         min(lit(2)),
+        mode(lit(2)),
+        percentile_cont(lit(2), lit(0.5)),
+        percentile_disc(lit(2), lit(0.5)),
         var_sample(lit(2.2)),
         var_pop(lit(2.2)),
         stddev(lit(2.2)),
@@ -92,6 +92,8 @@ struct FunctionArgs {
     null_treatment: Option<NullTreatment>,
     /// DISTINCT
     distinct: bool,
+    /// WITHIN GROUP clause, if any
+    within_group: Vec<OrderByExpr>,
 }
 
 impl FunctionArgs {
@@ -116,6 +118,7 @@ impl FunctionArgs {
                 filter,
                 null_treatment,
                 distinct: false,
+                within_group,
             });
         };
 
@@ -177,8 +180,10 @@ impl FunctionArgs {
             }
         }
 
-        if !within_group.is_empty() {
-            return not_impl_err!("WITHIN GROUP is not supported yet: {within_group:?}");
+        if order_by.is_some() && !within_group.is_empty() {
+            return plan_err!(
+                "Calling {name}: ORDER BY in function arguments cannot be combined with WITHIN GROUP"
+            );
         }
 
         let order_by = order_by.unwrap_or_default();
@@ -191,6 +196,7 @@ impl FunctionArgs {
             filter,
             null_treatment,
             distinct,
+            within_group,
         })
     }
 }
@@ -211,6 +217,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             filter,
             null_treatment,
             distinct,
+            within_group,
         } = function_args;
 
         // If function is a window function (it has an OVER clause),
@@ -260,6 +267,10 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             );
         }
 
+        if !within_group.is_empty() && is_function_window {
+            return not_impl_err!("WITHIN GROUP is not supported for window functions");
+        }
+
         // Then, window function
         if let Some(WindowType::WindowSpec(window)) = over {
             let partition_by = window
@@ -328,15 +339,38 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         } else {
             // User defined aggregate functions (UDAF) have precedence in case it has the same name as a scalar built-in function
             if let Some(fm) = self.context_provider.get_aggregate_meta(&name) {
+                let is_within_group = !within_group.is_empty();
+                if is_within_group && !fm.is_ordered_set_aggregate() {
+                    return plan_err!(
+                        "WITHIN GROUP is only supported for ordered-set aggregate functions, {} is not one",
+                        fm.name()
+                    );
+                }
                 let order_by = self.order_by_to_sort_expr(
-                    order_by,
+                    if is_within_group {
+                        within_group
+                    } else {
+                        order_by
+                    },
                     schema,
                     planner_context,
                     true,
                     None,
                 )?;
+                let mut args =
+                    self.function_args_to_expr(args, schema, planner_context)?;
+                // The WITHIN GROUP expression is the aggregated argument of an
+                // ordered-set aggregate, followed by its direct arguments
+                if is_within_group {
+                    let [sort] = order_by.as_slice() else {
+                        return plan_err!(
+                            "WITHIN GROUP for {} requires exactly one ORDER BY expression",
+                            fm.name()
+                        );
+                    };
+                    args.insert(0, sort.expr.clone());
+                }
                 let order_by = (!order_by.is_empty()).then_some(order_by);
-                let args = self.function_args_to_expr(args, schema, planner_context)?;
                 let filter: Option<Box<Expr>> = filter
                     .map(|e| self.sql_expr_to_logical_expr(*e, schema, planner_context))
                     .transpose()?
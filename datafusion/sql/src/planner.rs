@@ -305,6 +305,25 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(column_defaults)
     }
 
+    /// Returns the comments declared on the given columns
+    pub(super) fn build_column_comments(
+        &self,
+        columns: &[SQLColumnDef],
+    ) -> Vec<(String, String)> {
+        columns
+            .iter()
+            .filter_map(|column| {
+                column.options.iter().find_map(|o| match &o.option {
+                    ColumnOption::Comment(comment) => Some((
+                        self.ident_normalizer.normalize(column.name.clone()),
+                        comment.clone(),
+                    )),
+                    _ => None,
+                })
+            })
+            .collect()
+    }
+
     /// Apply the given TableAlias to the input plan
     pub(crate) fn apply_table_alias(
         &self,
@@ -144,6 +144,10 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     or_replace: false,
                     temporary: false,
                     column_defaults: vec![],
+                    partition_by: vec![],
+                    sort_order: vec![],
+                    comment: None,
+                    column_comments: vec![],
                 },
             ))),
             _ => Ok(plan),
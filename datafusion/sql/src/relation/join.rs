@@ -16,10 +16,13 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{not_impl_err, Column, Result};
-use datafusion_expr::{JoinType, LogicalPlan, LogicalPlanBuilder};
+use datafusion_common::{internal_err, not_impl_err, plan_err, Column, Result};
+use datafusion_expr::expr::BinaryExpr;
+use datafusion_expr::utils::split_conjunction;
+use datafusion_expr::{Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Operator};
 use sqlparser::ast::{
-    Join, JoinConstraint, JoinOperator, ObjectName, TableFactor, TableWithJoins,
+    Expr as SQLExpr, Join, JoinConstraint, JoinOperator, ObjectName, TableFactor,
+    TableWithJoins,
 };
 use std::collections::HashSet;
 
@@ -96,6 +99,16 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 self.parse_join(left, right, constraint, JoinType::Full, planner_context)
             }
             JoinOperator::CrossJoin => self.parse_cross_join(left, right),
+            JoinOperator::AsOf {
+                match_condition,
+                constraint,
+            } => self.parse_asof_join(
+                left,
+                right,
+                match_condition,
+                constraint,
+                planner_context,
+            ),
             other => not_impl_err!("Unsupported JOIN operator {other:?}"),
         }
     }
@@ -108,6 +121,74 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         LogicalPlanBuilder::from(left).cross_join(right)?.build()
     }
 
+    /// Plans an `ASOF JOIN` as a [`JoinType::LeftAsof`] join, whose equijoin keys
+    /// come from the `ON` or `USING` constraint. The match condition is always
+    /// the last conjunct of the join filter.
+    fn parse_asof_join(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        match_condition: SQLExpr,
+        constraint: JoinConstraint,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let join_schema = left.schema().join(right.schema())?;
+        let match_condition =
+            self.sql_to_expr(match_condition, &join_schema, planner_context)?;
+        if !matches!(
+            match_condition,
+            Expr::BinaryExpr(BinaryExpr {
+                op: Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq,
+                ..
+            })
+        ) {
+            return plan_err!(
+                "ASOF JOIN match condition must be a comparison using <, <=, > or >=, got {match_condition}"
+            );
+        }
+
+        let plan = match constraint {
+            JoinConstraint::On(sql_expr) => {
+                let expr = self.sql_to_expr(sql_expr, &join_schema, planner_context)?;
+                if let Some(predicate) = split_conjunction(&expr).into_iter().find(|e| {
+                    !matches!(
+                        e,
+                        Expr::BinaryExpr(BinaryExpr {
+                            op: Operator::Eq,
+                            ..
+                        })
+                    )
+                }) {
+                    return plan_err!(
+                        "ASOF JOIN ON condition must only contain equality predicates, got {predicate}"
+                    );
+                }
+                LogicalPlanBuilder::from(left)
+                    .join_on(right, JoinType::LeftAsof, Some(expr))?
+                    .build()?
+            }
+            JoinConstraint::Natural => {
+                return not_impl_err!("NATURAL is not supported for ASOF JOIN")
+            }
+            constraint => self.parse_join(
+                left,
+                right,
+                constraint,
+                JoinType::LeftAsof,
+                planner_context,
+            )?,
+        };
+
+        let LogicalPlan::Join(mut join) = plan else {
+            return internal_err!("Expected ASOF JOIN to be planned as a join");
+        };
+        join.filter = Some(match join.filter {
+            Some(filter) => filter.and(match_condition),
+            None => match_condition,
+        });
+        Ok(LogicalPlan::Join(join))
+    }
+
     fn parse_join(
         &self,
         left: LogicalPlan,
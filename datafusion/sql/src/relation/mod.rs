@@ -19,12 +19,16 @@ use std::sync::Arc;
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
+use arrow::datatypes::DataType;
 use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{
-    not_impl_err, plan_err, DFSchema, Diagnostic, Result, Span, TableReference,
+    internal_err, not_impl_err, plan_err, Column, DFSchema, Diagnostic, Result, Span,
+    TableReference, UnnestOptions,
+};
+use datafusion_expr::builder::{get_ordinality_column, subquery_alias};
+use datafusion_expr::{
+    expr::Unnest, when, Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder,
 };
-use datafusion_expr::builder::subquery_alias;
-use datafusion_expr::{expr::Unnest, Expr, LogicalPlan, LogicalPlanBuilder};
 use datafusion_expr::{Subquery, SubqueryAlias};
 use sqlparser::ast::{FunctionArg, FunctionArgExpr, Spanned, TableFactor};
 
@@ -32,7 +36,7 @@ mod join;
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
     /// Create a `LogicalPlan` that scans the named relation
-    fn create_relation(
+    pub(crate) fn create_relation(
         &self,
         relation: TableFactor,
         planner_context: &mut PlannerContext,
@@ -121,10 +125,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 with_offset_alias: None,
                 with_ordinality,
             } => {
-                if with_ordinality {
-                    return not_impl_err!("UNNEST with ordinality is not supported yet");
-                }
-
                 // Unnest table factor has empty input
                 let schema = DFSchema::empty();
                 let input = LogicalPlanBuilder::empty(true).build()?;
@@ -145,7 +145,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 if unnest_exprs.is_empty() {
                     return plan_err!("UNNEST must have at least one argument");
                 }
-                let logical_plan = self.try_process_unnest(input, unnest_exprs)?;
+                let logical_plan = if with_ordinality {
+                    self.plan_unnest_with_ordinality(input, unnest_exprs)?
+                } else {
+                    self.try_process_unnest(input, unnest_exprs)?
+                };
                 (logical_plan, alias)
             }
             TableFactor::UNNEST { .. } => {
@@ -169,6 +173,70 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    /// Plan an `UNNEST(...) WITH ORDINALITY` table factor, whose unnested
+    /// columns are followed by a single `ordinality` column
+    fn plan_unnest_with_ordinality(
+        &self,
+        input: LogicalPlan,
+        unnest_exprs: Vec<Expr>,
+    ) -> Result<LogicalPlan> {
+        let mut inner_projection_exprs = vec![];
+        let mut unnest_columns = vec![];
+        for unnest_expr in unnest_exprs {
+            let name = unnest_expr.schema_name().to_string();
+            let Expr::Unnest(Unnest { expr }) = unnest_expr else {
+                return internal_err!("Expected an unnest expression, got {unnest_expr}");
+            };
+            match expr.get_type(input.schema())? {
+                DataType::List(_)
+                | DataType::LargeList(_)
+                | DataType::FixedSizeList(_, _) => {}
+                other => {
+                    return not_impl_err!(
+                        "UNNEST WITH ORDINALITY is only supported for arrays, got {other}"
+                    )
+                }
+            }
+            inner_projection_exprs.push(expr.alias(&name));
+            unnest_columns.push(Column::from_name(name));
+        }
+
+        // Every row has the same position in all the unnested arrays that are
+        // not padded with nulls, so take the first one that is not null
+        let mut ordinalities = unnest_columns
+            .iter()
+            .map(|column| Expr::Column(get_ordinality_column(&column.name).0));
+        let first = ordinalities.next().unwrap();
+        let ordinality = match ordinalities.next() {
+            None => first,
+            Some(second) => ordinalities
+                .fold(
+                    when(first.clone().is_not_null(), first)
+                        .when(second.clone().is_not_null(), second),
+                    |mut case, ordinality| {
+                        case.when(ordinality.clone().is_not_null(), ordinality)
+                    },
+                )
+                .end()?,
+        };
+        let outer_projection_exprs = unnest_columns
+            .iter()
+            .cloned()
+            .map(Expr::Column)
+            .chain(std::iter::once(ordinality.alias("ordinality")))
+            .collect::<Vec<_>>();
+
+        // Set preserve_nulls to false to ensure compatibility with DuckDB and PostgreSQL
+        let options = UnnestOptions::new()
+            .with_preserve_nulls(false)
+            .with_ordinality(true);
+        LogicalPlanBuilder::from(input)
+            .project(inner_projection_exprs)?
+            .unnest_columns_with_options(unnest_columns, options)?
+            .project(outer_projection_exprs)?
+            .build()
+    }
+
     pub(crate) fn create_relation_subquery(
         &self,
         subquery: TableFactor,
@@ -18,6 +18,8 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use arrow::datatypes::DataType;
+
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 use crate::utils::{
     check_columns_satisfy_exprs, extract_aliases, rebase_expr, resolve_aliases_to_exprs,
@@ -27,9 +29,13 @@ use crate::utils::{
 
 use datafusion_common::error::DataFusionErrorBuilder;
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
-use datafusion_common::{not_impl_err, plan_err, Result};
+use datafusion_common::{
+    internal_datafusion_err, not_impl_err, plan_err, Column, Result, ScalarValue,
+};
 use datafusion_common::{RecursionUnnestOption, UnnestOptions};
-use datafusion_expr::expr::{Alias, PlannedReplaceSelectItem, WildcardOptions};
+use datafusion_expr::expr::{
+    Alias, PlannedReplaceSelectItem, ScalarFunction, WildcardOptions,
+};
 use datafusion_expr::expr_rewriter::{
     normalize_col, normalize_col_with_schemas_and_ambiguity_check, normalize_sorts,
 };
@@ -37,13 +43,14 @@ use datafusion_expr::utils::{
     expr_as_column_expr, expr_to_columns, find_aggregate_exprs, find_window_exprs,
 };
 use datafusion_expr::{
-    qualified_wildcard_with_options, wildcard_with_options, Aggregate, Expr, Filter,
-    GroupingSet, LogicalPlan, LogicalPlanBuilder, Partitioning,
+    lit, qualified_wildcard_with_options, when, wildcard_with_options, Aggregate, Expr,
+    ExprSchemable, Filter, GroupingSet, LogicalPlan, LogicalPlanBuilder, Partitioning,
 };
 
 use indexmap::IndexMap;
 use sqlparser::ast::{
-    Distinct, Expr as SQLExpr, GroupByExpr, NamedWindowExpr, OrderByExpr,
+    Distinct, Expr as SQLExpr, FunctionArg, FunctionArgExpr, FunctionArgumentList,
+    FunctionArguments, GroupByExpr, LateralView, NamedWindowExpr, OrderByExpr,
     WildcardAdditionalOptions, WindowType,
 };
 use sqlparser::ast::{NamedWindowDefinition, Select, SelectItem, TableWithJoins};
@@ -57,26 +64,48 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         // Check for unsupported syntax first
-        if !select.cluster_by.is_empty() {
-            return not_impl_err!("CLUSTER BY");
-        }
-        if !select.lateral_views.is_empty() {
-            return not_impl_err!("LATERAL VIEWS");
-        }
-        if select.qualify.is_some() {
-            return not_impl_err!("QUALIFY");
-        }
         if select.top.is_some() {
             return not_impl_err!("TOP");
         }
-        if !select.sort_by.is_empty() {
-            return not_impl_err!("SORT BY");
+
+        // CLUSTER BY is a shorthand for DISTRIBUTE BY and SORT BY on the same expressions
+        if !select.cluster_by.is_empty() {
+            if !select.distribute_by.is_empty() || !select.sort_by.is_empty() {
+                return plan_err!(
+                    "CLUSTER BY cannot be combined with DISTRIBUTE BY or SORT BY"
+                );
+            }
+            select.distribute_by = select.cluster_by.clone();
+            select.sort_by = std::mem::take(&mut select.cluster_by);
+        }
+
+        // SORT BY only orders the rows within each partition
+        if !select.sort_by.is_empty() && !order_by.is_empty() {
+            return plan_err!("ORDER BY cannot be combined with SORT BY or CLUSTER BY");
         }
+        let sort_by = std::mem::take(&mut select.sort_by)
+            .into_iter()
+            .map(|expr| OrderByExpr {
+                expr,
+                asc: None,
+                nulls_first: None,
+                with_fill: None,
+            })
+            .collect::<Vec<_>>();
 
         // Process `from` clause
         let plan = self.plan_from_tables(select.from, planner_context)?;
         let empty_from = matches!(plan, LogicalPlan::EmptyRelation(_));
 
+        // Process `lateral view`s, which may be referenced by the `where` clause
+        let plan =
+            select
+                .lateral_views
+                .into_iter()
+                .try_fold(plan, |plan, lateral_view| {
+                    self.plan_lateral_view(plan, lateral_view, planner_context)
+                })?;
+
         // Process `where` clause
         let base_plan = self.plan_selection(select.selection, plan, planner_context)?;
 
@@ -110,6 +139,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             Some(base_plan.schema().as_ref()),
         )?;
         let order_by_rex = normalize_sorts(order_by_rex, &projected_plan)?;
+        let sort_by_rex = self.order_by_to_sort_expr(
+            sort_by,
+            projected_plan.schema().as_ref(),
+            planner_context,
+            true,
+            Some(base_plan.schema().as_ref()),
+        )?;
+        let sort_by_rex = normalize_sorts(sort_by_rex, &projected_plan)?;
 
         // This alias map is resolved and looked up in both having exprs and group by exprs
         let alias_map = extract_aliases(&select_exprs);
@@ -141,9 +178,31 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             })
             .transpose()?;
 
+        // Optionally the QUALIFY expression.
+        let qualify_expr_opt = select
+            .qualify
+            .map::<Result<Expr>, _>(|qualify_expr| {
+                let qualify_expr = self.sql_expr_to_logical_expr(
+                    qualify_expr,
+                    &combined_schema,
+                    planner_context,
+                )?;
+                // Like HAVING, QUALIFY may refer to aliases from the projection:
+                //
+                //   SELECT c1, ROW_NUMBER() OVER (PARTITION BY c1) AS rn FROM t QUALIFY rn = 1;
+                //
+                let qualify_expr = resolve_aliases_to_exprs(qualify_expr, &alias_map)?;
+                normalize_col(qualify_expr, &projected_plan)
+            })
+            .transpose()?;
+
         // The outer expressions we will search through for aggregates.
-        // Aggregates may be sourced from the SELECT list or from the HAVING expression.
-        let aggr_expr_haystack = select_exprs.iter().chain(having_expr_opt.iter());
+        // Aggregates may be sourced from the SELECT list or from the HAVING
+        // and QUALIFY expressions.
+        let aggr_expr_haystack = select_exprs
+            .iter()
+            .chain(having_expr_opt.iter())
+            .chain(qualify_expr_opt.iter());
         // All of the aggregate expressions (deduplicated).
         let aggr_exprs = find_aggregate_exprs(aggr_expr_haystack);
 
@@ -192,21 +251,24 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         };
 
         // Process group by, aggregation or having
-        let (plan, mut select_exprs_post_aggr, having_expr_post_aggr) = if !group_by_exprs
-            .is_empty()
-            || !aggr_exprs.is_empty()
-        {
+        let (
+            plan,
+            mut select_exprs_post_aggr,
+            having_expr_post_aggr,
+            qualify_expr_post_aggr,
+        ) = if !group_by_exprs.is_empty() || !aggr_exprs.is_empty() {
             self.aggregate(
                 &base_plan,
                 &select_exprs,
                 having_expr_opt.as_ref(),
+                qualify_expr_opt.as_ref(),
                 &group_by_exprs,
                 &aggr_exprs,
             )?
         } else {
             match having_expr_opt {
                 Some(having_expr) => return plan_err!("HAVING clause references: {having_expr} must appear in the GROUP BY clause or be used in an aggregate function"),
-                None => (base_plan.clone(), select_exprs.clone(), having_expr_opt)
+                None => (base_plan.clone(), select_exprs.clone(), having_expr_opt, qualify_expr_opt)
             }
         };
 
@@ -219,7 +281,15 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         };
 
         // Process window function
-        let window_func_exprs = find_window_exprs(&select_exprs_post_aggr);
+        // Window functions may be sourced from the SELECT list or from the
+        // QUALIFY expression.
+        let window_func_exprs = find_window_exprs(
+            &select_exprs_post_aggr
+                .iter()
+                .chain(qualify_expr_post_aggr.iter())
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
 
         let plan = if window_func_exprs.is_empty() {
             plan
@@ -235,6 +305,21 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             plan
         };
 
+        // Process QUALIFY, which filters on the output of the window functions
+        let plan = if let Some(qualify_expr) = qualify_expr_post_aggr {
+            if window_func_exprs.is_empty() {
+                return plan_err!(
+                    "QUALIFY clause requires window functions in the SELECT list or QUALIFY clause"
+                );
+            }
+            let qualify_expr = rebase_expr(&qualify_expr, &window_func_exprs, &plan)?;
+            LogicalPlanBuilder::from(plan)
+                .filter(qualify_expr)?
+                .build()?
+        } else {
+            plan
+        };
+
         // Try processing unnest expression or do the final projection
         let plan = self.try_process_unnest(plan, select_exprs_post_aggr)?;
 
@@ -286,6 +371,15 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             plan
         };
 
+        // SORT BY
+        let plan = if !sort_by_rex.is_empty() {
+            LogicalPlanBuilder::from(plan)
+                .sort_within_partitions(sort_by_rex)?
+                .build()?
+        } else {
+            plan
+        };
+
         self.order_by(plan, order_by_rex)
     }
 
@@ -567,6 +661,110 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    /// Plans `LATERAL VIEW [OUTER] explode(expr) view_name [AS col]` as an
+    /// [`Unnest`] of `expr` on top of `input`.
+    ///
+    /// The exploded values are added to the columns of `input` as
+    /// `view_name.col`, where `col` defaults to `col` like in Hive. With `OUTER`,
+    /// input rows whose array is null or empty are kept with a null value.
+    ///
+    /// [`Unnest`]: datafusion_expr::Unnest
+    fn plan_lateral_view(
+        &self,
+        input: LogicalPlan,
+        lateral_view: LateralView,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let LateralView {
+            lateral_view,
+            lateral_view_name,
+            lateral_col_alias,
+            outer,
+        } = lateral_view;
+        let SQLExpr::Function(function) = lateral_view else {
+            return not_impl_err!("Unsupported LATERAL VIEW expression: {lateral_view}");
+        };
+        if !function.name.to_string().eq_ignore_ascii_case("explode") {
+            return not_impl_err!(
+                "Unsupported LATERAL VIEW function: {}, only explode is supported",
+                function.name
+            );
+        }
+        let arg = match function.args {
+            FunctionArguments::List(FunctionArgumentList { args, .. })
+                if args.len() == 1 =>
+            {
+                match args.into_iter().next() {
+                    Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))) => arg,
+                    _ => return plan_err!("explode expects a single array argument"),
+                }
+            }
+            _ => return plan_err!("explode expects a single array argument"),
+        };
+        let arg = self.sql_expr_to_logical_expr(arg, input.schema(), planner_context)?;
+        let arg_type = arg.get_type(input.schema())?;
+        if !matches!(
+            arg_type,
+            DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _)
+        ) {
+            return plan_err!("explode expects an array argument, got {arg_type}");
+        }
+        // Unnest only keeps null arrays, so map empty arrays to null for OUTER
+        let arg = if outer {
+            let empty = self
+                .context_provider
+                .get_function_meta("empty")
+                .ok_or_else(|| {
+                    internal_datafusion_err!("Unable to find expected 'empty' function")
+                })?;
+            let is_empty =
+                Expr::ScalarFunction(ScalarFunction::new_udf(empty, vec![arg.clone()]));
+            when(is_empty, lit(ScalarValue::Null)).otherwise(arg)?
+        } else {
+            arg
+        };
+
+        let view_name = self.object_name_to_table_reference(lateral_view_name)?;
+        let column_name = match lateral_col_alias.as_slice() {
+            [] => "col".to_string(),
+            [alias] => self.ident_normalizer.normalize(alias.clone()),
+            aliases => {
+                return plan_err!(
+                    "explode produces a single column, but {} column aliases were given",
+                    aliases.len()
+                )
+            }
+        };
+
+        let input_columns = input.schema().columns();
+        let exploded = Column::new(Some(view_name.clone()), column_name.clone());
+        let exprs =
+            input_columns
+                .iter()
+                .cloned()
+                .map(Expr::Column)
+                .chain(std::iter::once(
+                    arg.alias_qualified(Some(view_name.clone()), &column_name),
+                ));
+        let plan = LogicalPlanBuilder::from(input)
+            .project(exprs)?
+            .unnest_column_with_options(
+                exploded,
+                UnnestOptions::new().with_preserve_nulls(outer),
+            )?
+            .build()?;
+
+        // Unnest drops the qualifier of the exploded column
+        let unnested = Column::from(plan.schema().qualified_field(input_columns.len()));
+        let exprs = input_columns
+            .into_iter()
+            .map(Expr::Column)
+            .chain(std::iter::once(
+                Expr::Column(unnested).alias_qualified(Some(view_name), column_name),
+            ));
+        LogicalPlanBuilder::from(plan).project(exprs)?.build()
+    }
+
     /// Returns the `Expr`'s corresponding to a SQL query's SELECT expressions.
     fn prepare_select_exprs(
         &self,
@@ -722,27 +920,32 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     ///                       "having" expressions must all be resolvable from this plan.
     /// * `select_exprs`    - The projection expressions from the SELECT clause.
     /// * `having_expr_opt` - Optional HAVING clause.
+    /// * `qualify_expr_opt` - Optional QUALIFY clause.
     /// * `group_by_exprs`  - Grouping expressions from the GROUP BY clause. These can be column
     ///                       references or more complex expressions.
     /// * `aggr_exprs`      - Aggregate expressions, such as `SUM(a)` or `COUNT(1)`.
     ///
     /// # Return
     ///
-    /// The return value is a triplet of the following items:
+    /// The return value is a tuple of the following items:
     ///
     /// * `plan`                   - A [LogicalPlan::Aggregate] plan for the newly created aggregate.
     /// * `select_exprs_post_aggr` - The projection expressions rewritten to reference columns from
     ///                              the aggregate
     /// * `having_expr_post_aggr`  - The "having" expression rewritten to reference a column from
     ///                              the aggregate
+    /// * `qualify_expr_post_aggr` - The "qualify" expression rewritten to reference columns from
+    ///                              the aggregate
+    #[allow(clippy::type_complexity)]
     fn aggregate(
         &self,
         input: &LogicalPlan,
         select_exprs: &[Expr],
         having_expr_opt: Option<&Expr>,
+        qualify_expr_opt: Option<&Expr>,
         group_by_exprs: &[Expr],
         aggr_exprs: &[Expr],
-    ) -> Result<(LogicalPlan, Vec<Expr>, Option<Expr>)> {
+    ) -> Result<(LogicalPlan, Vec<Expr>, Option<Expr>, Option<Expr>)> {
         // create the aggregate plan
         let plan = LogicalPlanBuilder::from(input.clone())
             .aggregate(group_by_exprs.to_vec(), aggr_exprs.to_vec())?
@@ -823,7 +1026,29 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             None
         };
 
-        Ok((plan, select_exprs_post_aggr, having_expr_post_aggr))
+        // Rewrite the QUALIFY expression to use the columns produced by the
+        // aggregation.
+        let qualify_expr_post_aggr = if let Some(qualify_expr) = qualify_expr_opt {
+            let qualify_expr_post_aggr =
+                rebase_expr(qualify_expr, &aggr_projection_exprs, input)?;
+
+            check_columns_satisfy_exprs(
+                &column_exprs_post_aggr,
+                std::slice::from_ref(&qualify_expr_post_aggr),
+                CheckColumnsSatisfyExprsPurpose::QualifyMustReferenceAggregate,
+            )?;
+
+            Some(qualify_expr_post_aggr)
+        } else {
+            None
+        };
+
+        Ok((
+            plan,
+            select_exprs_post_aggr,
+            having_expr_post_aggr,
+            qualify_expr_post_aggr,
+        ))
     }
 }
 
@@ -54,14 +54,17 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         return Err(err);
                     }
                 };
-                self.validate_set_expr_num_of_columns(
-                    op,
-                    left_span,
-                    right_span,
-                    &left_plan,
-                    &right_plan,
-                    set_expr_span,
-                )?;
+                // Inputs of BY NAME operations are aligned by column name
+                if !Self::is_by_name(set_quantifier) {
+                    self.validate_set_expr_num_of_columns(
+                        op,
+                        left_span,
+                        right_span,
+                        &left_plan,
+                        &right_plan,
+                        set_expr_span,
+                    )?;
+                }
 
                 self.set_operation_to_plan(op, left_plan, right_plan, set_quantifier)
             }
@@ -86,6 +89,15 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    fn is_by_name(set_quantifier: SetQuantifier) -> bool {
+        matches!(
+            set_quantifier,
+            SetQuantifier::ByName
+                | SetQuantifier::AllByName
+                | SetQuantifier::DistinctByName
+        )
+    }
+
     fn validate_set_expr_num_of_columns(
         &self,
         op: SetOperator,
@@ -127,6 +139,18 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         right_plan: LogicalPlan,
         set_quantifier: SetQuantifier,
     ) -> Result<LogicalPlan> {
+        if op == SetOperator::Union && Self::is_by_name(set_quantifier) {
+            let builder = LogicalPlanBuilder::from(left_plan);
+            let builder = if set_quantifier == SetQuantifier::AllByName {
+                builder.union_by_name(right_plan)?
+            } else {
+                builder.union_by_name_distinct(right_plan)?
+            };
+            return builder.build();
+        }
+        if Self::is_by_name(set_quantifier) {
+            return not_impl_err!("{op} BY NAME not implemented");
+        }
         let all = Self::is_union_all(set_quantifier)?;
         match (op, all) {
             (SetOperator::Union, true) => LogicalPlanBuilder::from(left_plan)
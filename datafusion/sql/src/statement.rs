@@ -38,35 +38,63 @@ use datafusion_common::{
     DataFusionError, Result, ScalarValue, SchemaError, SchemaReference, TableReference,
     ToDFSchema,
 };
-use datafusion_expr::dml::{CopyTo, InsertOp};
+use datafusion_expr::dml::{CopyTo, InsertOp, MERGE_ACTION_COLUMN};
+use datafusion_expr::expr::{self, Case};
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::utils::expr_to_columns;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
+    cast, col, lit, when, Analyze, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateFunctionBody,
     CreateIndex as PlanCreateIndex, CreateMemoryTable, CreateView, Deallocate,
     DescribeTable, DmlStatement, DropCatalogSchema, DropFunction, DropTable, DropView,
-    EmptyRelation, Execute, Explain, Expr, ExprSchemable, Filter, LogicalPlan,
-    LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare, SetVariable, SortExpr,
-    Statement as PlanStatement, ToStringifiedPlan, TransactionAccessMode,
-    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
-    Volatility, WriteOp,
+    EmptyRelation, Execute, Explain, Expr, ExprFunctionExt, ExprSchemable, Filter,
+    JoinType, LogicalPlan, LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare,
+    SetVariable, SortExpr, Statement as PlanStatement, ToStringifiedPlan,
+    TransactionAccessMode, TransactionConclusion, TransactionEnd,
+    TransactionIsolationLevel, TransactionStart, Volatility, WindowFunctionDefinition,
+    WriteOp,
 };
 use sqlparser::ast::{
     self, BeginTransactionKind, NullsDistinctOption, ShowStatementIn,
     ShowStatementOptions, SqliteOnConflict, TableObject, UpdateTableFromKind,
 };
 use sqlparser::ast::{
-    Assignment, AssignmentTarget, ColumnDef, CreateIndex, CreateTable,
+    Assignment, AssignmentTarget, ColumnDef, CommentDef, CreateIndex, CreateTable,
     CreateTableOptions, Delete, DescribeAlias, Expr as SQLExpr, FromTable, Ident, Insert,
-    ObjectName, ObjectType, OneOrManyWithParens, Query, SchemaName, SetExpr,
-    ShowCreateObject, ShowStatementFilter, Statement, TableConstraint, TableFactor,
-    TableWithJoins, TransactionMode, UnaryOperator, Value,
+    MergeAction, MergeClause, MergeClauseKind, MergeInsertKind, ObjectName, ObjectType,
+    OneOrManyWithParens, Query, SchemaName, SetExpr, ShowCreateObject,
+    ShowStatementFilter, Statement, TableConstraint, TableFactor, TableWithJoins,
+    TransactionMode, UnaryOperator, Value,
 };
 use sqlparser::parser::ParserError::ParserError;
 
+/// Marker column added to the target table of a `MERGE` before joining it
+/// with the source, holding a unique id for each target row
+const MERGE_TARGET_MARKER: &str = "__merge_target";
+/// Marker column added to the source of a `MERGE` before joining it with the
+/// target table
+const MERGE_SOURCE_MARKER: &str = "__merge_source";
+/// Index of the first `WHEN` clause of a `MERGE` that applies to a row
+const MERGE_CLAUSE_COLUMN: &str = "__merge_clause";
+/// Number of the joined row among the rows of the same target row
+const MERGE_MATCH_COLUMN: &str = "__merge_match";
+/// Number of joined rows of the same target row to which a `WHEN` clause applies
+const MERGE_APPLIED_COLUMN: &str = "__merge_applied";
+
+/// Adds a `marker` column with the value `true` to `plan`
+fn mark_merge_side(plan: LogicalPlan, marker: &str) -> Result<LogicalPlan> {
+    let mut exprs = plan
+        .schema()
+        .columns()
+        .into_iter()
+        .map(Expr::Column)
+        .collect::<Vec<_>>();
+    exprs.push(lit(true).alias(marker));
+    project(plan, exprs)
+}
+
 fn ident_to_string(ident: &Ident) -> String {
     normalize_ident(ident.to_owned())
 }
@@ -167,6 +195,23 @@ fn calc_inline_constraints_from_columns(columns: &[ColumnDef]) -> Vec<TableConst
     constraints
 }
 
+/// Extract the columns of a `PRIMARY KEY <expr>` table option, which is either
+/// a single column or a parenthesized list of columns.
+fn primary_key_columns(expr: SQLExpr) -> Result<Vec<Ident>> {
+    match expr {
+        SQLExpr::Identifier(ident) => Ok(vec![ident]),
+        SQLExpr::Nested(expr) => primary_key_columns(*expr),
+        SQLExpr::Tuple(exprs) => exprs
+            .into_iter()
+            .map(|expr| match expr {
+                SQLExpr::Identifier(ident) => Ok(ident),
+                _ => plan_err!("Primary key must consist of columns, got {expr}"),
+            })
+            .collect(),
+        _ => plan_err!("Primary key must consist of columns, got {expr}"),
+    }
+}
+
 impl<S: ContextProvider> SqlToRel<'_, S> {
     /// Generate a logical plan from an DataFusion SQL statement
     pub fn statement_to_plan(&self, statement: DFStatement) -> Result<LogicalPlan> {
@@ -325,9 +370,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 if engine.is_some() {
                     return not_impl_err!("Engine not supported")?;
                 }
-                if comment.is_some() {
-                    return not_impl_err!("Comment not supported")?;
-                }
                 if auto_increment_offset.is_some() {
                     return not_impl_err!("Auto increment offset not supported")?;
                 }
@@ -343,15 +385,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 if on_cluster.is_some() {
                     return not_impl_err!("On cluster not supported")?;
                 }
-                if primary_key.is_some() {
-                    return not_impl_err!("Primary key not supported")?;
-                }
-                if order_by.is_some() {
-                    return not_impl_err!("Order by not supported")?;
-                }
-                if partition_by.is_some() {
-                    return not_impl_err!("Partition by not supported")?;
-                }
                 if cluster_by.is_some() {
                     return not_impl_err!("Cluster by not supported")?;
                 }
@@ -394,13 +427,24 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     return not_impl_err!("With tags not supported")?;
                 }
 
-                // Merge inline constraints and existing constraints
+                // Merge inline constraints, existing constraints and the primary key
                 let mut all_constraints = constraints;
                 let inline_constraints = calc_inline_constraints_from_columns(&columns);
                 all_constraints.extend(inline_constraints);
-                // Build column default values
+                if let Some(primary_key) = primary_key {
+                    all_constraints.push(TableConstraint::PrimaryKey {
+                        name: None,
+                        index_name: None,
+                        index_type: None,
+                        columns: primary_key_columns(*primary_key)?,
+                        index_options: vec![],
+                        characteristics: None,
+                    });
+                }
+                // Build column default values and comments
                 let column_defaults =
                     self.build_column_defaults(&columns, planner_context)?;
+                let column_comments = self.build_column_comments(&columns);
 
                 let has_columns = !columns.is_empty();
                 let schema = self.build_schema(columns)?.to_dfschema_ref()?;
@@ -408,12 +452,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     planner_context.set_table_schema(Some(Arc::clone(&schema)));
                 }
 
-                match query {
+                let plan = match query {
                     Some(query) => {
                         let plan = self.query_to_plan(*query, planner_context)?;
                         let input_schema = plan.schema();
 
-                        let plan = if has_columns {
+                        if has_columns {
                             if schema.fields().len() != input_schema.fields().len() {
                                 return plan_err!(
                             "Mismatch: {} columns specified, but result has {} columns",
@@ -439,49 +483,63 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 .build()?
                         } else {
                             plan
-                        };
+                        }
+                    }
 
-                        let constraints = Self::new_constraint_from_table_constraints(
-                            &all_constraints,
-                            plan.schema(),
-                        )?;
+                    None => LogicalPlan::EmptyRelation(EmptyRelation {
+                        produce_one_row: false,
+                        schema,
+                    }),
+                };
 
-                        Ok(LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(
-                            CreateMemoryTable {
-                                name: self.object_name_to_table_reference(name)?,
-                                constraints,
-                                input: Arc::new(plan),
-                                if_not_exists,
-                                or_replace,
-                                column_defaults,
-                                temporary,
-                            },
-                        )))
-                    }
+                let constraints = Self::new_constraint_from_table_constraints(
+                    &all_constraints,
+                    plan.schema(),
+                )?;
 
-                    None => {
-                        let plan = EmptyRelation {
-                            produce_one_row: false,
-                            schema,
-                        };
-                        let plan = LogicalPlan::EmptyRelation(plan);
-                        let constraints = Self::new_constraint_from_table_constraints(
-                            &all_constraints,
-                            plan.schema(),
-                        )?;
-                        Ok(LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(
-                            CreateMemoryTable {
-                                name: self.object_name_to_table_reference(name)?,
-                                constraints,
-                                input: Arc::new(plan),
-                                if_not_exists,
-                                or_replace,
-                                column_defaults,
-                                temporary,
-                            },
-                        )))
-                    }
+                // PARTITION BY and ORDER BY refer to the columns of the new table,
+                // so resolve them against its unqualified schema
+                let table_schema = DFSchema::try_from(plan.schema().as_arrow().clone())?;
+                let partition_by = match partition_by.map(|expr| *expr) {
+                    Some(SQLExpr::Tuple(exprs)) => exprs,
+                    Some(expr) => vec![expr],
+                    None => vec![],
+                }
+                .into_iter()
+                .map(|expr| self.sql_to_expr(expr, &table_schema, planner_context))
+                .collect::<Result<Vec<_>>>()?;
+                let sort_order = match order_by {
+                    Some(OneOrManyWithParens::One(expr)) => vec![expr],
+                    Some(OneOrManyWithParens::Many(exprs)) => exprs,
+                    None => vec![],
                 }
+                .into_iter()
+                .map(|expr| {
+                    let expr = self.sql_to_expr(expr, &table_schema, planner_context)?;
+                    Ok(expr.sort(true, false))
+                })
+                .collect::<Result<Vec<_>>>()?;
+                let comment = comment.map(|comment| match comment {
+                    CommentDef::WithEq(comment)
+                    | CommentDef::WithoutEq(comment)
+                    | CommentDef::AfterColumnDefsWithoutEq(comment) => comment,
+                });
+
+                Ok(LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(
+                    CreateMemoryTable {
+                        name: self.object_name_to_table_reference(name)?,
+                        constraints,
+                        input: Arc::new(plan),
+                        if_not_exists,
+                        or_replace,
+                        column_defaults,
+                        temporary,
+                        partition_by,
+                        sort_order,
+                        comment,
+                        column_comments,
+                    },
+                )))
             }
 
             Statement::CreateView {
@@ -950,6 +1008,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 self.delete_to_plan(table_name, selection)
             }
 
+            Statement::Merge {
+                into: _,
+                table,
+                source,
+                on,
+                clauses,
+            } => self.merge_to_plan(table, source, *on, clauses),
+
             Statement::StartTransaction {
                 modes,
                 begin: false,
@@ -1858,6 +1924,299 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         Ok(plan)
     }
 
+    fn merge_to_plan(
+        &self,
+        table: TableFactor,
+        source: TableFactor,
+        on: SQLExpr,
+        clauses: Vec<MergeClause>,
+    ) -> Result<LogicalPlan> {
+        let table_name = match &table {
+            TableFactor::Table {
+                name, args: None, ..
+            } => name.clone(),
+            _ => plan_err!("Cannot merge into non-table relation!")?,
+        };
+        if clauses.is_empty() {
+            plan_err!("MERGE requires at least one WHEN clause")?;
+        }
+
+        // Do a table lookup to verify the table exists
+        let table_name = self.object_name_to_table_reference(table_name)?;
+        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+        let table_schema = DFSchema::try_from_qualified_schema(
+            table_name.clone(),
+            &table_source.schema(),
+        )?;
+
+        // Window functions used to find the target rows matching several source rows
+        let Some(row_number) = self.context_provider.get_window_meta("row_number") else {
+            return plan_err!("MERGE requires the row_number window function");
+        };
+        let Some(count) = self.context_provider.get_aggregate_meta("count") else {
+            return plan_err!("MERGE requires the count aggregate function");
+        };
+
+        // Join the target table with the source, keeping all rows of the target.
+        // Marker columns record on which sides of the join each row was found,
+        // the target marker also identifies the target row of each joined row.
+        let mut planner_context = PlannerContext::new();
+        let target = self.create_relation(table, &mut planner_context)?;
+        let target_columns = target.schema().columns();
+        let row_id = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunctionDefinition::WindowUDF(Arc::clone(&row_number)),
+            vec![],
+        ))
+        .alias(MERGE_TARGET_MARKER);
+        let target = LogicalPlanBuilder::from(target)
+            .window(vec![row_id])?
+            .build()?;
+        let source = self.create_relation(source, &mut planner_context)?;
+        let source = mark_merge_side(source, MERGE_SOURCE_MARKER)?;
+        let join_type = if clauses.iter().any(|clause| {
+            matches!(
+                clause.clause_kind,
+                MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget
+            )
+        }) {
+            JoinType::Full
+        } else {
+            JoinType::Left
+        };
+        let join_schema = target.schema().join(source.schema())?;
+        let on = self.sql_to_expr(on, &join_schema, &mut planner_context)?;
+        let joined = LogicalPlanBuilder::from(target)
+            .join_on(source, join_type, Some(on))?
+            .build()?;
+
+        // Plan the condition and the new column values of each clause
+        let target_found = col(MERGE_TARGET_MARKER).is_not_null();
+        let source_found = col(MERGE_SOURCE_MARKER).is_not_null();
+        let mut conditions = Vec::with_capacity(clauses.len());
+        let mut actions = Vec::with_capacity(clauses.len());
+        let mut values: Vec<Vec<(usize, Expr)>> =
+            vec![vec![]; table_schema.fields().len()];
+        for (clause_idx, clause) in clauses.into_iter().enumerate() {
+            let MergeClause {
+                clause_kind,
+                predicate,
+                action,
+            } = clause;
+            let mut condition = match clause_kind {
+                MergeClauseKind::Matched => {
+                    target_found.clone().and(source_found.clone())
+                }
+                MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget => {
+                    col(MERGE_TARGET_MARKER).is_null()
+                }
+                MergeClauseKind::NotMatchedBySource => col(MERGE_SOURCE_MARKER).is_null(),
+            };
+            if let Some(predicate) = predicate {
+                let predicate =
+                    self.sql_to_expr(predicate, joined.schema(), &mut planner_context)?;
+                condition = condition.and(predicate);
+            }
+            conditions.push(condition);
+
+            let inserts = matches!(
+                clause_kind,
+                MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget
+            );
+            match action {
+                MergeAction::Insert(insert) if inserts => {
+                    let MergeInsertKind::Values(ast::Values { mut rows, .. }) =
+                        insert.kind
+                    else {
+                        return not_impl_err!("MERGE INSERT ROW is not supported");
+                    };
+                    if rows.len() != 1 {
+                        plan_err!("MERGE INSERT must have exactly one row of values")?;
+                    }
+                    let row = rows.swap_remove(0);
+                    // Empty means we're inserting into all columns of the table
+                    let column_indices = if insert.columns.is_empty() {
+                        (0..table_schema.fields().len()).collect::<Vec<_>>()
+                    } else {
+                        insert
+                            .columns
+                            .into_iter()
+                            .map(|c| {
+                                let c = self.ident_normalizer.normalize(c);
+                                table_schema.index_of_column_by_name(None, &c).ok_or_else(
+                                    || unqualified_field_not_found(&c, &table_schema),
+                                )
+                            })
+                            .collect::<Result<Vec<_>>>()?
+                    };
+                    if column_indices.len() != row.len() {
+                        plan_err!("Column count doesn't match MERGE INSERT values!")?;
+                    }
+                    let mut row_values = vec![None; table_schema.fields().len()];
+                    for (column_idx, value) in column_indices.into_iter().zip(row) {
+                        if row_values[column_idx].is_some() {
+                            let name = table_schema.field(column_idx).name().clone();
+                            return schema_err!(SchemaError::DuplicateUnqualifiedField {
+                                name
+                            });
+                        }
+                        let value = self.sql_to_expr(
+                            value,
+                            joined.schema(),
+                            &mut planner_context,
+                        )?;
+                        row_values[column_idx] = Some(value);
+                    }
+                    for (column_idx, value) in row_values.into_iter().enumerate() {
+                        // Fill in the default value for the columns that are not specified
+                        let value = value.unwrap_or_else(|| {
+                            table_source
+                                .get_column_default(table_schema.field(column_idx).name())
+                                .cloned()
+                                .unwrap_or(Expr::Literal(ScalarValue::Null))
+                        });
+                        values[column_idx].push((clause_idx, value));
+                    }
+                    actions.push("insert");
+                }
+                MergeAction::Update { assignments } if !inserts => {
+                    for assign in assignments {
+                        let cols = match &assign.target {
+                            AssignmentTarget::ColumnName(cols) => cols,
+                            _ => plan_err!("Tuples are not supported")?,
+                        };
+                        let col_name: &Ident = cols
+                            .0
+                            .iter()
+                            .last()
+                            .ok_or_else(|| plan_datafusion_err!("Empty column id"))?;
+                        let col_name = self.ident_normalizer.normalize(col_name.clone());
+                        let column_idx = table_schema
+                            .index_of_column_by_name(None, &col_name)
+                            .ok_or_else(|| {
+                                unqualified_field_not_found(&col_name, &table_schema)
+                            })?;
+                        let value = self.sql_to_expr(
+                            assign.value,
+                            joined.schema(),
+                            &mut planner_context,
+                        )?;
+                        values[column_idx].push((clause_idx, value));
+                    }
+                    actions.push("update");
+                }
+                MergeAction::Delete if !inserts => actions.push("delete"),
+                action => {
+                    return plan_err!(
+                        "Invalid action for WHEN {clause_kind} clause of MERGE: {action}"
+                    )
+                }
+            }
+        }
+
+        // Find the first clause that applies to each row
+        let clause_col = col(MERGE_CLAUSE_COLUMN);
+        let matching_clause = Expr::Case(Case::new(
+            None,
+            conditions
+                .into_iter()
+                .enumerate()
+                .map(|(idx, condition)| (Box::new(condition), Box::new(lit(idx as i64))))
+                .collect(),
+            None,
+        ));
+        let mut exprs = joined
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        exprs.push(matching_clause.alias(MERGE_CLAUSE_COLUMN));
+
+        // A target row matching several source rows is joined once per match.
+        // It is a cardinality violation if a clause applies to any of these
+        // rows, otherwise the target row is kept once.
+        let partition_by = vec![col(MERGE_TARGET_MARKER)];
+        let match_number = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunctionDefinition::WindowUDF(row_number),
+            vec![],
+        ))
+        .partition_by(partition_by.clone())
+        .build()?
+        .alias(MERGE_MATCH_COLUMN);
+        let applied_clauses = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunctionDefinition::AggregateUDF(count),
+            vec![clause_col.clone()],
+        ))
+        .partition_by(partition_by)
+        .build()?
+        .alias(MERGE_APPLIED_COLUMN);
+        let duplicate = target_found
+            .clone()
+            .and(col(MERGE_MATCH_COLUMN).gt(lit(1_u64)));
+        let source = LogicalPlanBuilder::from(joined)
+            .project(exprs)?
+            .window(vec![match_number, applied_clauses])?
+            // Drop source rows that are not inserted and repeated target rows
+            // that are left unchanged
+            .filter(
+                target_found.or(clause_col.clone().is_not_null()).and(
+                    col(MERGE_TARGET_MARKER)
+                        .is_null()
+                        .or(col(MERGE_MATCH_COLUMN).eq(lit(1_u64)))
+                        .or(col(MERGE_APPLIED_COLUMN).gt(lit(0_i64))),
+                ),
+            )?
+            .build()?;
+
+        // Compute the new values of the table columns and the action applied to each row
+        let mut exprs = target_columns
+            .into_iter()
+            .zip(values)
+            .zip(table_schema.fields())
+            .map(|((column, values), field)| {
+                let column = Expr::Column(column);
+                let expr = if values.is_empty() {
+                    column
+                } else {
+                    let when_then = values
+                        .into_iter()
+                        .map(|(clause_idx, value)| {
+                            let value =
+                                value.cast_to(field.data_type(), source.schema())?;
+                            Ok((Box::new(lit(clause_idx as i64)), Box::new(value)))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Expr::Case(Case::new(
+                        Some(Box::new(clause_col.clone())),
+                        when_then,
+                        Some(Box::new(column)),
+                    ))
+                };
+                Ok(expr.alias(field.name()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let action = Expr::Case(Case::new(
+            Some(Box::new(clause_col)),
+            actions
+                .into_iter()
+                .enumerate()
+                .map(|(idx, action)| (Box::new(lit(idx as i64)), Box::new(lit(action))))
+                .collect(),
+            None,
+        ));
+        let action = when(duplicate, lit("conflict")).otherwise(action)?;
+        exprs.push(action.alias(MERGE_ACTION_COLUMN));
+        let source = project(source, exprs)?;
+
+        let plan = LogicalPlan::Dml(DmlStatement::new(
+            table_name,
+            Arc::new(table_schema),
+            WriteOp::Merge,
+            Arc::new(source),
+        ));
+        Ok(plan)
+    }
+
     fn insert_to_plan(
         &self,
         table_name: ObjectName,
@@ -194,6 +194,15 @@ pub trait Dialect: Send + Sync {
     fn unnest_as_table_factor(&self) -> bool {
         false
     }
+
+    /// Does the dialect support the `QUALIFY` clause for filtering on the
+    /// results of window functions?
+    ///
+    /// Plans that filter on window function results cannot be unparsed for
+    /// dialects that do not support it.
+    fn supports_qualify(&self) -> bool {
+        false
+    }
 }
 
 /// `IntervalStyle` to use for unparsing
@@ -252,6 +261,10 @@ impl Dialect for DefaultDialect {
             None
         }
     }
+
+    fn supports_qualify(&self) -> bool {
+        true
+    }
 }
 
 pub struct PostgreSqlDialect {}
@@ -391,6 +404,10 @@ impl Dialect for DuckDBDialect {
 
         Ok(None)
     }
+
+    fn supports_qualify(&self) -> bool {
+        true
+    }
 }
 
 pub struct MySqlDialect {}
@@ -516,6 +533,7 @@ pub struct CustomDialect {
     window_func_support_window_frame: bool,
     full_qualified_col: bool,
     unnest_as_table_factor: bool,
+    supports_qualify: bool,
 }
 
 impl Default for CustomDialect {
@@ -544,6 +562,7 @@ impl Default for CustomDialect {
             window_func_support_window_frame: true,
             full_qualified_col: false,
             unnest_as_table_factor: false,
+            supports_qualify: false,
         }
     }
 }
@@ -665,6 +684,10 @@ impl Dialect for CustomDialect {
     fn unnest_as_table_factor(&self) -> bool {
         self.unnest_as_table_factor
     }
+
+    fn supports_qualify(&self) -> bool {
+        self.supports_qualify
+    }
 }
 
 /// `CustomDialectBuilder` to build `CustomDialect` using builder pattern
@@ -702,6 +725,7 @@ pub struct CustomDialectBuilder {
     window_func_support_window_frame: bool,
     full_qualified_col: bool,
     unnest_as_table_factor: bool,
+    supports_qualify: bool,
 }
 
 impl Default for CustomDialectBuilder {
@@ -736,6 +760,7 @@ impl CustomDialectBuilder {
             window_func_support_window_frame: true,
             full_qualified_col: false,
             unnest_as_table_factor: false,
+            supports_qualify: false,
         }
     }
 
@@ -762,6 +787,7 @@ impl CustomDialectBuilder {
             window_func_support_window_frame: self.window_func_support_window_frame,
             full_qualified_col: self.full_qualified_col,
             unnest_as_table_factor: self.unnest_as_table_factor,
+            supports_qualify: self.supports_qualify,
         }
     }
 
@@ -902,4 +928,10 @@ impl CustomDialectBuilder {
         self.unnest_as_table_factor = _unnest_as_table_factor;
         self
     }
+
+    /// Customize the dialect to support the `QUALIFY` clause
+    pub fn with_supports_qualify(mut self, supports_qualify: bool) -> Self {
+        self.supports_qualify = supports_qualify;
+        self
+    }
 }
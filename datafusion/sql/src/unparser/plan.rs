@@ -44,10 +44,11 @@ use datafusion_common::{
     Column, DataFusionError, Result, ScalarValue, TableReference,
 };
 use datafusion_expr::expr::OUTER_REFERENCE_COLUMN_PREFIX;
+use datafusion_expr::utils::{conjunction, split_conjunction};
 use datafusion_expr::{
-    expr::Alias, BinaryExpr, Distinct, Expr, JoinConstraint, JoinType, LogicalPlan,
-    LogicalPlanBuilder, Operator, Projection, SortExpr, TableScan, Unnest,
-    UserDefinedLogicalNode,
+    expr::Alias, BinaryExpr, Distinct, Expr, Extension, JoinConstraint, JoinType,
+    LogicalPlan, LogicalPlanBuilder, Operator, Projection, SortExpr,
+    SortWithinPartitions, TableScan, Unnest, UserDefinedLogicalNode,
 };
 use sqlparser::ast::{self, Ident, SetExpr, TableAliasColumnDef};
 use std::sync::Arc;
@@ -115,6 +116,11 @@ impl Unparser<'_> {
             | LogicalPlan::Values(_)
             | LogicalPlan::Distinct(_) => self.select_to_sql_statement(&plan),
             LogicalPlan::Dml(_) => self.dml_to_sql(&plan),
+            LogicalPlan::Extension(ref extension)
+                if extension.node.as_any().is::<SortWithinPartitions>() =>
+            {
+                self.select_to_sql_statement(&plan)
+            }
             LogicalPlan::Extension(extension) => {
                 self.extension_to_statement(extension.node.as_ref())
             }
@@ -397,7 +403,33 @@ impl Unparser<'_> {
                 self.select_to_sql_recursively(p.input.as_ref(), query, select, relation)
             }
             LogicalPlan::Filter(filter) => {
-                if let Some(agg) =
+                // A filter above window functions of the same SELECT is a QUALIFY
+                if let Some(window) = find_window_nodes_within_select(
+                    plan,
+                    None,
+                    select.already_projected(),
+                ) {
+                    if !self.dialect.supports_qualify() {
+                        return not_impl_err!(
+                            "Unsupported filter on window functions: the dialect does not support QUALIFY"
+                        );
+                    }
+                    let unprojected = match find_agg_node_within_select(
+                        plan,
+                        select.already_projected(),
+                    ) {
+                        Some(agg) => unproject_agg_exprs(
+                            filter.predicate.clone(),
+                            agg,
+                            Some(&window),
+                        )?,
+                        None => {
+                            unproject_window_exprs(filter.predicate.clone(), &window)?
+                        }
+                    };
+                    let filter_expr = self.expr_to_sql(&unprojected)?;
+                    select.qualify(Some(filter_expr));
+                } else if let Some(agg) =
                     find_agg_node_within_select(plan, select.already_projected())
                 {
                     let unprojected =
@@ -601,8 +633,23 @@ impl Unparser<'_> {
                     &mut right_relation,
                 )?;
 
+                // The match condition of an ASOF join is the last conjunct of
+                // its filter, and is unparsed separately from the join constraint
+                let (join_filter, match_condition) = match (join.join_type, &join.filter)
+                {
+                    (JoinType::LeftAsof, Some(filter)) => {
+                        let mut predicates = split_conjunction(filter);
+                        let match_condition = predicates.pop().cloned();
+                        (
+                            conjunction(predicates.into_iter().cloned()),
+                            match_condition,
+                        )
+                    }
+                    _ => (join.filter.clone(), None),
+                };
+
                 let join_filters = if table_scan_filters.is_empty() {
-                    join.filter.clone()
+                    join_filter
                 } else {
                     // Combine `table_scan_filters` into a single filter using `AND`
                     let Some(combined_filters) =
@@ -618,9 +665,9 @@ impl Unparser<'_> {
                     };
 
                     // Combine `join.filter` with `combined_filters` using `AND`
-                    match &join.filter {
+                    match join_filter {
                         Some(filter) => Some(Expr::BinaryExpr(BinaryExpr {
-                            left: Box::new(filter.clone()),
+                            left: Box::new(filter),
                             op: Operator::And,
                             right: Box::new(combined_filters),
                         })),
@@ -645,11 +692,18 @@ impl Unparser<'_> {
                     return internal_err!("Failed to build right relation");
                 };
 
+                let join_operator = match match_condition {
+                    Some(match_condition) => ast::JoinOperator::AsOf {
+                        match_condition: self.expr_to_sql(&match_condition)?,
+                        constraint: join_constraint,
+                    },
+                    None => self.join_operator_to_sql(join.join_type, join_constraint)?,
+                };
+
                 let ast_join = ast::Join {
                     relation,
                     global: false,
-                    join_operator: self
-                        .join_operator_to_sql(join.join_type, join_constraint)?,
+                    join_operator,
                 };
                 let mut from = select.pop_from().unwrap();
                 from.push_join(ast_join);
@@ -765,6 +819,43 @@ impl Unparser<'_> {
                 }
                 Ok(())
             }
+            // SORT BY
+            LogicalPlan::Extension(Extension { node })
+                if node.as_any().is::<SortWithinPartitions>() =>
+            {
+                let sort = node
+                    .as_any()
+                    .downcast_ref::<SortWithinPartitions>()
+                    .unwrap();
+                if select.already_projected() {
+                    return self.derive_with_dialect_alias(
+                        "derived_sort",
+                        plan,
+                        relation,
+                        false,
+                    );
+                }
+
+                let agg = find_agg_node_within_select(plan, select.already_projected());
+                // SORT BY only supports the default ascending order
+                let sort_by = sort
+                    .expr()
+                    .iter()
+                    .map(|sort_expr| {
+                        if !sort_expr.asc || sort_expr.nulls_first {
+                            return not_impl_err!(
+                                "Unsupported SORT BY expression: {sort_expr}"
+                            );
+                        }
+                        let sort_expr =
+                            unproject_sort_expr(sort_expr, agg, sort.input())?;
+                        self.expr_to_sql(&sort_expr.expr)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                select.sort_by(sort_by);
+
+                self.select_to_sql_recursively(sort.input(), query, select, relation)
+            }
             LogicalPlan::Extension(extension) => {
                 if let Some(query) = query.as_mut() {
                     self.extension_to_sql(
@@ -1079,6 +1170,9 @@ impl Unparser<'_> {
             JoinType::RightAnti => ast::JoinOperator::RightAnti(constraint),
             JoinType::RightSemi => ast::JoinOperator::RightSemi(constraint),
             JoinType::LeftMark => unimplemented!("Unparsing of Left Mark join type"),
+            JoinType::LeftAsof => {
+                return internal_err!("Left Asof join without match condition")
+            }
         })
     }
 
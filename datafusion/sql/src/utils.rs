@@ -90,10 +90,12 @@ pub(crate) fn rebase_expr(
         .data()
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum CheckColumnsSatisfyExprsPurpose {
     ProjectionMustReferenceAggregate,
     HavingMustReferenceAggregate,
+    QualifyMustReferenceAggregate,
 }
 
 impl CheckColumnsSatisfyExprsPurpose {
@@ -105,6 +107,9 @@ impl CheckColumnsSatisfyExprsPurpose {
             CheckColumnsSatisfyExprsPurpose::HavingMustReferenceAggregate => {
                 "HAVING clause references non-aggregate values"
             }
+            CheckColumnsSatisfyExprsPurpose::QualifyMustReferenceAggregate => {
+                "QUALIFY clause references non-aggregate values"
+            }
         }
     }
 
@@ -107,6 +107,7 @@ fn roundtrip_statement() -> Result<()> {
             "select 1 limit 0;",
             "select ta.j1_id from j1 ta join (select 1 as j1_id) tb on ta.j1_id = tb.j1_id;",
             "select ta.j1_id from j1 ta join (select 1 as j1_id) tb using (j1_id);",
+            "select ta.j1_id, tb.j2_string from j1 ta asof join j2 tb match_condition (ta.j1_id >= tb.j2_id) on ta.j1_string = tb.j2_string;",
             "select ta.j1_id from j1 ta join (select 1 as j1_id) tb on ta.j1_id = tb.j1_id where ta.j1_id > 1;",
             "select ta.j1_id from (select 1 as j1_id) ta;",
             "select ta.j1_id from j1 ta;",
@@ -1391,6 +1392,16 @@ fn test_with_offset95() {
     sql_round_trip(MySqlDialect {}, "select 1 offset 95", "SELECT 1 OFFSET 95");
 }
 
+#[test]
+fn test_sort_by_to_sql() {
+    // SORT BY only sorts within partitions and is not unparsed as ORDER BY
+    sql_round_trip(
+        GenericDialect {},
+        "select id, first_name from person sort by id",
+        "SELECT person.id, person.first_name FROM person SORT BY person.id",
+    );
+}
+
 #[test]
 fn test_order_by_to_sql() {
     // order by aggregation function
@@ -1437,6 +1448,42 @@ GROUP BY person.id, person.first_name"#.replace("\n", " ").as_str(),
     );
 }
 
+#[test]
+fn test_qualify_to_sql() -> Result<()> {
+    sql_round_trip(
+        GenericDialect {},
+        r#"SELECT id, first_name FROM person QUALIFY rank() OVER (PARTITION BY first_name ORDER BY id) = 1"#,
+        r#"SELECT person.id, person.first_name FROM person QUALIFY (rank() OVER (PARTITION BY person.first_name ORDER BY person.id ASC NULLS LAST RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) = 1)"#,
+    );
+
+    // QUALIFY on an aliased window function over an aggregation
+    sql_round_trip(
+        GenericDialect {},
+        r#"SELECT first_name, rank() OVER (ORDER BY sum(id)) AS r FROM person WHERE age > 10 GROUP BY first_name HAVING sum(age) > 1 QUALIFY r <= 3"#,
+        r#"SELECT person.first_name, rank() OVER (ORDER BY sum(person.id) ASC NULLS LAST RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS r FROM person WHERE (person.age > 10) GROUP BY person.first_name HAVING (sum(person.age) > 1) QUALIFY (rank() OVER (ORDER BY sum(person.id) ASC NULLS LAST RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) <= 3)"#,
+    );
+
+    // Dialects without QUALIFY cannot express the filter
+    let statement = Parser::new(&GenericDialect {})
+        .try_with_sql(
+            "SELECT id FROM person QUALIFY rank() OVER (PARTITION BY first_name ORDER BY id) = 1",
+        )?
+        .parse_statement()?;
+    let context = MockContextProvider {
+        state: MockSessionState::default().with_window_function(rank_udwf()),
+    };
+    let plan = SqlToRel::new(&context).sql_statement_to_plan(statement)?;
+    let err = Unparser::new(&SqliteDialect {})
+        .plan_to_sql(&plan)
+        .unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "This feature is not implemented: Unsupported filter on window functions: the dialect does not support QUALIFY"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_unnest_to_sql() {
     sql_round_trip(
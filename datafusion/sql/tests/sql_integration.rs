@@ -43,11 +43,12 @@ use crate::common::{CustomExprPlanner, CustomTypePlanner, MockSessionState};
 use datafusion_functions::core::planner::CoreFunctionPlanner;
 use datafusion_functions_aggregate::{
     approx_median::approx_median_udaf, count::count_udaf, min_max::max_udaf,
-    min_max::min_udaf,
+    min_max::min_udaf, percentile::percentile_cont_udaf,
 };
 use datafusion_functions_aggregate::{average::avg_udaf, grouping::grouping_udaf};
+use datafusion_functions_nested::empty::array_empty_udf;
 use datafusion_functions_nested::make_array::make_array_udf;
-use datafusion_functions_window::rank::rank_udwf;
+use datafusion_functions_window::{rank::rank_udwf, row_number::row_number_udwf};
 use rstest::rstest;
 use sqlparser::dialect::{Dialect, GenericDialect, HiveDialect, MySqlDialect};
 
@@ -311,6 +312,47 @@ CreateMemoryTable: Bare { table: "person" } constraints=[PrimaryKey([1, 0])]
     quick_test(sql, plan);
 }
 
+#[test]
+fn plan_create_table_with_table_options() {
+    let sql = "create table person (id int, name string comment 'full name') \
+        primary key (id) order by (name, id) partition by id comment 'people'";
+    let plan = logical_plan(sql).unwrap();
+    let expected = r#"
+CreateMemoryTable: Bare { table: "person" } constraints=[PrimaryKey([0])]
+  EmptyRelation
+    "#
+    .trim();
+    assert_eq!(plan.to_string(), expected);
+
+    let LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(create)) = plan else {
+        panic!("expected CreateMemoryTable, got {plan:?}");
+    };
+    assert_eq!(create.partition_by, vec![col("id")]);
+    assert_eq!(
+        create.sort_order,
+        vec![col("name").sort(true, false), col("id").sort(true, false)]
+    );
+    assert_eq!(create.comment.as_deref(), Some("people"));
+    assert_eq!(
+        create.column_comments,
+        vec![("name".to_string(), "full name".to_string())]
+    );
+
+    let sql = "create table person (id int, name string) order by nme";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_contains!(
+        err.strip_backtrace(),
+        "Schema error: No field named nme. Did you mean 'name'?"
+    );
+
+    let sql = "create table person (id int, name string) primary key (id + 1)";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: Primary key must consist of columns, got id + 1"
+    );
+}
+
 #[test]
 fn plan_create_table_with_multi_pk() {
     let sql = "create table person (id int, name string primary key, primary key(id))";
@@ -555,6 +597,28 @@ Dml: op=[Delete] table=[person]
     quick_test(sql, plan);
 }
 
+#[test]
+fn plan_merge() {
+    let sql = "merge into j1 using j2 on j1_id = j2_id \
+        when matched and j2_string = 'x' then delete \
+        when matched then update set j1_string = j2_string \
+        when not matched then insert values (j2_id, j2_string)";
+    let plan = r#"
+Dml: op=[Merge] table=[j1]
+  Projection: CASE __merge_clause WHEN Int64(2) THEN j2.j2_id ELSE j1.j1_id END AS j1_id, CASE __merge_clause WHEN Int64(1) THEN j2.j2_string WHEN Int64(2) THEN j2.j2_string ELSE j1.j1_string END AS j1_string, CASE WHEN __merge_target IS NOT NULL AND __merge_match > UInt64(1) THEN Utf8("conflict") ELSE CASE __merge_clause WHEN Int64(0) THEN Utf8("delete") WHEN Int64(1) THEN Utf8("update") WHEN Int64(2) THEN Utf8("insert") END END AS __merge_action
+    Filter: (__merge_target IS NOT NULL OR __merge_clause IS NOT NULL) AND (__merge_target IS NULL OR __merge_match = UInt64(1) OR __merge_applied > Int64(0))
+      WindowAggr: windowExpr=[[row_number() PARTITION BY [__merge_target] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS __merge_match, count(__merge_clause) PARTITION BY [__merge_target] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS __merge_applied]]
+        Projection: j1.j1_id, j1.j1_string, __merge_target, j2.j2_id, j2.j2_string, __merge_source, CASE WHEN __merge_target IS NOT NULL AND __merge_source IS NOT NULL AND j2.j2_string = Utf8("x") THEN Int64(0) WHEN __merge_target IS NOT NULL AND __merge_source IS NOT NULL THEN Int64(1) WHEN __merge_target IS NULL THEN Int64(2) END AS __merge_clause
+          Full Join:  Filter: j1.j1_id = j2.j2_id
+            WindowAggr: windowExpr=[[row_number() ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS __merge_target]]
+              TableScan: j1
+            Projection: j2.j2_id, j2.j2_string, Boolean(true) AS __merge_source
+              TableScan: j2
+    "#
+    .trim();
+    quick_test(sql, plan);
+}
+
 #[test]
 fn select_column_does_not_exist() {
     let sql = "SELECT doesnotexist FROM person";
@@ -1641,6 +1705,35 @@ fn select_approx_median() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn select_within_group() {
+    let sql = "SELECT percentile_cont(0.9) WITHIN GROUP (ORDER BY age DESC) FROM person";
+    let expected = "Projection: percentile_cont(person.age,Float64(0.9)) ORDER BY [person.age DESC NULLS FIRST]\
+                        \n  Aggregate: groupBy=[[]], aggr=[[percentile_cont(person.age, Float64(0.9)) ORDER BY [person.age DESC NULLS FIRST]]]\
+                        \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_within_group_not_ordered_set_aggregate() {
+    let sql = "SELECT approx_median(0.9) WITHIN GROUP (ORDER BY age) FROM person";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: WITHIN GROUP is only supported for ordered-set aggregate functions, approx_median is not one",
+        err.strip_backtrace()
+    );
+}
+
+#[test]
+fn select_within_group_with_order_by() {
+    let sql = "SELECT percentile_cont(0.9 ORDER BY age) WITHIN GROUP (ORDER BY age) FROM person";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: Calling percentile_cont: ORDER BY in function arguments cannot be combined with WITHIN GROUP",
+        err.strip_backtrace()
+    );
+}
+
 #[test]
 fn select_scalar_func() {
     let sql = "SELECT sqrt(age) FROM person";
@@ -2047,6 +2140,50 @@ fn join_with_using() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn asof_join() {
+    let sql = "SELECT p.id, o.order_id \
+            FROM person p \
+            ASOF JOIN orders o \
+            MATCH_CONDITION (p.birth_date >= o.delivered) \
+            ON p.id = o.customer_id";
+    let expected = "Projection: p.id, o.order_id\
+        \n  LeftAsof Join:  Filter: p.id = o.customer_id AND p.birth_date >= o.delivered\
+        \n    SubqueryAlias: p\
+        \n      TableScan: person\
+        \n    SubqueryAlias: o\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn asof_join_with_using() {
+    let sql = "SELECT p.first_name, id \
+            FROM person p \
+            ASOF JOIN person p2 \
+            MATCH_CONDITION (p.age < p2.age) \
+            USING (id)";
+    let expected = "Projection: p.first_name, p.id\
+        \n  LeftAsof Join: Using p.id = p2.id Filter: p.age < p2.age\
+        \n    SubqueryAlias: p\
+        \n      TableScan: person\
+        \n    SubqueryAlias: p2\
+        \n      TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn asof_join_invalid_match_condition() {
+    let sql = "SELECT p.id FROM person p \
+            ASOF JOIN orders o \
+            MATCH_CONDITION (p.id = o.customer_id)";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: ASOF JOIN match condition must be a comparison using <, <=, > or >=, got p.id = o.customer_id",
+        err.strip_backtrace()
+    );
+}
+
 #[test]
 fn project_wildcard_on_join_with_using() {
     let sql = "SELECT * \
@@ -2172,6 +2309,61 @@ fn empty_over_multiple() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn qualify() {
+    let sql = "SELECT order_id, MAX(qty) OVER (PARTITION BY order_id) AS max_qty FROM orders QUALIFY max_qty > 1";
+    let expected = "\
+        Projection: orders.order_id, max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS max_qty\
+        \n  Filter: max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING > Int64(1)\
+        \n    WindowAggr: windowExpr=[[max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_window_not_in_projection() {
+    let sql =
+        "SELECT order_id FROM orders QUALIFY MIN(qty) OVER (PARTITION BY order_id) = qty";
+    let expected = "\
+        Projection: orders.order_id\
+        \n  Filter: min(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING = orders.qty\
+        \n    WindowAggr: windowExpr=[[min(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_with_aggregate() {
+    let sql = "SELECT customer_id, SUM(qty) FROM orders GROUP BY customer_id QUALIFY MAX(SUM(qty)) OVER () = SUM(qty)";
+    let expected = "\
+        Projection: orders.customer_id, sum(orders.qty)\
+        \n  Filter: max(sum(orders.qty)) ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING = sum(orders.qty)\
+        \n    WindowAggr: windowExpr=[[max(sum(orders.qty)) ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      Aggregate: groupBy=[[orders.customer_id]], aggr=[[sum(orders.qty)]]\
+        \n        TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_without_window_function() {
+    let sql = "SELECT order_id FROM orders QUALIFY order_id > 1";
+    let err = logical_plan(sql).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: QUALIFY clause requires window functions in the SELECT list or QUALIFY clause"
+    );
+}
+
+#[test]
+fn qualify_non_aggregate_column() {
+    let sql = "SELECT customer_id FROM orders GROUP BY customer_id QUALIFY MAX(qty) OVER () > 1";
+    let err = logical_plan(sql).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: QUALIFY clause references non-aggregate values: Expression orders.qty could not be resolved from available columns: orders.customer_id"
+    );
+}
+
 /// psql result
 /// ```text
 ///                               QUERY PLAN
@@ -2537,7 +2729,9 @@ fn logical_plan_with_options(sql: &str, options: ParserOptions) -> Result<Logica
 }
 
 fn logical_plan_with_dialect(sql: &str, dialect: &dyn Dialect) -> Result<LogicalPlan> {
-    let state = MockSessionState::default().with_aggregate_function(sum_udaf());
+    let state = MockSessionState::default()
+        .with_scalar_function(array_empty_udf())
+        .with_aggregate_function(sum_udaf());
     let context = MockContextProvider { state };
     let planner = SqlToRel::new(&context);
     let result = DFParser::parse_sql_with_dialect(sql, dialect);
@@ -2580,12 +2774,14 @@ fn logical_plan_with_dialect_and_options(
         )))
         .with_aggregate_function(sum_udaf())
         .with_aggregate_function(approx_median_udaf())
+        .with_aggregate_function(percentile_cont_udaf())
         .with_aggregate_function(count_udaf())
         .with_aggregate_function(avg_udaf())
         .with_aggregate_function(min_udaf())
         .with_aggregate_function(max_udaf())
         .with_aggregate_function(grouping_udaf())
         .with_window_function(rank_udwf())
+        .with_window_function(row_number_udwf())
         .with_expr_planner(Arc::new(CoreFunctionPlanner::default()));
 
     let context = MockContextProvider { state };
@@ -3198,6 +3394,47 @@ fn lateral_unnest() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn lateral_view_explode() -> Result<()> {
+    let dialect = &HiveDialect {};
+    let sql = "SELECT u.struct_col, v.x FROM unnest_table u LATERAL VIEW explode(u.array_col) v AS x WHERE v.x > 1";
+    let plan = logical_plan_with_dialect(sql, dialect)?;
+    let expected = "Projection: u.struct_col, v.x\
+        \n  Filter: v.x > Int64(1)\
+        \n    Projection: u.array_col, u.struct_col, x AS x\
+        \n      Unnest: lists[v.x|depth=1] structs[]\
+        \n        Projection: u.array_col, u.struct_col, u.array_col AS x\
+        \n          SubqueryAlias: u\
+        \n            TableScan: unnest_table";
+    assert_eq!(plan.display_indent().to_string(), expected);
+
+    let sql =
+        "SELECT v.col FROM unnest_table u LATERAL VIEW OUTER explode(u.array_col) v";
+    let plan = logical_plan_with_dialect(sql, dialect)?;
+    let expected = "Projection: v.col\
+        \n  Projection: u.array_col, u.struct_col, col AS col\
+        \n    Unnest: lists[v.col|depth=1] structs[]\
+        \n      Projection: u.array_col, u.struct_col, CASE WHEN empty(u.array_col) THEN NULL ELSE u.array_col END AS col\
+        \n        SubqueryAlias: u\
+        \n          TableScan: unnest_table";
+    assert_eq!(plan.display_indent().to_string(), expected);
+
+    let sql = "SELECT * FROM unnest_table u LATERAL VIEW explode(1) v";
+    let err = logical_plan_with_dialect(sql, dialect).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: explode expects an array argument, got Int64"
+    );
+
+    let sql = "SELECT * FROM unnest_table u LATERAL VIEW posexplode(u.array_col) v";
+    let err = logical_plan_with_dialect(sql, dialect).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "This feature is not implemented: Unsupported LATERAL VIEW function: posexplode, only explode is supported"
+    );
+    Ok(())
+}
+
 #[test]
 fn hive_aggregate_with_filter() -> Result<()> {
     let dialect = &HiveDialect {};
@@ -3292,9 +3529,30 @@ fn test_offset_before_limit() {
 #[test]
 fn test_distribute_by() {
     let sql = "select id from person distribute by state";
-    let expected = "Repartition: DistributeBy(person.state)\
-        \n  Projection: person.id\
-        \n    TableScan: person";
+    let expected = "Projection: person.id\
+        \n  Repartition: DistributeBy(person.state)\
+        \n    Projection: person.id, person.state\
+        \n      TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn test_sort_by() {
+    let sql = "select id, state from person distribute by state sort by id";
+    let expected = "SortWithinPartitions: person.id ASC NULLS LAST\
+        \n  Repartition: DistributeBy(person.state)\
+        \n    Projection: person.id, person.state\
+        \n      TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn test_cluster_by() {
+    let sql = "select id, state from person cluster by state";
+    let expected = "SortWithinPartitions: person.state ASC NULLS LAST\
+        \n  Repartition: DistributeBy(person.state)\
+        \n    Projection: person.id, person.state\
+        \n      TableScan: person";
     quick_test(sql, expected);
 }
 
@@ -3509,25 +3767,17 @@ fn test_select_distinct_order_by() {
 }
 
 #[rstest]
-#[case::select_cluster_by_unsupported(
-    "SELECT customer_name, sum(order_total) as total_order_amount FROM orders CLUSTER BY customer_name",
-    "This feature is not implemented: CLUSTER BY"
-)]
-#[case::select_lateral_view_unsupported(
-    "SELECT id, number FROM person LATERAL VIEW explode(numbers) exploded_table AS number",
-    "This feature is not implemented: LATERAL VIEWS"
-)]
-#[case::select_qualify_unsupported(
-    "SELECT i, p, o FROM person QUALIFY ROW_NUMBER() OVER (PARTITION BY p ORDER BY o) = 1",
-    "This feature is not implemented: QUALIFY"
-)]
 #[case::select_top_unsupported(
     "SELECT TOP (5) * FROM person",
     "This feature is not implemented: TOP"
 )]
-#[case::select_sort_by_unsupported(
-    "SELECT * FROM person SORT BY id",
-    "This feature is not implemented: SORT BY"
+#[case::select_cluster_by_with_sort_by(
+    "SELECT * FROM person CLUSTER BY state SORT BY id",
+    "Error during planning: CLUSTER BY cannot be combined with DISTRIBUTE BY or SORT BY"
+)]
+#[case::select_sort_by_with_order_by(
+    "SELECT * FROM person SORT BY id ORDER BY id",
+    "Error during planning: ORDER BY cannot be combined with SORT BY or CLUSTER BY"
 )]
 #[test]
 fn test_select_unsupported_syntax_errors(#[case] sql: &str, #[case] error: &str) {
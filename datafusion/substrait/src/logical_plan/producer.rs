@@ -819,7 +819,7 @@ pub fn from_distinct(
 pub fn from_join(producer: &mut impl SubstraitProducer, join: &Join) -> Result<Box<Rel>> {
     let left = producer.handle_plan(join.left.as_ref())?;
     let right = producer.handle_plan(join.right.as_ref())?;
-    let join_type = to_substrait_jointype(join.join_type);
+    let join_type = to_substrait_jointype(join.join_type)?;
     // we only support basic joins so return an error for anything not yet supported
     match join.join_constraint {
         JoinConstraint::On => {}
@@ -1069,8 +1069,8 @@ fn to_substrait_join_expr(
     Ok(join_expr)
 }
 
-fn to_substrait_jointype(join_type: JoinType) -> join_rel::JoinType {
-    match join_type {
+fn to_substrait_jointype(join_type: JoinType) -> Result<join_rel::JoinType> {
+    Ok(match join_type {
         JoinType::Inner => join_rel::JoinType::Inner,
         JoinType::Left => join_rel::JoinType::Left,
         JoinType::Right => join_rel::JoinType::Right,
@@ -1078,10 +1078,10 @@ fn to_substrait_jointype(join_type: JoinType) -> join_rel::JoinType {
         JoinType::LeftAnti => join_rel::JoinType::LeftAnti,
         JoinType::LeftSemi => join_rel::JoinType::LeftSemi,
         JoinType::LeftMark => join_rel::JoinType::LeftMark,
-        JoinType::RightAnti | JoinType::RightSemi => {
-            unimplemented!()
+        JoinType::RightAnti | JoinType::RightSemi | JoinType::LeftAsof => {
+            return not_impl_err!("Unsupported join type: {join_type}");
         }
-    }
+    })
 }
 
 pub fn operator_to_name(op: Operator) -> &'static str {